@@ -0,0 +1,197 @@
+//! Token-2022 transfer hook for OGAL objects. Every transfer of a hooked
+//! object mint calls back into OGAL's `sync_hooked_owner`, which caches the
+//! new holder on the manifest and extends its custody trail.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{
+            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Account as Token2022Account,
+    },
+    token_interface::{Mint, TokenAccount},
+};
+use owner_governed_asset_ledger::{cpi, program::OwnerGovernedAssetLedger};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("CpbPbgWToyAmM3H1qEU6ZXvLCiLmCjeCR3uvB8xi9tJy");
+
+/// Seed of the PDA this program signs `sync_hooked_owner` with.
+pub const HOOK_AUTHORITY_SEED: &[u8] = b"hook_authority";
+/// Seed of the per-mint extra account metas PDA, fixed by the transfer hook
+/// interface.
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+const CUSTODY_SEED: &[u8] = b"custody_trail";
+/// Extra accounts every hooked transfer resolves: the OGAL program, the
+/// hook authority, the manifest, and the manifest's custody trail.
+const OBJECT_HOOK_META_COUNT: usize = 4;
+/// Index of the first extra account in the `Execute` instruction, after the
+/// source, mint, destination, owner, and extra account metas.
+const FIRST_EXTRA_ACCOUNT_INDEX: u8 = 5;
+
+#[program]
+pub mod ogal_transfer_hook {
+    use super::*;
+
+    /// Creates the extra account metas Token-2022 resolves on every transfer
+    /// of `mint`. OGAL calls this while its auth PDA is still the mint
+    /// authority, binding the mint to `object_manifest`.
+    pub fn initialize_object_hook(ctx: Context<InitializeObjectHook>) -> Result<()> {
+        let metas = object_hook_metas(&ctx.accounts.object_manifest.key())?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_metas.try_borrow_mut_data()?,
+            &metas,
+        )?;
+        Ok(())
+    }
+
+    /// Transfer hook entrypoint. Transfers signed by an OGAL-owned authority,
+    /// such as `recover_object`, update the manifest themselves and are
+    /// skipped, since OGAL cannot be re-entered.
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        require_transferring(&ctx.accounts.source_token)?;
+        require_transferring(&ctx.accounts.destination_token)?;
+        if ctx.accounts.owner.owner == &owner_governed_asset_ledger::ID {
+            return Ok(());
+        }
+
+        let custody_trail = ctx.accounts.custody_trail.to_account_info();
+        let custody_trail =
+            (custody_trail.owner == &owner_governed_asset_ledger::ID).then_some(custody_trail);
+        let signer_seeds: &[&[u8]] = &[HOOK_AUTHORITY_SEED, &[ctx.bumps.hook_authority]];
+        cpi::sync_hooked_owner(CpiContext::new_with_signer(
+            ctx.accounts.ledger_program.to_account_info(),
+            cpi::accounts::SyncHookedOwner {
+                hook_authority: ctx.accounts.hook_authority.to_account_info(),
+                object_manifest: ctx.accounts.object_manifest.to_account_info(),
+                object_mint: ctx.accounts.mint.to_account_info(),
+                destination_token_account: ctx.accounts.destination_token.to_account_info(),
+                transfer_authority: ctx.accounts.owner.to_account_info(),
+                custody_trail,
+            },
+            &[signer_seeds],
+        ))
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeObjectHook<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Written within the instruction as an `ExtraAccountMetaList`.
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(OBJECT_HOOK_META_COUNT)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_metas: UncheckedAccount<'info>,
+    #[account(mint::authority = mint_authority)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub mint_authority: Signer<'info>,
+    /// CHECK: Only recorded in the metas; OGAL checks it against the mint on
+    /// every transfer.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of the transfer hook interface's `Execute`, followed by the
+/// extra accounts listed in [`object_hook_metas`].
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Owner or delegate that signed the transfer.
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: The mint's extra account metas, resolved by Token-2022.
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_metas: UncheckedAccount<'info>,
+    pub ledger_program: Program<'info, OwnerGovernedAssetLedger>,
+    /// CHECK: PDA of this program, signing the OGAL call.
+    #[account(seeds = [HOOK_AUTHORITY_SEED], bump)]
+    pub hook_authority: UncheckedAccount<'info>,
+    /// CHECK: Verified against the mint by OGAL.
+    #[account(mut)]
+    pub object_manifest: UncheckedAccount<'info>,
+    /// CHECK: The manifest's custody trail PDA, passed to OGAL only when it
+    /// exists.
+    #[account(mut)]
+    pub custody_trail: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The transfer hook was invoked outside of a Token-2022 transfer.")]
+    NotTransferring,
+}
+
+/// Extra accounts Token-2022 appends to `Execute` for an object mint bound
+/// to `manifest`.
+fn object_hook_metas(manifest: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&owner_governed_asset_ledger::ID, false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal {
+                bytes: HOOK_AUTHORITY_SEED.to_vec(),
+            }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_pubkey(manifest, false, true)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            FIRST_EXTRA_ACCOUNT_INDEX,
+            &[
+                Seed::Literal {
+                    bytes: CUSTODY_SEED.to_vec(),
+                },
+                Seed::AccountKey {
+                    index: FIRST_EXTRA_ACCOUNT_INDEX + 2,
+                },
+            ],
+            false,
+            true,
+        )?,
+    ])
+}
+
+/// Rejects calls that did not come from Token-2022 mid-transfer, which sets
+/// the `transferring` flag on both token accounts for the hook's duration.
+fn require_transferring(token_account: &InterfaceAccount<TokenAccount>) -> Result<()> {
+    let info = token_account.to_account_info();
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+    let extension = state.get_extension::<TransferHookAccount>()?;
+    require!(
+        bool::from(extension.transferring),
+        ErrorCode::NotTransferring
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_hook_metas_fit_their_allocation() {
+        let manifest = Pubkey::new_unique();
+        let metas = object_hook_metas(&manifest).unwrap();
+        assert_eq!(metas.len(), OBJECT_HOOK_META_COUNT);
+
+        let mut data = vec![0u8; ExtraAccountMetaList::size_of(OBJECT_HOOK_META_COUNT).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas).unwrap();
+        assert_eq!(
+            metas[2],
+            ExtraAccountMeta::new_with_pubkey(&manifest, false, true).unwrap()
+        );
+    }
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use bytemuck::Zeroable;
+use libfuzzer_sys::fuzz_target;
+use owner_governed_asset_ledger::{fuzzing::MAX_URI_LENGTH, ObjectManifest};
+
+fuzz_target!(|uris: Vec<String>| {
+    let mut manifest = ObjectManifest::zeroed();
+    for uri in uris.iter().filter(|uri| uri.len() <= MAX_URI_LENGTH) {
+        manifest.set_metadata_uri(uri);
+        assert!(manifest.metadata_uri_equals(uri));
+        assert_eq!(manifest.metadata_uri_len(), uri.len());
+        assert!(manifest.metadata_uri[uri.len()..]
+            .iter()
+            .all(|byte| *byte == 0));
+    }
+});
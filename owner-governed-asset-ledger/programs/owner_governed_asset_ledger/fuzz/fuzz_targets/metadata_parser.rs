@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use owner_governed_asset_ledger::fuzzing::{
+    metadata_account_base_len, read_collection_details_from_tlv,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(base_len) = metadata_account_base_len(data) {
+        assert!(base_len <= data.len());
+    }
+    let _ = read_collection_details_from_tlv(data);
+});
@@ -0,0 +1,1032 @@
+//! Off-chain helpers for integrators, enabled with the `client` feature: PDA
+//! finders, instruction builders that include the remaining-accounts tail, and
+//! account fetch/decode helpers.
+
+use crate::{
+    accounts, instruction, Config, CreatorInput, MetadataDelegateScope, MintedBitmap,
+    ObjectManifest, AIRDROP_SEED, APPROVED_CREATOR_SEED, AUDIT_LOG_SEED, AUTH_SEED, CHANGELOG_SEED,
+    CONFIG_SEED, CORE_ASSET_SEED, CREATOR_PROFILE_SEED, CREATOR_ROYALTY_SEED, CREATOR_SEED,
+    CUSTODY_SEED, EPOCH_STATS_SEED, EXPORT_MESSAGE_SEED, EXTERNAL_LINK_SEED,
+    EXTRA_ACCOUNT_METAS_SEED, FOREIGN_EMITTER_SEED, GLOBAL_PAUSE_SEED,
+    GOVERNANCE_NATIVE_TREASURY_SEED, HISTORY_SEED, IMPORT_RECEIPT_SEED, KYC_GATE_SEED,
+    LIGHT_CPI_AUTHORITY_SEED, LIVENESS_ORACLE_SEED, MANIFEST_ATTESTATION_SEED,
+    MANIFEST_MIGRATION_SEED, MANIFEST_SEED, MEMO_PROGRAM_ID, METADATA_DEFAULTS_SEED,
+    MINTED_BITMAP_SEED, MINT_PRICE_SEED, MINT_SEED, MPL_CORE_ID, MPL_INSCRIPTION_ID,
+    NAMESPACE_REGISTRY_PAGE_CAPACITY, NAMESPACE_REGISTRY_PAGE_SEED, NAMESPACE_REGISTRY_SEED,
+    NAMESPACE_TRANSFER_SEED, OBJECT_INDEX_PAGE_CAPACITY, OBJECT_INDEX_SEED, PROPOSAL_SEED,
+    RECOVERY_SEED, RESERVATION_SEED, SCHEDULED_UPDATE_SEED, SHARE_MINT_SEED, SNAPSHOT_SEED,
+    SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID, STATS_SEED, TRANSFER_HOOK_PROGRAM_ID,
+    URI_ATTESTATION_SEED, VAULT_RECEIPT_SEED, VAULT_SEED, VOTE_SEED, WORMHOLE_EMITTER_SEED,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        address_lookup_table::instruction::{create_lookup_table, extend_lookup_table},
+        instruction::{AccountMeta, Instruction},
+        sysvar,
+    },
+    system_program, AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_client::{client_error::ClientError as RpcError, rpc_client::RpcClient};
+
+pub fn find_config_address(namespace: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, namespace.as_ref()], &crate::ID)
+}
+
+pub fn find_auth_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTH_SEED, config.as_ref()], &crate::ID)
+}
+
+/// Native treasury that `set_governance_authority` installs as the
+/// authority, and that proposals sign authority-gated instructions with.
+pub fn find_governance_native_treasury_address(
+    governance_program: &Pubkey,
+    governance: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GOVERNANCE_NATIVE_TREASURY_SEED, governance.as_ref()],
+        governance_program,
+    )
+}
+
+pub fn find_manifest_address(config: &Pubkey, object_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MANIFEST_SEED, config.as_ref(), &object_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_object_mint_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_manifest_history_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HISTORY_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_scheduled_update_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SCHEDULED_UPDATE_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_custody_trail_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CUSTODY_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_object_recovery_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECOVERY_SEED, manifest.as_ref()], &crate::ID)
+}
+
+/// Vault PDA owning every object deposited under `config`.
+pub fn find_vault_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_vault_receipt_mint_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_RECEIPT_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_share_mint_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARE_MINT_SEED, manifest.as_ref()], &crate::ID)
+}
+
+/// Companion transfer-hook program, passed as `transfer_hook_program`.
+pub fn transfer_hook_program_id() -> Pubkey {
+    TRANSFER_HOOK_PROGRAM_ID
+}
+
+pub fn find_extra_account_metas_address(object_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, object_mint.as_ref()],
+        &TRANSFER_HOOK_PROGRAM_ID,
+    )
+}
+
+pub fn find_config_stats_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_creator_record_address(config: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CREATOR_SEED, config.as_ref(), creator.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_epoch_stats_address(config: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EPOCH_STATS_SEED, config.as_ref(), &epoch.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_object_index_page_address(config: &Pubkey, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OBJECT_INDEX_SEED, config.as_ref(), &page.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Chunk of the minted bitmap covering `object_id`.
+pub fn find_minted_bitmap_address(config: &Pubkey, object_id: u64) -> (Pubkey, u8) {
+    let chunk = MintedBitmap::chunk_for(object_id);
+    Pubkey::find_program_address(
+        &[MINTED_BITMAP_SEED, config.as_ref(), &chunk.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_holder_snapshot_address(config: &Pubkey, snapshot_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SNAPSHOT_SEED, config.as_ref(), &snapshot_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_holder_proposal_address(config: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_SEED, config.as_ref(), &proposal_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_holder_vote_address(proposal: &Pubkey, manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_SEED, proposal.as_ref(), manifest.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Emitter PDA that signs the config's `export_object` Wormhole messages.
+pub fn find_wormhole_emitter_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WORMHOLE_EMITTER_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_export_message_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXPORT_MESSAGE_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_mint_price_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_PRICE_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_kyc_gate_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[KYC_GATE_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_external_link_address(manifest: &Pubkey, foreign_manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            EXTERNAL_LINK_SEED,
+            manifest.as_ref(),
+            foreign_manifest.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_manifest_migration_address(old_manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MANIFEST_MIGRATION_SEED, old_manifest.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_metadata_defaults_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METADATA_DEFAULTS_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_creator_profile_address(config: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CREATOR_PROFILE_SEED, config.as_ref(), creator.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_approved_creator_address(config: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[APPROVED_CREATOR_SEED, config.as_ref(), creator.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_creator_royalty_address(config: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CREATOR_ROYALTY_SEED, config.as_ref(), creator.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_audit_log_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUDIT_LOG_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_object_reservation_address(config: &Pubkey, object_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RESERVATION_SEED, config.as_ref(), &object_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_airdrop_address(config: &Pubkey, airdrop_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AIRDROP_SEED, config.as_ref(), &airdrop_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Root of the Merkle tree over `leaves`, as `create_airdrop` expects.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = merkle_parents(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// The level above `level`, carrying an odd last node up unchanged.
+fn merkle_parents(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => crate::merkle_node(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Sibling hashes leading from `leaves[index]` to [`merkle_root`].
+pub fn merkle_proof(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = merkle_parents(&level);
+        index /= 2;
+    }
+    proof
+}
+
+pub fn find_global_pause_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_PAUSE_SEED], &crate::ID)
+}
+
+pub fn find_liveness_oracle_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LIVENESS_ORACLE_SEED, config.as_ref()], &crate::ID)
+}
+
+pub fn find_uri_attestation_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[URI_ATTESTATION_SEED, manifest.as_ref()], &crate::ID)
+}
+
+pub fn find_manifest_attestation_address(manifest: &Pubkey, attestation: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MANIFEST_ATTESTATION_SEED,
+            manifest.as_ref(),
+            attestation.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_foreign_emitter_address(config: &Pubkey, chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FOREIGN_EMITTER_SEED, config.as_ref(), &chain.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_import_receipt_address(posted_vaa: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[IMPORT_RECEIPT_SEED, posted_vaa.as_ref()], &crate::ID)
+}
+
+/// PDA the program signs Light system program CPIs with.
+pub fn find_light_cpi_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LIGHT_CPI_AUTHORITY_SEED], &crate::ID)
+}
+
+/// Compressed address `create_compressed_manifest` gives `object_id` in
+/// `address_tree`.
+pub fn find_compressed_manifest_address(
+    config: &Pubkey,
+    object_id: u64,
+    address_tree: &Pubkey,
+) -> [u8; 32] {
+    let seed = crate::compressed_manifest_address_seed(config, object_id);
+    crate::compressed_manifest_address(address_tree, &seed)
+}
+
+/// Inscription and inscription metadata accounts `inscribe_object` writes
+/// for `mint`.
+pub fn find_inscription_addresses(mint: &Pubkey) -> (Pubkey, Pubkey) {
+    crate::find_inscription_pdas(mint)
+}
+
+/// Metaplex Inscription program, passed as `inscription_program`.
+pub fn inscription_program_id() -> Pubkey {
+    MPL_INSCRIPTION_ID
+}
+
+/// Token Metadata delegate record through which the config's auth PDA acts
+/// for `update_authority` on the metadata of `mint`, created by
+/// `delegate_object_metadata` or `delegate_collection_metadata`. Pass it in
+/// the remaining accounts of updates and mints once the metadata is
+/// delegated.
+pub fn find_metadata_delegate_record_address(
+    config: &Pubkey,
+    mint: &Pubkey,
+    scope: MetadataDelegateScope,
+    update_authority: &Pubkey,
+) -> Pubkey {
+    let (auth, _) = find_auth_address(config);
+    crate::find_metadata_delegate_record(
+        &crate::mpl_program_id(),
+        mint,
+        scope,
+        update_authority,
+        &auth,
+    )
+}
+
+/// Metaplex Core asset `mint_core_object` creates for `manifest`.
+pub fn find_core_asset_address(manifest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CORE_ASSET_SEED, manifest.as_ref()], &crate::ID)
+}
+
+/// Metaplex Core program, passed as `core_program`.
+pub fn core_program_id() -> Pubkey {
+    MPL_CORE_ID
+}
+
+pub fn find_manifest_changelog_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHANGELOG_SEED, config.as_ref()], &crate::ID)
+}
+
+/// Accounts to append to a mint's remaining-accounts tail, after the memo
+/// program if any, or to an update's, once the config keeps a changelog.
+pub fn changelog_account_metas(config: &Pubkey, tree: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(SPL_ACCOUNT_COMPRESSION_ID, false),
+        AccountMeta::new_readonly(SPL_NOOP_ID, false),
+        AccountMeta::new_readonly(find_manifest_changelog_address(config).0, false),
+        AccountMeta::new(*tree, false),
+    ]
+}
+
+/// Metaplex metadata PDA. Configs set to another Token Metadata program
+/// derive it with [`find_metadata_address_for`].
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    find_metadata_address_for(mint, &crate::mpl_program_id())
+}
+
+pub fn find_metadata_address_for(mint: &Pubkey, metadata_program: &Pubkey) -> Pubkey {
+    crate::find_metadata_pda(mint, metadata_program)
+}
+
+/// Metaplex master edition PDA, see [`find_master_edition_address_for`].
+pub fn find_master_edition_address(mint: &Pubkey) -> Pubkey {
+    find_master_edition_address_for(mint, &crate::mpl_program_id())
+}
+
+pub fn find_master_edition_address_for(mint: &Pubkey, metadata_program: &Pubkey) -> Pubkey {
+    crate::find_master_edition_pda(mint, metadata_program)
+}
+
+pub fn find_namespace_registry_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NAMESPACE_REGISTRY_SEED], &crate::ID)
+}
+
+pub fn find_namespace_registry_page_address(page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NAMESPACE_REGISTRY_PAGE_SEED, &page.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn find_namespace_transfer_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NAMESPACE_TRANSFER_SEED, config.as_ref()], &crate::ID)
+}
+
+/// Registry page that the next config created is listed in, given the
+/// current `NamespaceRegistry::config_count` (zero before the first config).
+pub fn next_namespace_registry_page(config_count: u64) -> Pubkey {
+    let page = (config_count / NAMESPACE_REGISTRY_PAGE_CAPACITY as u64) as u32;
+    find_namespace_registry_page_address(page).0
+}
+
+/// Builds `initialize`. `config_count` is the registry's current
+/// `NamespaceRegistry::config_count`.
+pub fn build_initialize_ix(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    namespace: Pubkey,
+    config_count: u64,
+) -> Instruction {
+    let (config, _) = find_config_address(&namespace);
+    let (auth, _) = find_auth_address(&config);
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::Initialize {
+            authority: *authority,
+            payer: *payer,
+            config,
+            auth,
+            namespace_registry: find_namespace_registry_address().0,
+            registry_page: next_namespace_registry_page(config_count),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Initialize { namespace }.data(),
+    }
+}
+
+/// Optional tracking accounts expected by a mint, mirroring the config flags.
+#[derive(Clone, Debug, Default)]
+pub struct MintTracking {
+    /// Current epoch when `Config::stats_enabled` is set.
+    pub stats_epoch: Option<u64>,
+    /// Page holding the next index entry, when the mint creates a manifest
+    /// under a config with `Config::object_index_enabled` set.
+    pub object_index_page: Option<u32>,
+    /// Whether `Config::minted_bitmap_enabled` is set.
+    pub minted_bitmap: bool,
+}
+
+impl MintTracking {
+    /// Derives the tracking accounts from a decoded config. `creates_manifest`
+    /// is true when the manifest PDA does not exist yet.
+    pub fn for_config(config: &Config, epoch: u64, creates_manifest: bool) -> Self {
+        Self {
+            stats_epoch: config.stats_enabled().then_some(epoch),
+            object_index_page: (config.object_index_enabled() && creates_manifest)
+                .then(|| (config.indexed_object_count / OBJECT_INDEX_PAGE_CAPACITY as u64) as u32),
+            minted_bitmap: config.minted_bitmap_enabled(),
+        }
+    }
+}
+
+/// Accounts a mint passes while the config charges a USD price.
+#[derive(Clone, Debug)]
+pub struct UsdPriceAccounts {
+    /// Fully verified Pyth `PriceUpdateV2` account for the configured feed.
+    pub price_update: Pubkey,
+    /// Treasury stored on the config's `MintPrice`.
+    pub treasury: Pubkey,
+}
+
+/// Everything `build_mint_object_nft_ix` needs to assemble a mint.
+#[derive(Clone, Debug)]
+pub struct MintObjectNftParams {
+    pub namespace: Pubkey,
+    /// Config authority; it does not need to sign.
+    pub authority: Pubkey,
+    /// Minting wallet, recorded as the manifest creator.
+    pub minter: Pubkey,
+    /// Funds rent for the accounts the mint creates; may equal `minter`.
+    pub rent_payer: Pubkey,
+    pub recipient: Pubkey,
+    /// Defaults to the recipient's ATA. Set it to deliver to another token
+    /// account once the config allows non-ATA recipients.
+    pub recipient_token_account: Option<Pubkey>,
+    pub collection_mint: Pubkey,
+    pub object_id: u64,
+    pub manifest_uri: String,
+    pub manifest_hash: [u8; 32],
+    pub metadata_name: String,
+    pub metadata_symbol: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<CreatorInput>,
+    pub hash_algorithm: u8,
+    pub category: u16,
+    pub tag_hash: [u8; 32],
+    pub arweave_tx_id: [u8; 32],
+    pub tracking: MintTracking,
+    /// Required when `Config::usd_price_enabled` is set.
+    pub usd_price: Option<UsdPriceAccounts>,
+    /// Solana Attestation Service attestation naming the recipient, required
+    /// when the config's `KycGate` restricts `category`.
+    pub kyc_attestation: Option<Pubkey>,
+    /// Passes the minter's `ApprovedCreator` record, required for new
+    /// objects when `Config::creator_allowlist_enabled` is set.
+    pub use_approved_creator: bool,
+    /// Passes the config's `MetadataDefaults`, which fill in an empty symbol,
+    /// `DEFAULT_SELLER_FEE_BASIS_POINTS`, or empty creators. The PDA must
+    /// exist.
+    pub use_metadata_defaults: bool,
+    /// Passes the minter's `CreatorProfile`, which routes their royalty
+    /// share to its payout wallet. The PDA must exist.
+    pub use_creator_profile: bool,
+    pub include_instructions_sysvar: bool,
+    /// Required when `Config::memo_on_mint` is set.
+    pub include_memo_program: bool,
+    /// Verified creator signers appended after the fixed remaining accounts.
+    pub creator_accounts: Vec<AccountMeta>,
+}
+
+/// Builds `mint_object_nft`, including the remaining-accounts tail
+/// documented on `MintObjectNftMetadata`.
+pub fn build_mint_object_nft_ix(params: &MintObjectNftParams) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: mint_object_nft_metas(params),
+        data: instruction::MintObjectNft {
+            object_id: params.object_id,
+            manifest_uri: params.manifest_uri.clone(),
+            manifest_hash: params.manifest_hash,
+            metadata_name: params.metadata_name.clone(),
+            metadata_symbol: params.metadata_symbol.clone(),
+            seller_fee_basis_points: params.seller_fee_basis_points,
+            creators: params.creators.clone(),
+            hash_algorithm: params.hash_algorithm,
+            category: params.category,
+            tag_hash: params.tag_hash,
+            arweave_tx_id: params.arweave_tx_id,
+        }
+        .data(),
+    }
+}
+
+/// Builds `prepare_object_mint`, which takes the same accounts and arguments
+/// as `mint_object_nft`. Send [`build_finalize_object_mint_ix`] in a later
+/// transaction to complete the mint.
+pub fn build_prepare_object_mint_ix(params: &MintObjectNftParams) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: mint_object_nft_metas(params),
+        data: instruction::PrepareObjectMint {
+            object_id: params.object_id,
+            manifest_uri: params.manifest_uri.clone(),
+            manifest_hash: params.manifest_hash,
+            metadata_name: params.metadata_name.clone(),
+            metadata_symbol: params.metadata_symbol.clone(),
+            seller_fee_basis_points: params.seller_fee_basis_points,
+            creators: params.creators.clone(),
+            hash_algorithm: params.hash_algorithm,
+            category: params.category,
+            tag_hash: params.tag_hash,
+            arweave_tx_id: params.arweave_tx_id,
+        }
+        .data(),
+    }
+}
+
+/// Builds the read-only `check_mint` preflight for the same parameters as
+/// [`build_mint_object_nft_ix`]. Simulate it and decode the return data as a
+/// [`crate::MintCheck`].
+pub fn build_check_mint_ix(params: &MintObjectNftParams) -> Instruction {
+    let (config, _) = find_config_address(&params.namespace);
+    let (auth, _) = find_auth_address(&config);
+    let (object_manifest, _) = find_manifest_address(&config, params.object_id);
+    let (object_mint, _) = find_object_mint_address(&object_manifest);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::CheckMint {
+            config,
+            auth,
+            minter: params.minter,
+            object_manifest,
+            object_mint,
+            recipient_token_account: params
+                .recipient_token_account
+                .unwrap_or_else(|| get_associated_token_address(&params.recipient, &object_mint)),
+            recipient: params.recipient,
+            metadata: find_metadata_address(&object_mint),
+            master_edition: find_master_edition_address(&object_mint),
+            collection_mint: params.collection_mint,
+            collection_metadata: find_metadata_address(&params.collection_mint),
+            collection_master_edition: find_master_edition_address(&params.collection_mint),
+            metadata_defaults: params
+                .use_metadata_defaults
+                .then(|| find_metadata_defaults_address(&config).0),
+            creator_royalty: find_creator_royalty_address(&config, &params.minter).0,
+        }
+        .to_account_metas(None),
+        data: instruction::CheckMint {
+            object_id: params.object_id,
+            manifest_uri: params.manifest_uri.clone(),
+            manifest_hash: params.manifest_hash,
+            metadata_name: params.metadata_name.clone(),
+            metadata_symbol: params.metadata_symbol.clone(),
+            seller_fee_basis_points: params.seller_fee_basis_points,
+            creators: params.creators.clone(),
+            hash_algorithm: params.hash_algorithm,
+            category: params.category,
+            tag_hash: params.tag_hash,
+            arweave_tx_id: params.arweave_tx_id,
+        }
+        .data(),
+    }
+}
+
+/// Builds `finalize_object_mint` for an object prepared with
+/// [`build_prepare_object_mint_ix`].
+pub fn build_finalize_object_mint_ix(
+    namespace: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    collection_mint: &Pubkey,
+    object_id: u64,
+) -> Instruction {
+    let (config, _) = find_config_address(namespace);
+    let (auth, _) = find_auth_address(&config);
+    let (object_manifest, _) = find_manifest_address(&config, object_id);
+    let (object_mint, _) = find_object_mint_address(&object_manifest);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::FinalizeObjectMint {
+            authority: *authority,
+            config,
+            auth,
+            payer: *payer,
+            object_manifest,
+            object_mint,
+            metadata: find_metadata_address(&object_mint),
+            master_edition: find_master_edition_address(&object_mint),
+            collection_mint: *collection_mint,
+            collection_metadata: find_metadata_address(collection_mint),
+            collection_master_edition: find_master_edition_address(collection_mint),
+            token_metadata_program: crate::mpl_program_id(),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            global_pause: find_global_pause_address().0,
+        }
+        .to_account_metas(None),
+        data: instruction::FinalizeObjectMint {}.data(),
+    }
+}
+
+fn mint_object_nft_metas(params: &MintObjectNftParams) -> Vec<AccountMeta> {
+    let (config, _) = find_config_address(&params.namespace);
+    let (auth, _) = find_auth_address(&config);
+    let (object_manifest, _) = find_manifest_address(&config, params.object_id);
+    let (object_mint, _) = find_object_mint_address(&object_manifest);
+    let tracking = &params.tracking;
+
+    let mut metas = accounts::MintObjectNft {
+        base: accounts::MintObjectNftBase {
+            authority: params.authority,
+            config,
+            auth,
+            minter: params.minter,
+            rent_payer: params.rent_payer,
+            object_manifest,
+            object_mint,
+            recipient_token_account: params
+                .recipient_token_account
+                .unwrap_or_else(|| get_associated_token_address(&params.recipient, &object_mint)),
+            recipient: params.recipient,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            config_stats: tracking
+                .stats_epoch
+                .map(|_| find_config_stats_address(&config).0),
+            creator_record: tracking
+                .stats_epoch
+                .map(|_| find_creator_record_address(&config, &params.minter).0),
+            epoch_stats: tracking
+                .stats_epoch
+                .map(|epoch| find_epoch_stats_address(&config, epoch).0),
+            object_index_page: tracking
+                .object_index_page
+                .map(|page| find_object_index_page_address(&config, page).0),
+            minted_bitmap: tracking
+                .minted_bitmap
+                .then(|| find_minted_bitmap_address(&config, params.object_id).0),
+            mint_price: params
+                .usd_price
+                .as_ref()
+                .map(|_| find_mint_price_address(&config).0),
+            price_update: params.usd_price.as_ref().map(|usd| usd.price_update),
+            treasury: params.usd_price.as_ref().map(|usd| usd.treasury),
+            kyc_gate: params
+                .kyc_attestation
+                .map(|_| find_kyc_gate_address(&config).0),
+            kyc_attestation: params.kyc_attestation,
+            global_pause: find_global_pause_address().0,
+            object_reservation: find_object_reservation_address(&config, params.object_id).0,
+            approved_creator: params
+                .use_approved_creator
+                .then(|| find_approved_creator_address(&config, &params.minter).0),
+        },
+        metadata: accounts::MintObjectNftMetadata {
+            metadata: find_metadata_address(&object_mint),
+            master_edition: find_master_edition_address(&object_mint),
+            collection_mint: params.collection_mint,
+            token_metadata_program: crate::mpl_program_id(),
+            metadata_defaults: params
+                .use_metadata_defaults
+                .then(|| find_metadata_defaults_address(&config).0),
+            creator_profile: params
+                .use_creator_profile
+                .then(|| find_creator_profile_address(&config, &params.minter).0),
+            creator_royalty: find_creator_royalty_address(&config, &params.minter).0,
+        },
+    }
+    .to_account_metas(None);
+
+    metas.push(AccountMeta::new(
+        find_metadata_address(&params.collection_mint),
+        false,
+    ));
+    metas.push(AccountMeta::new(
+        find_master_edition_address(&params.collection_mint),
+        false,
+    ));
+    metas.push(AccountMeta::new_readonly(sysvar::rent::ID, false));
+    if params.include_instructions_sysvar {
+        metas.push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+    }
+    if params.include_memo_program {
+        metas.push(AccountMeta::new_readonly(MEMO_PROGRAM_ID, false));
+    }
+    metas.extend(params.creator_accounts.iter().cloned());
+    metas
+}
+
+/// Accounts shared by every mint under a config: the config and auth PDAs,
+/// the config stats PDA, the collection accounts, the programs (including
+/// OGAL itself, which stands in for omitted optional accounts), and the
+/// sysvars.
+pub fn mint_lookup_table_addresses(namespace: &Pubkey, collection_mint: &Pubkey) -> Vec<Pubkey> {
+    let (config, _) = find_config_address(namespace);
+    let (auth, _) = find_auth_address(&config);
+    vec![
+        config,
+        auth,
+        find_config_stats_address(&config).0,
+        find_global_pause_address().0,
+        *collection_mint,
+        find_metadata_address(collection_mint),
+        find_master_edition_address(collection_mint),
+        crate::ID,
+        crate::mpl_program_id(),
+        anchor_spl::token::ID,
+        anchor_spl::associated_token::ID,
+        system_program::ID,
+        MEMO_PROGRAM_ID,
+        sysvar::rent::ID,
+        sysvar::instructions::ID,
+    ]
+}
+
+/// Builds the instructions that create an address lookup table holding
+/// [`mint_lookup_table_addresses`], returning the table address with them.
+/// `recent_slot` must be a recent finalized slot. The table can be used in
+/// v0 mint transactions from the slot after the extend lands.
+pub fn build_mint_lookup_table_ixs(
+    namespace: &Pubkey,
+    collection_mint: &Pubkey,
+    table_authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+) -> (Pubkey, Vec<Instruction>) {
+    let (create_ix, table) = create_lookup_table(*table_authority, *payer, recent_slot);
+    let extend_ix = extend_lookup_table(
+        table,
+        *table_authority,
+        Some(*payer),
+        mint_lookup_table_addresses(namespace, collection_mint),
+    );
+    (table, vec![create_ix, extend_ix])
+}
+
+/// Decodes any Borsh OGAL account (e.g. [`crate::Auth`],
+/// [`crate::ConfigStats`]) after checking its discriminator.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a config, failing on configs that still need
+/// `migrate_config_layout`.
+pub fn decode_config(data: &[u8]) -> Result<Config> {
+    Config::from_account_data(data)
+}
+
+/// Decodes a manifest, zero-filling fields missing from older layouts.
+pub fn decode_manifest(data: &[u8]) -> Result<ObjectManifest> {
+    ObjectManifest::from_account_data(data)
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Rpc(RpcError),
+    Decode(anchor_lang::error::Error),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Rpc(err) => write!(f, "rpc error: {}", err),
+            ClientError::Decode(err) => write!(f, "decode error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<RpcError> for ClientError {
+    fn from(err: RpcError) -> Self {
+        ClientError::Rpc(err)
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(err: anchor_lang::error::Error) -> Self {
+        ClientError::Decode(err)
+    }
+}
+
+pub fn fetch_account<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> std::result::Result<T, ClientError> {
+    let data = rpc.get_account_data(address)?;
+    Ok(decode_account(&data)?)
+}
+
+pub fn fetch_config(
+    rpc: &RpcClient,
+    namespace: &Pubkey,
+) -> std::result::Result<Config, ClientError> {
+    let data = rpc.get_account_data(&find_config_address(namespace).0)?;
+    Ok(decode_config(&data)?)
+}
+
+pub fn fetch_manifest(
+    rpc: &RpcClient,
+    config: &Pubkey,
+    object_id: u64,
+) -> std::result::Result<ObjectManifest, ClientError> {
+    let data = rpc.get_account_data(&find_manifest_address(config, object_id).0)?;
+    Ok(decode_manifest(&data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_params(tracking: MintTracking) -> MintObjectNftParams {
+        MintObjectNftParams {
+            namespace: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            minter: Pubkey::new_unique(),
+            rent_payer: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            recipient_token_account: None,
+            collection_mint: Pubkey::new_unique(),
+            object_id: 7,
+            manifest_uri: "https://example.com/manifest.json".into(),
+            manifest_hash: [1u8; 32],
+            metadata_name: "Level".into(),
+            metadata_symbol: "LVL".into(),
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+            hash_algorithm: 0,
+            category: 0,
+            tag_hash: [0u8; 32],
+            arweave_tx_id: [0u8; 32],
+            tracking,
+            usd_price: None,
+            include_instructions_sysvar: false,
+            kyc_attestation: None,
+            use_approved_creator: false,
+            use_metadata_defaults: false,
+            use_creator_profile: false,
+            include_memo_program: true,
+            creator_accounts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn mint_ix_appends_remaining_accounts_tail() {
+        let params = mint_params(MintTracking::default());
+        let ix = build_mint_object_nft_ix(&params);
+        let (config, _) = find_config_address(&params.namespace);
+        let (manifest, _) = find_manifest_address(&config, params.object_id);
+
+        assert_eq!(ix.accounts[1].pubkey, config);
+        assert_eq!(ix.accounts[3].pubkey, params.minter);
+        assert!(ix.accounts[4].is_signer && ix.accounts[4].is_writable);
+        assert_eq!(ix.accounts[4].pubkey, params.rent_payer);
+        assert_eq!(ix.accounts[5].pubkey, manifest);
+        // 32 named accounts, then collection metadata, collection master
+        // edition, rent, and the memo program.
+        assert_eq!(ix.accounts.len(), 36);
+        assert_eq!(ix.accounts[12].pubkey, crate::ID);
+        assert!(!ix.accounts[12].is_writable);
+        assert_eq!(ix.accounts[34].pubkey, sysvar::rent::ID);
+        assert_eq!(ix.accounts[35].pubkey, MEMO_PROGRAM_ID);
+    }
+
+    #[test]
+    fn mint_ix_includes_tracking_accounts() {
+        let params = mint_params(MintTracking {
+            stats_epoch: Some(3),
+            object_index_page: Some(0),
+            minted_bitmap: true,
+        });
+        let ix = build_mint_object_nft_ix(&params);
+        let (config, _) = find_config_address(&params.namespace);
+
+        assert_eq!(ix.accounts[12].pubkey, find_config_stats_address(&config).0);
+        assert_eq!(
+            ix.accounts[13].pubkey,
+            find_creator_record_address(&config, &params.minter).0
+        );
+        assert_eq!(
+            ix.accounts[14].pubkey,
+            find_epoch_stats_address(&config, 3).0
+        );
+        assert!(ix.accounts[12..17].iter().all(|meta| meta.is_writable));
+    }
+
+    #[test]
+    fn check_mint_ix_is_read_only() {
+        let params = mint_params(MintTracking::default());
+        let ix = build_check_mint_ix(&params);
+        let mint_ix = build_mint_object_nft_ix(&params);
+
+        assert_eq!(ix.accounts.len(), 14);
+        assert!(ix
+            .accounts
+            .iter()
+            .all(|meta| !meta.is_signer && !meta.is_writable));
+        assert_eq!(ix.accounts[3].pubkey, mint_ix.accounts[5].pubkey);
+    }
+
+    #[test]
+    fn merkle_proofs_lead_to_the_root() {
+        let leaves: Vec<[u8; 32]> = (0..5u32)
+            .map(|index| {
+                crate::airdrop_leaf(
+                    index,
+                    &Pubkey::new_unique(),
+                    index as u64,
+                    &[index as u8; 32],
+                    0,
+                    "https://example.com/object.json",
+                    &[0u8; 32],
+                )
+            })
+            .collect();
+        let root = merkle_root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = merkle_proof(&leaves, index);
+            assert!(crate::verify_merkle_proof(&proof, &root, *leaf));
+        }
+        let proof = merkle_proof(&leaves, 0);
+        assert!(!crate::verify_merkle_proof(&proof, &root, leaves[1]));
+        assert_eq!(merkle_root(&leaves[..1]), leaves[0]);
+    }
+
+    #[test]
+    fn lookup_table_covers_fixed_mint_accounts() {
+        let params = mint_params(MintTracking::default());
+        let ix = build_mint_object_nft_ix(&params);
+        let addresses = mint_lookup_table_addresses(&params.namespace, &params.collection_mint);
+        let (config, _) = find_config_address(&params.namespace);
+        let (manifest, _) = find_manifest_address(&config, params.object_id);
+        let (object_mint, _) = find_object_mint_address(&manifest);
+        let per_object = [
+            params.authority,
+            params.minter,
+            params.rent_payer,
+            params.recipient,
+            manifest,
+            object_mint,
+            get_associated_token_address(&params.recipient, &object_mint),
+            find_metadata_address(&object_mint),
+            find_master_edition_address(&object_mint),
+            find_object_reservation_address(&config, params.object_id).0,
+            find_creator_royalty_address(&config, &params.minter).0,
+        ];
+
+        for meta in &ix.accounts {
+            assert!(
+                addresses.contains(&meta.pubkey) || per_object.contains(&meta.pubkey),
+                "{} is neither fixed nor per-object",
+                meta.pubkey
+            );
+        }
+
+        let (table, ixs) = build_mint_lookup_table_ixs(
+            &params.namespace,
+            &params.collection_mint,
+            &params.minter,
+            &params.minter,
+            42,
+        );
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(ixs[0].accounts[0].pubkey, table);
+        assert_eq!(ixs[1].accounts[0].pubkey, table);
+    }
+}
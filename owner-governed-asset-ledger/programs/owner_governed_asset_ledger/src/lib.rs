@@ -1,29 +1,47 @@
 use anchor_lang::{
     prelude::*,
     solana_program::{
-        program::invoke_signed, pubkey::Pubkey as SolanaProgramPubkey, system_instruction, sysvar,
+        hash::{hash, hashv},
+        instruction::{AccountMeta, Instruction},
+        keccak,
+        program::{invoke, invoke_signed},
+        pubkey::Pubkey as SolanaProgramPubkey,
+        sysvar,
     },
-    Discriminator,
+    system_program, Discriminator,
 };
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, InitializeMint2, Mint, MintTo, Token, TokenAccount},
+    token::{
+        self, Approve, Burn, CloseAccount, InitializeMint2, Mint, MintTo, Revoke, Token,
+        TokenAccount, TransferChecked,
+    },
+    token_2022::{self, spl_token_2022, Token2022},
+    token_2022_extensions::{
+        self, spl_token_metadata_interface::state::Field as TokenMetadataField,
+    },
+    token_interface::{Mint as InterfaceMint, TokenAccount as InterfaceTokenAccount},
 };
 use borsh::BorshDeserialize;
-use bytemuck::from_bytes_mut;
+use bytemuck::{from_bytes_mut, Zeroable};
 use mpl_token_metadata::{
-    accounts::{MasterEdition as MetadataMasterEdition, Metadata as MetadataAccount},
+    accounts::Metadata as MetadataAccount,
     instructions::{
         CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts,
         CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3Cpi,
         CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
+        DelegateCollectionV1Cpi, DelegateCollectionV1CpiAccounts,
+        DelegateCollectionV1InstructionArgs, DelegateDataV1Cpi, DelegateDataV1CpiAccounts,
+        DelegateDataV1InstructionArgs, UpdateAsDataDelegateV2Cpi,
+        UpdateAsDataDelegateV2CpiAccounts, UpdateAsDataDelegateV2InstructionArgs,
         UpdateMetadataAccountV2Cpi, UpdateMetadataAccountV2CpiAccounts,
         UpdateMetadataAccountV2InstructionArgs, VerifyCollectionCpi, VerifyCollectionCpiAccounts,
-        VerifySizedCollectionItemCpi, VerifySizedCollectionItemCpiAccounts,
+        VerifyCollectionV1Cpi, VerifyCollectionV1CpiAccounts, VerifySizedCollectionItemCpi,
+        VerifySizedCollectionItemCpiAccounts,
     },
     types::{
         Collection, CollectionDetails, Creator as MetadataCreator, Data, DataV2,
-        Key as MetadataKey, ProgrammableConfig, TokenStandard, Uses,
+        Key as MetadataKey, ProgrammableConfig, Uses,
     },
     MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
     MAX_URI_LENGTH as METADATA_MAX_URI_LENGTH,
@@ -34,10 +52,120 @@ use std::collections::HashSet;
 
 declare_id!("GwMpopxNkDYsnucBRPf47QSEsEzA3rS1o6ioMX78hgqx");
 
+// Embedded in the deployed binary for scanners such as the Solana Explorer.
+// Release builds can override the contact, policy, and revision through the
+// `OGAL_SECURITY_CONTACTS`, `OGAL_SECURITY_POLICY`, and `GITHUB_SHA`
+// environment variables.
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "Owner-Governed Asset Ledger",
+    project_url: "https://github.com/NanoRes/Owner-Governed-Asset-Ledger",
+    contacts: default_env::default_env!(
+        "OGAL_SECURITY_CONTACTS",
+        "link:https://github.com/NanoRes/Owner-Governed-Asset-Ledger/security/advisories/new"
+    ),
+    policy: default_env::default_env!(
+        "OGAL_SECURITY_POLICY",
+        "https://github.com/NanoRes/Owner-Governed-Asset-Ledger/blob/main/SECURITY.md"
+    ),
+    source_code: "https://github.com/NanoRes/Owner-Governed-Asset-Ledger",
+    source_release: concat!("v", env!("CARGO_PKG_VERSION")),
+    source_revision: default_env::default_env!("GITHUB_SHA", ""),
+    preferred_languages: "en"
+}
+
+#[cfg(all(feature = "client", not(target_os = "solana")))]
+pub mod client;
+
+#[cfg(all(feature = "test-utils", not(target_os = "solana")))]
+pub mod test_utils;
+
 const CONFIG_SEED: &[u8] = b"config";
 const AUTH_SEED: &[u8] = b"auth";
 const MANIFEST_SEED: &[u8] = b"object_manifest";
 const MINT_SEED: &[u8] = b"object_mint";
+const HISTORY_SEED: &[u8] = b"manifest_history";
+const PAYLOAD_SEED: &[u8] = b"manifest_payload";
+const LEVEL_DELEGATE_SEED: &[u8] = b"level_delegate";
+const XP_SCORER_SEED: &[u8] = b"xp_scorer";
+const CUSTODY_SEED: &[u8] = b"custody_trail";
+const STATS_SEED: &[u8] = b"config_stats";
+const CREATOR_SEED: &[u8] = b"creator_record";
+const EPOCH_STATS_SEED: &[u8] = b"epoch_stats";
+const OBJECT_INDEX_SEED: &[u8] = b"object_index";
+const NAMESPACE_REGISTRY_SEED: &[u8] = b"namespace_registry";
+const NAMESPACE_REGISTRY_PAGE_SEED: &[u8] = b"namespace_registry_page";
+const NAMESPACE_TRANSFER_SEED: &[u8] = b"namespace_transfer";
+const MANIFEST_MIGRATION_SEED: &[u8] = b"manifest_migration";
+const MINTED_BITMAP_SEED: &[u8] = b"minted_bitmap";
+const SNAPSHOT_SEED: &[u8] = b"holder_snapshot";
+const WORMHOLE_EMITTER_SEED: &[u8] = b"wormhole_emitter";
+const EXPORT_MESSAGE_SEED: &[u8] = b"export_message";
+const FOREIGN_EMITTER_SEED: &[u8] = b"foreign_emitter";
+const IMPORT_RECEIPT_SEED: &[u8] = b"import_receipt";
+const COMPRESSED_MANIFEST_SEED: &[u8] = b"compressed_manifest";
+/// Seed of the PDA a program signs Light system program CPIs with.
+const LIGHT_CPI_AUTHORITY_SEED: &[u8] = b"cpi_authority";
+const CHANGELOG_SEED: &[u8] = b"manifest_changelog";
+const SCHEDULED_UPDATE_SEED: &[u8] = b"scheduled_update";
+const RECOVERY_SEED: &[u8] = b"object_recovery";
+const VAULT_SEED: &[u8] = b"object_vault";
+const VAULT_RECEIPT_SEED: &[u8] = b"vault_receipt";
+const SHARE_MINT_SEED: &[u8] = b"object_shares";
+const PROPOSAL_SEED: &[u8] = b"holder_proposal";
+const VOTE_SEED: &[u8] = b"holder_vote";
+const MANIFEST_ATTESTATION_SEED: &[u8] = b"manifest_attestation";
+const EXTERNAL_LINK_SEED: &[u8] = b"external_link";
+const LIVENESS_ORACLE_SEED: &[u8] = b"liveness_oracle";
+const URI_ATTESTATION_SEED: &[u8] = b"uri_attestation";
+const MINT_PRICE_SEED: &[u8] = b"mint_price";
+const KYC_GATE_SEED: &[u8] = b"kyc_gate";
+const METADATA_DEFAULTS_SEED: &[u8] = b"metadata_defaults";
+const CREATOR_PROFILE_SEED: &[u8] = b"creator_profile";
+const APPROVED_CREATOR_SEED: &[u8] = b"approved_creator";
+const CREATOR_ROYALTY_SEED: &[u8] = b"creator_royalty";
+const GLOBAL_PAUSE_SEED: &[u8] = b"global_pause";
+const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+const RESERVATION_SEED: &[u8] = b"object_reservation";
+const AIRDROP_SEED: &[u8] = b"airdrop";
+const GIFT_SEED: &[u8] = b"gift";
+const CORE_ASSET_SEED: &[u8] = b"core_asset";
+/// SPL Memo v2 program, invoked at mint when [`Config::memo_on_mint`] is set.
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Offset of the verification level in a Pyth `PriceUpdateV2` account; the
+/// price message follows a fully verified level at the next byte.
+const PYTH_VERIFICATION_LEVEL_OFFSET: usize = 40;
+const PYTH_VERIFICATION_FULL: u8 = 1;
+const MPL_INSCRIPTION_ID: Pubkey = pubkey!("1NSCRfGeyo7wPUazGbaPBUsTM49e1k2aXewHGARfzSo");
+const INSCRIPTION_SEED: &[u8] = b"Inscription";
+/// Instruction indices of the Metaplex Inscription program.
+const INSCRIPTION_INITIALIZE_FROM_MINT: u8 = 1;
+const INSCRIPTION_WRITE_DATA: u8 = 3;
+/// Light Protocol programs that hold compressed manifests. Light logs
+/// through the same Noop program as SPL Account Compression.
+const LIGHT_SYSTEM_PROGRAM_ID: Pubkey = pubkey!("SySTEM1eSU2p4BGQfQpimFEWWSC1XDFeun3Nqzz3rT7");
+const LIGHT_ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("compr6CUsB5m2jS4Y3831ztGSTnDpnKJTKS95d64XVq");
+const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+/// Instruction index of Metaplex Core's `CreateV1`.
+const CORE_CREATE_V1: u8 = 0;
+/// Leading key byte of a Metaplex Core `CollectionV1`, whose update
+/// authority follows it.
+const CORE_KEY_COLLECTION_V1: u8 = 5;
+/// Companion transfer-hook program hooked Token-2022 objects name, and the
+/// seed of the PDA it signs `sync_hooked_owner` with.
+const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("CpbPbgWToyAmM3H1qEU6ZXvLCiLmCjeCR3uvB8xi9tJy");
+const HOOK_AUTHORITY_SEED: &[u8] = b"hook_authority";
+const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+/// Seed of an SPL Governance native treasury, derived with the governance
+/// account under the governance program.
+const GOVERNANCE_NATIVE_TREASURY_SEED: &[u8] = b"native-treasury";
+/// Anchor discriminator of the transfer-hook program's
+/// `initialize_object_hook`.
+const TRANSFER_HOOK_INITIALIZE: [u8; 8] = [128, 57, 76, 119, 248, 19, 246, 91];
 /// Update this array with any wallet addresses that are permitted to deploy the
 /// program or run the `initialize` instruction. For example:
 /// `const ALLOWED_DEPLOYERS: [Pubkey; 1] = [pubkey!("DeployerPubkey...")];`
@@ -48,21 +176,706 @@ const ALLOWED_DEPLOYERS: [Pubkey; 1] = [pubkey!("GwMpopxNkDYsnucBRPf47QSEsEzA3rS
 /// validation code) within Solana's stack limits while still supporting
 /// typical HTTPS or IPFS style URIs.
 const MAX_URI_LENGTH: usize = 128;
-const MANIFEST_PADDING: usize = 8;
+/// Number of mirror URIs a manifest can carry alongside its primary URI.
+const MAX_MIRROR_URIS: usize = 3;
+/// Domain prefixes a config can restrict metadata URIs to, and their length.
+const MAX_URI_PREFIXES: usize = 2;
+const MAX_URI_PREFIX_LENGTH: usize = 64;
+/// Layout written by the current program into `ObjectManifest::layout_version`.
+/// Manifests created before the field existed read as zero.
+const MANIFEST_LAYOUT_VERSION: u8 = 7;
+/// Layout written by the current program into `Config::layout_version`.
+/// Configs created before the field existed read as zero.
+const CONFIG_LAYOUT_VERSION: u8 = 2;
 const CREATOR_TOTAL_SHARE: u16 = 100;
+/// `seller_fee_basis_points` a mint passes to take the config's
+/// [`MetadataDefaults`] fee.
+pub const DEFAULT_SELLER_FEE_BASIS_POINTS: u16 = u16::MAX;
+/// Number of updates retained by a [`ManifestHistory`] ring buffer.
+const MANIFEST_HISTORY_CAPACITY: usize = 16;
+/// Number of ownership changes retained by a [`CustodyTrail`].
+const CUSTODY_TRAIL_CAPACITY: usize = 32;
+/// Number of authority actions retained by an [`AuditLog`].
+const AUDIT_LOG_CAPACITY: usize = 64;
+/// Longest [`ObjectReservation`] `reserve_object_id` grants, about a day.
+const MAX_RESERVATION_SLOTS: u64 = 216_000;
+/// Most assignments one [`Airdrop`] can hold, bounding its bitmap to 1 KiB.
+const MAX_AIRDROP_RECIPIENTS: u32 = 8192;
+/// Number of object ids stored in each [`ObjectIndexPage`].
+const OBJECT_INDEX_PAGE_CAPACITY: usize = 64;
+/// Number of configs listed in each [`NamespaceRegistryPage`].
+const NAMESPACE_REGISTRY_PAGE_CAPACITY: usize = 32;
+/// Number of object ids covered by each [`MintedBitmap`] chunk.
+const MINTED_BITMAP_BITS: usize = 8192;
+/// Maximum number of consecutive object ids covered by one [`HolderSnapshot`].
+const HOLDER_SNAPSHOT_CAPACITY: usize = 64;
+/// Layout version of [`ManifestView`], bumped whenever fields are added.
+const MANIFEST_VIEW_VERSION: u8 = 1;
+/// Upper bound on content staged in a [`ManifestPayloadBuffer`]. Keeps the
+/// buffer well inside the BPF heap when it is deserialized for hashing.
+const MAX_PAYLOAD_BUFFER_LEN: usize = 8 * 1024;
+/// Maximum number of curated categories a config can enable. Category `0`
+/// means "uncategorized" and is always accepted.
+const MAX_CATEGORIES: usize = 16;
+/// Maximum number of ancestors walked when linking objects, bounding both the
+/// hierarchy depth and the accounts a link transaction must supply.
+const MAX_OBJECT_DEPTH: usize = 8;
+/// Maximum number of source object ids recorded in a manifest's provenance.
+const MAX_PROVENANCE: usize = 8;
+/// Minimum number of objects consumed by `fuse_objects`.
+const MIN_FUSION_INPUTS: usize = 2;
+/// Maximum number of parts minted by `split_object`, bounded by transaction
+/// size since every part carries its own accounts.
+const MAX_SPLIT_PARTS: usize = 4;
+/// Decimals and supply of a Token-2022 object minted with a transfer fee.
+/// One object is 10,000 units, so a transfer of the whole object withholds
+/// exactly `seller_fee_basis_points` units for its creator.
+const ROYALTY_MINT_DECIMALS: u8 = 4;
+const ROYALTY_MINT_SUPPLY: u64 = 10_000;
+/// Transfer fees must stay below half of a transfer, so the recipient of a
+/// whole-object transfer still holds most of its units.
+const TRANSFER_FEE_BASIS_POINTS_LIMIT: u16 = 5_000;
 
 fn mpl_program_id() -> Pubkey {
     Pubkey::new_from_array(mpl_token_metadata::ID.to_bytes())
 }
 
+/// Metadata PDA of `mint` under `metadata_program`, derived like Token
+/// Metadata derives it so forks with another program id resolve too.
+fn find_metadata_pda(mint: &Pubkey, metadata_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata_program.as_ref(), mint.as_ref()],
+        metadata_program,
+    )
+    .0
+}
+
+/// Master edition PDA of `mint` under `metadata_program`.
+fn find_master_edition_pda(mint: &Pubkey, metadata_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            metadata_program.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        metadata_program,
+    )
+    .0
+}
+
+/// Token Metadata `MetadataDelegateRecord` PDA through which `delegate` acts
+/// for `update_authority` on the metadata of `mint`.
+fn find_metadata_delegate_record(
+    metadata_program: &Pubkey,
+    mint: &Pubkey,
+    scope: MetadataDelegateScope,
+    update_authority: &Pubkey,
+    delegate: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            metadata_program.as_ref(),
+            mint.as_ref(),
+            scope.role_seed(),
+            update_authority.as_ref(),
+            delegate.as_ref(),
+        ],
+        metadata_program,
+    )
+    .0
+}
+
 fn to_solana_pubkey(key: &Pubkey) -> SolanaProgramPubkey {
     SolanaProgramPubkey::new_from_array(key.to_bytes())
 }
 
-fn from_solana_pubkey(key: &SolanaProgramPubkey) -> Pubkey {
-    Pubkey::new_from_array(key.to_bytes())
+/// Fails with `$error` unless `$actual == $expected`, first logging
+/// `mismatch field=<field> expected=<expected> actual=<actual>` so failed
+/// transactions can be diagnosed from their logs alone.
+macro_rules! require_match {
+    ($field:literal, $actual:expr, $expected:expr, $error:expr $(,)?) => {{
+        let actual = $actual;
+        let expected = $expected;
+        if actual != expected {
+            msg!(
+                "mismatch field={} expected={} actual={}",
+                $field,
+                expected,
+                actual
+            );
+            return Err(Error::from($error));
+        }
+    }};
+}
+
+/// Emits `$event` as an Anchor event and also logs its compact
+/// [`log_record`] line, see [`log_record_layouts!`].
+macro_rules! emit_logged {
+    ($event:expr) => {{
+        let event = $event;
+        event.log_record();
+        emit!(event);
+    }};
+}
+
+/// [`emit_logged!`] for authority-gated instructions, which first append the
+/// event to the config's [`AuditLog`] when one is passed.
+macro_rules! emit_audited {
+    ($audit_log:expr, $event:expr) => {{
+        let event = $event;
+        record_audit_entry($audit_log, &event, &event.context)?;
+        event.log_record();
+        emit!(event);
+    }};
+}
+
+/// Declares the compact log line for each event: its kind, then the fields
+/// that follow the config, in order.
+macro_rules! log_record_layouts {
+    ($($event:ident($binding:ident) => $kind:literal [$($field:expr),* $(,)?];)*) => {
+        $(
+            impl $event {
+                fn log_record(&self) {
+                    let $binding = self;
+                    log_record($kind, &[$(&$field),*]);
+                }
+            }
+        )*
+    };
+}
+
+/// Lowercase hex rendering of hashes for logs and memos.
+#[derive(PartialEq, Eq)]
+struct Hex<'a>(&'a [u8]);
+
+impl std::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Version tag that starts every [`log_record`] line.
+const LOG_RECORD_PREFIX: &str = "OGAL1";
+
+/// Logs one `OGAL1|<kind>|<field>|...` line so log subscribers can follow
+/// ledger activity without decoding Anchor events. Fields never contain `|`.
+fn log_record(kind: &str, fields: &[&dyn std::fmt::Display]) {
+    msg!(&format_log_record(kind, fields));
+}
+
+fn format_log_record(kind: &str, fields: &[&dyn std::fmt::Display]) -> String {
+    use std::fmt::Write;
+
+    let mut line = format!("{}|{}", LOG_RECORD_PREFIX, kind);
+    for field in fields {
+        let _ = write!(line, "|{}", field);
+    }
+    line
+}
+
+/// Extends the manifest history chain with a new content link.
+///
+/// Each link commits to the previous link, the manifest hash being recorded,
+/// and the slot in which it was recorded, so the full sequence of content
+/// changes can be replayed and verified from emitted events.
+fn next_history_chain(previous: &[u8; 32], manifest_hash: &[u8; 32], slot: u64) -> [u8; 32] {
+    hashv(&[previous, manifest_hash, &slot.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes manifest content with the algorithm recorded on the manifest.
+///
+/// Blake3 is accepted as a declared algorithm but its syscall is not enabled on
+/// mainnet, so it cannot be checked on-chain.
+fn hash_manifest_payload(algorithm: ManifestHashAlgorithm, chunks: &[&[u8]]) -> Result<[u8; 32]> {
+    match algorithm {
+        ManifestHashAlgorithm::Sha256 => Ok(hashv(chunks).to_bytes()),
+        ManifestHashAlgorithm::Keccak256 => Ok(keccak::hashv(chunks).to_bytes()),
+        ManifestHashAlgorithm::Blake3 => err!(ErrorCode::UnsupportedHashAlgorithm),
+    }
+}
+
+const IPFS_SCHEME: &str = "ipfs://";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE16_ALPHABET: &[u8] = b"0123456789abcdef";
+
+pub const URI_SCHEME_HTTPS: u8 = 1 << 0;
+pub const URI_SCHEME_IPFS: u8 = 1 << 1;
+pub const URI_SCHEME_ARWEAVE: u8 = 1 << 2;
+/// Subsystems a config can switch off with `set_enabled_instructions`. Every
+/// config starts with all of them enabled.
+pub const INSTRUCTION_MINT: u16 = 1 << 0;
+pub const INSTRUCTION_UPDATE: u16 = 1 << 1;
+pub const INSTRUCTION_FUSE: u16 = 1 << 2;
+pub const INSTRUCTION_MARKETPLACE: u16 = 1 << 3;
+pub const INSTRUCTION_VAULT: u16 = 1 << 4;
+pub const INSTRUCTION_BRIDGE: u16 = 1 << 5;
+pub const INSTRUCTION_RECOVERY: u16 = 1 << 6;
+pub const INSTRUCTION_ALL: u16 = INSTRUCTION_MINT
+    | INSTRUCTION_UPDATE
+    | INSTRUCTION_FUSE
+    | INSTRUCTION_MARKETPLACE
+    | INSTRUCTION_VAULT
+    | INSTRUCTION_BRIDGE
+    | INSTRUCTION_RECOVERY;
+const URI_SCHEMES: [(u8, &str); 3] = [
+    (URI_SCHEME_HTTPS, "https://"),
+    (URI_SCHEME_IPFS, IPFS_SCHEME),
+    (URI_SCHEME_ARWEAVE, "ar://"),
+];
+const ALL_URI_SCHEMES: u8 = URI_SCHEME_HTTPS | URI_SCHEME_IPFS | URI_SCHEME_ARWEAVE;
+
+/// Applies the config's URI rules to a metadata URI. Control characters,
+/// embedded NULs included, are always rejected because wallets render these
+/// URIs; the scheme and domain prefix rules only apply once configured.
+fn validate_uri_rules(config: &Config, uri: &str) -> Result<()> {
+    require!(
+        !uri.chars().any(char::is_control),
+        ErrorCode::UriContainsControlCharacters
+    );
+    let schemes = config.allowed_uri_schemes;
+    if schemes != 0 {
+        require!(
+            URI_SCHEMES
+                .iter()
+                .any(|(bit, scheme)| schemes & bit != 0 && uri.starts_with(scheme)),
+            ErrorCode::UriSchemeNotAllowed
+        );
+    }
+    let mut prefixes = config.uri_prefixes().peekable();
+    if prefixes.peek().is_some() {
+        require!(
+            prefixes.any(|prefix| uri.as_bytes().starts_with(prefix)),
+            ErrorCode::UriPrefixNotAllowed
+        );
+    }
+
+    Ok(())
+}
+
+/// Seeds the Wormhole core bridge derives its own accounts from.
+const WORMHOLE_BRIDGE_SEED: &[u8] = b"Bridge";
+const WORMHOLE_FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+const WORMHOLE_SEQUENCE_SEED: &[u8] = b"Sequence";
+/// Core bridge `post_message` instruction tag and its finalized consistency
+/// level, so guardians only attest exports that cannot be rolled back.
+const WORMHOLE_POST_MESSAGE: u8 = 1;
+const WORMHOLE_CONSISTENCY_FINALIZED: u8 = 1;
+/// Offset of the message fee in the core bridge's `BridgeData` account.
+const WORMHOLE_FEE_OFFSET: usize = 16;
+/// Leading byte of the export payload, bumped if its layout ever changes.
+pub const EXPORT_PAYLOAD_ID: u8 = 1;
+
+/// Wormhole payload posted by `export_object`: the payload id, config,
+/// object id, manifest hash, hash algorithm, and owner. Integers are
+/// big-endian, as Wormhole payloads conventionally are.
+fn export_payload(manifest: &ObjectManifest, owner: &Pubkey) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(106);
+    payload.push(EXPORT_PAYLOAD_ID);
+    payload.extend_from_slice(manifest.config.as_ref());
+    payload.extend_from_slice(&manifest.object_id.to_be_bytes());
+    payload.extend_from_slice(&manifest.manifest_hash);
+    payload.push(manifest.hash_algorithm);
+    payload.extend_from_slice(owner.as_ref());
+    payload
+}
+
+/// Leading byte of the import payload a foreign emitter attests.
+pub const IMPORT_PAYLOAD_ID: u8 = 2;
+/// Leading bytes of a core bridge `PostedVAA` account, which the bridge only
+/// writes once the guardian signatures have been verified.
+const POSTED_VAA_MAGIC: &[u8] = b"vaa";
+/// Offset of `sequence` in a `PostedVAA` account, after the magic, version,
+/// consistency level, VAA time, signature set, submission time, and nonce.
+const POSTED_VAA_SEQUENCE_OFFSET: usize = 49;
+
+/// Fields of a posted VAA that `import_object` acts on.
+#[derive(Debug, PartialEq, Eq)]
+struct PostedVaa<'a> {
+    sequence: u64,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    payload: &'a [u8],
+}
+
+fn parse_posted_vaa(data: &[u8]) -> Result<PostedVaa<'_>> {
+    let header_len = POSTED_VAA_SEQUENCE_OFFSET + 8 + 2 + 32 + 4;
+    require!(
+        data.starts_with(POSTED_VAA_MAGIC) && data.len() >= header_len,
+        ErrorCode::InvalidVaa
+    );
+    let fields = &data[POSTED_VAA_SEQUENCE_OFFSET..header_len];
+    let payload_len = u32::from_le_bytes(fields[42..46].try_into().unwrap()) as usize;
+    let payload = data
+        .get(header_len..header_len + payload_len)
+        .ok_or(ErrorCode::InvalidVaa)?;
+
+    Ok(PostedVaa {
+        sequence: u64::from_le_bytes(fields[..8].try_into().unwrap()),
+        emitter_chain: u16::from_le_bytes(fields[8..10].try_into().unwrap()),
+        emitter_address: fields[10..42].try_into().unwrap(),
+        payload,
+    })
+}
+
+/// Foreign asset described by an import payload: the payload id, the
+/// asset's identifier on its origin chain, its manifest hash and hash
+/// algorithm, and the Solana recipient of the ledger object.
+#[derive(Debug, PartialEq, Eq)]
+struct ImportPayload {
+    foreign_id: [u8; 32],
+    manifest_hash: [u8; 32],
+    hash_algorithm: u8,
+    recipient: Pubkey,
+}
+
+fn parse_import_payload(payload: &[u8]) -> Result<ImportPayload> {
+    require!(
+        payload.len() == 98 && payload[0] == IMPORT_PAYLOAD_ID,
+        ErrorCode::InvalidImportPayload
+    );
+    Ok(ImportPayload {
+        foreign_id: payload[1..33].try_into().unwrap(),
+        manifest_hash: payload[33..65].try_into().unwrap(),
+        hash_algorithm: payload[65],
+        recipient: Pubkey::new_from_array(payload[66..98].try_into().unwrap()),
+    })
+}
+
+/// Reads the little-endian u64 at `offset` of a core bridge account, or
+/// `None` when the account is shorter, as a sequence tracker that the bridge
+/// has not created yet is.
+fn read_wormhole_u64(account: &AccountInfo, offset: usize) -> Result<Option<u64>> {
+    let data = account.try_borrow_data()?;
+    Ok(data
+        .get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap())))
+}
+
+/// Keccak-256 of `parts` with the leading byte cleared, which keeps the hash
+/// inside the BN254 field Light's circuits work in.
+fn light_field_hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hash = keccak::hashv(parts).to_bytes();
+    hash[0] = 0;
+    hash
+}
+
+/// Seed Light derives the compressed address of `object_id` under `config`
+/// from.
+pub fn compressed_manifest_address_seed(config: &Pubkey, object_id: u64) -> [u8; 32] {
+    light_field_hash(&[
+        crate::ID.as_ref(),
+        COMPRESSED_MANIFEST_SEED,
+        config.as_ref(),
+        &object_id.to_le_bytes(),
+    ])
+}
+
+/// Compressed address of `seed` in `address_tree`, as Light's
+/// `derive_address` computes it.
+pub fn compressed_manifest_address(address_tree: &Pubkey, seed: &[u8; 32]) -> [u8; 32] {
+    light_field_hash(&[address_tree.as_ref(), seed, &[u8::MAX]])
+}
+
+/// Groth16 validity proof from a Light prover, in its compressed form.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressedProof {
+    pub a: [u8; 32],
+    pub b: [u8; 64],
+    pub c: [u8; 32],
+}
+
+/// Address tree a new compressed manifest's address is inserted into: the
+/// indices of the tree and its queue among the remaining accounts, and of the
+/// tree root the proof was made against.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LightAddressTreeInfo {
+    pub address_merkle_tree_account_index: u8,
+    pub address_queue_account_index: u8,
+    pub address_merkle_tree_root_index: u16,
+}
+
+/// Leaf holding an existing compressed manifest: the indices of its state
+/// tree and nullifier queue among the remaining accounts, its leaf index, and
+/// the index of the tree root the proof was made against.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LightStateTreeInfo {
+    pub merkle_tree_account_index: u8,
+    pub nullifier_queue_account_index: u8,
+    pub leaf_index: u32,
+    pub root_index: u16,
+}
+
+/// Light system program `InstructionDataInvokeCpi`. The field order of this
+/// and the types below is Light's Borsh layout.
+#[derive(AnchorSerialize)]
+struct LightInvokeCpi {
+    proof: Option<CompressedProof>,
+    new_address_params: Vec<LightNewAddressParams>,
+    input_compressed_accounts: Vec<LightInputAccount>,
+    output_compressed_accounts: Vec<LightOutputAccount>,
+    relay_fee: Option<u64>,
+    compress_or_decompress_lamports: Option<u64>,
+    is_compress: bool,
+    /// Always `None`: each instruction makes a single Light CPI.
+    cpi_context: Option<[u8; 3]>,
+}
+
+#[derive(AnchorSerialize)]
+struct LightNewAddressParams {
+    seed: [u8; 32],
+    address_queue_account_index: u8,
+    address_merkle_tree_account_index: u8,
+    address_merkle_tree_root_index: u16,
+}
+
+#[derive(AnchorSerialize)]
+struct LightCompressedAccount {
+    owner: Pubkey,
+    lamports: u64,
+    address: Option<[u8; 32]>,
+    data: Option<LightCompressedAccountData>,
+}
+
+#[derive(AnchorSerialize)]
+struct LightCompressedAccountData {
+    discriminator: [u8; 8],
+    data: Vec<u8>,
+    data_hash: [u8; 32],
+}
+
+#[derive(AnchorSerialize)]
+struct LightInputAccount {
+    compressed_account: LightCompressedAccount,
+    merkle_tree_account_index: u8,
+    nullifier_queue_account_index: u8,
+    leaf_index: u32,
+    /// Always `None`, so inclusion is proven against `root_index`.
+    queue_index: Option<(u8, u16)>,
+    root_index: u16,
+    read_only: bool,
+}
+
+#[derive(AnchorSerialize)]
+struct LightOutputAccount {
+    compressed_account: LightCompressedAccount,
+    merkle_tree_index: u8,
+}
+
+/// Compressed account holding `manifest` at `address`. Inputs only carry the
+/// data hash, which Light checks against the leaf.
+fn light_manifest_account(
+    manifest: &CompressedManifest,
+    address: [u8; 32],
+    with_data: bool,
+) -> LightCompressedAccount {
+    let data = manifest.data();
+    let data_hash = light_field_hash(&[&data]);
+    LightCompressedAccount {
+        owner: crate::ID,
+        lamports: 0,
+        address: Some(address),
+        data: Some(LightCompressedAccountData {
+            discriminator: CompressedManifest::DISCRIMINATOR,
+            data: if with_data { data } else { Vec::new() },
+            data_hash,
+        }),
+    }
+}
+
+/// Invokes the Light system program's `invoke_cpi` signed by the program's
+/// CPI authority. `trees` are the remaining accounts the indices in `inputs`
+/// point into.
+fn invoke_light_system<'info>(
+    light: &LightSystemAccounts<'info>,
+    payer: &AccountInfo<'info>,
+    trees: &[AccountInfo<'info>],
+    cpi_authority_bump: u8,
+    inputs: LightInvokeCpi,
+) -> Result<()> {
+    let inputs = inputs
+        .try_to_vec()
+        .expect("Light CPI inputs always serialize");
+    let mut data = hash(b"global:invoke_cpi").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&(inputs.len() as u32).to_le_bytes());
+    data.extend_from_slice(&inputs);
+
+    // The SOL pool, decompression recipient, and CPI context accounts are
+    // unused; Light takes its own program id in their place.
+    let mut accounts = vec![
+        AccountMeta::new(payer.key(), true),
+        AccountMeta::new_readonly(light.cpi_authority.key(), true),
+        AccountMeta::new_readonly(light.registered_program_pda.key(), false),
+        AccountMeta::new_readonly(SPL_NOOP_ID, false),
+        AccountMeta::new_readonly(light.account_compression_authority.key(), false),
+        AccountMeta::new_readonly(LIGHT_ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+        AccountMeta::new_readonly(crate::ID, false),
+        AccountMeta::new_readonly(LIGHT_SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(LIGHT_SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(LIGHT_SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(trees.iter().map(|tree| AccountMeta::new(tree.key(), false)));
+    let mut infos = vec![
+        payer.clone(),
+        light.cpi_authority.to_account_info(),
+        light.registered_program_pda.to_account_info(),
+        light.noop_program.to_account_info(),
+        light.account_compression_authority.to_account_info(),
+        light.account_compression_program.to_account_info(),
+        light.invoking_program.to_account_info(),
+        light.system_program.to_account_info(),
+        light.light_system_program.to_account_info(),
+    ];
+    infos.extend_from_slice(trees);
+
+    invoke_signed(
+        &Instruction {
+            program_id: LIGHT_SYSTEM_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &infos,
+        &[&[LIGHT_CPI_AUTHORITY_SEED, &[cpi_authority_bump]]],
+    )?;
+    Ok(())
+}
+
+/// Solana Attestation Service, whose attestations `add_attestation` links to
+/// manifests.
+const SAS_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+/// Leading byte of a Solana Attestation Service `Attestation` account.
+const SAS_ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+/// Fields of a Solana Attestation Service attestation that OGAL records.
+/// `nonce` names the attestation's subject, and an `expiry` of zero never
+/// expires.
+#[derive(Debug, PartialEq, Eq)]
+struct SasAttestation {
+    nonce: Pubkey,
+    credential: Pubkey,
+    schema: Pubkey,
+    signer: Pubkey,
+    expiry: i64,
+}
+
+/// Parses an `Attestation` account: the discriminator, `nonce`,
+/// `credential`, and `schema`, a `u32`-prefixed `data` blob, then `signer`,
+/// `expiry`, and `token_account`.
+fn parse_sas_attestation(data: &[u8]) -> Result<SasAttestation> {
+    require!(
+        data.len() >= 101 && data[0] == SAS_ATTESTATION_DISCRIMINATOR,
+        ErrorCode::InvalidAttestationAccount
+    );
+    let data_len = u32::from_le_bytes(data[97..101].try_into().unwrap()) as usize;
+    let tail = data
+        .get(101 + data_len..101 + data_len + 72)
+        .ok_or(ErrorCode::InvalidAttestationAccount)?;
+
+    Ok(SasAttestation {
+        nonce: Pubkey::new_from_array(data[1..33].try_into().unwrap()),
+        credential: Pubkey::new_from_array(data[33..65].try_into().unwrap()),
+        schema: Pubkey::new_from_array(data[65..97].try_into().unwrap()),
+        signer: Pubkey::new_from_array(tail[..32].try_into().unwrap()),
+        expiry: i64::from_le_bytes(tail[32..40].try_into().unwrap()),
+    })
+}
+
+/// Checks that an attestation is about this object, naming either its
+/// manifest or its mint as the subject, and has not expired.
+fn require_linkable_attestation(
+    attestation: &SasAttestation,
+    manifest_key: &Pubkey,
+    manifest: &ObjectManifest,
+    now: i64,
+) -> Result<()> {
+    require!(
+        attestation.nonce == *manifest_key || attestation.nonce == manifest.mint,
+        ErrorCode::AttestationSubjectMismatch
+    );
+    require!(
+        attestation.expiry == 0 || attestation.expiry > now,
+        ErrorCode::AttestationExpired
+    );
+    Ok(())
+}
+
+/// Checks that a KYC attestation was issued under the gate's credential and
+/// schema, names `recipient` as its subject, and has not expired.
+fn require_kyc_attestation(
+    kyc_gate: &KycGate,
+    attestation: &SasAttestation,
+    recipient: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        attestation.credential == kyc_gate.credential
+            && (kyc_gate.schema == Pubkey::default() || attestation.schema == kyc_gate.schema),
+        ErrorCode::InvalidKycAttestation
+    );
+    require!(
+        attestation.nonce == *recipient,
+        ErrorCode::AttestationSubjectMismatch
+    );
+    require!(
+        attestation.expiry == 0 || attestation.expiry > now,
+        ErrorCode::AttestationExpired
+    );
+    Ok(())
+}
+
+/// Returns whether `uri` is content addressed, rejecting `ipfs://` URIs whose
+/// CID is not syntactically well-formed. Other schemes are accepted as-is.
+///
+/// CIDv0 must be a 46 character base58btc `Qm...` string. CIDv1 must carry a
+/// base32 (`b`), base58btc (`z`), or base16 (`f`) multibase prefix and be at
+/// least long enough to encode a 32 byte multihash.
+fn validate_content_address(uri: &str) -> Result<bool> {
+    let Some(path) = uri.strip_prefix(IPFS_SCHEME) else {
+        return Ok(false);
+    };
+    let cid = path.split('/').next().unwrap_or_default().as_bytes();
+
+    // CIDv0 has no multibase prefix; "Qm" is part of the base58btc body.
+    let (alphabet, body, min_len, max_len) = match cid {
+        [b'Q', b'm', ..] => (BASE58_ALPHABET, cid, 46, 46),
+        [b'b', body @ ..] => (BASE32_ALPHABET, body, 59, MAX_URI_LENGTH),
+        [b'z', body @ ..] => (BASE58_ALPHABET, body, 49, MAX_URI_LENGTH),
+        [b'f', body @ ..] => (BASE16_ALPHABET, body, 73, MAX_URI_LENGTH),
+        _ => return err!(ErrorCode::InvalidIpfsCid),
+    };
+    require!(
+        (min_len..=max_len).contains(&cid.len()) && body.iter().all(|c| alphabet.contains(c)),
+        ErrorCode::InvalidIpfsCid
+    );
+
+    Ok(true)
+}
+
+const ARWEAVE_URI_PREFIXES: [&str; 3] = ["ar://", "https://arweave.net/", "http://arweave.net/"];
+
+/// Requires a non-zero Arweave transaction id whenever `uri` points at
+/// Arweave, so verifiers can resolve the permanent copy if the gateway rots.
+fn validate_arweave_tx_id(uri: &str, arweave_tx_id: &[u8; 32]) -> Result<()> {
+    if ARWEAVE_URI_PREFIXES
+        .iter()
+        .any(|prefix| uri.starts_with(prefix))
+    {
+        require!(*arweave_tx_id != [0u8; 32], ErrorCode::MissingArweaveTxId);
+    }
+    Ok(())
 }
 
+/// Length of the Borsh-encoded part of a Token Metadata account, where any
+/// TLV extensions begin. Token Metadata appends optional fields over time and
+/// older accounts stop before the newer ones, so the optional tail is parsed
+/// leniently: it ends at the end of the data, or at the first field that does
+/// not decode, such as a TLV entry written straight after a shorter layout.
 fn metadata_account_base_len(account_data: &[u8]) -> Option<usize> {
     let mut cursor = account_data;
 
@@ -72,64 +885,61 @@ fn metadata_account_base_len(account_data: &[u8]) -> Option<usize> {
     Data::deserialize(&mut cursor).ok()?;
     bool::deserialize(&mut cursor).ok()?;
     bool::deserialize(&mut cursor).ok()?;
-    Option::<u8>::deserialize(&mut cursor).ok()?;
-    Option::<TokenStandard>::deserialize(&mut cursor).ok()?;
-    Option::<Collection>::deserialize(&mut cursor).ok()?;
-    Option::<Uses>::deserialize(&mut cursor).ok()?;
-    fn consume_optional<'a, T: BorshDeserialize>(cursor: &mut &'a [u8]) -> bool {
-        if cursor.is_empty() {
-            return false;
-        }
-
-        match cursor[0] {
-            0 => {
-                *cursor = &cursor[1..];
+
+    // edition_nonce, token_standard, collection, uses, collection_details,
+    // and programmable_config, in account order.
+    let optional_fields: [fn(&mut &[u8]) -> bool; 6] = [
+        skip_borsh::<u8>,
+        skip_token_standard,
+        skip_borsh::<Collection>,
+        skip_borsh::<Uses>,
+        skip_borsh::<CollectionDetails>,
+        skip_borsh::<ProgrammableConfig>,
+    ];
+    for skip_value in optional_fields {
+        if !skip_optional(&mut cursor, skip_value) {
+            break;
+        }
+    }
+
+    Some(account_data.len() - cursor.len())
+}
+
+/// Advances past one Borsh `Option` if it decodes, leaving the cursor in
+/// place otherwise.
+fn skip_optional(cursor: &mut &[u8], skip_value: fn(&mut &[u8]) -> bool) -> bool {
+    match cursor.first() {
+        Some(0) => {
+            *cursor = &cursor[1..];
+            true
+        }
+        Some(1) => {
+            let mut rest = &cursor[1..];
+            if skip_value(&mut rest) {
+                *cursor = rest;
                 true
+            } else {
+                false
             }
-            1 => {
-                let before = *cursor;
-                let mut rest = &cursor[1..];
-                match T::deserialize(&mut rest) {
-                    Ok(_) => {
-                        *cursor = rest;
-                        true
-                    }
-                    Err(_) => {
-                        #[cfg(test)]
-                        eprintln!(
-                            "consume_optional failed for {} (first byte {}, len {})",
-                            std::any::type_name::<T>(),
-                            cursor[0],
-                            cursor.len()
-                        );
-                        *cursor = before;
-                        false
-                    }
-                }
-            }
-            _ => false,
         }
+        _ => false,
     }
+}
 
-    let collection_details_cursor = cursor;
-    if !consume_optional::<CollectionDetails>(&mut cursor) {
-        return Some(
-            account_data
-                .len()
-                .saturating_sub(collection_details_cursor.len()),
-        );
-    }
+fn skip_borsh<T: BorshDeserialize>(cursor: &mut &[u8]) -> bool {
+    T::deserialize(cursor).is_ok()
+}
 
-    let programmable_config_cursor = cursor;
-    if !consume_optional::<ProgrammableConfig>(&mut cursor) {
-        return Some(
-            account_data
-                .len()
-                .saturating_sub(programmable_config_cursor.len()),
-        );
+/// Token standards are a one-byte enum that Token Metadata keeps extending,
+/// so any value is accepted rather than only the variants this crate knows.
+fn skip_token_standard(cursor: &mut &[u8]) -> bool {
+    match cursor.split_first() {
+        Some((_, rest)) => {
+            *cursor = rest;
+            true
+        }
+        None => false,
     }
-
-    Some(account_data.len().saturating_sub(cursor.len()))
 }
 
 #[derive(Clone, Copy, SplDiscriminate)]
@@ -148,13 +958,32 @@ fn read_collection_details_from_tlv(account_data: &[u8]) -> Option<CollectionDet
     CollectionDetails::deserialize(&mut value).ok()
 }
 
+/// Parser entry points for the `fuzz` crate. Not part of the stable API.
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzzing {
+    use mpl_token_metadata::types::CollectionDetails;
+
+    pub const MAX_URI_LENGTH: usize = super::MAX_URI_LENGTH;
+
+    pub fn metadata_account_base_len(account_data: &[u8]) -> Option<usize> {
+        super::metadata_account_base_len(account_data)
+    }
+
+    pub fn read_collection_details_from_tlv(account_data: &[u8]) -> Option<CollectionDetails> {
+        super::read_collection_details_from_tlv(account_data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use borsh::BorshSerialize;
+    use mpl_token_metadata::accounts::MasterEdition as MetadataMasterEdition;
+    use mpl_token_metadata::types::{TokenStandard, UseMethod};
+    use proptest::prelude::*;
 
-    #[test]
-    fn metadata_base_len_handles_missing_optional_tail() {
+    fn collection_metadata_bytes(collection_details: Option<CollectionDetails>) -> Vec<u8> {
         let metadata = MetadataAccount {
             key: MetadataKey::MetadataV1,
             update_authority: Pubkey::new_unique(),
@@ -170,1278 +999,18162 @@ mod tests {
             token_standard: None,
             collection: None,
             uses: None,
-            collection_details: None,
+            collection_details,
             programmable_config: None,
         };
-
         let mut data = Vec::new();
         metadata.serialize(&mut data).unwrap();
+        data
+    }
 
-        let base_len = metadata_account_base_len(&data).unwrap();
-        assert_eq!(base_len, data.len());
-        assert!(read_collection_details_from_tlv(&data).is_none());
+    fn append_collection_details_tlv(data: &mut Vec<u8>, size: u64) {
+        let mut value = Vec::new();
+        CollectionDetails::V1 { size }
+            .serialize(&mut value)
+            .unwrap();
+        data.extend_from_slice(CollectionDetailsTag::SPL_DISCRIMINATOR_SLICE);
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(&value);
     }
-}
 
-#[program]
-pub mod owner_governed_asset_ledger {
-    use super::*;
+    proptest! {
+        #[test]
+        fn metadata_parser_accepts_arbitrary_bytes(
+            data in proptest::collection::vec(any::<u8>(), 0..1024)
+        ) {
+            if let Some(base_len) = metadata_account_base_len(&data) {
+                prop_assert!(base_len <= data.len());
+            }
+            let _ = read_collection_details_from_tlv(&data);
+        }
 
-    /// Initializes a configuration instance under the provided namespace.
-    ///
-    /// Passing a distinct namespace allows the authority to operate multiple
-    /// configurations concurrently or migrate to a new namespace without
-    /// redeploying the program. To migrate, derive the desired namespace,
-    /// invoke [`initialize`] (or [`migrate_config_namespace`]) with the new
-    /// namespace, and point subsequent instructions at the new config PDA.
-    pub fn initialize(ctx: Context<Initialize>, namespace: Pubkey) -> Result<()> {
-        let config_bump = ctx.bumps.config;
-        let auth_bump = ctx.bumps.auth;
+        #[test]
+        fn metadata_parser_handles_truncated_accounts(
+            size in any::<u64>(),
+            sized in any::<bool>(),
+            cut in 0usize..512,
+        ) {
+            let details = sized.then_some(CollectionDetails::V1 { size });
+            let data = collection_metadata_bytes(details);
+            let cut = cut.min(data.len());
+            let truncated = &data[..cut];
 
-        let authority_key = ctx.accounts.authority.key();
-        let payer_key = ctx.accounts.payer.key();
-        require!(
-            authority_key == payer_key || is_allowed_deployer(&authority_key),
-            ErrorCode::UnauthorizedDeployer
-        );
+            if let Some(base_len) = metadata_account_base_len(truncated) {
+                prop_assert!(base_len <= cut);
+            }
+            prop_assert!(read_collection_details_from_tlv(truncated).is_none());
+        }
 
-        let config = &mut ctx.accounts.config;
-        config.authority = authority_key;
-        config.config_bump = config_bump;
-        config.auth_bump = auth_bump;
-        config.object_count = 0;
-        config.namespace = namespace;
-        config.paused = false;
+        #[test]
+        fn tlv_collection_details_round_trip(
+            size in any::<u64>(),
+            drop_options in any::<bool>(),
+            padding in 0usize..64,
+        ) {
+            let mut data = collection_metadata_bytes(None);
+            if drop_options {
+                data.truncate(data.len() - 2);
+            }
+            append_collection_details_tlv(&mut data, size);
+            data.resize(data.len() + padding, 0);
 
-        let auth = &mut ctx.accounts.auth;
-        auth.config = config.key();
-        auth.bump = auth_bump;
+            prop_assert_eq!(
+                read_collection_details_from_tlv(&data),
+                Some(CollectionDetails::V1 { size })
+            );
+        }
 
-        Ok(())
+        #[test]
+        fn set_metadata_uri_round_trips_up_to_capacity(
+            previous in "\\PC{0,32}",
+            uri in "\\PC{0,32}",
+            ascii in "[ -~]{0,128}",
+        ) {
+            let mut manifest = ObjectManifest::zeroed();
+            for value in [previous.as_str(), uri.as_str(), ascii.as_str()] {
+                manifest.set_metadata_uri(value);
+                prop_assert!(manifest.metadata_uri_equals(value));
+                prop_assert_eq!(manifest.metadata_uri_len(), value.len());
+                prop_assert!(manifest.metadata_uri[value.len()..].iter().all(|byte| *byte == 0));
+            }
+        }
     }
 
-    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        config.authority = new_authority;
+    /// The required fields every Token Metadata account starts with.
+    fn metadata_required_fields() -> Vec<u8> {
+        let mut data = Vec::new();
+        MetadataKey::MetadataV1.serialize(&mut data).unwrap();
+        Pubkey::new_unique().serialize(&mut data).unwrap();
+        Pubkey::new_unique().serialize(&mut data).unwrap();
+        Data {
+            name: "Collection".into(),
+            symbol: "COLL".into(),
+            uri: "https://example.com/collection.json".into(),
+            seller_fee_basis_points: 500,
+            creators: None,
+        }
+        .serialize(&mut data)
+        .unwrap();
+        false.serialize(&mut data).unwrap();
+        true.serialize(&mut data).unwrap();
+        data
+    }
 
-        Ok(())
+    fn borsh_bytes<T: BorshSerialize>(value: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        value.serialize(&mut data).unwrap();
+        data
     }
 
-    pub fn rotate_collection_authority(
-        ctx: Context<RotateCollectionAuthority>,
-        new_update_authority: Pubkey,
-    ) -> Result<()> {
-        require_keys_eq!(
-            ctx.accounts.token_metadata_program.key(),
-            mpl_program_id(),
-            ErrorCode::InvalidTokenMetadataProgram
-        );
+    /// Optional tails Token Metadata has written after the required fields,
+    /// from accounts that predate the optional fields to current pNFTs.
+    fn metadata_optional_tail_corpus() -> Vec<(&'static str, Vec<u8>)> {
+        let collection = Collection {
+            verified: true,
+            key: Pubkey::new_unique(),
+        };
+        let uses = Uses {
+            use_method: UseMethod::Multiple,
+            remaining: 3,
+            total: 5,
+        };
+        vec![
+            ("no optional fields", Vec::new()),
+            ("edition nonce only", vec![1, 254]),
+            ("truncated after token standard", vec![1, 254, 1, 0]),
+            ("legacy, truncated after uses", vec![0, 1, 3, 0, 0]),
+            ("every optional unset", vec![0; 6]),
+            (
+                "collection item with uses",
+                [
+                    vec![1, 253, 1, 0],
+                    borsh_bytes(&Some(collection.clone())),
+                    borsh_bytes(&Some(uses)),
+                    vec![0, 0],
+                ]
+                .concat(),
+            ),
+            (
+                "sized collection",
+                [
+                    vec![0, 1, 0, 0, 0],
+                    borsh_bytes(&Some(CollectionDetails::V1 { size: 42 })),
+                    vec![0],
+                ]
+                .concat(),
+            ),
+            (
+                "programmable NFT with a rule set",
+                [
+                    vec![1, 255],
+                    borsh_bytes(&Some(TokenStandard::ProgrammableNonFungible)),
+                    borsh_bytes(&Some(collection.clone())),
+                    vec![0, 0],
+                    borsh_bytes(&Some(ProgrammableConfig::V1 {
+                        rule_set: Some(Pubkey::new_unique()),
+                    })),
+                ]
+                .concat(),
+            ),
+            (
+                "programmable edition without a rule set",
+                [
+                    vec![0],
+                    borsh_bytes(&Some(TokenStandard::ProgrammableNonFungibleEdition)),
+                    borsh_bytes(&Some(collection)),
+                    vec![0, 0],
+                    borsh_bytes(&Some(ProgrammableConfig::V1 { rule_set: None })),
+                ]
+                .concat(),
+            ),
+            (
+                "token standard newer than this crate",
+                vec![0, 1, 0xf0, 0, 0, 0, 0],
+            ),
+        ]
+    }
 
-        let config_key = ctx.accounts.config.key();
-        let collection_mint_key = ctx.accounts.collection_mint.key();
-        let mpl_collection_mint_key = to_solana_pubkey(&collection_mint_key);
-        let (expected_collection_metadata_mpl, _) =
-            MetadataAccount::find_pda(&mpl_collection_mint_key);
-        let expected_collection_metadata = from_solana_pubkey(&expected_collection_metadata_mpl);
+    #[test]
+    fn metadata_parser_reads_every_layout_in_the_corpus() {
+        for (layout, tail) in metadata_optional_tail_corpus() {
+            let mut data = metadata_required_fields();
+            data.extend_from_slice(&tail);
+            let base_len = data.len();
+            assert_eq!(metadata_account_base_len(&data), Some(base_len), "{layout}");
+            assert!(
+                read_collection_details_from_tlv(&data).is_none(),
+                "{layout}"
+            );
 
-        require_keys_eq!(
-            ctx.accounts.collection_metadata.key(),
-            expected_collection_metadata,
-            ErrorCode::InvalidCollectionMetadataAccount
-        );
+            append_collection_details_tlv(&mut data, 7);
+            assert_eq!(
+                metadata_account_base_len(&data),
+                Some(base_len),
+                "{layout} with TLV"
+            );
+            assert_eq!(
+                read_collection_details_from_tlv(&data),
+                Some(CollectionDetails::V1 { size: 7 }),
+                "{layout} with TLV"
+            );
+        }
+    }
 
-        let metadata_program_info = ctx.accounts.token_metadata_program.to_account_info();
-        let collection_metadata_info = ctx.accounts.collection_metadata.to_account_info();
-        let auth_info = ctx.accounts.auth.to_account_info();
+    #[test]
+    fn metadata_parser_stops_at_an_undecodable_optional() {
+        let required_len = metadata_required_fields().len();
+        // A collection whose key is cut short ends the base at its tag.
+        let mut data = metadata_required_fields();
+        data.extend_from_slice(&[0, 0, 1, 1, 9, 9]);
+        assert_eq!(metadata_account_base_len(&data), Some(required_len + 2));
+        assert!(read_collection_details_from_tlv(&data).is_none());
 
-        let auth_bump = ctx.accounts.auth.bump;
-        let signer_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[auth_bump]];
+        // Truncated required fields are still rejected.
+        let data = metadata_required_fields();
+        assert_eq!(metadata_account_base_len(&data[..required_len - 1]), None);
+    }
 
-        let args = UpdateMetadataAccountV2InstructionArgs {
-            data: None,
-            new_update_authority: Some(to_solana_pubkey(&new_update_authority)),
-            primary_sale_happened: None,
-            is_mutable: None,
+    #[test]
+    fn metadata_base_len_handles_missing_optional_tail() {
+        let metadata = MetadataAccount {
+            key: MetadataKey::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: "Collection".into(),
+            symbol: "COLL".into(),
+            uri: "https://example.com/collection.json".into(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
         };
 
-        UpdateMetadataAccountV2Cpi::new(
-            &metadata_program_info,
-            UpdateMetadataAccountV2CpiAccounts {
-                metadata: &collection_metadata_info,
-                update_authority: &auth_info,
-            },
-            args,
-        )
-        .invoke_signed(&[signer_seeds])
-        .map_err(anchor_lang::error::Error::from)?;
+        let mut data = Vec::new();
+        metadata.serialize(&mut data).unwrap();
 
-        Ok(())
+        let base_len = metadata_account_base_len(&data).unwrap();
+        assert_eq!(base_len, data.len());
+        assert!(read_collection_details_from_tlv(&data).is_none());
     }
 
-    pub fn mint_object_nft<'info>(
-        ctx: Context<'_, '_, 'info, 'info, MintObjectNft<'info>>,
-        object_id: u64,
-        manifest_uri: String,
-        manifest_hash: [u8; 32],
-        metadata_name: String,
-        metadata_symbol: String,
-        seller_fee_basis_points: u16,
-        creators: Vec<CreatorInput>,
-    ) -> Result<()> {
-        let metadata_accounts = ctx.accounts.metadata.clone();
-        let (
-            collection_metadata_account,
-            collection_master_edition_account,
-            rent_sysvar_account,
-            instructions_sysvar_account,
-            creator_remaining_accounts,
-        ) = metadata_remaining_accounts(ctx.remaining_accounts)?;
-        require!(
-            collection_metadata_account.is_writable,
-            ErrorCode::InvalidCollectionMetadataAccount
-        );
-        require!(
-            collection_master_edition_account.is_writable,
-            ErrorCode::InvalidCollectionMasterEditionAccount
-        );
+    #[test]
+    fn history_chain_commits_to_every_input() {
+        let genesis = [0u8; 32];
+        let hash = [7u8; 32];
+        let link = next_history_chain(&genesis, &hash, 10);
 
-        require!(!ctx.accounts.base.config.paused, ErrorCode::MintingPaused);
+        assert_eq!(link, next_history_chain(&genesis, &hash, 10));
+        assert_ne!(link, next_history_chain(&genesis, &hash, 11));
+        assert_ne!(link, next_history_chain(&genesis, &[8u8; 32], 10));
+        assert_ne!(link, next_history_chain(&link, &hash, 10));
+    }
 
-        let config_key = ctx.accounts.base.config.key();
-        let payer = &ctx.accounts.base.payer;
-        let payer_key = payer.key();
-        let payer_account_info = payer.to_account_info();
-        let system_program_account_info = ctx.accounts.base.system_program.to_account_info();
-        let token_program_account_info = ctx.accounts.base.token_program.to_account_info();
-        let associated_token_program_account_info =
-            ctx.accounts.base.associated_token_program.to_account_info();
-        let auth_account_info = ctx.accounts.base.auth.to_account_info();
-        let recipient_account_info = ctx.accounts.base.recipient.to_account_info();
-
-        let object_id_bytes = object_id.to_le_bytes();
-        let manifest_key = ctx.accounts.base.object_manifest.key();
-        let (expected_manifest_key, manifest_bump) = Pubkey::find_program_address(
-            &[MANIFEST_SEED, config_key.as_ref(), &object_id_bytes],
-            ctx.program_id,
+    #[test]
+    fn hash_algorithm_rejects_unknown_values() {
+        assert_eq!(
+            ManifestHashAlgorithm::try_from(0).unwrap(),
+            ManifestHashAlgorithm::Sha256
         );
-        require_keys_eq!(
-            manifest_key,
-            expected_manifest_key,
-            ErrorCode::InvalidManifestAccount
+        assert_eq!(
+            ManifestHashAlgorithm::try_from(2).unwrap(),
+            ManifestHashAlgorithm::Blake3
         );
+        assert!(ManifestHashAlgorithm::try_from(3).is_err());
+    }
 
-        let manifest_info = ctx.accounts.base.object_manifest.to_account_info();
-        ensure_object_manifest_account(
-            &manifest_info,
-            &payer_account_info,
-            &system_program_account_info,
-            ctx.program_id,
-            &[
-                MANIFEST_SEED,
-                config_key.as_ref(),
-                &object_id_bytes,
-                &[manifest_bump],
-            ],
-        )?;
-
-        let mint_key = ctx.accounts.base.object_mint.key();
-        let (expected_mint_key, object_mint_bump) =
-            Pubkey::find_program_address(&[MINT_SEED, manifest_key.as_ref()], ctx.program_id);
-        require_keys_eq!(
-            mint_key,
-            expected_mint_key,
-            ErrorCode::InvalidObjectMintAccount
-        );
+    #[test]
+    fn payload_hash_matches_across_chunk_boundaries() {
+        let payload: &[u8] = b"{\"level\":1,\"pieces\":[1,2,3]}";
+        let (head, tail) = payload.split_at(9);
 
-        require_keys_eq!(
-            rent_sysvar_account.key(),
-            sysvar::rent::id(),
-            ErrorCode::InvalidRentSysvar
-        );
-        if let Some(ref account) = instructions_sysvar_account {
-            require_keys_eq!(
-                account.key(),
-                sysvar::instructions::id(),
-                ErrorCode::InvalidInstructionsSysvar
+        for algorithm in [
+            ManifestHashAlgorithm::Sha256,
+            ManifestHashAlgorithm::Keccak256,
+        ] {
+            assert_eq!(
+                hash_manifest_payload(algorithm, &[head, tail]).unwrap(),
+                hash_manifest_payload(algorithm, &[payload]).unwrap()
             );
         }
+        assert!(hash_manifest_payload(ManifestHashAlgorithm::Blake3, &[payload]).is_err());
+    }
 
-        let object_mint_info = ctx.accounts.base.object_mint.to_account_info();
-        ensure_object_mint_account(
-            &object_mint_info,
-            &payer_account_info,
-            &system_program_account_info,
-            &token_program_account_info,
-            &[MINT_SEED, manifest_key.as_ref(), &[object_mint_bump]],
-            &auth_account_info,
-        )?;
+    #[test]
+    fn category_filter_offsets_match_layout() {
+        let manifest = ObjectManifest::zeroed();
+        let base = &manifest as *const ObjectManifest as usize;
+        let category = &manifest.category as *const u16 as usize;
+        let tag_hash = manifest.tag_hash.as_ptr() as usize;
 
-        let expected_recipient_ata = associated_token::get_associated_token_address(
-            &ctx.accounts.base.recipient.key(),
-            &mint_key,
+        assert_eq!(8 + category - base, ObjectManifest::CATEGORY_OFFSET);
+        assert_eq!(8 + tag_hash - base, ObjectManifest::TAG_HASH_OFFSET);
+        let parent = manifest.parent.as_ref().as_ptr() as usize;
+        assert_eq!(8 + parent - base, ObjectManifest::PARENT_OFFSET);
+        let level = &manifest.level as *const u16 as usize;
+        assert_eq!(8 + level - base, ObjectManifest::LEVEL_OFFSET);
+        let owner = manifest.owner.as_ref().as_ptr() as usize;
+        assert_eq!(8 + owner - base, ObjectManifest::OWNER_OFFSET);
+        let delegate = manifest.marketplace_delegate.as_ref().as_ptr() as usize;
+        assert_eq!(
+            8 + delegate - base,
+            ObjectManifest::MARKETPLACE_DELEGATE_OFFSET
         );
-        require_keys_eq!(
-            ctx.accounts.base.recipient_token_account.key(),
-            expected_recipient_ata,
-            ErrorCode::InvalidRecipientTokenAccount
+        let did_hash = manifest.did_hash.as_ptr() as usize;
+        assert_eq!(8 + did_hash - base, ObjectManifest::DID_HASH_OFFSET);
+        let layout_version = &manifest.layout_version as *const u8 as usize;
+        assert_eq!(
+            8 + layout_version - base,
+            ObjectManifest::LAYOUT_VERSION_OFFSET
         );
+    }
 
-        let recipient_token_account_info =
-            ctx.accounts.base.recipient_token_account.to_account_info();
-        ensure_recipient_token_account(
-            &recipient_token_account_info,
-            &recipient_account_info,
-            &payer_account_info,
-            &system_program_account_info,
-            &token_program_account_info,
-            &associated_token_program_account_info,
-            &object_mint_info,
-        )?;
+    #[test]
+    fn account_filter_offsets_match_layout() {
+        let manifest = ObjectManifest::zeroed();
+        let base = &manifest as *const ObjectManifest as usize;
+        let offset = |field: *const u8| 8 + field as usize - base;
 
-        let mut increment_object_count = false;
-        let was_minted;
-        let stored_manifest_uri: String;
-        let manifest_creator: Pubkey;
-        {
-            let mut data = manifest_info.try_borrow_mut_data()?;
-            require!(
-                data.len() >= ObjectManifest::LEN,
-                ErrorCode::ManifestAccountTooSmall
-            );
-            let (disc_bytes, rest) = data.split_at_mut(8);
-            if disc_bytes != ObjectManifest::discriminator() {
-                disc_bytes.copy_from_slice(&ObjectManifest::discriminator());
-            }
-            let manifest_slice = &mut rest[..core::mem::size_of::<ObjectManifest>()];
-            let manifest = from_bytes_mut::<ObjectManifest>(manifest_slice);
+        assert_eq!(
+            offset(manifest.config.as_ref().as_ptr()),
+            ObjectManifest::CONFIG_OFFSET
+        );
+        assert_eq!(
+            offset(&manifest.object_id as *const u64 as *const u8),
+            ObjectManifest::OBJECT_ID_OFFSET
+        );
+        assert_eq!(
+            offset(manifest.mint.as_ref().as_ptr()),
+            ObjectManifest::MINT_OFFSET
+        );
+        assert_eq!(
+            offset(&manifest.is_active),
+            ObjectManifest::IS_ACTIVE_OFFSET
+        );
+        assert_eq!(offset(&manifest.minted), ObjectManifest::MINTED_OFFSET);
+        assert_eq!(
+            offset(manifest.manifest_hash.as_ptr()),
+            ObjectManifest::MANIFEST_HASH_OFFSET
+        );
+        assert_eq!(
+            offset(manifest.creator.as_ref().as_ptr()),
+            ObjectManifest::CREATOR_OFFSET
+        );
+        assert_eq!(offset(&manifest.origin), ObjectManifest::ORIGIN_OFFSET);
+        assert_eq!(offset(&manifest.burned), ObjectManifest::BURNED_OFFSET);
+        assert_eq!(offset(&manifest.locked), ObjectManifest::LOCKED_OFFSET);
+    }
 
-            was_minted = manifest.minted();
+    #[test]
+    fn set_provenance_clears_previous_entries() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_provenance(&[4, 5, 6]);
+        assert_eq!(manifest.provenance(), &[4, 5, 6]);
 
-            if !manifest.initialized() {
-                require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
-                require!(
-                    manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
-                    ErrorCode::UriTooLong
-                );
+        manifest.set_provenance(&[9]);
+        assert_eq!(manifest.provenance(), &[9]);
+        assert!(manifest.provenance[1..].iter().all(|id| *id == 0));
+        assert_eq!(manifest.origin().unwrap(), ObjectOrigin::Minted);
+    }
 
-                manifest.config = config_key;
-                manifest.object_id = object_id;
-                manifest.mint = mint_key;
-                manifest.bump = manifest_bump;
-                manifest.mint_bump = object_mint_bump;
-                manifest.set_is_active(true);
-                manifest.set_initialized(true);
-                manifest.set_minted(false);
-                manifest.manifest_hash = manifest_hash;
-                manifest.set_metadata_uri(&manifest_uri);
-                manifest.creator = payer_key;
-                increment_object_count = true;
-            } else {
-                require!(manifest.is_active(), ErrorCode::ObjectInactive);
-                require!(manifest.object_id == object_id, ErrorCode::ObjectIdMismatch);
-                require_keys_eq!(manifest.config, config_key, ErrorCode::InvalidConfig);
-                require_keys_eq!(manifest.mint, mint_key, ErrorCode::MintMismatch);
-                require!(
-                    manifest.manifest_hash == manifest_hash,
-                    ErrorCode::ManifestMismatch
-                );
-                require!(
-                    manifest.metadata_uri_len() <= METADATA_MAX_URI_LENGTH,
-                    ErrorCode::UriTooLong
-                );
-                if !manifest_uri.is_empty() {
-                    require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
-                    require!(
-                        manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
-                        ErrorCode::UriTooLong
-                    );
-                    require!(
-                        manifest.metadata_uri_equals(&manifest_uri),
-                        ErrorCode::ManifestMismatch
-                    );
-                }
-            }
+    #[test]
+    fn level_delegates_match_only_a_registered_signer() {
+        let delegate = Pubkey::new_unique();
+        let mut level_delegate = LevelDelegate {
+            config: Pubkey::new_unique(),
+            delegate,
+            bump: 253,
+        };
+        let mut data = Vec::new();
+        level_delegate.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), LevelDelegate::LEN);
 
-            manifest_creator = manifest.creator;
-            stored_manifest_uri = manifest.metadata_uri_string();
-        }
+        assert!(level_delegate.is_delegate(&delegate));
+        assert!(!level_delegate.is_delegate(&Pubkey::new_unique()));
+        level_delegate.delegate = Pubkey::default();
+        assert!(!level_delegate.is_delegate(&Pubkey::default()));
+    }
 
-        if increment_object_count {
-            ctx.accounts.base.config.object_count =
-                ctx.accounts.base.config.object_count.saturating_add(1);
-        }
+    #[test]
+    fn xp_accrual_respects_per_call_and_per_slot_caps() {
+        let scorer = XpScorer {
+            config: Pubkey::new_unique(),
+            scorer: Pubkey::new_unique(),
+            max_xp_per_call: 50,
+            max_xp_per_slot: 80,
+            bump: 252,
+        };
+        let mut data = Vec::new();
+        scorer.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), XpScorer::LEN);
+        assert!(scorer.is_scorer(&scorer.scorer));
+        assert!(!scorer.is_scorer(&Pubkey::new_unique()));
 
-        let is_first_mint = !was_minted;
+        let mut manifest = ObjectManifest::zeroed();
+        assert!(manifest.accrue_xp(0, 10, &scorer).is_err());
+        assert!(manifest.accrue_xp(51, 10, &scorer).is_err());
+        manifest.accrue_xp(50, 10, &scorer).unwrap();
+        manifest.accrue_xp(30, 10, &scorer).unwrap();
+        assert!(manifest.accrue_xp(1, 10, &scorer).is_err());
+        assert_eq!((manifest.xp, manifest.xp_slot_accrued), (80, 80));
 
-        let recipient_mint = anchor_spl::token::accessor::mint(&recipient_token_account_info)?;
-        require_keys_eq!(recipient_mint, mint_key, ErrorCode::MintMismatch);
-        let recipient_owner =
-            anchor_spl::token::accessor::authority(&recipient_token_account_info)?;
-        require_keys_eq!(
-            recipient_owner,
-            ctx.accounts.base.recipient.key(),
-            ErrorCode::RecipientMismatch
+        manifest.accrue_xp(50, 11, &scorer).unwrap();
+        assert_eq!(
+            (manifest.xp, manifest.xp_slot, manifest.xp_slot_accrued),
+            (130, 11, 50)
         );
+    }
 
-        let signer_seeds: &[&[u8]] = &[
-            AUTH_SEED,
-            config_key.as_ref(),
-            &[ctx.accounts.base.auth.bump],
-        ];
-        let auth_seeds = &[signer_seeds];
+    #[test]
+    fn custody_trail_overwrites_oldest_entry_when_full() {
+        let mut trail = CustodyTrail::zeroed();
+        for slot in 0..=CUSTODY_TRAIL_CAPACITY as u64 {
+            trail.record(CustodyEntry {
+                slot,
+                previous_owner: Pubkey::default(),
+            });
+        }
 
-        let mut signer_keys: HashSet<Pubkey> = HashSet::new();
-        signer_keys.insert(payer_key);
-        for account in creator_remaining_accounts {
-            if account.is_signer {
-                signer_keys.insert(account.key());
-            }
+        assert_eq!(trail.total_transfers, CUSTODY_TRAIL_CAPACITY as u64 + 1);
+        assert_eq!(trail.entries[0].slot, CUSTODY_TRAIL_CAPACITY as u64);
+        assert_eq!(trail.entries[1].slot, 1);
+    }
+
+    #[test]
+    fn audit_log_chains_entries_it_has_overwritten() {
+        let mut log = Box::new(AuditLog::zeroed());
+        let mut head_hash = [0u8; 32];
+        for slot in 0..=AUDIT_LOG_CAPACITY as u64 {
+            let entry = AuditEntry {
+                slot,
+                tag: PauseStatusUpdated::DISCRIMINATOR,
+                actor: Pubkey::new_unique(),
+                summary_hash: [slot as u8; 32],
+            };
+            head_hash = keccak::hashv(&[&head_hash, bytemuck::bytes_of(&entry)]).to_bytes();
+            log.record(entry);
         }
 
-        if is_first_mint {
-            require!(
-                metadata_name.as_bytes().len() <= MAX_NAME_LENGTH,
-                ErrorCode::MetadataNameTooLong
-            );
-            require!(
-                metadata_symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
-                ErrorCode::MetadataSymbolTooLong
-            );
-            require!(
-                !creators.is_empty(),
-                ErrorCode::InvalidCreatorShareDistribution
-            );
-            require!(
-                creators.len() <= MAX_CREATOR_LIMIT,
-                ErrorCode::TooManyCreators
-            );
-            require!(
-                seller_fee_basis_points <= 10_000,
-                ErrorCode::InvalidSellerFeeBasisPoints
-            );
-            require_keys_eq!(
-                metadata_accounts.token_metadata_program.key(),
-                mpl_program_id(),
-                ErrorCode::InvalidTokenMetadataProgram
-            );
+        assert_eq!(log.total_entries, AUDIT_LOG_CAPACITY as u64 + 1);
+        assert_eq!(log.entries[0].slot, AUDIT_LOG_CAPACITY as u64);
+        assert_eq!(log.entries[1].slot, 1);
+        assert_eq!(log.head_hash, head_hash);
+    }
 
-            let total_shares: u16 = creators.iter().map(|creator| creator.share as u16).sum();
-            require!(
-                total_shares == CREATOR_TOTAL_SHARE,
-                ErrorCode::InvalidCreatorShareDistribution
-            );
-            let includes_manifest_creator = creators
-                .iter()
-                .any(|creator| creator.address == manifest_creator);
-            require!(includes_manifest_creator, ErrorCode::MissingManifestCreator);
+    #[test]
+    fn display_data_round_trips() {
+        let mut manifest = ObjectManifest::zeroed();
+        assert!(!manifest.has_display_data());
 
-            let mpl_mint_key = to_solana_pubkey(&mint_key);
-            let (expected_metadata_mpl, _) = MetadataAccount::find_pda(&mpl_mint_key);
-            let expected_metadata = from_solana_pubkey(&expected_metadata_mpl);
-            require_keys_eq!(
-                metadata_accounts.metadata.key(),
-                expected_metadata,
-                ErrorCode::InvalidMetadataAccount
-            );
-            let (expected_master_edition_mpl, _) = MetadataMasterEdition::find_pda(&mpl_mint_key);
-            let expected_master_edition = from_solana_pubkey(&expected_master_edition_mpl);
-            require_keys_eq!(
-                metadata_accounts.master_edition.key(),
-                expected_master_edition,
-                ErrorCode::InvalidMasterEditionAccount
-            );
-            let collection_mint_key = metadata_accounts.collection_mint.key();
-            let mpl_collection_mint_key = to_solana_pubkey(&collection_mint_key);
-            let (expected_collection_metadata_mpl, _) =
-                MetadataAccount::find_pda(&mpl_collection_mint_key);
-            let expected_collection_metadata =
-                from_solana_pubkey(&expected_collection_metadata_mpl);
-            require_keys_eq!(
-                collection_metadata_account.key(),
-                expected_collection_metadata,
-                ErrorCode::InvalidCollectionMetadataAccount
-            );
-            let (expected_collection_master_mpl, _) =
-                MetadataMasterEdition::find_pda(&mpl_collection_mint_key);
-            let expected_collection_master = from_solana_pubkey(&expected_collection_master_mpl);
-            require_keys_eq!(
-                collection_master_edition_account.key(),
-                expected_collection_master,
-                ErrorCode::InvalidCollectionMasterEditionAccount
-            );
+        manifest.set_display_data("Longsword of Testing", "OGAL");
+        manifest.set_display_data("Sword", "SW");
+        assert!(manifest.has_display_data());
+        assert_eq!(manifest.metadata_name_string(), "Sword");
+        assert_eq!(manifest.metadata_symbol_string(), "SW");
+    }
 
-            let metadata_creators: Vec<MetadataCreator> = creators
-                .iter()
-                .map(|creator| -> Result<MetadataCreator> {
-                    if creator.verified {
-                        require!(
-                            signer_keys.contains(&creator.address),
-                            ErrorCode::CreatorMustSign
-                        );
-                    }
-                    Ok(MetadataCreator {
-                        address: to_solana_pubkey(&creator.address),
-                        verified: creator.verified && signer_keys.contains(&creator.address),
-                        share: creator.share,
-                    })
-                })
-                .collect::<Result<Vec<_>>>()?;
+    #[test]
+    fn mirror_uris_are_replaced_as_a_set() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_mirror_uris(&["ar://one".to_string(), "ipfs://two".to_string()]);
+        manifest.set_mirror_uris(&["https://three".to_string()]);
 
-            let data = DataV2 {
-                name: metadata_name.clone(),
-                symbol: metadata_symbol.clone(),
-                uri: stored_manifest_uri.clone(),
-                seller_fee_basis_points,
-                creators: Some(metadata_creators),
-                collection: Some(Collection {
-                    key: to_solana_pubkey(&collection_mint_key),
-                    verified: false,
-                }),
-                uses: None,
-            };
+        assert_eq!(
+            manifest.mirror_uri_strings(),
+            vec!["https://three".to_string()]
+        );
+        assert!(manifest.mirror_uris[1].iter().all(|byte| *byte == 0));
+    }
 
-            let metadata_program_info = metadata_accounts.token_metadata_program.to_account_info();
-            let metadata_info = metadata_accounts.metadata.to_account_info();
-            let mint_info = object_mint_info.clone();
-            let auth_info = auth_account_info.clone();
-            let payer_info = payer_account_info.clone();
-            let system_program_info = system_program_account_info.clone();
+    #[test]
+    fn validate_content_address_checks_ipfs_cids() {
+        assert!(!validate_content_address("https://example.com/object.json").unwrap());
+        assert!(
+            validate_content_address("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG")
+                .unwrap()
+        );
+        assert!(validate_content_address(
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/manifest.json"
+        )
+        .unwrap());
 
-            let mut creator_account_infos: Vec<(&AccountInfo<'info>, bool, bool)> =
-                Vec::with_capacity(creator_remaining_accounts.len());
-            for account in creator_remaining_accounts {
-                creator_account_infos.push((account, account.is_signer, account.is_writable));
-            }
+        assert!(validate_content_address("ipfs://hello").is_err());
+        assert!(
+            validate_content_address("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPb0G")
+                .is_err()
+        );
+        assert!(validate_content_address(
+            "ipfs://bafyBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"
+        )
+        .is_err());
+    }
 
-            CreateMetadataAccountV3Cpi::new(
-                &metadata_program_info,
-                CreateMetadataAccountV3CpiAccounts {
-                    metadata: &metadata_info,
-                    mint: &mint_info,
-                    mint_authority: &auth_info,
-                    payer: &payer_info,
-                    update_authority: (&auth_info, true),
-                    system_program: &system_program_info,
-                    rent: Some(&rent_sysvar_account),
-                },
-                CreateMetadataAccountV3InstructionArgs {
-                    data,
-                    is_mutable: true,
-                    collection_details: Option::<CollectionDetails>::None,
-                },
-            )
-            .invoke_signed_with_remaining_accounts(auth_seeds, &creator_account_infos)
-            .map_err(anchor_lang::error::Error::from)?;
-        }
+    #[test]
+    fn arweave_uris_require_a_transaction_id() {
+        let tx_id = [7u8; 32];
+        assert!(validate_arweave_tx_id("https://arweave.net/abc", &[0u8; 32]).is_err());
+        assert!(validate_arweave_tx_id("ar://abc", &[0u8; 32]).is_err());
+        assert!(validate_arweave_tx_id("https://arweave.net/abc", &tx_id).is_ok());
+        assert!(validate_arweave_tx_id("https://example.com/abc", &[0u8; 32]).is_ok());
+    }
 
-        token::mint_to(
-            CpiContext::new_with_signer(
-                token_program_account_info.clone(),
-                MintTo {
-                    mint: object_mint_info.clone(),
-                    to: recipient_token_account_info.clone(),
-                    authority: auth_account_info.clone(),
-                },
-                auth_seeds,
-            ),
-            1,
-        )?;
+    #[test]
+    fn mint_memo_encodes_object_id_and_hex_hash() {
+        let mut manifest_hash = [0u8; 32];
+        manifest_hash[0] = 0xab;
+        manifest_hash[31] = 0x01;
 
-        if is_first_mint {
-            let metadata_program_info = metadata_accounts.token_metadata_program.to_account_info();
-            let edition_info = metadata_accounts.master_edition.to_account_info();
-            let mint_info = object_mint_info.clone();
-            let auth_info = auth_account_info.clone();
-            let payer_info = payer_account_info.clone();
-            let metadata_info = metadata_accounts.metadata.to_account_info();
-            let token_program_info = token_program_account_info.clone();
-            let system_program_info = system_program_account_info.clone();
-
-            CreateMasterEditionV3Cpi::new(
-                &metadata_program_info,
-                CreateMasterEditionV3CpiAccounts {
-                    edition: &edition_info,
-                    mint: &mint_info,
-                    update_authority: &auth_info,
-                    mint_authority: &auth_info,
-                    payer: &payer_info,
-                    metadata: &metadata_info,
-                    token_program: &token_program_info,
-                    system_program: &system_program_info,
-                    rent: Some(&rent_sysvar_account),
-                },
-                CreateMasterEditionV3InstructionArgs {
-                    max_supply: Some(0),
-                },
-            )
-            .invoke_signed(auth_seeds)
-            .map_err(anchor_lang::error::Error::from)?;
+        let memo = mint_memo(42, &manifest_hash);
+        assert!(memo.starts_with("ogal:mint:42:ab00"));
+        assert!(memo.ends_with("0001"));
+        assert_eq!(memo.len(), "ogal:mint:42:".len() + 64);
+    }
 
-            let metadata_program_info = metadata_accounts.token_metadata_program.to_account_info();
-            let metadata_info = metadata_accounts.metadata.to_account_info();
-            let auth_info = auth_account_info.clone();
-            let payer_info = payer_account_info.clone();
-            let collection_mint_info = metadata_accounts.collection_mint.to_account_info();
-
-            let metadata_data = collection_metadata_account
-                .try_borrow_data()
-                .map_err(|_| Error::from(ErrorCode::InvalidCollectionMetadataAccount))?;
-            let metadata = MetadataAccount::safe_deserialize(&metadata_data)
-                .map_err(|_| Error::from(ErrorCode::InvalidCollectionMetadataAccount))?;
-            let tlv_collection_details = read_collection_details_from_tlv(&metadata_data);
-            let is_sized_collection =
-                metadata.collection_details.is_some() || tlv_collection_details.is_some();
-            drop(metadata_data);
-
-            if is_sized_collection {
-                VerifySizedCollectionItemCpi::new(
-                    &metadata_program_info,
-                    VerifySizedCollectionItemCpiAccounts {
-                        metadata: &metadata_info,
-                        collection_authority: &auth_info,
-                        payer: &payer_info,
-                        collection_mint: &collection_mint_info,
-                        collection: &collection_metadata_account,
-                        collection_master_edition_account: &collection_master_edition_account,
-                        collection_authority_record: None,
-                    },
-                )
-                .invoke_signed(auth_seeds)
-                .map_err(anchor_lang::error::Error::from)?;
-            } else {
-                VerifyCollectionCpi::new(
-                    &metadata_program_info,
-                    VerifyCollectionCpiAccounts {
-                        metadata: &metadata_info,
-                        collection_authority: &auth_info,
-                        payer: &payer_info,
-                        collection_mint: &collection_mint_info,
-                        collection: &collection_metadata_account,
-                        collection_master_edition_account: &collection_master_edition_account,
-                        collection_authority_record: None,
-                    },
-                )
-                .invoke_signed(auth_seeds)
-                .map_err(anchor_lang::error::Error::from)?;
-            }
-        }
+    #[test]
+    fn config_stats_record_mint_tracks_latest_slot() {
+        let mut stats = ConfigStats::default();
+        stats.record_mint(10);
+        stats.record_mint(25);
 
-        {
-            let mut data = manifest_info.try_borrow_mut_data()?;
-            let (_, rest) = data.split_at_mut(8);
-            let manifest = from_bytes_mut::<ObjectManifest>(
-                &mut rest[..core::mem::size_of::<ObjectManifest>()],
-            );
-            manifest.set_minted(true);
-        }
+        assert_eq!(stats.total_minted, 2);
+        assert_eq!(stats.last_mint_slot, 25);
+    }
 
-        emit!(ObjectMinted {
-            config: config_key,
-            manifest: manifest_key,
-            mint: mint_key,
-            recipient: ctx.accounts.base.recipient.key(),
-            object_id,
-        });
+    #[test]
+    fn epoch_stats_keep_first_mint_slot() {
+        let mut stats = EpochStats {
+            epoch: 7,
+            first_mint_slot: 100,
+            ..EpochStats::default()
+        };
+        stats.record_mint(100);
+        stats.record_mint(140);
 
-        Ok(())
+        assert_eq!(stats.total_minted, 2);
+        assert_eq!(stats.first_mint_slot, 100);
+        assert_eq!(stats.last_mint_slot, 140);
     }
 
-    pub fn update_object_manifest(
-        ctx: Context<UpdateObjectManifest>,
-        manifest_hash: [u8; 32],
-        metadata_uri: String,
-        is_active: bool,
-    ) -> Result<()> {
-        require!(metadata_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
-        require!(
-            metadata_uri.len() <= METADATA_MAX_URI_LENGTH,
-            ErrorCode::UriTooLong
+    #[test]
+    fn object_index_page_rejects_entries_past_capacity() {
+        let mut page = ObjectIndexPage::zeroed();
+        for object_id in 0..OBJECT_INDEX_PAGE_CAPACITY as u64 {
+            page.push(object_id).unwrap();
+        }
+
+        assert_eq!(page.object_ids().len(), OBJECT_INDEX_PAGE_CAPACITY);
+        assert_eq!(page.object_ids()[3], 3);
+        assert!(page.push(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn namespace_registry_page_rejects_entries_past_capacity() {
+        let mut page = NamespaceRegistryPage::zeroed();
+        let entry = |created_slot| NamespaceEntry {
+            namespace: Pubkey::new_unique(),
+            config: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            created_slot,
+        };
+        for slot in 0..NAMESPACE_REGISTRY_PAGE_CAPACITY as u64 {
+            page.push(entry(slot)).unwrap();
+        }
+
+        assert_eq!(page.entries().len(), NAMESPACE_REGISTRY_PAGE_CAPACITY);
+        assert_eq!(page.entries()[5].created_slot, 5);
+        assert!(page.push(entry(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn minted_bitmap_sets_bits_within_its_chunk() {
+        let mut bitmap = MintedBitmap::zeroed();
+        bitmap.chunk = 1;
+        let first = MINTED_BITMAP_BITS as u64;
+
+        bitmap.set_minted(first + 9).unwrap();
+
+        assert!(bitmap.is_minted(first + 9));
+        assert!(!bitmap.is_minted(first + 8));
+        assert!(!bitmap.is_minted(9));
+        assert!(bitmap.set_minted(9).is_err());
+    }
+
+    #[test]
+    fn holder_snapshot_records_each_object_once() {
+        let mut snapshot = HolderSnapshot::zeroed();
+        snapshot.start_object_id = 10;
+        snapshot.end_object_id = 12;
+        let entry = HolderSnapshotEntry {
+            object_id: 11,
+            slot: 5,
+            owner: Pubkey::new_unique(),
+        };
+
+        snapshot.record(entry).unwrap();
+
+        assert_eq!(snapshot.recorded_count, 1);
+        assert_eq!(snapshot.entries[1].owner, entry.owner);
+        assert!(snapshot.record(entry).is_err());
+        assert!(snapshot
+            .record(HolderSnapshotEntry {
+                object_id: 13,
+                ..entry
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn manifest_view_reports_expiry_as_inactive() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.object_id = 42;
+        manifest.set_is_active(true);
+        manifest.set_metadata_uri("ipfs://example");
+        manifest.expires_at = 100;
+
+        let view = ManifestView::from_manifest(&manifest, 50).unwrap();
+        assert_eq!(view.version, MANIFEST_VIEW_VERSION);
+        assert_eq!(view.object_id, 42);
+        assert_eq!(view.metadata_uri, "ipfs://example");
+        assert!(view.is_active);
+
+        let view = ManifestView::from_manifest(&manifest, 100).unwrap();
+        assert!(!view.is_active);
+    }
+
+    #[test]
+    fn require_match_reports_mismatch_error() {
+        fn check(actual: u64, expected: u64) -> Result<()> {
+            require_match!("object_id", actual, expected, ErrorCode::ObjectIdMismatch);
+            Ok(())
+        }
+
+        assert!(check(4, 4).is_ok());
+        assert_eq!(
+            check(4, 5).unwrap_err(),
+            Error::from(ErrorCode::ObjectIdMismatch)
         );
-        require_keys_eq!(
-            ctx.accounts.owner_token_account.owner,
-            ctx.accounts.owner.key(),
-            ErrorCode::InvalidOwnerTokenAccount
+        assert_eq!(Hex(&[0xab, 0x01]).to_string(), "ab01");
+    }
+
+    #[test]
+    fn log_records_join_fields_after_prefix_and_kind() {
+        let config = Pubkey::new_unique();
+        assert_eq!(
+            format_log_record("mint", &[&config, &42u64, &true, &Hex(&[0xab, 0xcd])]),
+            format!("OGAL1|mint|{}|42|true|abcd", config)
         );
-        require_keys_eq!(
-            ctx.accounts.owner_token_account.mint,
-            ctx.accounts.object_mint.key(),
-            ErrorCode::MintMismatch
+        assert_eq!(
+            format_log_record("minted_bitmap", &[]),
+            "OGAL1|minted_bitmap"
         );
-        require!(
-            ctx.accounts.owner_token_account.amount > 0,
-            ErrorCode::OwnerDoesNotHoldObjectNft
+    }
+
+    #[test]
+    fn stored_bumps_derive_the_canonical_address() {
+        let config = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[MANIFEST_SEED, config.as_ref(), &7u64.to_le_bytes()];
+        let canonical = Pubkey::find_program_address(seeds, &crate::ID);
+
+        assert_eq!(derive_pda(seeds, None, &crate::ID), canonical);
+        assert_eq!(derive_pda(seeds, Some(canonical.1), &crate::ID), canonical);
+        assert_ne!(
+            derive_pda(seeds, Some(canonical.1.wrapping_sub(1)), &crate::ID).0,
+            canonical.0
         );
+    }
 
-        require_keys_eq!(
-            ctx.accounts.metadata_program.key(),
-            mpl_program_id(),
-            ErrorCode::InvalidTokenMetadataProgram
+    #[test]
+    fn mint_pending_is_tracked_separately_from_minted() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_mint_pending(true);
+        assert!(manifest.mint_pending());
+        assert!(!manifest.minted());
+
+        manifest.set_mint_pending(false);
+        manifest.set_minted(true);
+        assert!(!manifest.mint_pending());
+        assert!(manifest.minted());
+    }
+
+    #[test]
+    fn parent_authorities_administer_child_configs() {
+        let parent_key = Pubkey::new_unique();
+        let parent_authority = Pubkey::new_unique();
+        let mut config = Config::zeroed();
+        config.authority = Pubkey::new_unique();
+
+        assert!(is_config_admin(&config, None, &config.authority));
+        assert!(!is_config_admin(
+            &config,
+            Some((parent_key, parent_authority)),
+            &parent_authority
+        ));
+
+        config.parent_config = parent_key;
+        assert!(is_config_admin(
+            &config,
+            Some((parent_key, parent_authority)),
+            &parent_authority
+        ));
+        assert!(!is_config_admin(&config, None, &parent_authority));
+        assert!(!is_config_admin(
+            &config,
+            Some((Pubkey::new_unique(), parent_authority)),
+            &parent_authority
+        ));
+        assert!(!is_config_admin(
+            &config,
+            Some((parent_key, parent_authority)),
+            &Pubkey::new_unique()
+        ));
+    }
+
+    #[test]
+    fn config_filter_offsets_match_layout() {
+        let config = Config::zeroed();
+        let base = &config as *const Config as usize;
+        let offset = |field: *const u8| 8 + field as usize - base;
+
+        assert_eq!(
+            offset(config.authority.as_ref().as_ptr()),
+            Config::AUTHORITY_OFFSET
         );
-        require_keys_eq!(
-            ctx.accounts.rent.key(),
-            sysvar::rent::id(),
-            ErrorCode::InvalidRentSysvar
+        assert_eq!(
+            offset(config.namespace.as_ref().as_ptr()),
+            Config::NAMESPACE_OFFSET
         );
-        if let Some(ref instructions_sysvar) = ctx.accounts.instructions {
-            require_keys_eq!(
-                instructions_sysvar.key(),
-                sysvar::instructions::id(),
-                ErrorCode::InvalidInstructionsSysvar
-            );
-        }
+        assert_eq!(
+            offset(&config.indexed_object_count as *const u64 as *const u8),
+            Config::INDEXED_OBJECT_COUNT_OFFSET
+        );
+        assert_eq!(
+            offset(&config.object_index_enabled),
+            Config::OBJECT_INDEX_ENABLED_OFFSET
+        );
+        assert_eq!(
+            offset(&config.minted_bitmap_enabled),
+            Config::MINTED_BITMAP_ENABLED_OFFSET
+        );
+        assert_eq!(
+            offset(config.parent_config.as_ref().as_ptr()),
+            Config::PARENT_CONFIG_OFFSET
+        );
+        assert_eq!(
+            offset(&config.layout_version),
+            Config::LAYOUT_VERSION_OFFSET
+        );
+        assert_eq!(
+            offset(&config.disabled_instructions as *const u16 as *const u8),
+            Config::DISABLED_INSTRUCTIONS_OFFSET
+        );
+    }
 
-        let manifest_info = ctx.accounts.object_manifest.to_account_info();
-        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+    #[test]
+    fn disabled_instructions_are_rejected_until_re_enabled() {
+        let mut config = Config::zeroed();
+        assert_eq!(config.enabled_instructions(), INSTRUCTION_ALL);
+        assert!(config
+            .require_instruction_enabled(INSTRUCTION_MARKETPLACE)
+            .is_ok());
 
-        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
-        require_keys_eq!(
-            manifest.config,
-            ctx.accounts.config.key(),
-            ErrorCode::InvalidConfig
+        config.set_enabled_instructions(INSTRUCTION_MINT | INSTRUCTION_UPDATE);
+        assert!(config.require_instruction_enabled(INSTRUCTION_MINT).is_ok());
+        assert!(config
+            .require_instruction_enabled(INSTRUCTION_MARKETPLACE)
+            .is_err());
+        assert!(config
+            .require_instruction_enabled(INSTRUCTION_VAULT)
+            .is_err());
+
+        config.set_enabled_instructions(INSTRUCTION_ALL);
+        assert_eq!(config.disabled_instructions, 0);
+        assert!(config
+            .require_instruction_enabled(INSTRUCTION_VAULT)
+            .is_ok());
+    }
+
+    #[test]
+    fn configs_upgrade_to_the_current_layout_version() {
+        let mut config = Config::zeroed();
+        config.object_count = 4;
+        config.set_paused(true);
+        let mut data = Config::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&config));
+        assert_eq!(data[Config::LAYOUT_VERSION_OFFSET], 0);
+
+        config.upgrade_layout();
+        assert_eq!(config.layout_version, CONFIG_LAYOUT_VERSION);
+        assert_eq!(config.object_count, 4);
+        assert!(config.paused());
+        let decoded = Config::from_account_data(&data).unwrap();
+        assert_eq!(decoded.layout_version, 0);
+    }
+
+    #[test]
+    fn legacy_configs_migrate_with_missing_fields_zeroed() {
+        let mut categories = [0u16; MAX_CATEGORIES];
+        categories[0] = 3;
+        let legacy = LegacyConfig {
+            authority: Pubkey::new_unique(),
+            config_bump: 254,
+            auth_bump: 253,
+            object_count: 12,
+            namespace: Pubkey::new_unique(),
+            paused: true,
+            categories,
+            category_count: 1,
+            memo_on_mint: true,
+            stats_enabled: true,
+            object_index_enabled: true,
+            indexed_object_count: 9,
+            minted_bitmap_enabled: true,
+        };
+        let mut data = Config::discriminator().to_vec();
+        legacy.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + LegacyConfig::LEN);
+
+        let mut config = Config::zeroed();
+        LegacyConfig::from_account_data(&data)
+            .unwrap()
+            .write_to(&mut config);
+        assert_eq!(config.authority, legacy.authority);
+        assert_eq!(config.namespace, legacy.namespace);
+        assert_eq!(config.object_count, 12);
+        assert_eq!(config.indexed_object_count, 9);
+        assert!(config.paused() && config.memo_on_mint() && config.minted_bitmap_enabled());
+        assert!(config.is_category_enabled(3));
+
+        // A config written before the index and bitmap fields existed.
+        let older = &data[..data.len() - 10];
+        let mut config = Config::zeroed();
+        LegacyConfig::from_account_data(older)
+            .unwrap()
+            .write_to(&mut config);
+        assert!(config.stats_enabled());
+        assert!(!config.object_index_enabled());
+        assert_eq!(config.indexed_object_count, 0);
+        assert!(!config.minted_bitmap_enabled());
+
+        // A config written before categories existed.
+        let oldest = &data[..8 + 32 + 1 + 1 + 8 + 32 + 1];
+        let mut config = Config::zeroed();
+        LegacyConfig::from_account_data(oldest)
+            .unwrap()
+            .write_to(&mut config);
+        assert_eq!(config.namespace, legacy.namespace);
+        assert!(config.paused());
+        assert_eq!(config.category_count, 0);
+        assert!(!config.is_category_enabled(3));
+
+        assert!(Config::from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn copying_a_short_manifest_clears_the_missing_fields() {
+        let mut stored = ObjectManifest::zeroed();
+        stored.object_id = 5;
+        let mut data = ObjectManifest::discriminator().to_vec();
+        data.extend_from_slice(&bytemuck::bytes_of(&stored)[..64]);
+
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.owner = Pubkey::new_unique();
+        manifest.copy_from_account_data(&data).unwrap();
+        assert_eq!(manifest.object_id, 5);
+        assert_eq!(manifest.owner, Pubkey::default());
+    }
+
+    #[test]
+    fn aliased_mint_accounts_are_rejected() {
+        let manifest = Pubkey::new_unique();
+        let metadata = Pubkey::new_unique();
+        assert!(
+            require_distinct_accounts(&[("manifest", manifest), ("metadata", metadata)]).is_ok()
+        );
+
+        assert_eq!(
+            require_distinct_accounts(&[
+                ("manifest", manifest),
+                ("metadata", metadata),
+                ("collection_metadata", metadata),
+            ])
+            .unwrap_err(),
+            Error::from(ErrorCode::DuplicateMintAccount)
+        );
+    }
+
+    #[test]
+    fn corrupted_manifest_uris_read_back_lossily() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_metadata_uri("ar://abc");
+        assert!(manifest.metadata_uri_is_valid());
+
+        manifest.metadata_uri[5] = 0xff;
+        assert!(!manifest.metadata_uri_is_valid());
+        assert_eq!(manifest.metadata_uri_string(), "ar://\u{fffd}bc");
+        assert!(!manifest.metadata_uri_equals("ar://abc"));
+
+        manifest.set_metadata_uri("ar://abd");
+        assert!(manifest.metadata_uri_is_valid());
+        assert!(manifest.metadata_uri_equals("ar://abd"));
+    }
+
+    #[test]
+    fn upgrading_a_legacy_manifest_clears_reclaimed_padding() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.object_id = 11;
+        manifest.set_metadata_uri("ar://abc");
+        manifest.metadata_uri_padding = 3;
+        manifest.exported = 1;
+        manifest.inscribed = 1;
+        manifest.uri_live = 1;
+        manifest.asset_backend = 1;
+        manifest.vaulted = 1;
+        manifest.display_reserved = [2u8; 4];
+        manifest.created_slot = 7;
+
+        manifest.upgrade_layout();
+        assert_eq!(manifest.layout_version, MANIFEST_LAYOUT_VERSION);
+        assert_eq!(manifest.metadata_uri_padding, 0);
+        assert!(!manifest.exported());
+        assert!(!manifest.inscribed());
+        assert!(!manifest.uri_live());
+        assert_eq!(
+            manifest.asset_backend().unwrap(),
+            AssetBackend::TokenMetadata
         );
+        assert!(!manifest.vaulted());
+        assert_eq!(manifest.display_reserved, [0u8; 4]);
+        assert_eq!(manifest.created_slot, 0);
+        assert_eq!(manifest.object_id, 11);
+        assert!(manifest.metadata_uri_equals("ar://abc"));
+    }
+
+    #[test]
+    fn sweeps_only_accept_accounts_scoped_to_the_config() {
+        let config = Pubkey::new_unique();
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.config = config;
+        let mut data = ObjectManifest::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&manifest));
+
+        assert!(belongs_to_config(&Pubkey::new_unique(), &data, &config));
+        assert!(belongs_to_config(&config, &[], &config));
+        assert!(!belongs_to_config(
+            &Pubkey::new_unique(),
+            &data,
+            &Pubkey::new_unique()
+        ));
+        assert!(!belongs_to_config(
+            &Pubkey::new_unique(),
+            &data[..20],
+            &config
+        ));
+    }
+
+    #[test]
+    fn unminted_manifests_become_collectable_after_the_window() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_initialized(true);
+        manifest.created_slot = 100;
+
+        assert!(!manifest.is_collectable_at(149, 50));
+        assert!(manifest.is_collectable_at(150, 50));
+        assert!(!manifest.is_collectable_at(150, 0));
+
+        manifest.set_minted(true);
+        assert!(!manifest.is_collectable_at(1_000, 50));
+
+        manifest.set_minted(false);
+        manifest.set_mint_pending(true);
+        assert!(!manifest.is_collectable_at(1_000, 50));
 
-        let (expected_manifest_key, expected_manifest_bump) = Pubkey::find_program_address(
+        manifest.set_mint_pending(false);
+        manifest.created_slot = 0;
+        assert!(!manifest.is_collectable_at(1_000, 50));
+    }
+
+    #[test]
+    fn collected_manifests_refund_the_recorded_rent_payer() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.creator = Pubkey::new_unique();
+        assert_eq!(manifest.rent_refund_account(), manifest.creator);
+
+        manifest.rent_payer = Pubkey::new_unique();
+        assert_eq!(manifest.rent_refund_account(), manifest.rent_payer);
+    }
+
+    #[test]
+    fn manifest_creators_transfer_only_before_the_first_mint() {
+        let mut manifest = ObjectManifest::zeroed();
+        assert!(!manifest.creator_transferable());
+
+        manifest.set_initialized(true);
+        assert!(manifest.creator_transferable());
+
+        manifest.set_mint_pending(true);
+        assert!(!manifest.creator_transferable());
+
+        manifest.set_mint_pending(false);
+        manifest.set_minted(true);
+        assert!(!manifest.creator_transferable());
+    }
+
+    #[test]
+    fn creator_lists_are_validated_entry_by_entry() {
+        let auth = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let creator = |address, verified, share| CreatorInput {
+            address,
+            verified,
+            share,
+        };
+
+        assert!(validate_creators(
             &[
-                MANIFEST_SEED,
-                ctx.accounts.config.key().as_ref(),
-                &manifest.object_id.to_le_bytes(),
+                creator(payer, true, 90),
+                creator(other, true, 0),
+                creator(Pubkey::new_unique(), false, 10)
             ],
-            ctx.program_id,
+            &auth,
+            &payer
+        )
+        .is_ok());
+        assert_eq!(
+            validate_creators(
+                &[creator(payer, true, 50), creator(payer, false, 50)],
+                &auth,
+                &payer
+            )
+            .unwrap_err(),
+            Error::from(ErrorCode::DuplicateCreator)
         );
-        require_keys_eq!(
-            manifest_info.key(),
-            expected_manifest_key,
-            ErrorCode::InvalidConfig
+        assert_eq!(
+            validate_creators(
+                &[creator(payer, true, 100), creator(other, false, 0)],
+                &auth,
+                &payer
+            )
+            .unwrap_err(),
+            Error::from(ErrorCode::ZeroCreatorShare)
         );
-        require!(
-            manifest.bump == expected_manifest_bump,
-            ErrorCode::InvalidConfig
+        assert_eq!(
+            validate_creators(
+                &[creator(payer, true, 90), creator(auth, false, 10)],
+                &auth,
+                &payer
+            )
+            .unwrap_err(),
+            Error::from(ErrorCode::AuthCreatorNotAllowed)
         );
-        require_keys_eq!(
-            manifest.mint,
-            ctx.accounts.object_mint.key(),
-            ErrorCode::MintMismatch
+        assert_eq!(
+            validate_creators(&[creator(other, false, 100)], &auth, &payer).unwrap_err(),
+            Error::from(ErrorCode::MissingManifestCreator)
         );
+    }
 
-        let mint_key = ctx.accounts.object_mint.key();
-        let mpl_mint_key = to_solana_pubkey(&mint_key);
-        let (expected_metadata_mpl, _) = MetadataAccount::find_pda(&mpl_mint_key);
-        let expected_metadata = from_solana_pubkey(&expected_metadata_mpl);
-        require_keys_eq!(
-            ctx.accounts.object_metadata.key(),
-            expected_metadata,
-            ErrorCode::InvalidMetadataAccount
+    #[test]
+    fn metadata_pdas_follow_the_configured_program() {
+        let mint = Pubkey::new_unique();
+        let metaplex = mpl_program_id();
+        assert_eq!(
+            to_solana_pubkey(&find_metadata_pda(&mint, &metaplex)),
+            MetadataAccount::find_pda(&to_solana_pubkey(&mint)).0
+        );
+        assert_eq!(
+            to_solana_pubkey(&find_master_edition_pda(&mint, &metaplex)),
+            MetadataMasterEdition::find_pda(&to_solana_pubkey(&mint)).0
         );
 
-        manifest.manifest_hash = manifest_hash;
-        manifest.set_metadata_uri(&metadata_uri);
-        manifest.set_is_active(is_active);
+        let mut config = Config::zeroed();
+        assert_eq!(config.metadata_program(), metaplex);
+        let fork = Pubkey::new_unique();
+        config.metadata_program_id = fork;
+        assert_eq!(config.metadata_program(), fork);
+        assert_ne!(
+            find_metadata_pda(&mint, &fork),
+            find_metadata_pda(&mint, &metaplex)
+        );
+    }
 
-        let config_key = manifest.config;
-        let config_account_key = ctx.accounts.config.key();
-        let manifest_mint = manifest.mint;
-        let object_id = manifest.object_id;
-        let manifest_pubkey = manifest_info.key();
+    #[test]
+    fn uri_rules_reject_disallowed_uris() {
+        let mut config = Config::zeroed();
+        assert!(validate_uri_rules(&config, "http://example.com/a.json").is_ok());
+        assert_eq!(
+            validate_uri_rules(&config, "https://example.com/a\0.json").unwrap_err(),
+            Error::from(ErrorCode::UriContainsControlCharacters)
+        );
+        assert_eq!(
+            validate_uri_rules(&config, "https://example.com/\na.json").unwrap_err(),
+            Error::from(ErrorCode::UriContainsControlCharacters)
+        );
 
-        drop(manifest);
+        config.allowed_uri_schemes = URI_SCHEME_HTTPS | URI_SCHEME_ARWEAVE;
+        assert!(validate_uri_rules(&config, "ar://abc").is_ok());
+        assert_eq!(
+            validate_uri_rules(&config, "ipfs://abc").unwrap_err(),
+            Error::from(ErrorCode::UriSchemeNotAllowed)
+        );
 
-        let metadata_info = ctx.accounts.object_metadata.to_account_info();
-        let metadata_account = {
-            let metadata_data = metadata_info
-                .try_borrow_data()
-                .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
-            let metadata = MetadataAccount::safe_deserialize(&metadata_data)
-                .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
-            drop(metadata_data);
-            metadata
-        };
+        config.set_uri_prefixes(&["https://cdn.example.com/".into(), "ar://".into()]);
+        assert!(validate_uri_rules(&config, "https://cdn.example.com/a.json").is_ok());
+        assert!(validate_uri_rules(&config, "ar://abc").is_ok());
+        assert_eq!(
+            validate_uri_rules(&config, "https://evil.example.com/a.json").unwrap_err(),
+            Error::from(ErrorCode::UriPrefixNotAllowed)
+        );
+    }
 
-        let mut data = DataV2 {
-            name: metadata_account.name.clone(),
-            symbol: metadata_account.symbol.clone(),
-            uri: metadata_account.uri.clone(),
-            seller_fee_basis_points: metadata_account.seller_fee_basis_points,
-            creators: metadata_account.creators.clone(),
-            collection: metadata_account.collection.clone(),
-            uses: metadata_account.uses.clone(),
-        };
-        data.uri = metadata_uri.clone();
+    #[test]
+    fn export_payload_carries_the_object_identity() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.config = Pubkey::new_unique();
+        manifest.object_id = 0x0102;
+        manifest.manifest_hash = [9u8; 32];
+        manifest.hash_algorithm = ManifestHashAlgorithm::Keccak256 as u8;
+        let owner = Pubkey::new_unique();
 
-        let metadata_program_info = ctx.accounts.metadata_program.to_account_info();
-        let auth_info = ctx.accounts.auth.to_account_info();
-        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_account_key.as_ref(), &[ctx.accounts.auth.bump]];
+        let payload = export_payload(&manifest, &owner);
+        assert_eq!(payload.len(), 106);
+        assert_eq!(payload[0], EXPORT_PAYLOAD_ID);
+        assert_eq!(&payload[1..33], manifest.config.as_ref());
+        assert_eq!(&payload[33..41], &[0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(&payload[41..73], &[9u8; 32]);
+        assert_eq!(payload[73], ManifestHashAlgorithm::Keccak256 as u8);
+        assert_eq!(&payload[74..], owner.as_ref());
+    }
 
-        UpdateMetadataAccountV2Cpi::new(
-            &metadata_program_info,
-            UpdateMetadataAccountV2CpiAccounts {
-                metadata: &metadata_info,
-                update_authority: &auth_info,
-            },
-            UpdateMetadataAccountV2InstructionArgs {
-                data: Some(data),
-                new_update_authority: None,
-                primary_sale_happened: None,
-                is_mutable: None,
-            },
-        )
-        .invoke_signed(&[auth_seeds])
-        .map_err(anchor_lang::error::Error::from)?;
+    #[test]
+    fn posted_vaas_yield_their_emitter_and_import_payload() {
+        let foreign_id = [4u8; 32];
+        let recipient = Pubkey::new_unique();
+        let mut payload = vec![IMPORT_PAYLOAD_ID];
+        payload.extend_from_slice(&foreign_id);
+        payload.extend_from_slice(&[5u8; 32]);
+        payload.push(ManifestHashAlgorithm::Sha256 as u8);
+        payload.extend_from_slice(recipient.as_ref());
 
-        emit!(ManifestUpdated {
-            config: config_key,
-            manifest: manifest_pubkey,
-            mint: manifest_mint,
-            object_id,
-            is_active,
-        });
+        let mut data = POSTED_VAA_MAGIC.to_vec();
+        data.resize(POSTED_VAA_SEQUENCE_OFFSET, 0);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[6u8; 32]);
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
 
-        Ok(())
+        let vaa = parse_posted_vaa(&data).unwrap();
+        assert_eq!(vaa.sequence, 42);
+        assert_eq!(vaa.emitter_chain, 2);
+        assert_eq!(vaa.emitter_address, [6u8; 32]);
+        assert_eq!(
+            parse_import_payload(vaa.payload).unwrap(),
+            ImportPayload {
+                foreign_id,
+                manifest_hash: [5u8; 32],
+                hash_algorithm: ManifestHashAlgorithm::Sha256 as u8,
+                recipient,
+            }
+        );
+
+        assert_eq!(
+            parse_posted_vaa(&data[..data.len() - 1]).unwrap_err(),
+            Error::from(ErrorCode::InvalidVaa)
+        );
+        data[..3].copy_from_slice(b"msg");
+        assert_eq!(
+            parse_posted_vaa(&data).unwrap_err(),
+            Error::from(ErrorCode::InvalidVaa)
+        );
+        payload[0] = EXPORT_PAYLOAD_ID;
+        assert_eq!(
+            parse_import_payload(&payload).unwrap_err(),
+            Error::from(ErrorCode::InvalidImportPayload)
+        );
     }
 
-    /// Creates a new configuration PDA under `new_namespace` using the state
-    /// from `old_config`.
-    ///
-    /// This instruction allows the authority to migrate to a fresh namespace
-    /// (for example, to rotate the config PDA) without requiring a program
-    /// upgrade. After migration, callers should reference the new config and
-    /// auth accounts.
-    pub fn migrate_config_namespace(
-        ctx: Context<MigrateConfigNamespace>,
-        new_namespace: Pubkey,
-    ) -> Result<()> {
-        let authority = ctx.accounts.authority.key();
-        let old_config = &ctx.accounts.old_config;
-        require_keys_eq!(old_config.authority, authority, ErrorCode::InvalidAuthority);
+    #[test]
+    fn compressed_manifest_addresses_are_per_object_field_elements() {
+        let config = Pubkey::new_unique();
+        let address_tree = Pubkey::new_unique();
+        let seed = compressed_manifest_address_seed(&config, 7);
+        let address = compressed_manifest_address(&address_tree, &seed);
+        assert_eq!((seed[0], address[0]), (0, 0));
+        assert_ne!(compressed_manifest_address_seed(&config, 8), seed);
+        assert_ne!(
+            compressed_manifest_address_seed(&Pubkey::new_unique(), 7),
+            seed
+        );
+        assert_ne!(
+            compressed_manifest_address(&Pubkey::new_unique(), &seed),
+            address
+        );
 
-        let new_config = &mut ctx.accounts.new_config;
-        new_config.authority = old_config.authority;
-        new_config.config_bump = ctx.bumps.new_config;
-        new_config.auth_bump = ctx.bumps.new_auth;
-        new_config.object_count = old_config.object_count;
-        new_config.namespace = new_namespace;
-        new_config.paused = old_config.paused;
+        let manifest = CompressedManifest {
+            config,
+            object_id: 7,
+            owner: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            manifest_hash: [3u8; 32],
+            hash_algorithm: ManifestHashAlgorithm::Sha256 as u8,
+            created_slot: 10,
+            updated_slot: 12,
+            metadata_uri: "ipfs://manifest".to_string(),
+        };
+        let input = light_manifest_account(&manifest, address, false)
+            .data
+            .unwrap();
+        let output = light_manifest_account(&manifest, address, true)
+            .data
+            .unwrap();
+        assert!(input.data.is_empty());
+        assert_eq!(output.data, manifest.data());
+        assert_eq!(input.data_hash, output.data_hash);
+        assert_eq!(input.data_hash[0], 0);
+    }
 
-        let new_auth = &mut ctx.accounts.new_auth;
-        new_auth.config = new_config.key();
-        new_auth.bump = ctx.bumps.new_auth;
+    #[test]
+    fn changelog_leaves_commit_to_the_manifest_state() {
+        let key = Pubkey::new_unique();
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.object_id = 3;
+        manifest.set_metadata_uri("ar://abc");
+        manifest.set_is_active(true);
+        let leaf = changelog_leaf(&key, &manifest, 10);
+        assert_eq!(leaf, changelog_leaf(&key, &manifest, 10));
+        assert_ne!(leaf, changelog_leaf(&key, &manifest, 11));
 
-        Ok(())
+        let mut updated = manifest;
+        updated.set_metadata_uri("ar://abd");
+        assert_ne!(leaf, changelog_leaf(&key, &updated, 10));
+        let mut transferred = manifest;
+        transferred.owner = Pubkey::new_unique();
+        assert_ne!(leaf, changelog_leaf(&key, &transferred, 10));
+
+        assert_eq!(
+            compression_ix_data("append"),
+            hash(b"global:append").to_bytes()[..8]
+        );
     }
 
-    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        config.paused = paused;
+    #[test]
+    fn inscription_writes_encode_offset_and_bytes() {
+        let data = inscription_write_data(0x0102, b"{}");
+        assert_eq!(
+            data,
+            [
+                INSCRIPTION_WRITE_DATA,
+                0,
+                0x02,
+                0x01,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                2,
+                0,
+                0,
+                0,
+                b'{',
+                b'}'
+            ]
+        );
 
-        emit!(PauseStatusUpdated {
-            config: config.key(),
-            paused,
-        });
+        let mint = Pubkey::new_unique();
+        let (inscription, metadata) = find_inscription_pdas(&mint);
+        assert_ne!(inscription, metadata);
+        assert_eq!(find_inscription_pdas(&mint), (inscription, metadata));
+    }
 
-        Ok(())
+    #[test]
+    fn scheduled_updates_fit_a_full_length_uri() {
+        let scheduled = ScheduledUpdate {
+            manifest: Pubkey::new_unique(),
+            scheduler: Pubkey::new_unique(),
+            trigger_slot: u64::MAX,
+            manifest_hash: [1u8; 32],
+            hash_algorithm: 0,
+            is_active: true,
+            arweave_tx_id: [2u8; 32],
+            metadata_uri: "a".repeat(MAX_URI_LENGTH),
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        scheduled.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ScheduledUpdate::LEN);
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct CreatorInput {
-    pub address: Pubkey,
-    pub verified: bool,
-    pub share: u8,
-}
+    #[test]
+    fn object_recoveries_fit_their_allocation() {
+        let recovery = ObjectRecovery {
+            manifest: Pubkey::new_unique(),
+            source: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+            reason_hash: [3u8; 32],
+            requested_slot: 1,
+            executable_slot: u64::MAX,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        recovery.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ObjectRecovery::LEN);
+    }
 
-#[derive(Accounts)]
-#[instruction(namespace: Pubkey)]
-pub struct Initialize<'info> {
+    #[test]
+    fn transfer_hook_initialize_matches_the_hook_instruction() {
+        assert_eq!(
+            TRANSFER_HOOK_INITIALIZE[..],
+            hash(b"global:initialize_object_hook").to_bytes()[..8]
+        );
+    }
+
+    #[test]
+    fn transfer_fee_objects_stay_held_after_a_whole_object_transfer() {
+        assert!(holds_object(1, 1));
+        assert!(!holds_object(0, 1));
+        let basis_points = TRANSFER_FEE_BASIS_POINTS_LIMIT - 1;
+        let fee = ROYALTY_MINT_SUPPLY * u64::from(basis_points) / 10_000;
+        assert!(holds_object(ROYALTY_MINT_SUPPLY - fee, ROYALTY_MINT_SUPPLY));
+        assert!(!holds_object(fee, ROYALTY_MINT_SUPPLY));
+        assert!(!holds_object(ROYALTY_MINT_SUPPLY / 2, ROYALTY_MINT_SUPPLY));
+    }
+
+    #[test]
+    fn owner_changes_drop_marketplace_delegates() {
+        let mut manifest = ObjectManifest::zeroed();
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        manifest.owner = owner;
+        manifest.marketplace_delegate = delegate;
+        manifest.marketplace_program = Pubkey::new_unique();
+        assert!(!manifest.is_marketplace_delegate(&Pubkey::default()));
+
+        record_owner_change(&mut manifest, Pubkey::new_unique(), None, owner).unwrap();
+        assert!(manifest.is_marketplace_delegate(&delegate));
+
+        record_owner_change(
+            &mut manifest,
+            Pubkey::new_unique(),
+            None,
+            Pubkey::new_unique(),
+        )
+        .unwrap();
+        assert!(!manifest.is_marketplace_delegate(&delegate));
+        assert_eq!(manifest.marketplace_program, Pubkey::default());
+    }
+
+    #[test]
+    fn namespace_transfers_serialize_to_their_allocation() {
+        let transfer = NamespaceTransfer {
+            config: Pubkey::new_unique(),
+            current_authority: Pubkey::new_unique(),
+            new_authority: Pubkey::new_unique(),
+            proposed_slot: 9,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        transfer.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), NamespaceTransfer::LEN);
+    }
+
+    #[test]
+    fn reservations_block_other_creators_until_they_expire() {
+        let creator = Pubkey::new_unique();
+        let reservation = ObjectReservation {
+            config: Pubkey::new_unique(),
+            object_id: 42,
+            creator,
+            reserved_slot: 100,
+            expires_slot: 200,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        reservation.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ObjectReservation::LEN);
+
+        let other = Pubkey::new_unique();
+        assert!(reservation.blocks(&other, 150));
+        assert!(!reservation.blocks(&creator, 150));
+        assert!(!reservation.blocks(&other, 200));
+    }
+
+    #[test]
+    fn airdrops_mark_each_assignment_once() {
+        let recipient_count = 10;
+        let mut airdrop = Airdrop {
+            config: Pubkey::new_unique(),
+            airdrop_id: 1,
+            authority: Pubkey::new_unique(),
+            merkle_root: [0u8; 32],
+            recipient_count,
+            distributed_count: 0,
+            metadata_name: "N".repeat(MAX_NAME_LENGTH),
+            metadata_symbol: "S".repeat(MAX_SYMBOL_LENGTH),
+            seller_fee_basis_points: 500,
+            category: 0,
+            bump: 255,
+            distributed: vec![0u8; 2],
+        };
+        let mut data = Vec::new();
+        airdrop.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Airdrop::space(recipient_count));
+
+        airdrop.mark_distributed(9).unwrap();
+        assert!(airdrop.is_distributed(9));
+        assert!(!airdrop.is_distributed(8));
+        assert_eq!(airdrop.distributed_count, 1);
+        assert!(airdrop.mark_distributed(9).is_err());
+        assert!(airdrop.mark_distributed(recipient_count).is_err());
+    }
+
+    #[test]
+    fn gift_serializes_to_its_declared_length() {
+        let gift = Gift {
+            config: Pubkey::new_unique(),
+            manifest: Pubkey::new_unique(),
+            sender: Pubkey::new_unique(),
+            secret_hash: keccak::hash(&[7u8; 32]).to_bytes(),
+            expires_at: 1_700_000_000,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        gift.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Gift::LEN);
+    }
+
+    #[test]
+    fn creator_profiles_route_the_creator_share_to_the_payout_wallet() {
+        let config = Pubkey::new_unique();
+        let minter = Pubkey::new_unique();
+        let studio = Pubkey::new_unique();
+        let auth = Pubkey::new_unique();
+        let profile = CreatorProfile {
+            config,
+            creator: minter,
+            display_name_hash: [3u8; 32],
+            payout_wallet: Pubkey::new_unique(),
+            verified: true,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        profile.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), CreatorProfile::LEN);
+
+        let creators = [
+            CreatorInput {
+                address: minter,
+                verified: true,
+                share: 80,
+            },
+            CreatorInput {
+                address: studio,
+                verified: false,
+                share: 20,
+            },
+        ];
+        let routed =
+            apply_creator_profile(&creators, Some(&profile), &config, &minter, &auth).unwrap();
+        assert_eq!(routed[0].address, profile.payout_wallet);
+        assert_eq!(routed[0].share, 80);
+        assert_eq!(routed[1].address, studio);
+
+        assert!(apply_creator_profile(&creators, Some(&profile), &config, &studio, &auth).is_err());
+        let mut listed = profile.clone();
+        listed.payout_wallet = studio;
+        assert!(apply_creator_profile(&creators, Some(&listed), &config, &minter, &auth).is_err());
+        assert_eq!(
+            apply_creator_profile(&creators, None, &config, &minter, &auth).unwrap()[0].address,
+            minter
+        );
+    }
+
+    #[test]
+    fn approved_creators_only_approve_their_own_config_and_wallet() {
+        let config = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let record = ApprovedCreator {
+            config,
+            creator,
+            bump: 254,
+        };
+        let mut data = Vec::new();
+        record.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ApprovedCreator::LEN);
+
+        assert!(record.approves(&config, &creator));
+        assert!(!record.approves(&Pubkey::new_unique(), &creator));
+        assert!(!record.approves(&config, &Pubkey::new_unique()));
+
+        let mut state = Config::zeroed();
+        assert!(!state.creator_allowlist_enabled());
+        state.set_creator_allowlist_enabled(true);
+        assert!(state.creator_allowlist_enabled());
+    }
+
+    #[test]
+    fn migrated_manifests_move_to_the_new_config() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.config = Pubkey::new_unique();
+        manifest.object_id = 12;
+        manifest.mint = Pubkey::new_unique();
+        manifest.bump = 254;
+        manifest.initialized = 1;
+        manifest.set_history_enabled(true);
+        manifest.set_custody_trail_enabled(true);
+        assert!(migrated_manifest(&manifest, Pubkey::new_unique(), 253).is_err());
+
+        manifest.minted = 1;
+        let new_config = Pubkey::new_unique();
+        let migrated = migrated_manifest(&manifest, new_config, 253).unwrap();
+        assert_eq!(migrated.config, new_config);
+        assert_eq!(migrated.bump, 253);
+        assert_eq!(migrated.object_id, 12);
+        assert_eq!(migrated.mint, manifest.mint);
+        assert_eq!(migrated.layout_version, MANIFEST_LAYOUT_VERSION);
+        assert!(!migrated.history_enabled() && !migrated.custody_trail_enabled());
+
+        manifest.vaulted = 1;
+        assert!(migrated_manifest(&manifest, new_config, 253).is_err());
+
+        let record = ManifestMigration {
+            old_config: manifest.config,
+            new_config,
+            old_manifest: Pubkey::new_unique(),
+            new_manifest: Pubkey::new_unique(),
+            object_id: 12,
+            migrated_slot: 9,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        record.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ManifestMigration::LEN);
+    }
+
+    #[test]
+    fn global_pause_stops_mints_and_updates_once_set() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut empty = Vec::new();
+        let system_owner = Pubkey::default();
+        let missing = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut empty,
+            &system_owner,
+            false,
+            0,
+        );
+        assert!(require_not_globally_paused(&missing).is_ok());
+
+        let mut state = GlobalPause {
+            paused: true,
+            updated_by: Pubkey::new_unique(),
+            updated_slot: 9,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), GlobalPause::LEN);
+        let mut lamports = 1;
+        let paused = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert!(require_not_globally_paused(&paused).is_err());
+
+        state.paused = false;
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        let mut lamports = 1;
+        let resumed = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert!(require_not_globally_paused(&resumed).is_ok());
+    }
+
+    #[test]
+    fn holder_proposals_need_a_majority_and_quorum() {
+        let mut proposal = HolderProposal {
+            config: Pubkey::new_unique(),
+            proposal_id: u64::MAX,
+            description_hash: [5u8; 32],
+            voting_ends_slot: u64::MAX,
+            quorum: 3,
+            yes_votes: 2,
+            no_votes: 0,
+            finalized: true,
+            passed: true,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        proposal.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), HolderProposal::LEN);
+
+        assert!(!proposal.passes());
+        proposal.no_votes = 1;
+        assert!(proposal.passes());
+        proposal.no_votes = 2;
+        assert!(!proposal.passes());
+
+        let vote = HolderVote {
+            proposal: Pubkey::new_unique(),
+            manifest: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            approve: true,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        vote.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), HolderVote::LEN);
+    }
+
+    #[test]
+    fn sas_attestations_must_name_the_object() {
+        let manifest_key = Pubkey::new_unique();
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.mint = Pubkey::new_unique();
+        let credential = Pubkey::new_unique();
+        let schema = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+
+        let mut data = vec![SAS_ATTESTATION_DISCRIMINATOR];
+        data.extend_from_slice(manifest.mint.as_ref());
+        data.extend_from_slice(credential.as_ref());
+        data.extend_from_slice(schema.as_ref());
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&[9u8; 3]);
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&500i64.to_le_bytes());
+        data.extend_from_slice(Pubkey::default().as_ref());
+
+        let mut attestation = parse_sas_attestation(&data).unwrap();
+        assert_eq!(
+            attestation,
+            SasAttestation {
+                nonce: manifest.mint,
+                credential,
+                schema,
+                signer,
+                expiry: 500,
+            }
+        );
+        assert!(parse_sas_attestation(&data[..data.len() - 1]).is_err());
+        data[0] = SAS_ATTESTATION_DISCRIMINATOR + 1;
+        assert!(parse_sas_attestation(&data).is_err());
+
+        assert!(require_linkable_attestation(&attestation, &manifest_key, &manifest, 499).is_ok());
+        assert!(require_linkable_attestation(&attestation, &manifest_key, &manifest, 500).is_err());
+        attestation.expiry = 0;
+        attestation.nonce = manifest_key;
+        assert!(require_linkable_attestation(&attestation, &manifest_key, &manifest, 500).is_ok());
+        attestation.nonce = Pubkey::new_unique();
+        assert!(require_linkable_attestation(&attestation, &manifest_key, &manifest, 0).is_err());
+    }
+
+    #[test]
+    fn metadata_defaults_fill_in_omitted_values() {
+        let config = Pubkey::new_unique();
+        let studio = Pubkey::new_unique();
+        let minter = Pubkey::new_unique();
+        let defaults = MetadataDefaults {
+            config,
+            symbol: "OGAL".into(),
+            seller_fee_basis_points: 500,
+            creators: vec![CreatorInput {
+                address: studio,
+                verified: false,
+                share: 30,
+            }],
+            bump: 255,
+        };
+
+        let (symbol, fee, creators) = apply_metadata_defaults(
+            Some(&defaults),
+            &config,
+            &minter,
+            "",
+            DEFAULT_SELLER_FEE_BASIS_POINTS,
+            &[],
+        )
+        .unwrap();
+        assert_eq!((symbol.as_str(), fee), ("OGAL", 500));
+        assert_eq!(creators.len(), 2);
+        assert_eq!(creators[1].address, minter);
+        assert!(creators[1].verified);
+        assert_eq!(creators[1].share, 70);
+        assert!(validate_creators(&creators, &Pubkey::new_unique(), &minter).is_ok());
+
+        let explicit = [CreatorInput {
+            address: minter,
+            verified: true,
+            share: 100,
+        }];
+        let (symbol, fee, creators) =
+            apply_metadata_defaults(Some(&defaults), &config, &minter, "LVL", 0, &explicit)
+                .unwrap();
+        assert_eq!((symbol.as_str(), fee, creators.len()), ("LVL", 0, 1));
+        assert!(apply_metadata_defaults(
+            Some(&defaults),
+            &Pubkey::new_unique(),
+            &minter,
+            "",
+            0,
+            &[]
+        )
+        .is_err());
+
+        let mut full = defaults.clone();
+        full.symbol = "S".repeat(MAX_SYMBOL_LENGTH);
+        full.creators = (0..MAX_CREATOR_LIMIT - 1)
+            .map(|_| CreatorInput {
+                address: Pubkey::new_unique(),
+                verified: false,
+                share: 1,
+            })
+            .collect();
+        assert!(validate_creator_template(&full.creators, &Pubkey::new_unique()).is_ok());
+        let mut data = Vec::new();
+        full.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MetadataDefaults::LEN);
+    }
+
+    #[test]
+    fn creator_royalties_replace_the_fee_and_creators() {
+        let minter = Pubkey::new_unique();
+        let platform = Pubkey::new_unique();
+        let royalty = CreatorRoyalty {
+            config: Pubkey::new_unique(),
+            creator: minter,
+            seller_fee_basis_points: 750,
+            creators: vec![CreatorInput {
+                address: platform,
+                verified: false,
+                share: 20,
+            }],
+            bump: 255,
+        };
+        let sent = vec![CreatorInput {
+            address: minter,
+            verified: true,
+            share: 100,
+        }];
+
+        let (fee, creators) = apply_creator_royalty(Some(&royalty), &minter, 0, sent.clone());
+        assert_eq!(fee, 750);
+        assert_eq!(creators.len(), 2);
+        assert_eq!((creators[0].address, creators[0].share), (platform, 20));
+        assert_eq!((creators[1].address, creators[1].share), (minter, 80));
+        assert!(creators[1].verified);
+        let (fee, creators) = apply_creator_royalty(None, &minter, 0, sent);
+        assert_eq!((fee, creators.len(), creators[0].share), (0, 1, 100));
+
+        let mut full = royalty.clone();
+        full.creators = (0..MAX_CREATOR_LIMIT - 1)
+            .map(|_| CreatorInput {
+                address: Pubkey::new_unique(),
+                verified: false,
+                share: 1,
+            })
+            .collect();
+        let mut data = Vec::new();
+        full.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), CreatorRoyalty::LEN);
+    }
+
+    #[test]
+    fn kyc_attestations_must_match_the_gate_and_recipient() {
+        let recipient = Pubkey::new_unique();
+        let mut kyc_gate = KycGate {
+            config: Pubkey::new_unique(),
+            credential: Pubkey::new_unique(),
+            schema: Pubkey::default(),
+            restricted_categories: [0u16; MAX_CATEGORIES],
+            restricted_count: 2,
+            bump: 0,
+        };
+        kyc_gate.restricted_categories[..2].copy_from_slice(&[4, 9]);
+        assert!(kyc_gate.is_restricted(9));
+        assert!(!kyc_gate.is_restricted(0));
+        assert!(!kyc_gate.is_restricted(5));
+
+        let mut attestation = SasAttestation {
+            nonce: recipient,
+            credential: kyc_gate.credential,
+            schema: Pubkey::new_unique(),
+            signer: Pubkey::new_unique(),
+            expiry: 100,
+        };
+        assert!(require_kyc_attestation(&kyc_gate, &attestation, &recipient, 99).is_ok());
+        assert!(require_kyc_attestation(&kyc_gate, &attestation, &recipient, 100).is_err());
+        assert!(
+            require_kyc_attestation(&kyc_gate, &attestation, &Pubkey::new_unique(), 0).is_err()
+        );
+
+        kyc_gate.schema = Pubkey::new_unique();
+        assert!(require_kyc_attestation(&kyc_gate, &attestation, &recipient, 0).is_err());
+        attestation.schema = kyc_gate.schema;
+        assert!(require_kyc_attestation(&kyc_gate, &attestation, &recipient, 0).is_ok());
+        attestation.credential = Pubkey::new_unique();
+        assert!(require_kyc_attestation(&kyc_gate, &attestation, &recipient, 0).is_err());
+    }
+
+    #[test]
+    fn external_references_must_name_a_foreign_manifest() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.config = Pubkey::new_unique();
+        let foreign_config = Pubkey::new_unique();
+        let (foreign_key, foreign_bump) = Pubkey::find_program_address(
+            &[MANIFEST_SEED, foreign_config.as_ref(), &3u64.to_le_bytes()],
+            &crate::ID,
+        );
+        let mut foreign = ObjectManifest::zeroed();
+        foreign.config = foreign_config;
+        foreign.object_id = 3;
+        foreign.bump = foreign_bump;
+
+        assert!(
+            require_external_reference(&manifest, &foreign_key, &foreign, &foreign_config, 3)
+                .is_err()
+        );
+        foreign.set_initialized(true);
+        assert!(
+            require_external_reference(&manifest, &foreign_key, &foreign, &foreign_config, 3)
+                .is_ok()
+        );
+        assert!(
+            require_external_reference(&manifest, &foreign_key, &foreign, &foreign_config, 4)
+                .is_err()
+        );
+        assert!(require_external_reference(
+            &manifest,
+            &Pubkey::new_unique(),
+            &foreign,
+            &foreign_config,
+            3
+        )
+        .is_err());
+        manifest.config = foreign_config;
+        assert!(
+            require_external_reference(&manifest, &foreign_key, &foreign, &foreign_config, 3)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn uri_attestations_must_match_and_be_fresh() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.manifest_hash = [5u8; 32];
+        manifest.set_metadata_uri("https://example.com/a.json");
+        let oracle = LivenessOracle {
+            config: Pubkey::new_unique(),
+            attester: Pubkey::new_unique(),
+            max_age_slots: 100,
+            bump: 255,
+        };
+        let mut attestation = UriAttestation {
+            manifest: Pubkey::new_unique(),
+            attester: oracle.attester,
+            manifest_hash: manifest.manifest_hash,
+            uri_hash: hash(b"https://example.com/a.json").to_bytes(),
+            slot: 1_000,
+            bump: 255,
+        };
+        assert!(require_current_uri_attestation(&attestation, &oracle, &manifest, 1_100).is_ok());
+        assert!(require_current_uri_attestation(&attestation, &oracle, &manifest, 1_101).is_err());
+
+        manifest.set_metadata_uri("https://example.com/b.json");
+        assert!(require_current_uri_attestation(&attestation, &oracle, &manifest, 1_000).is_err());
+
+        manifest.set_metadata_uri("https://example.com/a.json");
+        attestation.attester = Pubkey::new_unique();
+        assert!(require_current_uri_attestation(&attestation, &oracle, &manifest, 1_000).is_err());
+    }
+
+    #[test]
+    fn usd_mint_prices_convert_within_the_confidence_band() {
+        // $150.00 per SOL at exponent -8: a $3.00 mint costs 0.02 SOL.
+        assert_eq!(
+            usd_cents_to_lamports(300, 150_0000_0000, -8).unwrap(),
+            20_000_000
+        );
+        // Fractions of a lamport round up.
+        assert_eq!(
+            usd_cents_to_lamports(1, 3_0000_0000, -8).unwrap(),
+            3_333_334
+        );
+
+        let feed_id = [9u8; 32];
+        let mint_price = MintPrice {
+            config: Pubkey::new_unique(),
+            usd_cents: 300,
+            treasury: Pubkey::new_unique(),
+            feed_id,
+            max_confidence_bps: 100,
+            max_price_age_secs: 60,
+            bump: 255,
+        };
+        let mut data = hash(b"account:PriceUpdateV2").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&[0u8; 32]);
+        data.push(PYTH_VERIFICATION_FULL);
+        data.extend_from_slice(&feed_id);
+        data.extend_from_slice(&150_0000_0000i64.to_le_bytes());
+        data.extend_from_slice(&1_5000_0000u64.to_le_bytes());
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&1_000i64.to_le_bytes());
+        data.extend_from_slice(&[0u8; 24]);
+        let mut pyth = parse_pyth_price(&data).unwrap();
+        assert_eq!(
+            mint_price_lamports(&mint_price, &pyth, 1_060).unwrap(),
+            20_000_000
+        );
+        assert!(mint_price_lamports(&mint_price, &pyth, 1_061).is_err());
+
+        pyth.conf = 1_5000_0001;
+        assert!(mint_price_lamports(&mint_price, &pyth, 1_000).is_err());
+
+        data[PYTH_VERIFICATION_LEVEL_OFFSET] = 0;
+        assert!(parse_pyth_price(&data).is_err());
+    }
+
+    #[test]
+    fn core_assets_are_created_without_plugins() {
+        let data = core_create_v1_data("Lv", "ar://x");
+        assert_eq!(
+            data,
+            [
+                CORE_CREATE_V1,
+                0,
+                2,
+                0,
+                0,
+                0,
+                b'L',
+                b'v',
+                6,
+                0,
+                0,
+                0,
+                b'a',
+                b'r',
+                b':',
+                b'/',
+                b'/',
+                b'x',
+                0
+            ]
+        );
+
+        assert_eq!(AssetBackend::try_from(1).unwrap(), AssetBackend::Core);
+        assert!(AssetBackend::try_from(2).is_err());
+    }
+
+    #[test]
+    fn metadata_delegate_records_are_scoped_by_role_and_authority() {
+        let metadata_program = mpl_program_id();
+        let mint = Pubkey::new_unique();
+        let update_authority = Pubkey::new_unique();
+        let auth = Pubkey::new_unique();
+        let data = find_metadata_delegate_record(
+            &metadata_program,
+            &mint,
+            MetadataDelegateScope::Data,
+            &update_authority,
+            &auth,
+        );
+        assert_eq!(
+            data,
+            Pubkey::find_program_address(
+                &[
+                    b"metadata",
+                    metadata_program.as_ref(),
+                    mint.as_ref(),
+                    b"data_delegate",
+                    update_authority.as_ref(),
+                    auth.as_ref(),
+                ],
+                &metadata_program,
+            )
+            .0
+        );
+        assert_ne!(
+            data,
+            find_metadata_delegate_record(
+                &metadata_program,
+                &mint,
+                MetadataDelegateScope::Collection,
+                &update_authority,
+                &auth,
+            )
+        );
+        assert_ne!(
+            data,
+            find_metadata_delegate_record(
+                &metadata_program,
+                &mint,
+                MetadataDelegateScope::Data,
+                &Pubkey::new_unique(),
+                &auth,
+            )
+        );
+    }
+
+    #[test]
+    fn token2022_metadata_rent_covers_the_serialized_extension() {
+        use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata;
+        use spl_type_length_value::variable_len_pack::VariableLenPack;
+
+        let metadata = TokenMetadata {
+            name: "Level".into(),
+            symbol: "TT".into(),
+            uri: "https://example.com/manifest.json".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            token_metadata_extension_len(&metadata.name, &metadata.symbol, &metadata.uri),
+            4 + metadata.get_packed_len().unwrap()
+        );
+        assert_eq!(AssetBackend::try_from(2).unwrap(), AssetBackend::Token2022);
+    }
+
+    #[test]
+    fn expired_objects_are_treated_as_inactive() {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.set_is_active(true);
+        assert!(manifest.is_active_at(i64::MAX));
+
+        manifest.expires_at = 100;
+        assert!(manifest.is_active_at(99));
+        assert!(!manifest.is_active_at(100));
+        assert!(manifest.is_active());
+    }
+}
+
+#[program]
+pub mod owner_governed_asset_ledger {
+    use super::*;
+
+    /// Initializes a configuration instance under the provided namespace and
+    /// lists it in the program's [`NamespaceRegistry`].
+    ///
+    /// Passing a distinct namespace allows the authority to operate multiple
+    /// configurations concurrently or migrate to a new namespace without
+    /// redeploying the program. To migrate, derive the desired namespace,
+    /// invoke [`initialize`] (or [`migrate_config_namespace`]) with the new
+    /// namespace, and point subsequent instructions at the new config PDA.
+    pub fn initialize(ctx: Context<Initialize>, namespace: Pubkey) -> Result<()> {
+        let config_bump = ctx.bumps.config;
+        let auth_bump = ctx.bumps.auth;
+
+        let authority_key = ctx.accounts.authority.key();
+        let payer_key = ctx.accounts.payer.key();
+        require!(
+            authority_key == payer_key || is_allowed_deployer(&authority_key),
+            ErrorCode::UnauthorizedDeployer
+        );
+
+        let config_key = ctx.accounts.config.key();
+        // Every other field, including `parent_config`, starts zeroed.
+        let mut config = ctx.accounts.config.load_init()?;
+        config.authority = authority_key;
+        config.config_bump = config_bump;
+        config.auth_bump = auth_bump;
+        config.namespace = namespace;
+        config.layout_version = CONFIG_LAYOUT_VERSION;
+
+        let auth = &mut ctx.accounts.auth;
+        auth.config = config_key;
+        auth.bump = auth_bump;
+
+        let registry = &mut ctx.accounts.namespace_registry;
+        registry.bump = ctx.bumps.namespace_registry;
+        append_namespace_entry(
+            registry,
+            &ctx.accounts.registry_page,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            NamespaceEntry {
+                namespace,
+                config: config_key,
+                authority: authority_key,
+                created_slot: Clock::get()?.slot,
+            },
+        )?;
+
+        emit_logged!(ConfigInitialized {
+            config: config_key,
+            namespace,
+            authority: authority_key,
+            context: EventContext::new(payer_key, authority_key)?,
+        });
+
+        Ok(())
+    }
+
+    /// Rotates the config authority. The parent config's authority may do
+    /// this as well.
+    pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+        require_config_admin(
+            &ctx.accounts.config,
+            ctx.accounts.parent_config.as_ref(),
+            &ctx.accounts.authority.key(),
+        )?;
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_authority = config.authority;
+        config.authority = new_authority;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            AuthorityUpdated {
+                config: config_key,
+                previous_authority,
+                new_authority,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Proposes handing the whole namespace to `new_authority`, who completes
+    /// the handover with `accept_namespace_transfer`. Unlike `set_authority`,
+    /// the new authority must sign, so a registry cannot be handed to a key
+    /// nobody controls. A new proposal replaces a pending one.
+    pub fn transfer_namespace(
+        ctx: Context<TransferNamespace>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require_keys_neq!(
+            new_authority,
+            authority,
+            ErrorCode::InvalidNamespaceTransfer
+        );
+        require_keys_neq!(
+            new_authority,
+            Pubkey::default(),
+            ErrorCode::InvalidNamespaceTransfer
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let transfer = &mut ctx.accounts.namespace_transfer;
+        transfer.config = config_key;
+        transfer.current_authority = authority;
+        transfer.new_authority = new_authority;
+        transfer.proposed_slot = Clock::get()?.slot;
+        transfer.bump = ctx.bumps.namespace_transfer;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            NamespaceTransferProposed {
+                config: config_key,
+                namespace: ctx.accounts.config.load()?.namespace,
+                current_authority: authority,
+                new_authority,
+                context: EventContext::signed_by(authority)?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Completes a namespace handover proposed by `transfer_namespace`. The
+    /// proposal lapses if the authority changed since it was made. Closes the
+    /// proposal, returning its rent to the previous authority.
+    pub fn accept_namespace_transfer(ctx: Context<AcceptNamespaceTransfer>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let new_authority = ctx.accounts.new_authority.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_authority = config.authority;
+        require_keys_eq!(
+            previous_authority,
+            ctx.accounts.namespace_transfer.current_authority,
+            ErrorCode::NamespaceTransferStale
+        );
+        config.authority = new_authority;
+
+        let context = EventContext::new(new_authority, previous_authority)?;
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            AuthorityUpdated {
+                config: config_key,
+                previous_authority,
+                new_authority,
+                context,
+            }
+        );
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            NamespaceTransferred {
+                config: config_key,
+                namespace: config.namespace,
+                previous_authority,
+                new_authority,
+                proposed_slot: ctx.accounts.namespace_transfer.proposed_slot,
+                context,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Withdraws a pending namespace handover.
+    pub fn cancel_namespace_transfer(ctx: Context<CancelNamespaceTransfer>) -> Result<()> {
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            NamespaceTransferCancelled {
+                config: ctx.accounts.config.key(),
+                new_authority: ctx.accounts.namespace_transfer.new_authority,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Places the config under `parent_config`, whose authority co-signs and
+    /// may then pause the config and rotate its authority. Omitting the
+    /// parent detaches the config. A config cannot be its parent's parent.
+    pub fn set_parent_config(ctx: Context<SetParentConfig>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let (parent, context) = match &ctx.accounts.parent_config {
+            Some(parent_config) => {
+                let parent_authority = ctx
+                    .accounts
+                    .parent_authority
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidAuthority)?;
+                let parent = parent_config.load()?;
+                require_keys_eq!(
+                    parent.authority,
+                    parent_authority.key(),
+                    ErrorCode::InvalidAuthority
+                );
+                require!(
+                    parent_config.key() != config_key && parent.parent_config != config_key,
+                    ErrorCode::InvalidParentConfig
+                );
+                (
+                    parent_config.key(),
+                    EventContext::new(ctx.accounts.authority.key(), parent_authority.key())?,
+                )
+            }
+            None => (
+                Pubkey::default(),
+                EventContext::signed_by(ctx.accounts.authority.key())?,
+            ),
+        };
+
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_parent = config.parent_config;
+        config.parent_config = parent;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ParentConfigUpdated {
+                config: config_key,
+                previous_parent,
+                parent,
+                context,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Hands the config to an SPL Governance (Realms) governance by setting
+    /// the authority to its native treasury PDA, which signs every
+    /// authority-gated instruction a passed proposal executes. The treasury
+    /// address is derived here rather than supplied, so it cannot be mistyped.
+    pub fn set_governance_authority(ctx: Context<SetGovernanceAuthority>) -> Result<()> {
+        let governance_program = ctx.accounts.governance_program.key();
+        require!(
+            ctx.accounts.governance_program.executable,
+            ErrorCode::InvalidGovernanceProgram
+        );
+        require!(
+            ctx.accounts.governance.owner == &governance_program
+                && !ctx.accounts.governance.data_is_empty(),
+            ErrorCode::InvalidGovernanceAccount
+        );
+
+        let governance = ctx.accounts.governance.key();
+        let native_treasury = governance_native_treasury(&governance_program, &governance);
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_authority = config.authority;
+        config.authority = native_treasury;
+
+        let context = EventContext::signed_by(ctx.accounts.authority.key())?;
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            AuthorityUpdated {
+                config: config_key,
+                previous_authority,
+                new_authority: native_treasury,
+                context,
+            }
+        );
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            GovernanceAuthoritySet {
+                config: config_key,
+                previous_authority,
+                governance_program,
+                governance,
+                native_treasury,
+                context,
+            }
+        );
+
+        Ok(())
+    }
+
+    pub fn rotate_collection_authority(
+        ctx: Context<RotateCollectionAuthority>,
+        new_update_authority: Pubkey,
+    ) -> Result<()> {
+        let metadata_program = ctx.accounts.config.load()?.metadata_program();
+        require_keys_eq!(
+            ctx.accounts.token_metadata_program.key(),
+            metadata_program,
+            ErrorCode::InvalidTokenMetadataProgram
+        );
+
+        let config_key = ctx.accounts.config.key();
+        let collection_mint_key = ctx.accounts.collection_mint.key();
+        let expected_collection_metadata =
+            find_metadata_pda(&collection_mint_key, &metadata_program);
+
+        require_keys_eq!(
+            ctx.accounts.collection_metadata.key(),
+            expected_collection_metadata,
+            ErrorCode::InvalidCollectionMetadataAccount
+        );
+
+        let metadata_program_info = ctx.accounts.token_metadata_program.to_account_info();
+        let collection_metadata_info = ctx.accounts.collection_metadata.to_account_info();
+        let auth_info = ctx.accounts.auth.to_account_info();
+
+        let auth_bump = ctx.accounts.auth.bump;
+        let signer_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[auth_bump]];
+
+        let args = UpdateMetadataAccountV2InstructionArgs {
+            data: None,
+            new_update_authority: Some(to_solana_pubkey(&new_update_authority)),
+            primary_sale_happened: None,
+            is_mutable: None,
+        };
+
+        UpdateMetadataAccountV2Cpi::new(
+            &metadata_program_info,
+            UpdateMetadataAccountV2CpiAccounts {
+                metadata: &collection_metadata_info,
+                update_authority: &auth_info,
+            },
+            args,
+        )
+        .invoke_signed(&[signer_seeds])
+        .map_err(anchor_lang::error::Error::from)?;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CollectionAuthorityRotated {
+                config: config_key,
+                collection_mint: collection_mint_key,
+                previous_update_authority: ctx.accounts.auth.key(),
+                new_update_authority,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Hands the update authority of an object's metadata to
+    /// `update_authority` and approves the auth PDA as its Data delegate, so
+    /// an external authority owns the metadata while manifest updates still
+    /// rewrite its URI. Signed by the config authority and the new update
+    /// authority; when the metadata already belongs to `update_authority`
+    /// only the delegate is approved.
+    pub fn delegate_object_metadata(ctx: Context<DelegateObjectMetadata>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let manifest = accounts.object_manifest.load()?;
+        require_match!(
+            "config",
+            accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        drop(manifest);
+        approve_auth_delegate(
+            MetadataDelegation {
+                config: &accounts.config,
+                auth: &accounts.auth,
+                update_authority: accounts.update_authority.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                mint: accounts.object_mint.to_account_info(),
+                metadata: accounts.metadata.to_account_info(),
+                delegate_record: accounts.delegate_record.to_account_info(),
+                metadata_program: accounts.token_metadata_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                instructions: accounts.instructions.to_account_info(),
+                authority: accounts.authority.key(),
+                audit_log: accounts.audit_log.as_ref(),
+            },
+            MetadataDelegateScope::Data,
+        )
+    }
+
+    /// Hands the update authority of the collection metadata to
+    /// `update_authority` and approves the auth PDA as its Collection
+    /// delegate, so first mints keep verifying objects into the collection.
+    /// Mints then pass the delegate record and the instructions sysvar.
+    pub fn delegate_collection_metadata(ctx: Context<DelegateCollectionMetadata>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        approve_auth_delegate(
+            MetadataDelegation {
+                config: &accounts.config,
+                auth: &accounts.auth,
+                update_authority: accounts.update_authority.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                mint: accounts.collection_mint.to_account_info(),
+                metadata: accounts.collection_metadata.to_account_info(),
+                delegate_record: accounts.delegate_record.to_account_info(),
+                metadata_program: accounts.token_metadata_program.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                instructions: accounts.instructions.to_account_info(),
+                authority: accounts.authority.key(),
+                audit_log: accounts.audit_log.as_ref(),
+            },
+            MetadataDelegateScope::Collection,
+        )
+    }
+
+    pub fn mint_object_nft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintObjectNft<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<MintResult> {
+        let target = ObjectMintTarget::from_accounts(
+            &ctx.accounts.base,
+            &ctx.accounts.metadata,
+            &ctx.bumps.base,
+        );
+        process_mint_object(
+            &mut ctx.accounts.base,
+            &ctx.accounts.metadata,
+            target,
+            ctx.program_id,
+            ctx.remaining_accounts,
+            MintObjectArgs {
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash,
+                metadata_name: &metadata_name,
+                metadata_symbol: &metadata_symbol,
+                seller_fee_basis_points,
+                creators: &creators,
+                hash_algorithm: ManifestHashAlgorithm::try_from(hash_algorithm)?,
+                category,
+                tag_hash,
+                arweave_tx_id,
+                origin: ObjectOrigin::Minted,
+                provenance: &[],
+                creator: None,
+                stage: MintStage::Complete,
+            },
+        )
+    }
+
+    /// Mints a new object as a Metaplex Core asset in `core_collection`, for
+    /// configs whose backend is [`AssetBackend::Core`]. The asset is a PDA of
+    /// the manifest, recorded as its `mint`, and carries the manifest URI in
+    /// place of Token Metadata. Remaining accounts are the optional memo
+    /// program and changelog accounts, in that order.
+    pub fn mint_core_object<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintCoreObject<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<MintResult> {
+        let accounts = &mut *ctx.accounts;
+        require_not_globally_paused(&accounts.global_pause)?;
+        validate_new_object(
+            &accounts.config.load()?,
+            AssetBackend::Core,
+            &manifest_uri,
+            &metadata_name,
+            "",
+            category,
+            &arweave_tx_id,
+        )?;
+        let hash_algorithm = ManifestHashAlgorithm::try_from(hash_algorithm)?;
+        require_match!(
+            "core_collection_update_authority",
+            core_collection_update_authority(&accounts.core_collection.to_account_info())?,
+            accounts.auth.key(),
+            ErrorCode::InvalidCoreCollection
+        );
+
+        let config_key = accounts.config.key();
+        let manifest_key = accounts.object_manifest.key();
+        let asset_key = accounts.core_asset.key();
+        let recipient = accounts.recipient.key();
+        let creator = accounts.minter.key();
+        require_object_reservation(&accounts.object_reservation, &creator)?;
+        require_approved_creator(
+            &accounts.config.load()?,
+            &accounts.config.key(),
+            accounts.approved_creator.as_ref(),
+            &creator,
+        )?;
+        let slot = Clock::get()?.slot;
+        let leaf = {
+            let mut manifest = accounts.object_manifest.load_init()?;
+            write_new_manifest(
+                &mut manifest,
+                NewManifest {
+                    config: config_key,
+                    object_id,
+                    mint: asset_key,
+                    bump: ctx.bumps.object_manifest,
+                    mint_bump: ctx.bumps.core_asset,
+                    backend: AssetBackend::Core,
+                    manifest_uri: &manifest_uri,
+                    manifest_hash,
+                    hash_algorithm,
+                    arweave_tx_id,
+                    creator,
+                    owner: recipient,
+                    category,
+                    tag_hash,
+                    name: &metadata_name,
+                    symbol: "",
+                },
+                slot,
+            )?;
+            changelog_leaf(&manifest_key, &manifest, slot)
+        };
+
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        let asset_seeds: &[&[u8]] = &[
+            CORE_ASSET_SEED,
+            manifest_key.as_ref(),
+            &[ctx.bumps.core_asset],
+        ];
+        let core_program = accounts.core_program.to_account_info();
+        invoke_signed(
+            &Instruction {
+                program_id: MPL_CORE_ID,
+                accounts: vec![
+                    AccountMeta::new(asset_key, true),
+                    AccountMeta::new(accounts.core_collection.key(), false),
+                    AccountMeta::new_readonly(accounts.auth.key(), true),
+                    AccountMeta::new(accounts.rent_payer.key(), true),
+                    AccountMeta::new_readonly(recipient, false),
+                    AccountMeta::new_readonly(MPL_CORE_ID, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                    AccountMeta::new_readonly(MPL_CORE_ID, false),
+                ],
+                data: core_create_v1_data(&metadata_name, &manifest_uri),
+            },
+            &[
+                accounts.core_asset.to_account_info(),
+                accounts.core_collection.to_account_info(),
+                accounts.auth.to_account_info(),
+                accounts.rent_payer.to_account_info(),
+                accounts.recipient.to_account_info(),
+                accounts.system_program.to_account_info(),
+                core_program,
+            ],
+            &[asset_seeds, auth_seeds],
+        )?;
+
+        {
+            let mut tracking = accounts.tracking();
+            record_new_object(&mut tracking, ctx.program_id, object_id, &creator)?;
+            record_object_mint(&mut tracking, ctx.program_id, object_id)?;
+            charge_mint_price(&tracking, manifest_key, object_id)?;
+            check_kyc_gate(&tracking, category, &recipient)?;
+        }
+        let (memo_program, changelog) = single_mint_remaining_accounts(ctx.remaining_accounts);
+        log_mint_memo(&accounts.config, memo_program, object_id, &manifest_hash)?;
+        append_changelog_leaf(&accounts.config, changelog.as_ref(), leaf)?;
+
+        emit_logged!(ObjectMinted {
+            config: config_key,
+            manifest: manifest_key,
+            mint: asset_key,
+            recipient,
+            object_id,
+            manifest_hash,
+            hash_algorithm: hash_algorithm as u8,
+            metadata_uri: manifest_uri,
+            creator,
+            context: EventContext::new(creator, accounts.authority.key())?,
+        });
+
+        Ok(MintResult {
+            mint: asset_key,
+            manifest: manifest_key,
+            object_id,
+            first_mint: true,
+        })
+    }
+
+    /// Mints a new object as a Token-2022 mint whose metadata pointer names
+    /// the mint itself and whose token metadata extension holds the name,
+    /// symbol, and manifest URI, for configs whose backend is
+    /// [`AssetBackend::Token2022`]. No Token Metadata program is involved.
+    /// The supply is fixed at one by removing the mint authority. While the
+    /// config's `recovery_delay_slots` is set, the auth PDA is also the mint's
+    /// permanent delegate so `recover_object` can move the token. While
+    /// `transfer_hook_enabled` is set, the mint names the companion transfer
+    /// hook and its extra account metas are created here. While
+    /// `seller_fee_basis_points` is set, the mint charges that transfer fee,
+    /// which `harvest_royalties` pays to the creator, and the object is
+    /// minted as [`ROYALTY_MINT_SUPPLY`] units instead of one token.
+    /// Remaining accounts are the optional memo program and changelog
+    /// accounts, in that order.
+    pub fn mint_token2022_object<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintToken2022Object<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        metadata_symbol: String,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<MintResult> {
+        let accounts = &mut *ctx.accounts;
+        require_not_globally_paused(&accounts.global_pause)?;
+        let recoverable = accounts.config.load()?.recovery_delay_slots != 0;
+        let hooked = accounts.config.load()?.transfer_hook_enabled();
+        let seller_fee_basis_points = accounts.config.load()?.seller_fee_basis_points;
+        let (decimals, supply) = if seller_fee_basis_points != 0 {
+            (ROYALTY_MINT_DECIMALS, ROYALTY_MINT_SUPPLY)
+        } else {
+            (0, 1)
+        };
+        validate_new_object(
+            &accounts.config.load()?,
+            AssetBackend::Token2022,
+            &manifest_uri,
+            &metadata_name,
+            &metadata_symbol,
+            category,
+            &arweave_tx_id,
+        )?;
+        let hash_algorithm = ManifestHashAlgorithm::try_from(hash_algorithm)?;
+
+        let config_key = accounts.config.key();
+        let manifest_key = accounts.object_manifest.key();
+        let mint_key = accounts.object_mint.key();
+        let auth_key = accounts.auth.key();
+        let recipient = accounts.recipient.key();
+        require_match!(
+            "recipient_token_account",
+            accounts.recipient_token_account.key(),
+            associated_token::get_associated_token_address_with_program_id(
+                &recipient,
+                &mint_key,
+                &token_2022::ID,
+            ),
+            ErrorCode::InvalidRecipientTokenAccount
+        );
+
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        let mint_seeds: &[&[u8]] = &[MINT_SEED, manifest_key.as_ref(), &[ctx.bumps.object_mint]];
+        let token_program = accounts.token_program.to_account_info();
+        let mint_info = accounts.object_mint.to_account_info();
+        let auth_info = accounts.auth.to_account_info();
+
+        let mut extensions = vec![spl_token_2022::extension::ExtensionType::MetadataPointer];
+        if recoverable {
+            extensions.push(spl_token_2022::extension::ExtensionType::PermanentDelegate);
+        }
+        if hooked {
+            extensions.push(spl_token_2022::extension::ExtensionType::TransferHook);
+        }
+        if seller_fee_basis_points != 0 {
+            extensions.push(spl_token_2022::extension::ExtensionType::TransferFeeConfig);
+        }
+        let mint_len = spl_token_2022::extension::ExtensionType::try_calculate_account_len::<
+            spl_token_2022::state::Mint,
+        >(&extensions)?;
+        let lamports = Rent::get()?.minimum_balance(
+            mint_len
+                + token_metadata_extension_len(&metadata_name, &metadata_symbol, &manifest_uri),
+        );
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: accounts.rent_payer.to_account_info(),
+                    to: mint_info.clone(),
+                },
+                &[mint_seeds],
+            ),
+            lamports,
+            mint_len as u64,
+            &token_2022::ID,
+        )?;
+        token_2022_extensions::metadata_pointer_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                token_2022_extensions::MetadataPointerInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint_info.clone(),
+                },
+            ),
+            Some(auth_key),
+            Some(mint_key),
+        )?;
+        if recoverable {
+            token_2022_extensions::permanent_delegate_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    token_2022_extensions::PermanentDelegateInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint_info.clone(),
+                    },
+                ),
+                &auth_key,
+            )?;
+        }
+        if hooked {
+            token_2022_extensions::transfer_hook_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    token_2022_extensions::TransferHookInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint_info.clone(),
+                    },
+                ),
+                Some(auth_key),
+                Some(TRANSFER_HOOK_PROGRAM_ID),
+            )?;
+        }
+        if seller_fee_basis_points != 0 {
+            token_2022_extensions::transfer_fee_initialize(
+                CpiContext::new(
+                    token_program.clone(),
+                    token_2022_extensions::TransferFeeInitialize {
+                        token_program_id: token_program.clone(),
+                        mint: mint_info.clone(),
+                    },
+                ),
+                Some(&auth_key),
+                Some(&auth_key),
+                seller_fee_basis_points,
+                ROYALTY_MINT_SUPPLY * u64::from(seller_fee_basis_points) / 10_000,
+            )?;
+        }
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                token_program.clone(),
+                token_2022::InitializeMint2 {
+                    mint: mint_info.clone(),
+                },
+            ),
+            decimals,
+            &auth_key,
+            Some(&auth_key),
+        )?;
+        token_2022_extensions::token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                token_2022_extensions::TokenMetadataInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint_info.clone(),
+                    metadata: mint_info.clone(),
+                    mint_authority: auth_info.clone(),
+                    update_authority: auth_info.clone(),
+                },
+                &[auth_seeds],
+            ),
+            metadata_name.clone(),
+            metadata_symbol.clone(),
+            manifest_uri.clone(),
+        )?;
+        associated_token::create(CpiContext::new(
+            accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: accounts.rent_payer.to_account_info(),
+                associated_token: accounts.recipient_token_account.to_account_info(),
+                authority: accounts.recipient.to_account_info(),
+                mint: mint_info.clone(),
+                system_program: accounts.system_program.to_account_info(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                token_2022::MintTo {
+                    mint: mint_info.clone(),
+                    to: accounts.recipient_token_account.to_account_info(),
+                    authority: auth_info.clone(),
+                },
+                &[auth_seeds],
+            ),
+            supply,
+        )?;
+        if hooked {
+            initialize_object_hook(accounts, &[auth_seeds])?;
+        }
+        token_2022::set_authority(
+            CpiContext::new_with_signer(
+                token_program,
+                token_2022::SetAuthority {
+                    current_authority: auth_info,
+                    account_or_mint: mint_info,
+                },
+                &[auth_seeds],
+            ),
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let creator = accounts.minter.key();
+        require_object_reservation(&accounts.object_reservation, &creator)?;
+        require_approved_creator(
+            &accounts.config.load()?,
+            &accounts.config.key(),
+            accounts.approved_creator.as_ref(),
+            &creator,
+        )?;
+        let slot = Clock::get()?.slot;
+        let leaf = {
+            let mut manifest = accounts.object_manifest.load_init()?;
+            write_new_manifest(
+                &mut manifest,
+                NewManifest {
+                    config: config_key,
+                    object_id,
+                    mint: mint_key,
+                    bump: ctx.bumps.object_manifest,
+                    mint_bump: ctx.bumps.object_mint,
+                    backend: AssetBackend::Token2022,
+                    manifest_uri: &manifest_uri,
+                    manifest_hash,
+                    hash_algorithm,
+                    arweave_tx_id,
+                    creator,
+                    owner: recipient,
+                    category,
+                    tag_hash,
+                    name: &metadata_name,
+                    symbol: &metadata_symbol,
+                },
+                slot,
+            )?;
+            changelog_leaf(&manifest_key, &manifest, slot)
+        };
+
+        {
+            let mut tracking = accounts.tracking();
+            record_new_object(&mut tracking, ctx.program_id, object_id, &creator)?;
+            record_object_mint(&mut tracking, ctx.program_id, object_id)?;
+            charge_mint_price(&tracking, manifest_key, object_id)?;
+            check_kyc_gate(&tracking, category, &recipient)?;
+        }
+        let (memo_program, changelog) = single_mint_remaining_accounts(ctx.remaining_accounts);
+        log_mint_memo(&accounts.config, memo_program, object_id, &manifest_hash)?;
+        append_changelog_leaf(&accounts.config, changelog.as_ref(), leaf)?;
+
+        emit_logged!(ObjectMinted {
+            config: config_key,
+            manifest: manifest_key,
+            mint: mint_key,
+            recipient,
+            object_id,
+            manifest_hash,
+            hash_algorithm: hash_algorithm as u8,
+            metadata_uri: manifest_uri,
+            creator,
+            context: EventContext::new(creator, accounts.authority.key())?,
+        });
+
+        Ok(MintResult {
+            mint: mint_key,
+            manifest: manifest_key,
+            object_id,
+            first_mint: true,
+        })
+    }
+
+    /// First half of a first mint that does not fit in one transaction:
+    /// creates the manifest, mint, metadata, and recipient token account and
+    /// mints the token, leaving the manifest pending. Takes the same accounts
+    /// and arguments as `mint_object_nft`; `finalize_object_mint` completes it.
+    pub fn prepare_object_mint<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MintObjectNft<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<MintResult> {
+        let target = ObjectMintTarget::from_accounts(
+            &ctx.accounts.base,
+            &ctx.accounts.metadata,
+            &ctx.bumps.base,
+        );
+        process_mint_object(
+            &mut ctx.accounts.base,
+            &ctx.accounts.metadata,
+            target,
+            ctx.program_id,
+            ctx.remaining_accounts,
+            MintObjectArgs {
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash,
+                metadata_name: &metadata_name,
+                metadata_symbol: &metadata_symbol,
+                seller_fee_basis_points,
+                creators: &creators,
+                hash_algorithm: ManifestHashAlgorithm::try_from(hash_algorithm)?,
+                category,
+                tag_hash,
+                arweave_tx_id,
+                origin: ObjectOrigin::Minted,
+                provenance: &[],
+                creator: None,
+                stage: MintStage::Prepare,
+            },
+        )
+    }
+
+    /// Second half of a split first mint: creates the master edition,
+    /// verifies the collection, and marks the pending manifest as minted.
+    pub fn finalize_object_mint(ctx: Context<FinalizeObjectMint>) -> Result<MintResult> {
+        let accounts = &ctx.accounts;
+        require!(!accounts.config.load()?.paused(), ErrorCode::MintingPaused);
+        require_not_globally_paused(&accounts.global_pause)?;
+        accounts
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_MINT)?;
+        let config_key = accounts.config.key();
+        let manifest_key = accounts.object_manifest.key();
+        let manifest = read_manifest(&accounts.object_manifest)?;
+        require!(manifest.mint_pending(), ErrorCode::MintNotPending);
+        require_distinct_accounts(&[
+            ("manifest", manifest_key),
+            ("object_mint", accounts.object_mint.key()),
+            ("metadata", accounts.metadata.key()),
+            ("master_edition", accounts.master_edition.key()),
+            ("collection_metadata", accounts.collection_metadata.key()),
+            (
+                "collection_master_edition",
+                accounts.collection_master_edition.key(),
+            ),
+        ])?;
+        require_match!(
+            "config",
+            config_key,
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        let metadata_program = accounts.config.load()?.metadata_program();
+        require_match!(
+            "metadata_program",
+            accounts.token_metadata_program.key(),
+            metadata_program,
+            ErrorCode::InvalidTokenMetadataProgram
+        );
+        require_metaplex_pdas(
+            &metadata_program,
+            &manifest.mint,
+            &accounts.metadata.key(),
+            &accounts.master_edition.key(),
+            &accounts.collection_mint.key(),
+            &accounts.collection_metadata.key(),
+            &accounts.collection_master_edition.key(),
+        )?;
+
+        let signer_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        finalize_first_mint(
+            FirstMintFinalizeAccounts {
+                metadata_program: &accounts.token_metadata_program.to_account_info(),
+                metadata: &accounts.metadata.to_account_info(),
+                master_edition: &accounts.master_edition.to_account_info(),
+                mint: &accounts.object_mint.to_account_info(),
+                auth: &accounts.auth.to_account_info(),
+                payer: &accounts.payer.to_account_info(),
+                token_program: &accounts.token_program.to_account_info(),
+                system_program: &accounts.system_program.to_account_info(),
+                rent: &accounts.rent.to_account_info(),
+                collection_mint: &accounts.collection_mint.to_account_info(),
+                collection_metadata: &accounts.collection_metadata.to_account_info(),
+                collection_master_edition: &accounts.collection_master_edition.to_account_info(),
+                instructions: ctx
+                    .remaining_accounts
+                    .iter()
+                    .find(|account| account.key() == sysvar::instructions::id()),
+                extra_accounts: ctx.remaining_accounts,
+            },
+            &[signer_seeds],
+        )?;
+
+        {
+            let mut data = accounts.object_manifest.try_borrow_mut_data()?;
+            let (_, rest) = data.split_at_mut(8);
+            let stored = from_bytes_mut::<ObjectManifest>(
+                &mut rest[..core::mem::size_of::<ObjectManifest>()],
+            );
+            stored.set_mint_pending(false);
+            stored.set_minted(true);
+        }
+        append_changelog_leaf(
+            &accounts.config,
+            changelog_accounts(ctx.remaining_accounts).as_ref(),
+            changelog_leaf(&manifest_key, &manifest, Clock::get()?.slot),
+        )?;
+
+        emit_logged!(ObjectMinted {
+            config: config_key,
+            manifest: manifest_key,
+            mint: manifest.mint,
+            recipient: manifest.owner,
+            object_id: manifest.object_id,
+            manifest_hash: manifest.manifest_hash,
+            hash_algorithm: manifest.hash_algorithm,
+            metadata_uri: manifest.metadata_uri_string(),
+            creator: manifest.creator,
+            context: EventContext::new(accounts.payer.key(), accounts.authority.key())?,
+        });
+
+        Ok(MintResult {
+            mint: manifest.mint,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            first_mint: true,
+        })
+    }
+
+    pub fn update_object_manifest(
+        ctx: Context<UpdateObjectManifest>,
+        manifest_hash: [u8; 32],
+        metadata_uri: String,
+        is_active: bool,
+        hash_algorithm: u8,
+        arweave_tx_id: [u8; 32],
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &owner,
+            &ctx.accounts.object_mint.key(),
+        )?;
+
+        let accounts = &mut *ctx.accounts;
+        process_manifest_update(
+            ManifestUpdateAccounts {
+                actor: owner,
+                payer: accounts.owner.to_account_info(),
+                config: &accounts.config,
+                auth: &accounts.auth,
+                object_manifest: &accounts.object_manifest,
+                object_mint: accounts.object_mint.to_account_info(),
+                metadata: ObjectMetadataAccounts::TokenMetadata {
+                    metadata: accounts.object_metadata.to_account_info(),
+                    metadata_program: accounts.metadata_program.to_account_info(),
+                    rent: accounts.rent.key(),
+                },
+                instructions: accounts.instructions.clone(),
+                system_program: accounts.system_program.to_account_info(),
+                manifest_history: accounts.manifest_history.as_ref(),
+                config_stats: &mut accounts.config_stats,
+                global_pause: &accounts.global_pause,
+            },
+            ctx.remaining_accounts,
+            ManifestUpdateArgs {
+                manifest_hash,
+                metadata_uri,
+                is_active,
+                hash_algorithm,
+                arweave_tx_id,
+            },
+        )
+    }
+
+    /// `update_object_manifest` for objects minted with
+    /// `mint_token2022_object`: checks the holder's Token-2022 account and
+    /// rewrites the URI field of the mint's token metadata extension.
+    pub fn update_token2022_manifest(
+        ctx: Context<UpdateToken2022Manifest>,
+        manifest_hash: [u8; 32],
+        metadata_uri: String,
+        is_active: bool,
+        hash_algorithm: u8,
+        arweave_tx_id: [u8; 32],
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        require_token2022_holder(
+            &ctx.accounts.owner_token_account,
+            &owner,
+            &ctx.accounts.object_mint,
+        )?;
+
+        let accounts = &mut *ctx.accounts;
+        process_manifest_update(
+            ManifestUpdateAccounts {
+                actor: owner,
+                payer: accounts.owner.to_account_info(),
+                config: &accounts.config,
+                auth: &accounts.auth,
+                object_manifest: &accounts.object_manifest,
+                object_mint: accounts.object_mint.to_account_info(),
+                metadata: ObjectMetadataAccounts::Token2022 {
+                    token_program: accounts.token_program.to_account_info(),
+                },
+                instructions: accounts.instructions.clone(),
+                system_program: accounts.system_program.to_account_info(),
+                manifest_history: accounts.manifest_history.as_ref(),
+                config_stats: &mut accounts.config_stats,
+                global_pause: &accounts.global_pause,
+            },
+            ctx.remaining_accounts,
+            ManifestUpdateArgs {
+                manifest_hash,
+                metadata_uri,
+                is_active,
+                hash_algorithm,
+                arweave_tx_id,
+            },
+        )
+    }
+
+    /// Queues a manifest update that anyone can apply with
+    /// `execute_scheduled_update` once `trigger_slot` is reached, for timed
+    /// reveals and seasonal content. The holder pays the rent of the
+    /// [`ScheduledUpdate`] PDA, and the update only applies while they still
+    /// hold the object. One update can be queued per manifest.
+    pub fn schedule_manifest_update(
+        ctx: Context<ScheduleManifestUpdate>,
+        trigger_slot: u64,
+        manifest_hash: [u8; 32],
+        metadata_uri: String,
+        is_active: bool,
+        hash_algorithm: u8,
+        arweave_tx_id: [u8; 32],
+    ) -> Result<()> {
+        ManifestHashAlgorithm::try_from(hash_algorithm)?;
+        require!(metadata_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+        require!(
+            metadata_uri.len() <= METADATA_MAX_URI_LENGTH,
+            ErrorCode::UriTooLong
+        );
+        validate_arweave_tx_id(&metadata_uri, &arweave_tx_id)?;
+        let config = ctx.accounts.config.load()?;
+        config.require_instruction_enabled(INSTRUCTION_UPDATE)?;
+        validate_uri_rules(&config, &metadata_uri)?;
+        drop(config);
+        let slot = Clock::get()?.slot;
+        require!(trigger_slot > slot, ErrorCode::InvalidTriggerSlot);
+
+        let owner = ctx.accounts.owner.key();
+        let manifest = ctx.accounts.object_manifest.load()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(!manifest.locked(), ErrorCode::ManifestLocked);
+        require_object_holder(&ctx.accounts.owner_token_account, &owner, &manifest.mint)?;
+
+        let scheduled = &mut ctx.accounts.scheduled_update;
+        scheduled.manifest = ctx.accounts.object_manifest.key();
+        scheduled.scheduler = owner;
+        scheduled.trigger_slot = trigger_slot;
+        scheduled.manifest_hash = manifest_hash;
+        scheduled.hash_algorithm = hash_algorithm;
+        scheduled.is_active = is_active;
+        scheduled.arweave_tx_id = arweave_tx_id;
+        scheduled.metadata_uri = metadata_uri;
+        scheduled.bump = ctx.bumps.scheduled_update;
+
+        emit_logged!(ManifestUpdateScheduled {
+            config: manifest.config,
+            manifest: scheduled.manifest,
+            object_id: manifest.object_id,
+            scheduled_update: scheduled.key(),
+            scheduler: owner,
+            trigger_slot,
+            manifest_hash,
+            context: EventContext::signed_by(owner)?,
+        });
+
+        Ok(())
+    }
+
+    /// Drops a queued update and returns its rent to the holder who
+    /// scheduled it.
+    pub fn cancel_scheduled_update(ctx: Context<CancelScheduledUpdate>) -> Result<()> {
+        let scheduled = &ctx.accounts.scheduled_update;
+        let manifest = ctx.accounts.object_manifest.load()?;
+
+        emit_logged!(ScheduledUpdateCancelled {
+            config: manifest.config,
+            manifest: scheduled.manifest,
+            object_id: manifest.object_id,
+            scheduled_update: scheduled.key(),
+            trigger_slot: scheduled.trigger_slot,
+            context: EventContext::signed_by(ctx.accounts.scheduler.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank applying a queued update once its trigger slot
+    /// has been reached, exactly as `update_object_manifest` would for the
+    /// holder who scheduled it. The queued PDA is closed back to them.
+    pub fn execute_scheduled_update(ctx: Context<ExecuteScheduledUpdate>) -> Result<()> {
+        let scheduled = &ctx.accounts.scheduled_update;
+        require!(
+            Clock::get()?.slot >= scheduled.trigger_slot,
+            ErrorCode::ScheduledUpdateNotDue
+        );
+        let scheduler = scheduled.scheduler;
+        require_object_holder(
+            &ctx.accounts.scheduler_token_account,
+            &scheduler,
+            &ctx.accounts.object_mint.key(),
+        )?;
+        let args = ManifestUpdateArgs {
+            manifest_hash: scheduled.manifest_hash,
+            metadata_uri: scheduled.metadata_uri.clone(),
+            is_active: scheduled.is_active,
+            hash_algorithm: scheduled.hash_algorithm,
+            arweave_tx_id: scheduled.arweave_tx_id,
+        };
+        let scheduled_update = scheduled.key();
+        let trigger_slot = scheduled.trigger_slot;
+
+        let accounts = &mut *ctx.accounts;
+        process_manifest_update(
+            ManifestUpdateAccounts {
+                actor: scheduler,
+                payer: accounts.payer.to_account_info(),
+                config: &accounts.config,
+                auth: &accounts.auth,
+                object_manifest: &accounts.object_manifest,
+                object_mint: accounts.object_mint.to_account_info(),
+                metadata: ObjectMetadataAccounts::TokenMetadata {
+                    metadata: accounts.object_metadata.to_account_info(),
+                    metadata_program: accounts.metadata_program.to_account_info(),
+                    rent: accounts.rent.key(),
+                },
+                instructions: accounts.instructions.clone(),
+                system_program: accounts.system_program.to_account_info(),
+                manifest_history: accounts.manifest_history.as_ref(),
+                config_stats: &mut accounts.config_stats,
+                global_pause: &accounts.global_pause,
+            },
+            ctx.remaining_accounts,
+            args,
+        )?;
+
+        let manifest = ctx.accounts.object_manifest.load()?;
+        emit_logged!(ScheduledUpdateExecuted {
+            config: manifest.config,
+            manifest: ctx.accounts.object_manifest.key(),
+            object_id: manifest.object_id,
+            scheduled_update,
+            trigger_slot,
+            context: EventContext::new(ctx.accounts.payer.key(), scheduler)?,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the program-wide pause that stops every mint and manifest update
+    /// in every namespace, for incidents that affect all configs. Only an
+    /// allowed deployer or the program's upgrade authority, shown by passing
+    /// the program data account, may call it.
+    pub fn set_global_pause(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let is_upgrade_authority = ctx
+            .accounts
+            .program_data
+            .as_ref()
+            .is_some_and(|program_data| {
+                program_data.key() == program_data_address()
+                    && program_data.upgrade_authority_address == Some(authority)
+            });
+        require!(
+            is_allowed_deployer(&authority) || is_upgrade_authority,
+            ErrorCode::UnauthorizedDeployer
+        );
+
+        let global_pause = &mut ctx.accounts.global_pause;
+        let previous_paused = global_pause.paused;
+        global_pause.paused = paused;
+        global_pause.updated_by = authority;
+        global_pause.updated_slot = Clock::get()?.slot;
+        global_pause.bump = ctx.bumps.global_pause;
+
+        emit_logged!(GlobalPauseUpdated {
+            global_pause: global_pause.key(),
+            previous_paused,
+            paused,
+            context: EventContext::signed_by(authority)?,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a new configuration PDA under `new_namespace` using the state
+    /// from `old_config`, and lists it in the [`NamespaceRegistry`].
+    ///
+    /// This instruction allows the authority to migrate to a fresh namespace
+    /// (for example, to rotate the config PDA) without requiring a program
+    /// upgrade. After migration, callers should reference the new config and
+    /// auth accounts.
+    pub fn migrate_config_namespace(
+        ctx: Context<MigrateConfigNamespace>,
+        new_namespace: Pubkey,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let old_config_key = ctx.accounts.old_config.key();
+        let new_config_key = ctx.accounts.new_config.key();
+        let old_config = ctx.accounts.old_config.load()?;
+        require_keys_eq!(old_config.authority, authority, ErrorCode::InvalidAuthority);
+
+        let mut new_config = ctx.accounts.new_config.load_init()?;
+        new_config.authority = old_config.authority;
+        new_config.config_bump = ctx.bumps.new_config;
+        new_config.auth_bump = ctx.bumps.new_auth;
+        new_config.layout_version = CONFIG_LAYOUT_VERSION;
+        new_config.object_count = old_config.object_count;
+        new_config.namespace = new_namespace;
+        new_config.paused = old_config.paused;
+        new_config.categories = old_config.categories;
+        new_config.category_count = old_config.category_count;
+        new_config.memo_on_mint = old_config.memo_on_mint;
+        new_config.parent_config = old_config.parent_config;
+        new_config.disabled_instructions = old_config.disabled_instructions;
+        // Stats PDAs are keyed by config, so the new namespace starts untracked.
+        new_config.set_stats_enabled(false);
+        // Index pages are keyed by config as well and are rebuilt from scratch.
+        new_config.set_object_index_enabled(false);
+        new_config.indexed_object_count = 0;
+        new_config.set_minted_bitmap_enabled(false);
+
+        let new_auth = &mut ctx.accounts.new_auth;
+        new_auth.config = new_config_key;
+        new_auth.bump = ctx.bumps.new_auth;
+
+        let registry = &mut ctx.accounts.namespace_registry;
+        registry.bump = ctx.bumps.namespace_registry;
+        append_namespace_entry(
+            registry,
+            &ctx.accounts.registry_page,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            ctx.program_id,
+            NamespaceEntry {
+                namespace: new_namespace,
+                config: new_config_key,
+                authority,
+                created_slot: Clock::get()?.slot,
+            },
+        )?;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ConfigNamespaceMigrated {
+                old_config: old_config_key,
+                new_config: new_config_key,
+                old_namespace: old_config.namespace,
+                new_namespace,
+                context: EventContext::signed_by(authority)?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Re-creates manifests of `old_config` under `new_config`, a config
+    /// created from it by `migrate_config_namespace`, so the objects follow the
+    /// namespace. Remaining accounts are (old manifest, new manifest,
+    /// migration record, object metadata) quadruplets. Each object keeps its
+    /// mint, its metadata update authority moves to the new auth PDA, and a
+    /// [`ManifestMigration`] record links the old manifest to the new one.
+    /// With `close_old`, the old manifests are closed to the payer.
+    pub fn migrate_manifests<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateManifests<'info>>,
+        close_old: bool,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 4 == 0,
+            ErrorCode::MissingManifestMigrationAccounts
+        );
+        let old_config_key = ctx.accounts.old_config.key();
+        let new_config_key = ctx.accounts.new_config.key();
+        let metadata_program = {
+            let old_config = ctx.accounts.old_config.load()?;
+            let new_config = ctx.accounts.new_config.load()?;
+            require_keys_neq!(old_config_key, new_config_key, ErrorCode::InvalidConfig);
+            require_keys_eq!(
+                new_config.authority,
+                old_config.authority,
+                ErrorCode::InvalidAuthority
+            );
+            // Closed manifests could otherwise be minted again under the old
+            // config while their objects live on under the new one.
+            require!(
+                old_config.paused(),
+                ErrorCode::ManifestMigrationRequiresPause
+            );
+            old_config.metadata_program()
+        };
+        require_keys_eq!(
+            ctx.accounts.token_metadata_program.key(),
+            metadata_program,
+            ErrorCode::InvalidTokenMetadataProgram
+        );
+
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let metadata_program_info = ctx.accounts.token_metadata_program.to_account_info();
+        let old_auth_info = ctx.accounts.old_auth.to_account_info();
+        let old_auth_bump = ctx.accounts.old_auth.bump;
+        let old_auth_seeds: &[&[u8]] = &[AUTH_SEED, old_config_key.as_ref(), &[old_auth_bump]];
+        let slot = Clock::get()?.slot;
+
+        for accounts in ctx.remaining_accounts.chunks(4) {
+            let old_manifest_key = accounts[0].key();
+            let old_manifest = read_manifest(&accounts[0])?;
+            require_keys_eq!(
+                old_manifest.config,
+                old_config_key,
+                ErrorCode::InvalidConfig
+            );
+            let object_id = old_manifest.object_id;
+            let object_id_bytes = object_id.to_le_bytes();
+
+            let (new_manifest_key, new_manifest_bump) = derive_pda(
+                &[MANIFEST_SEED, new_config_key.as_ref(), &object_id_bytes],
+                None,
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                accounts[1].key(),
+                new_manifest_key,
+                ErrorCode::ManifestMismatch
+            );
+            let (migration_key, migration_bump) = derive_pda(
+                &[MANIFEST_MIGRATION_SEED, old_manifest_key.as_ref()],
+                None,
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                accounts[2].key(),
+                migration_key,
+                ErrorCode::InvalidManifestMigration
+            );
+            require_keys_eq!(
+                accounts[3].key(),
+                find_metadata_pda(&old_manifest.mint, &metadata_program),
+                ErrorCode::InvalidMetadataAccount
+            );
+
+            let new_manifest = migrated_manifest(&old_manifest, new_config_key, new_manifest_bump)?;
+            create_pda_account(
+                &accounts[1],
+                &payer,
+                &system_program,
+                ctx.program_id,
+                ObjectManifest::LEN,
+                &[
+                    MANIFEST_SEED,
+                    new_config_key.as_ref(),
+                    &object_id_bytes,
+                    &[new_manifest_bump],
+                ],
+            )?;
+            {
+                let mut data = accounts[1].try_borrow_mut_data()?;
+                data[..8].copy_from_slice(&ObjectManifest::discriminator());
+                data[8..8 + core::mem::size_of::<ObjectManifest>()]
+                    .copy_from_slice(bytemuck::bytes_of(new_manifest.as_ref()));
+            }
+
+            create_pda_account(
+                &accounts[2],
+                &payer,
+                &system_program,
+                ctx.program_id,
+                ManifestMigration::LEN,
+                &[
+                    MANIFEST_MIGRATION_SEED,
+                    old_manifest_key.as_ref(),
+                    &[migration_bump],
+                ],
+            )?;
+            ManifestMigration {
+                old_config: old_config_key,
+                new_config: new_config_key,
+                old_manifest: old_manifest_key,
+                new_manifest: new_manifest_key,
+                object_id,
+                migrated_slot: slot,
+                bump: migration_bump,
+            }
+            .try_serialize(&mut &mut accounts[2].try_borrow_mut_data()?[..])?;
+
+            UpdateMetadataAccountV2Cpi::new(
+                &metadata_program_info,
+                UpdateMetadataAccountV2CpiAccounts {
+                    metadata: &accounts[3],
+                    update_authority: &old_auth_info,
+                },
+                UpdateMetadataAccountV2InstructionArgs {
+                    data: None,
+                    new_update_authority: Some(to_solana_pubkey(&ctx.accounts.new_auth.key())),
+                    primary_sale_happened: None,
+                    is_mutable: None,
+                },
+            )
+            .invoke_signed(&[old_auth_seeds])
+            .map_err(anchor_lang::error::Error::from)?;
+
+            if close_old {
+                close_program_account(&accounts[0], &payer)?;
+            }
+
+            emit_audited!(
+                ctx.accounts.audit_log.as_ref(),
+                ManifestMigrated {
+                    old_config: old_config_key,
+                    new_config: new_config_key,
+                    object_id,
+                    old_manifest: old_manifest_key,
+                    new_manifest: new_manifest_key,
+                    closed: close_old,
+                    context: EventContext::new(
+                        ctx.accounts.payer.key(),
+                        ctx.accounts.authority.key()
+                    )?,
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites a config created before [`Config`] became zero-copy into the
+    /// current layout, growing the account to [`Config::LEN`] with the payer
+    /// covering the extra rent. Until this runs, every other instruction
+    /// rejects the config.
+    pub fn migrate_config_layout(ctx: Context<MigrateConfigLayout>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        let (legacy, previous_len) = {
+            let data = config_info.try_borrow_data()?;
+            require!(data.len() < Config::LEN, ErrorCode::ConfigAlreadyMigrated);
+            (LegacyConfig::from_account_data(&data)?, data.len() as u64)
+        };
+        require_match!(
+            "authority",
+            ctx.accounts.authority.key(),
+            legacy.authority,
+            ErrorCode::InvalidAuthority
+        );
+        let expected_config = Pubkey::create_program_address(
+            &[
+                CONFIG_SEED,
+                legacy.namespace.as_ref(),
+                &[legacy.config_bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| Error::from(ErrorCode::InvalidConfig))?;
+        require_match!(
+            "config",
+            config_info.key(),
+            expected_config,
+            ErrorCode::InvalidConfig
+        );
+
+        let required_lamports = Rent::get()?.minimum_balance(Config::LEN);
+        if config_info.lamports() < required_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                required_lamports.saturating_sub(config_info.lamports()),
+            )?;
+        }
+        config_info.realloc(Config::LEN, true)?;
+
+        {
+            let mut data = config_info.try_borrow_mut_data()?;
+            let config = from_bytes_mut::<Config>(&mut data[8..Config::LEN]);
+            *config = Config::zeroed();
+            legacy.write_to(config);
+            config.upgrade_layout();
+        }
+
+        emit_logged!(ConfigLayoutMigrated {
+            config: config_info.key(),
+            previous_len,
+            context: EventContext::new(ctx.accounts.payer.key(), legacy.authority)?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank that brings a config written by an older program
+    /// up to [`CONFIG_LAYOUT_VERSION`], growing it to [`Config::LEN`] with the
+    /// caller covering any extra rent and giving new fields their defaults.
+    /// Configs still in the Borsh layout need `migrate_config_layout` instead.
+    pub fn upgrade_config(ctx: Context<UpgradeConfig>) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
+        let previous_len = config_info.data_len() as u64;
+        require!(
+            config_info.data_len() > Config::LAYOUT_VERSION_OFFSET,
+            ErrorCode::ConfigLayoutOutdated
+        );
+        ensure_config_capacity(
+            &config_info,
+            &ctx.accounts.caller.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut config = ctx.accounts.config.load_mut()?;
+        require!(
+            config.layout_version < CONFIG_LAYOUT_VERSION,
+            ErrorCode::ConfigAlreadyUpgraded
+        );
+
+        let previous_version = config.layout_version;
+        config.upgrade_layout();
+
+        emit_logged!(ConfigLayoutUpgraded {
+            config: config_info.key(),
+            previous_version,
+            layout_version: config.layout_version,
+            previous_len,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Pauses or resumes minting. The parent config's authority may do this
+    /// as well.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        require_config_admin(
+            &ctx.accounts.config,
+            ctx.accounts.parent_config.as_ref(),
+            &ctx.accounts.authority.key(),
+        )?;
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_paused = config.paused();
+        config.set_paused(paused);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            PauseStatusUpdated {
+                config: config_key,
+                paused,
+                previous_paused,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Opts a manifest into on-chain update history.
+    ///
+    /// Creates the [`ManifestHistory`] ring buffer for the manifest. Once
+    /// enabled, every `update_object_manifest` call must supply the history
+    /// account and appends an entry to it.
+    pub fn enable_manifest_history(ctx: Context<EnableManifestHistory>) -> Result<()> {
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &ctx.accounts.object_mint.key(),
+        )?;
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_keys_eq!(
+            manifest.config,
+            ctx.accounts.config.key(),
+            ErrorCode::InvalidConfig
+        );
+        require_keys_eq!(
+            manifest.mint,
+            ctx.accounts.object_mint.key(),
+            ErrorCode::MintMismatch
+        );
+        manifest.set_history_enabled(true);
+
+        let mut history = ctx.accounts.manifest_history.load_init()?;
+        history.manifest = manifest_info.key();
+        history.bump = ctx.bumps.manifest_history;
+
+        emit_logged!(ManifestHistoryEnabled {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            history: ctx.accounts.manifest_history.key(),
+            object_id: manifest.object_id,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Stages a chunk of manifest content in the writer's payload buffer so
+    /// content larger than a single transaction can be verified with
+    /// [`verify_manifest_payload`].
+    pub fn write_manifest_payload(
+        ctx: Context<WriteManifestPayload>,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        let manifest_key = ctx.accounts.object_manifest.key();
+        let writer_key = ctx.accounts.writer.key();
+        require!(
+            ctx.accounts.object_manifest.load()?.initialized(),
+            ErrorCode::ManifestNotInitialized
+        );
+
+        let buffer = &mut ctx.accounts.payload_buffer;
+        if buffer.manifest == Pubkey::default() {
+            buffer.manifest = manifest_key;
+            buffer.writer = writer_key;
+            buffer.bump = ctx.bumps.payload_buffer;
+        }
+        require_keys_eq!(
+            buffer.manifest,
+            manifest_key,
+            ErrorCode::InvalidPayloadBuffer
+        );
+
+        let new_len = buffer
+            .data
+            .len()
+            .checked_add(chunk.len())
+            .ok_or(ErrorCode::PayloadTooLarge)?;
+        require!(
+            new_len <= MAX_PAYLOAD_BUFFER_LEN,
+            ErrorCode::PayloadTooLarge
+        );
+
+        let buffer_info = buffer.to_account_info();
+        let required_space = ManifestPayloadBuffer::space(new_len);
+        let required_lamports = Rent::get()?.minimum_balance(required_space);
+        if buffer_info.lamports() < required_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.writer.to_account_info(),
+                        to: buffer_info.clone(),
+                    },
+                ),
+                required_lamports.saturating_sub(buffer_info.lamports()),
+            )?;
+        }
+        if buffer_info.data_len() < required_space {
+            buffer_info.realloc(required_space, false)?;
+        }
+
+        ctx.accounts.payload_buffer.data.extend_from_slice(&chunk);
+
+        let config_key = ctx.accounts.object_manifest.load()?.config;
+        log_record("payload_write", &[&config_key, &manifest_key, &new_len]);
+        Ok(())
+    }
+
+    /// Hashes the supplied content (prefixed by any staged buffer) with the
+    /// manifest's hash algorithm and marks the manifest as verified on-chain
+    /// when the digest matches `manifest_hash`.
+    ///
+    /// Anyone may verify a manifest; the staged buffer is closed back to the
+    /// writer once it has been consumed.
+    pub fn verify_manifest_payload(
+        ctx: Context<VerifyManifestPayload>,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.writer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+
+        let buffered: &[u8] = match &ctx.accounts.payload_buffer {
+            Some(buffer) => {
+                require_keys_eq!(
+                    buffer.manifest,
+                    manifest_info.key(),
+                    ErrorCode::InvalidPayloadBuffer
+                );
+                require_keys_eq!(
+                    buffer.writer,
+                    ctx.accounts.writer.key(),
+                    ErrorCode::InvalidPayloadBuffer
+                );
+                buffer.data.as_slice()
+            }
+            None => &[],
+        };
+
+        let digest =
+            hash_manifest_payload(manifest.hash_algorithm()?, &[buffered, payload.as_slice()])?;
+        require!(
+            digest == manifest.manifest_hash,
+            ErrorCode::ManifestPayloadMismatch
+        );
+        manifest.set_verified_on_chain(true);
+
+        emit_logged!(ManifestPayloadVerified {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            verifier: ctx.accounts.writer.key(),
+            manifest_hash: manifest.manifest_hash,
+            payload_len: (buffered.len() + payload.len()) as u64,
+            context: EventContext::signed_by(ctx.accounts.writer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Writes `data` at `offset` into the object's Metaplex inscription,
+    /// creating the inscription on the first call, so the manifest JSON lives
+    /// fully on-chain. The auth PDA, as the metadata update authority, signs
+    /// for the Inscription program.
+    ///
+    /// When a write ends the inscription and its bytes hash to
+    /// `manifest_hash`, the manifest is marked inscribed and no further writes
+    /// are accepted.
+    pub fn inscribe_object(ctx: Context<InscribeObject>, offset: u64, data: Vec<u8>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let owner = ctx.accounts.owner.key();
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            config_key,
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.minted() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require!(!manifest.inscribed(), ErrorCode::ObjectAlreadyInscribed);
+        require_object_holder(&ctx.accounts.owner_token_account, &owner, &manifest.mint)?;
+        let algorithm = manifest.hash_algorithm()?;
+
+        let metadata_program = ctx.accounts.config.load()?.metadata_program();
+        require_match!(
+            "object_metadata",
+            ctx.accounts.object_metadata.key(),
+            find_metadata_pda(&manifest.mint, &metadata_program),
+            ErrorCode::InvalidMetadataAccount
+        );
+        let (inscription, inscription_metadata) = find_inscription_pdas(&manifest.mint);
+        require_match!(
+            "inscription",
+            ctx.accounts.inscription.key(),
+            inscription,
+            ErrorCode::InvalidInscriptionAccount
+        );
+        require_match!(
+            "inscription_metadata",
+            ctx.accounts.inscription_metadata.key(),
+            inscription_metadata,
+            ErrorCode::InvalidInscriptionAccount
+        );
+
+        let signer_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[ctx.accounts.auth.bump]];
+        if ctx.accounts.inscription.data_is_empty() {
+            invoke_signed(
+                &Instruction {
+                    program_id: MPL_INSCRIPTION_ID,
+                    accounts: vec![
+                        AccountMeta::new(inscription, false),
+                        AccountMeta::new(inscription_metadata, false),
+                        AccountMeta::new_readonly(manifest.mint, false),
+                        AccountMeta::new_readonly(ctx.accounts.object_metadata.key(), false),
+                        AccountMeta::new(ctx.accounts.inscription_shard.key(), false),
+                        AccountMeta::new(owner, true),
+                        AccountMeta::new_readonly(ctx.accounts.auth.key(), true),
+                        AccountMeta::new_readonly(system_program::ID, false),
+                    ],
+                    data: vec![INSCRIPTION_INITIALIZE_FROM_MINT],
+                },
+                &[
+                    ctx.accounts.inscription.to_account_info(),
+                    ctx.accounts.inscription_metadata.to_account_info(),
+                    ctx.accounts.object_mint.to_account_info(),
+                    ctx.accounts.object_metadata.to_account_info(),
+                    ctx.accounts.inscription_shard.to_account_info(),
+                    ctx.accounts.owner.to_account_info(),
+                    ctx.accounts.auth.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    ctx.accounts.inscription_program.to_account_info(),
+                ],
+                &[signer_seeds],
+            )?;
+        }
+
+        invoke_signed(
+            &Instruction {
+                program_id: MPL_INSCRIPTION_ID,
+                accounts: vec![
+                    AccountMeta::new(inscription, false),
+                    AccountMeta::new(inscription_metadata, false),
+                    AccountMeta::new(owner, true),
+                    AccountMeta::new_readonly(ctx.accounts.auth.key(), true),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                ],
+                data: inscription_write_data(offset, &data),
+            },
+            &[
+                ctx.accounts.inscription.to_account_info(),
+                ctx.accounts.inscription_metadata.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.auth.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.inscription_program.to_account_info(),
+            ],
+            &[signer_seeds],
+        )?;
+
+        let manifest_key = manifest_info.key();
+        let inscribed = ctx.accounts.inscription.try_borrow_data()?;
+        let end = offset.saturating_add(data.len() as u64);
+        if end != inscribed.len() as u64
+            || hash_manifest_payload(algorithm, &[&inscribed])? != manifest.manifest_hash
+        {
+            log_record("inscription_write", &[&config_key, &manifest_key, &end]);
+            return Ok(());
+        }
+        manifest.set_inscribed(true);
+
+        emit_logged!(ObjectInscribed {
+            config: config_key,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            inscription,
+            manifest_hash: manifest.manifest_hash,
+            inscription_len: inscribed.len() as u64,
+            context: EventContext::signed_by(owner)?,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the key a Switchboard function signs URI attestations with,
+    /// and how many slots an attestation stays fresh for `verify_uri_liveness`
+    /// (zero for no limit). Passing the default pubkey stops new attestations
+    /// from being accepted.
+    pub fn set_liveness_oracle(
+        ctx: Context<SetLivenessOracle>,
+        attester: Pubkey,
+        max_age_slots: u64,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let oracle = &mut ctx.accounts.liveness_oracle;
+        let previous_attester = oracle.attester;
+        oracle.config = config_key;
+        oracle.attester = attester;
+        oracle.max_age_slots = max_age_slots;
+        oracle.bump = ctx.bumps.liveness_oracle;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            LivenessOracleUpdated {
+                config: config_key,
+                previous_attester,
+                attester,
+                max_age_slots,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Called by the registered Switchboard function after fetching the
+    /// manifest's URI: records that, at this slot, the URI with hash
+    /// `uri_hash` served content hashing to `manifest_hash`. The attestation
+    /// PDA is reused for later attestations of the same manifest.
+    pub fn record_uri_attestation(
+        ctx: Context<RecordUriAttestation>,
+        manifest_hash: [u8; 32],
+        uri_hash: [u8; 32],
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let attester = ctx.accounts.attester.key();
+        require!(
+            ctx.accounts.liveness_oracle.attester != Pubkey::default(),
+            ErrorCode::InvalidLivenessAttester
+        );
+        let manifest_key = ctx.accounts.object_manifest.key();
+        let manifest = ctx.accounts.object_manifest.load()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            config_key,
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+
+        let slot = Clock::get()?.slot;
+        let attestation = &mut ctx.accounts.uri_attestation;
+        attestation.manifest = manifest_key;
+        attestation.attester = attester;
+        attestation.manifest_hash = manifest_hash;
+        attestation.uri_hash = uri_hash;
+        attestation.slot = slot;
+        attestation.bump = ctx.bumps.uri_attestation;
+
+        emit_logged!(UriAttestationRecorded {
+            config: config_key,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            attester,
+            manifest_hash,
+            uri_hash,
+            slot,
+            context: EventContext::signed_by(attester)?,
+        });
+
+        Ok(())
+    }
+
+    /// Marks the manifest's URI as live once a fresh attestation from the
+    /// registered attester matches its current manifest hash and URI. The
+    /// flag clears whenever the hash or URI changes. Anyone may call this.
+    pub fn verify_uri_liveness(ctx: Context<VerifyUriLiveness>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let manifest_key = ctx.accounts.object_manifest.key();
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            config_key,
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        let attestation = &ctx.accounts.uri_attestation;
+        require_current_uri_attestation(
+            attestation,
+            &ctx.accounts.liveness_oracle,
+            &manifest,
+            Clock::get()?.slot,
+        )?;
+        manifest.set_uri_live(true);
+
+        emit_logged!(UriLivenessVerified {
+            config: config_key,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            attester: attestation.attester,
+            attested_slot: attestation.slot,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the mint price in USD cents, converted to lamports at mint time
+    /// from a Pyth `PriceUpdateV2` for `feed_id` no older than
+    /// `max_price_age_secs` and with a confidence interval within
+    /// `max_confidence_bps` of the price. The rent payer pays it to
+    /// `treasury`. A zero price makes mints free again.
+    pub fn set_mint_price(
+        ctx: Context<SetMintPrice>,
+        usd_cents: u64,
+        treasury: Pubkey,
+        feed_id: [u8; 32],
+        max_confidence_bps: u16,
+        max_price_age_secs: u32,
+    ) -> Result<()> {
+        if usd_cents > 0 {
+            require!(treasury != Pubkey::default(), ErrorCode::InvalidTreasury);
+            require!(
+                (1..=10_000).contains(&max_confidence_bps) && max_price_age_secs > 0,
+                ErrorCode::InvalidMintPrice
+            );
+        }
+        let config_key = ctx.accounts.config.key();
+        let mint_price = &mut ctx.accounts.mint_price;
+        let previous_usd_cents = mint_price.usd_cents;
+        mint_price.config = config_key;
+        mint_price.usd_cents = usd_cents;
+        mint_price.treasury = treasury;
+        mint_price.feed_id = feed_id;
+        mint_price.max_confidence_bps = max_confidence_bps;
+        mint_price.max_price_age_secs = max_price_age_secs;
+        mint_price.bump = ctx.bumps.mint_price;
+        ctx.accounts
+            .config
+            .load_mut()?
+            .set_usd_price_enabled(usd_cents > 0);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            MintPriceUpdated {
+                config: config_key,
+                previous_usd_cents,
+                usd_cents,
+                treasury,
+                feed_id,
+                max_confidence_bps,
+                max_price_age_secs,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets the symbol, seller fee, and creators template Token Metadata
+    /// mints fall back to when they pass an empty symbol,
+    /// [`DEFAULT_SELLER_FEE_BASIS_POINTS`], or no creators. The template may
+    /// leave part of the share unassigned; the minting creator is appended
+    /// with the rest.
+    pub fn set_metadata_defaults(
+        ctx: Context<SetMetadataDefaults>,
+        symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+    ) -> Result<()> {
+        require!(
+            symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
+            ErrorCode::MetadataSymbolTooLong
+        );
+        require!(
+            seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        validate_creator_template(&creators, &ctx.accounts.auth.key())?;
+
+        let config_key = ctx.accounts.config.key();
+        let defaults = &mut ctx.accounts.metadata_defaults;
+        defaults.config = config_key;
+        defaults.symbol = symbol;
+        defaults.seller_fee_basis_points = seller_fee_basis_points;
+        defaults.creators = creators;
+        defaults.bump = ctx.bumps.metadata_defaults;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            MetadataDefaultsUpdated {
+                config: config_key,
+                symbol: defaults.symbol.clone(),
+                seller_fee_basis_points,
+                creator_count: defaults.creators.len() as u8,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Fixes the seller fee and creators split for `creator`'s Token Metadata
+    /// mints, replacing whatever the mint sends or the config defaults
+    /// supply. The template follows the rules of `set_metadata_defaults`.
+    pub fn set_creator_royalty(
+        ctx: Context<SetCreatorRoyalty>,
+        creator: Pubkey,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+    ) -> Result<()> {
+        require!(
+            seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        validate_creator_template(&creators, &ctx.accounts.auth.key())?;
+
+        let royalty = &mut ctx.accounts.creator_royalty;
+        royalty.config = ctx.accounts.config.key();
+        royalty.creator = creator;
+        royalty.seller_fee_basis_points = seller_fee_basis_points;
+        royalty.creators = creators;
+        royalty.bump = ctx.bumps.creator_royalty;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorRoyaltyUpdated {
+                config: royalty.config,
+                creator,
+                seller_fee_basis_points,
+                creator_count: royalty.creators.len() as u8,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Removes a creator's royalty override, refunding its rent to the
+    /// authority. Objects already minted keep the split they were minted with.
+    pub fn clear_creator_royalty(ctx: Context<ClearCreatorRoyalty>) -> Result<()> {
+        let royalty = &ctx.accounts.creator_royalty;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorRoyaltyCleared {
+                config: royalty.config,
+                creator: royalty.creator,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Creates or updates the signer's [`CreatorProfile`] under a config.
+    /// Changing the name or payout wallet of a verified profile clears its
+    /// verification until the authority reviews it again.
+    pub fn set_creator_profile(
+        ctx: Context<SetCreatorProfile>,
+        display_name_hash: [u8; 32],
+        payout_wallet: Pubkey,
+    ) -> Result<()> {
+        require!(
+            payout_wallet != Pubkey::default(),
+            ErrorCode::InvalidPayoutWallet
+        );
+        let profile = &mut ctx.accounts.creator_profile;
+        if profile.display_name_hash != display_name_hash || profile.payout_wallet != payout_wallet
+        {
+            profile.verified = false;
+        }
+        profile.config = ctx.accounts.config.key();
+        profile.creator = ctx.accounts.creator.key();
+        profile.display_name_hash = display_name_hash;
+        profile.payout_wallet = payout_wallet;
+        profile.bump = ctx.bumps.creator_profile;
+
+        emit_logged!(CreatorProfileUpdated {
+            config: profile.config,
+            creator: profile.creator,
+            profile: profile.key(),
+            display_name_hash,
+            payout_wallet,
+            verified: profile.verified,
+            context: EventContext::signed_by(profile.creator)?,
+        });
+
+        Ok(())
+    }
+
+    /// Marks a creator profile as reviewed by the config authority, or
+    /// withdraws that mark.
+    pub fn verify_creator_profile(
+        ctx: Context<VerifyCreatorProfile>,
+        verified: bool,
+    ) -> Result<()> {
+        let profile = &mut ctx.accounts.creator_profile;
+        profile.verified = verified;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorProfileVerified {
+                config: profile.config,
+                creator: profile.creator,
+                profile: profile.key(),
+                verified,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Turns the creator allowlist on or off. While it is on, new manifests
+    /// can only be created by the config authority and by creators added with
+    /// `add_approved_creator`; objects that already exist keep minting.
+    pub fn set_creator_allowlist(ctx: Context<SetCreatorAllowlist>, enabled: bool) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.creator_allowlist_enabled();
+        config.set_creator_allowlist_enabled(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorAllowlistUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Records `creator` as approved to create manifests under the config's
+    /// creator allowlist.
+    pub fn add_approved_creator(ctx: Context<AddApprovedCreator>, creator: Pubkey) -> Result<()> {
+        let record = &mut ctx.accounts.approved_creator;
+        record.config = ctx.accounts.config.key();
+        record.creator = creator;
+        record.bump = ctx.bumps.approved_creator;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorApprovalUpdated {
+                config: record.config,
+                creator,
+                approved: true,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Closes a creator's [`ApprovedCreator`] record, refunding its rent to
+    /// the authority. Objects the creator already made are unaffected.
+    pub fn remove_approved_creator(ctx: Context<RemoveApprovedCreator>) -> Result<()> {
+        let record = &ctx.accounts.approved_creator;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CreatorApprovalUpdated {
+                config: record.config,
+                creator: record.creator,
+                approved: false,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Restricts minting `restricted_categories` to recipients holding an
+    /// unexpired Solana Attestation Service attestation issued under
+    /// `credential`, with `schema` unless it is the default key. An empty
+    /// list lifts the gate.
+    pub fn set_kyc_gate(
+        ctx: Context<SetKycGate>,
+        credential: Pubkey,
+        schema: Pubkey,
+        restricted_categories: Vec<u16>,
+    ) -> Result<()> {
+        require!(
+            restricted_categories.len() <= MAX_CATEGORIES,
+            ErrorCode::TooManyCategories
+        );
+        for (index, category) in restricted_categories.iter().enumerate() {
+            require!(*category != 0, ErrorCode::InvalidCategory);
+            require!(
+                !restricted_categories[..index].contains(category),
+                ErrorCode::InvalidCategory
+            );
+        }
+        if !restricted_categories.is_empty() {
+            require!(credential != Pubkey::default(), ErrorCode::InvalidKycGate);
+        }
+
+        let config_key = ctx.accounts.config.key();
+        let kyc_gate = &mut ctx.accounts.kyc_gate;
+        let previous_categories = kyc_gate.restricted_categories().to_vec();
+        kyc_gate.config = config_key;
+        kyc_gate.credential = credential;
+        kyc_gate.schema = schema;
+        kyc_gate.restricted_categories = [0u16; MAX_CATEGORIES];
+        kyc_gate.restricted_categories[..restricted_categories.len()]
+            .copy_from_slice(&restricted_categories);
+        kyc_gate.restricted_count = restricted_categories.len() as u8;
+        kyc_gate.bump = ctx.bumps.kyc_gate;
+        ctx.accounts
+            .config
+            .load_mut()?
+            .set_kyc_gate_enabled(!restricted_categories.is_empty());
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            KycGateUpdated {
+                config: config_key,
+                credential,
+                schema,
+                previous_categories,
+                restricted_categories,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Replaces the set of categories manifests may be minted under.
+    pub fn set_categories(ctx: Context<SetCategories>, categories: Vec<u16>) -> Result<()> {
+        require!(
+            categories.len() <= MAX_CATEGORIES,
+            ErrorCode::TooManyCategories
+        );
+        for (index, category) in categories.iter().enumerate() {
+            require!(*category != 0, ErrorCode::InvalidCategory);
+            require!(
+                !categories[..index].contains(category),
+                ErrorCode::InvalidCategory
+            );
+        }
+
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_categories = config.categories[..config.category_count as usize].to_vec();
+        config.categories = [0u16; MAX_CATEGORIES];
+        config.categories[..categories.len()].copy_from_slice(&categories);
+        config.category_count = categories.len() as u8;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CategoriesUpdated {
+                config: config_key,
+                previous_categories,
+                categories,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Records `parent_manifest` as the parent of `child_manifest`.
+    ///
+    /// Both object holders must sign. The parent's ancestor manifests must be
+    /// supplied, nearest first, as writable remaining accounts so the program
+    /// can reject links that would introduce a cycle or leave the child's
+    /// subtree deeper than [`MAX_OBJECT_DEPTH`], and can raise their
+    /// `subtree_height`.
+    pub fn link_child_object(ctx: Context<LinkChildObject>) -> Result<()> {
+        let child_key = ctx.accounts.child_manifest.key();
+        let parent_key = ctx.accounts.parent_manifest.key();
+        require_keys_neq!(child_key, parent_key, ErrorCode::ObjectLinkCycle);
+
+        let parent_info = ctx.accounts.parent_manifest.to_account_info();
+        let parent = read_manifest(&parent_info)?;
+        require!(parent.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.parent_owner_token_account,
+            &ctx.accounts.parent_owner.key(),
+            &parent.mint,
+        )?;
+
+        let mut ancestor = parent.parent;
+        let mut ancestors = ctx.remaining_accounts.iter();
+        let mut path = Vec::new();
+        let mut depth = 1;
+        while ancestor != Pubkey::default() {
+            require_keys_neq!(ancestor, child_key, ErrorCode::ObjectLinkCycle);
+            depth += 1;
+            require!(depth < MAX_OBJECT_DEPTH, ErrorCode::ObjectHierarchyTooDeep);
+            let account = ancestors.next().ok_or(ErrorCode::MissingAncestorAccounts)?;
+            require_keys_eq!(account.key(), ancestor, ErrorCode::MissingAncestorAccounts);
+            let hop = read_manifest(account)?;
+            require_keys_eq!(hop.config, parent.config, ErrorCode::InvalidConfig);
+            ancestor = hop.parent;
+            path.push(account);
+        }
+
+        let child_info = ctx.accounts.child_manifest.to_account_info();
+        let child_height = usize::from(read_manifest(&child_info)?.subtree_height);
+        require!(
+            depth + child_height < MAX_OBJECT_DEPTH,
+            ErrorCode::ObjectHierarchyTooDeep
+        );
+        ensure_manifest_capacity(
+            &child_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut child = ctx.accounts.child_manifest.load_mut()?;
+        require!(child.initialized(), ErrorCode::ManifestNotInitialized);
+        require_keys_eq!(child.config, parent.config, ErrorCode::InvalidConfig);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &child.mint,
+        )?;
+
+        let previous_parent = child.parent;
+        child.parent = parent_key;
+
+        raise_subtree_heights(
+            core::iter::once(&parent_info).chain(path),
+            child_height + 1,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_logged!(ObjectLinked {
+            config: child.config,
+            parent: parent_key,
+            child: child_key,
+            previous_parent,
+            parent_object_id: parent.object_id,
+            child_object_id: child.object_id,
+            context: EventContext::new(ctx.accounts.owner.key(), ctx.accounts.parent_owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Clears the parent recorded on `child_manifest`.
+    pub fn unlink_child_object(ctx: Context<UnlinkChildObject>) -> Result<()> {
+        let child_info = ctx.accounts.child_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &child_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut child = ctx.accounts.child_manifest.load_mut()?;
+        require!(child.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &child.mint,
+        )?;
+        require_keys_neq!(child.parent, Pubkey::default(), ErrorCode::ObjectNotLinked);
+
+        let parent = child.parent;
+        child.parent = Pubkey::default();
+
+        emit_logged!(ObjectUnlinked {
+            config: child.config,
+            parent,
+            child: child_info.key(),
+            child_object_id: child.object_id,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Records a reference from the holder's object to `foreign_manifest`, an
+    /// object in another config. The foreign manifest is loaded and must be
+    /// object `foreign_object_id` of `foreign_config`. Objects in the same
+    /// config are related with `link_child_object` instead.
+    pub fn link_external_object(
+        ctx: Context<LinkExternalObject>,
+        foreign_config: Pubkey,
+        foreign_object_id: u64,
+    ) -> Result<()> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        let foreign_key = ctx.accounts.foreign_manifest.key();
+        let foreign = read_manifest(&ctx.accounts.foreign_manifest)?;
+        require_external_reference(
+            &manifest,
+            &foreign_key,
+            &foreign,
+            &foreign_config,
+            foreign_object_id,
+        )?;
+
+        let link = &mut ctx.accounts.external_link;
+        link.manifest = ctx.accounts.object_manifest.key();
+        link.foreign_config = foreign_config;
+        link.foreign_object_id = foreign_object_id;
+        link.foreign_manifest = foreign_key;
+        link.linked_slot = Clock::get()?.slot;
+        link.bump = ctx.bumps.external_link;
+
+        emit_logged!(ExternalObjectLinked {
+            config: manifest.config,
+            manifest: link.manifest,
+            object_id: manifest.object_id,
+            foreign_config,
+            foreign_manifest: foreign_key,
+            foreign_object_id,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Removes a cross-config reference and returns its rent to the holder.
+    pub fn unlink_external_object(ctx: Context<UnlinkExternalObject>) -> Result<()> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+
+        let link = &ctx.accounts.external_link;
+        emit_logged!(ExternalObjectUnlinked {
+            config: manifest.config,
+            manifest: link.manifest,
+            object_id: manifest.object_id,
+            foreign_config: link.foreign_config,
+            foreign_manifest: link.foreign_manifest,
+            foreign_object_id: link.foreign_object_id,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Burns `consumed_count` objects held by the minter and mints a new object
+    /// whose manifest records the consumed object ids as its provenance.
+    ///
+    /// The consumed objects are passed first in the remaining accounts as
+    /// `(manifest, mint, token account)` triplets, followed by the accounts
+    /// documented on [`MintObjectNftMetadata`].
+    pub fn fuse_objects<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FuseObjects<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+        consumed_count: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .base
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_FUSE)?;
+        let consumed_count = consumed_count as usize;
+        require!(
+            (MIN_FUSION_INPUTS..=MAX_PROVENANCE).contains(&consumed_count),
+            ErrorCode::InvalidFusionInputCount
+        );
+        require!(
+            ctx.remaining_accounts.len() >= consumed_count * 3,
+            ErrorCode::MissingConsumedObjectAccounts
+        );
+        let (consumed_accounts, mint_remaining_accounts) =
+            ctx.remaining_accounts.split_at(consumed_count * 3);
+
+        let mut consumed_manifests: HashSet<Pubkey> = HashSet::new();
+        let mut consumed_object_ids = Vec::with_capacity(consumed_count);
+        for accounts in consumed_accounts.chunks(3) {
+            require!(
+                consumed_manifests.insert(accounts[0].key()),
+                ErrorCode::DuplicateConsumedObject
+            );
+            consumed_object_ids.push(consume_object(
+                &mut ctx.accounts.base,
+                &accounts[0],
+                &accounts[1],
+                &accounts[2],
+            )?);
+        }
+
+        let target = ObjectMintTarget::from_accounts(
+            &ctx.accounts.base,
+            &ctx.accounts.metadata,
+            &ctx.bumps.base,
+        );
+        process_mint_object(
+            &mut ctx.accounts.base,
+            &ctx.accounts.metadata,
+            target,
+            ctx.program_id,
+            mint_remaining_accounts,
+            MintObjectArgs {
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash,
+                metadata_name: &metadata_name,
+                metadata_symbol: &metadata_symbol,
+                seller_fee_basis_points,
+                creators: &creators,
+                hash_algorithm: ManifestHashAlgorithm::try_from(hash_algorithm)?,
+                category,
+                tag_hash,
+                arweave_tx_id,
+                origin: ObjectOrigin::Fused,
+                provenance: &consumed_object_ids,
+                creator: None,
+                stage: MintStage::Complete,
+            },
+        )?;
+
+        emit_logged!(ObjectsFused {
+            config: ctx.accounts.base.config.key(),
+            manifest: ctx.accounts.base.object_manifest.key(),
+            mint: ctx.accounts.base.object_mint.key(),
+            object_id,
+            consumed_object_ids,
+            context: EventContext::new(
+                ctx.accounts.base.minter.key(),
+                ctx.accounts.base.authority.key()
+            )?,
+        });
+
+        Ok(())
+    }
+
+    /// Burns one object held by the minter and mints each of `parts` as a new
+    /// object whose provenance points back at the consumed parent.
+    ///
+    /// Remaining accounts start with the parent's `(manifest, mint, token
+    /// account)`, then `(manifest, mint, recipient token account, metadata,
+    /// master edition, reservation)` for every part after the first (the
+    /// first part uses the named accounts), followed by the accounts
+    /// documented on [`MintObjectNftMetadata`].
+    pub fn split_object<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitObject<'info>>,
+        object_id: u64,
+        parts: Vec<SplitPart>,
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .base
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_FUSE)?;
+        require!(
+            (2..=MAX_SPLIT_PARTS).contains(&parts.len()),
+            ErrorCode::InvalidSplitPartCount
+        );
+        require!(
+            parts[0].object_id == object_id,
+            ErrorCode::SplitObjectIdMismatch
+        );
+        let hash_algorithm = ManifestHashAlgorithm::try_from(hash_algorithm)?;
+        let targets_len = (parts.len() - 1) * ObjectMintTarget::ACCOUNTS_LEN;
+        require!(
+            ctx.remaining_accounts.len() >= 3 + targets_len,
+            ErrorCode::MissingSplitPartAccounts
+        );
+        let (parent_accounts, rest) = ctx.remaining_accounts.split_at(3);
+        let (target_accounts, mint_remaining_accounts) = rest.split_at(targets_len);
+
+        let parent_manifest = parent_accounts[0].key();
+        let parent_object_id = consume_object(
+            &mut ctx.accounts.base,
+            &parent_accounts[0],
+            &parent_accounts[1],
+            &parent_accounts[2],
+        )?;
+
+        let mut targets = vec![ObjectMintTarget::from_accounts(
+            &ctx.accounts.base,
+            &ctx.accounts.metadata,
+            &ctx.bumps.base,
+        )];
+        targets.extend(
+            target_accounts
+                .chunks(ObjectMintTarget::ACCOUNTS_LEN)
+                .map(ObjectMintTarget::from_remaining),
+        );
+
+        let mut part_object_ids = Vec::with_capacity(parts.len());
+        for (part, target) in parts.iter().zip(targets) {
+            require!(
+                !part_object_ids.contains(&part.object_id),
+                ErrorCode::DuplicateSplitPart
+            );
+            part_object_ids.push(part.object_id);
+            process_mint_object(
+                &mut ctx.accounts.base,
+                &ctx.accounts.metadata,
+                target,
+                ctx.program_id,
+                mint_remaining_accounts,
+                MintObjectArgs {
+                    object_id: part.object_id,
+                    manifest_uri: &part.manifest_uri,
+                    manifest_hash: part.manifest_hash,
+                    metadata_name: &metadata_name,
+                    metadata_symbol: &metadata_symbol,
+                    seller_fee_basis_points,
+                    creators: &creators,
+                    hash_algorithm,
+                    category,
+                    tag_hash,
+                    arweave_tx_id: part.arweave_tx_id,
+                    origin: ObjectOrigin::Split,
+                    provenance: core::slice::from_ref(&parent_object_id),
+                    creator: None,
+                    stage: MintStage::Complete,
+                },
+            )?;
+        }
+
+        emit_logged!(ObjectSplit {
+            config: ctx.accounts.base.config.key(),
+            parent_manifest,
+            parent_object_id,
+            part_object_ids,
+            context: EventContext::new(
+                ctx.accounts.base.minter.key(),
+                ctx.accounts.base.authority.key()
+            )?,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the game server allowed to raise the level of the config's
+    /// objects on its own. Pass the default key to remove it, leaving only
+    /// holder level ups co-signed by the authority.
+    pub fn set_level_delegate(ctx: Context<SetLevelDelegate>, delegate: Pubkey) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let level_delegate = &mut ctx.accounts.level_delegate;
+        let previous_delegate = level_delegate.delegate;
+        level_delegate.config = config_key;
+        level_delegate.delegate = delegate;
+        level_delegate.bump = ctx.bumps.level_delegate;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            LevelDelegateUpdated {
+                config: config_key,
+                previous_delegate,
+                delegate,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Raises an object's level. The config's level delegate can sign alone;
+    /// otherwise the holder signs with the config authority co-signing.
+    /// Levels only move up, and the manifest hash and URI are untouched.
+    pub fn level_up(ctx: Context<LevelUp>, level: u16) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        let signer = ctx.accounts.signer.key();
+        let by_delegate = ctx
+            .accounts
+            .level_delegate
+            .as_ref()
+            .is_some_and(|level_delegate| level_delegate.is_delegate(&signer));
+        if !by_delegate {
+            let authority = ctx
+                .accounts
+                .authority
+                .as_ref()
+                .ok_or(ErrorCode::LevelUpUnauthorized)?;
+            require_keys_eq!(
+                authority.key(),
+                ctx.accounts.config.load()?.authority,
+                ErrorCode::InvalidAuthority
+            );
+            let owner_token_account = ctx
+                .accounts
+                .owner_token_account
+                .as_ref()
+                .ok_or(ErrorCode::LevelUpUnauthorized)?;
+            require_object_holder(owner_token_account, &signer, &manifest.mint)?;
+        }
+        require!(level > manifest.level, ErrorCode::InvalidLevel);
+
+        let previous_level = manifest.level;
+        manifest.level = level;
+
+        emit_logged!(ObjectLeveledUp {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            previous_level,
+            level,
+            by_delegate,
+            context: EventContext::signed_by(signer)?,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the scorer allowed to grant experience points to the
+    /// config's objects, with its per-call and per-slot caps. Pass the
+    /// default key to stop all accrual.
+    pub fn set_xp_scorer(
+        ctx: Context<SetXpScorer>,
+        scorer: Pubkey,
+        max_xp_per_call: u64,
+        max_xp_per_slot: u64,
+    ) -> Result<()> {
+        require!(max_xp_per_call <= max_xp_per_slot, ErrorCode::InvalidXpCaps);
+        let config_key = ctx.accounts.config.key();
+        let xp_scorer = &mut ctx.accounts.xp_scorer;
+        let previous_scorer = xp_scorer.scorer;
+        xp_scorer.config = config_key;
+        xp_scorer.scorer = scorer;
+        xp_scorer.max_xp_per_call = max_xp_per_call;
+        xp_scorer.max_xp_per_slot = max_xp_per_slot;
+        xp_scorer.bump = ctx.bumps.xp_scorer;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            XpScorerUpdated {
+                config: config_key,
+                previous_scorer,
+                scorer,
+                max_xp_per_call,
+                max_xp_per_slot,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Grants `amount` experience points to an object, signed by the
+    /// config's registered scorer and bounded by its caps.
+    pub fn accrue_xp(ctx: Context<AccrueXp>, amount: u64) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.scorer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        manifest.accrue_xp(amount, Clock::get()?.slot, &ctx.accounts.xp_scorer)?;
+
+        emit_logged!(XpAccrued {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            amount,
+            xp: manifest.xp,
+            context: EventContext::signed_by(ctx.accounts.scorer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the timestamp after which an object is treated as inactive. Passing
+    /// zero clears the expiry.
+    pub fn set_object_expiry(ctx: Context<SetObjectExpiry>, expires_at: i64) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_keys_eq!(
+            manifest.config,
+            ctx.accounts.config.key(),
+            ErrorCode::InvalidConfig
+        );
+        require!(expires_at >= 0, ErrorCode::InvalidExpiry);
+
+        let previous_expires_at = manifest.expires_at;
+        manifest.expires_at = expires_at;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ObjectExpirySet {
+                config: manifest.config,
+                manifest: manifest_info.key(),
+                object_id: manifest.object_id,
+                previous_expires_at,
+                expires_at,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Reserves `object_id` for the signing creator for `duration_slots`, so
+    /// an announced id cannot be minted by someone else while its content is
+    /// uploaded. The creator pays the reservation's rent and gets it back
+    /// from `cancel_object_reservation` or `expire_object_reservation`.
+    pub fn reserve_object_id(
+        ctx: Context<ReserveObjectId>,
+        object_id: u64,
+        duration_slots: u64,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_RESERVATION_SLOTS).contains(&duration_slots),
+            ErrorCode::InvalidReservationDuration
+        );
+        let config = ctx.accounts.config.load()?;
+        require!(!config.paused(), ErrorCode::MintingPaused);
+        config.require_instruction_enabled(INSTRUCTION_MINT)?;
+        drop(config);
+        require!(
+            ctx.accounts.object_manifest.data_is_empty(),
+            ErrorCode::ObjectAlreadyExists
+        );
+
+        let slot = Clock::get()?.slot;
+        let reservation = &mut ctx.accounts.object_reservation;
+        reservation.config = ctx.accounts.config.key();
+        reservation.object_id = object_id;
+        reservation.creator = ctx.accounts.creator.key();
+        reservation.reserved_slot = slot;
+        reservation.expires_slot = slot.saturating_add(duration_slots);
+        reservation.bump = ctx.bumps.object_reservation;
+
+        emit_logged!(ObjectIdReserved {
+            config: reservation.config,
+            reservation: reservation.key(),
+            object_id,
+            creator: reservation.creator,
+            expires_slot: reservation.expires_slot,
+            context: EventContext::signed_by(reservation.creator)?,
+        });
+
+        Ok(())
+    }
+
+    /// Releases a reservation early, refunding its rent to the creator.
+    pub fn cancel_object_reservation(ctx: Context<CancelObjectReservation>) -> Result<()> {
+        let reservation = &ctx.accounts.object_reservation;
+        emit_logged!(ObjectReservationReleased {
+            config: reservation.config,
+            reservation: reservation.key(),
+            object_id: reservation.object_id,
+            creator: reservation.creator,
+            expired: false,
+            context: EventContext::signed_by(reservation.creator)?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank that closes a lapsed reservation, refunding its
+    /// rent to the creator.
+    pub fn expire_object_reservation(ctx: Context<ExpireObjectReservation>) -> Result<()> {
+        let reservation = &ctx.accounts.object_reservation;
+        require!(
+            Clock::get()?.slot >= reservation.expires_slot,
+            ErrorCode::ReservationNotExpired
+        );
+        emit_logged!(ObjectReservationReleased {
+            config: reservation.config,
+            reservation: reservation.key(),
+            object_id: reservation.object_id,
+            creator: reservation.creator,
+            expired: true,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Creates an [`Airdrop`] of `recipient_count` assignments committed to
+    /// by `merkle_root`. Every object it mints uses the given name, symbol,
+    /// seller fee, and category, and records the authority as its creator.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_airdrop(
+        ctx: Context<CreateAirdrop>,
+        airdrop_id: u64,
+        merkle_root: [u8; 32],
+        recipient_count: u32,
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        category: u16,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_AIRDROP_RECIPIENTS).contains(&recipient_count),
+            ErrorCode::InvalidAirdropRecipientCount
+        );
+        require!(
+            metadata_name.as_bytes().len() <= MAX_NAME_LENGTH,
+            ErrorCode::MetadataNameTooLong
+        );
+        require!(
+            metadata_symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
+            ErrorCode::MetadataSymbolTooLong
+        );
+        require!(
+            seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        let config = ctx.accounts.config.load()?;
+        config.require_instruction_enabled(INSTRUCTION_MINT)?;
+        require!(
+            config.is_category_enabled(category),
+            ErrorCode::CategoryNotEnabled
+        );
+        drop(config);
+
+        let airdrop = &mut ctx.accounts.airdrop;
+        airdrop.config = ctx.accounts.config.key();
+        airdrop.airdrop_id = airdrop_id;
+        airdrop.authority = ctx.accounts.authority.key();
+        airdrop.merkle_root = merkle_root;
+        airdrop.recipient_count = recipient_count;
+        airdrop.distributed_count = 0;
+        airdrop.metadata_name = metadata_name;
+        airdrop.metadata_symbol = metadata_symbol;
+        airdrop.seller_fee_basis_points = seller_fee_basis_points;
+        airdrop.category = category;
+        airdrop.bump = ctx.bumps.airdrop;
+        airdrop.distributed = vec![0u8; (recipient_count as usize).div_ceil(8)];
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            AirdropCreated {
+                config: airdrop.config,
+                airdrop: airdrop.key(),
+                airdrop_id,
+                merkle_root,
+                recipient_count,
+                context: EventContext::new(ctx.accounts.payer.key(), airdrop.authority)?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless crank that mints assignment `index` of an airdrop to
+    /// its recipient, the `recipient` of the mint accounts. `proof` must
+    /// lead from the assignment's [`airdrop_leaf`] to the airdrop's root.
+    /// The caller signs as `minter` and may also fund rent; the airdrop's
+    /// authority is recorded as the object's creator and sole, unverified
+    /// Metaplex creator. Cranks batch by packing several of these into one
+    /// transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribute_airdrop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeAirdrop<'info>>,
+        object_id: u64,
+        index: u32,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        hash_algorithm: u8,
+        arweave_tx_id: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        mint_airdrop_assignment(
+            ctx.accounts,
+            &ctx.bumps.base,
+            ctx.program_id,
+            ctx.remaining_accounts,
+            AirdropAssignment {
+                index,
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash,
+                hash_algorithm,
+                arweave_tx_id,
+                proof: &proof,
+            },
+            false,
+        )
+    }
+
+    /// Lets a recipient mint their own assignment of an airdrop, signing as
+    /// the minter and recipient, instead of waiting for a crank. Takes the
+    /// same accounts and arguments as `distribute_airdrop`, and shares its
+    /// bitmap, so each assignment is minted once either way.
+    #[allow(clippy::too_many_arguments)]
+    pub fn claim_airdrop<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeAirdrop<'info>>,
+        object_id: u64,
+        index: u32,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        hash_algorithm: u8,
+        arweave_tx_id: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.base.minter.key(),
+            ctx.accounts.base.recipient.key(),
+            ErrorCode::ClaimerNotRecipient
+        );
+        mint_airdrop_assignment(
+            ctx.accounts,
+            &ctx.bumps.base,
+            ctx.program_id,
+            ctx.remaining_accounts,
+            AirdropAssignment {
+                index,
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash,
+                hash_algorithm,
+                arweave_tx_id,
+                proof: &proof,
+            },
+            true,
+        )
+    }
+
+    /// Permissionless crank that grows a manifest created by an older program
+    /// to [`ObjectManifest::LEN`] and rewrites it into the current layout, with
+    /// the caller covering any extra rent.
+    pub fn migrate_manifest_v2(ctx: Context<MigrateManifestV2>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.caller.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require!(
+            manifest.layout_version < MANIFEST_LAYOUT_VERSION,
+            ErrorCode::ManifestAlreadyMigrated
+        );
+
+        let previous_version = manifest.layout_version;
+        manifest.upgrade_layout();
+
+        emit_logged!(ManifestLayoutMigrated {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            previous_version,
+            layout_version: manifest.layout_version,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank that deactivates an object once its expiry has
+    /// passed.
+    pub fn expire_object(ctx: Context<ExpireObject>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.caller.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require!(manifest.is_active(), ErrorCode::ObjectInactive);
+        require!(
+            manifest.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::ObjectNotExpired
+        );
+
+        manifest.set_is_active(false);
+
+        emit_logged!(ObjectExpired {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            expires_at: manifest.expires_at,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless instruction that refreshes the cached `owner` on a
+    /// manifest from the token account holding the object's entire supply.
+    pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.caller.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_keys_eq!(
+            manifest.mint,
+            ctx.accounts.object_mint.key(),
+            ErrorCode::MintMismatch
+        );
+
+        let holder = &ctx.accounts.holder_token_account;
+        require_keys_eq!(
+            holder.mint,
+            ctx.accounts.object_mint.key(),
+            ErrorCode::MintMismatch
+        );
+        require!(
+            holder.amount > 0 && holder.amount == ctx.accounts.object_mint.supply,
+            ErrorCode::OwnerDoesNotHoldObjectNft
+        );
+
+        let previous_owner = manifest.owner;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            holder.owner,
+        )?;
+
+        emit_logged!(OwnerSynced {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            previous_owner,
+            owner: holder.owner,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Called by the companion transfer-hook program on every transfer of a
+    /// hooked Token-2022 object. Caches the destination's owner on the
+    /// manifest and extends its custody trail as `sync_owner` would, so no
+    /// crank is needed. Only the hook's `hook_authority` PDA can sign it.
+    pub fn sync_hooked_owner(ctx: Context<SyncHookedOwner>) -> Result<()> {
+        let manifest_key = ctx.accounts.object_manifest.key();
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        let destination = &ctx.accounts.destination_token_account;
+        require_match!(
+            "destination_mint",
+            destination.mint,
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        if !holds_object(destination.amount, ctx.accounts.object_mint.supply) {
+            return Ok(());
+        }
+
+        let previous_owner = manifest.owner;
+        record_owner_change(
+            &mut manifest,
+            manifest_key,
+            ctx.accounts.custody_trail.as_ref(),
+            destination.owner,
+        )?;
+
+        emit_logged!(OwnerSynced {
+            config: manifest.config,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            previous_owner,
+            owner: destination.owner,
+            context: EventContext::signed_by(ctx.accounts.transfer_authority.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Queues the recovery of a recoverable Token-2022 object from one token
+    /// account to another, executable with `recover_object` once the
+    /// config's `recovery_delay_slots` have passed. `reason_hash` commits to
+    /// the off-chain record justifying the recovery. The authority pays the
+    /// [`ObjectRecovery`] PDA's rent, and one recovery can be queued per
+    /// manifest.
+    pub fn request_object_recovery(
+        ctx: Context<RequestObjectRecovery>,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        let accounts = &mut *ctx.accounts;
+        let config = accounts.config.load()?;
+        config.require_instruction_enabled(INSTRUCTION_RECOVERY)?;
+        let delay_slots = config.recovery_delay_slots;
+        drop(config);
+        require!(delay_slots != 0, ErrorCode::RecoveryDisabled);
+        let manifest = accounts.object_manifest.load()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.asset_backend()? == AssetBackend::Token2022,
+            ErrorCode::WrongAssetBackend
+        );
+        require!(
+            mint_permanent_delegate(&accounts.object_mint.to_account_info())?
+                == Some(accounts.auth.key()),
+            ErrorCode::ObjectNotRecoverable
+        );
+
+        let source = &accounts.source_token_account;
+        let destination = &accounts.destination_token_account;
+        require_match!(
+            "source_mint",
+            source.mint,
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require_match!(
+            "destination_mint",
+            destination.mint,
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(source.amount > 0, ErrorCode::OwnerDoesNotHoldObjectNft);
+        require_keys_neq!(
+            source.key(),
+            destination.key(),
+            ErrorCode::InvalidObjectRecovery
+        );
+
+        let slot = Clock::get()?.slot;
+        let manifest_key = accounts.object_manifest.key();
+        let recovery = &mut accounts.object_recovery;
+        recovery.manifest = manifest_key;
+        recovery.source = source.key();
+        recovery.destination = destination.key();
+        recovery.reason_hash = reason_hash;
+        recovery.requested_slot = slot;
+        recovery.executable_slot = slot.saturating_add(delay_slots);
+        recovery.bump = ctx.bumps.object_recovery;
+
+        emit_audited!(
+            accounts.audit_log.as_ref(),
+            ObjectRecoveryRequested {
+                config: manifest.config,
+                manifest: recovery.manifest,
+                object_id: manifest.object_id,
+                object_recovery: recovery.key(),
+                source_owner: source.owner,
+                destination_owner: destination.owner,
+                reason_hash,
+                executable_slot: recovery.executable_slot,
+                context: EventContext::signed_by(accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Drops a queued recovery and returns its rent to the authority.
+    pub fn cancel_object_recovery(ctx: Context<CancelObjectRecovery>) -> Result<()> {
+        let recovery = &ctx.accounts.object_recovery;
+        let manifest = ctx.accounts.object_manifest.load()?;
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ObjectRecoveryCancelled {
+                config: manifest.config,
+                manifest: recovery.manifest,
+                object_id: manifest.object_id,
+                object_recovery: recovery.key(),
+                reason_hash: recovery.reason_hash,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Executes a queued recovery once its waiting period has passed, moving
+    /// the object's tokens to the destination account with the auth PDA's
+    /// permanent delegate authority and caching the destination's owner on
+    /// the manifest. The queued PDA is closed back to the authority. For
+    /// hooked mints, the remaining accounts are the transfer hook's extra
+    /// accounts; the hook leaves transfers the auth PDA signs to OGAL.
+    pub fn recover_object<'info>(
+        ctx: Context<'_, '_, '_, 'info, RecoverObject<'info>>,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let recovery = &accounts.object_recovery;
+        require!(
+            Clock::get()?.slot >= recovery.executable_slot,
+            ErrorCode::RecoveryNotDue
+        );
+        let manifest_key = accounts.object_manifest.key();
+        {
+            let manifest = accounts.object_manifest.load()?;
+            require_match!(
+                "config",
+                accounts.config.key(),
+                manifest.config,
+                ErrorCode::InvalidConfig
+            );
+            require_match!(
+                "mint",
+                accounts.object_mint.key(),
+                manifest.mint,
+                ErrorCode::MintMismatch
+            );
+        }
+
+        let config_key = accounts.config.key();
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        spl_token_2022::onchain::invoke_transfer_checked(
+            &token_2022::ID,
+            accounts.source_token_account.to_account_info(),
+            accounts.object_mint.to_account_info(),
+            accounts.destination_token_account.to_account_info(),
+            accounts.auth.to_account_info(),
+            ctx.remaining_accounts,
+            accounts.source_token_account.amount,
+            accounts.object_mint.decimals,
+            &[auth_seeds],
+        )?;
+
+        let mut manifest = accounts.object_manifest.load_mut()?;
+        let previous_owner = manifest.owner;
+        let owner = accounts.destination_token_account.owner;
+        record_owner_change(
+            &mut manifest,
+            manifest_key,
+            accounts.custody_trail.as_ref(),
+            owner,
+        )?;
+
+        emit_audited!(
+            accounts.audit_log.as_ref(),
+            ObjectRecovered {
+                config: config_key,
+                manifest: manifest_key,
+                object_id: manifest.object_id,
+                object_recovery: recovery.key(),
+                previous_owner,
+                owner,
+                amount: accounts.source_token_account.amount,
+                reason_hash: recovery.reason_hash,
+                context: EventContext::signed_by(accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Pays the transfer fees a Token-2022 object has withheld to its
+    /// recorded creator's Token-2022 account, which the payer opens if it
+    /// does not exist yet. The remaining accounts are the object's token
+    /// accounts holding withheld fees; they are harvested to the mint first,
+    /// then the auth PDA withdraws everything the mint holds as the fee's
+    /// withdraw authority. Anyone may call it.
+    pub fn harvest_royalties<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestRoyalties<'info>>,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let manifest_key = accounts.object_manifest.key();
+        let (object_id, creator) = {
+            let manifest = accounts.object_manifest.load()?;
+            require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+            require_match!(
+                "config",
+                accounts.config.key(),
+                manifest.config,
+                ErrorCode::InvalidConfig
+            );
+            require_match!(
+                "mint",
+                accounts.object_mint.key(),
+                manifest.mint,
+                ErrorCode::MintMismatch
+            );
+            require_match!(
+                "creator",
+                accounts.creator.key(),
+                manifest.creator,
+                ErrorCode::InvalidRoyaltyCreator
+            );
+            (manifest.object_id, manifest.creator)
+        };
+
+        let token_program = accounts.token_program.to_account_info();
+        let mint_info = accounts.object_mint.to_account_info();
+        require!(
+            mint_withheld_fees(&mint_info)?.is_some(),
+            ErrorCode::ObjectHasNoTransferFee
+        );
+        if !ctx.remaining_accounts.is_empty() {
+            token_2022_extensions::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token_program.clone(),
+                    token_2022_extensions::HarvestWithheldTokensToMint {
+                        token_program_id: token_program.clone(),
+                        mint: mint_info.clone(),
+                    },
+                ),
+                ctx.remaining_accounts.to_vec(),
+            )?;
+        }
+        let amount = mint_withheld_fees(&mint_info)?.unwrap_or_default();
+        require!(amount > 0, ErrorCode::NoRoyaltiesWithheld);
+
+        let config_key = accounts.config.key();
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        token_2022_extensions::withdraw_withheld_tokens_from_mint(CpiContext::new_with_signer(
+            token_program.clone(),
+            token_2022_extensions::WithdrawWithheldTokensFromMint {
+                token_program_id: token_program,
+                mint: mint_info,
+                destination: accounts.creator_token_account.to_account_info(),
+                authority: accounts.auth.to_account_info(),
+            },
+            &[auth_seeds],
+        ))?;
+
+        emit_logged!(RoyaltiesHarvested {
+            config: config_key,
+            manifest: manifest_key,
+            object_id,
+            creator,
+            creator_token_account: accounts.creator_token_account.key(),
+            amount,
+            context: EventContext::signed_by(accounts.payer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the [`CustodyTrail`] for a manifest so future owner changes
+    /// detected by `sync_owner` are recorded. Anyone may pay for the trail.
+    pub fn enable_custody_trail(ctx: Context<EnableCustodyTrail>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        manifest.set_custody_trail_enabled(true);
+
+        let mut trail = ctx.accounts.custody_trail.load_init()?;
+        trail.manifest = manifest_info.key();
+        trail.bump = ctx.bumps.custody_trail;
+
+        emit_logged!(CustodyTrailEnabled {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            custody_trail: ctx.accounts.custody_trail.key(),
+            object_id: manifest.object_id,
+            context: EventContext::signed_by(ctx.accounts.payer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Opens a holder vote on a config change described off-chain by
+    /// `description_hash`. Every object under the config carries one vote
+    /// until `voting_slots` have passed; the authority executes the change
+    /// once `finalize_proposal` records that it passed.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_id: u64,
+        description_hash: [u8; 32],
+        voting_slots: u64,
+        quorum: u64,
+    ) -> Result<()> {
+        require!(voting_slots > 0, ErrorCode::InvalidVotingPeriod);
+        let config_key = ctx.accounts.config.key();
+        let proposal_key = ctx.accounts.proposal.key();
+        let voting_ends_slot = Clock::get()?.slot.saturating_add(voting_slots);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.config = config_key;
+        proposal.proposal_id = proposal_id;
+        proposal.description_hash = description_hash;
+        proposal.voting_ends_slot = voting_ends_slot;
+        proposal.quorum = quorum;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.finalized = false;
+        proposal.passed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            HolderProposalCreated {
+                config: config_key,
+                proposal: proposal_key,
+                proposal_id,
+                description_hash,
+                voting_ends_slot,
+                quorum,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Casts the vote of one object on an open proposal. The signer must hold
+    /// the object, and its [`HolderVote`] record keeps the object from voting
+    /// twice, even after it changes hands.
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require!(
+            manifest.minted() && !manifest.mint_pending() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require_object_holder(
+            &ctx.accounts.voter_token_account,
+            &ctx.accounts.voter.key(),
+            &manifest.mint,
+        )?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.finalized, ErrorCode::ProposalFinalized);
+        require!(
+            Clock::get()?.slot < proposal.voting_ends_slot,
+            ErrorCode::VotingEnded
+        );
+        if approve {
+            proposal.yes_votes = proposal.yes_votes.saturating_add(1);
+        } else {
+            proposal.no_votes = proposal.no_votes.saturating_add(1);
+        }
+
+        let vote = &mut ctx.accounts.vote;
+        vote.proposal = proposal.key();
+        vote.manifest = ctx.accounts.object_manifest.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.approve = approve;
+        vote.bump = ctx.bumps.vote;
+
+        emit_logged!(HolderVoteCast {
+            config: manifest.config,
+            proposal: proposal.key(),
+            manifest: vote.manifest,
+            object_id: manifest.object_id,
+            voter: vote.voter,
+            approve,
+            context: EventContext::signed_by(ctx.accounts.voter.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Closes voting once the voting period is over and records whether the
+    /// proposal passed. Anyone may finalize.
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.finalized, ErrorCode::ProposalFinalized);
+        require!(
+            Clock::get()?.slot >= proposal.voting_ends_slot,
+            ErrorCode::VotingNotEnded
+        );
+        proposal.finalized = true;
+        proposal.passed = proposal.passes();
+
+        emit_logged!(HolderProposalFinalized {
+            config: proposal.config,
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            passed: proposal.passed,
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Permanently freezes the manifest hash and URI. Only the current holder
+    /// may lock a manifest, and the lock cannot be lifted by anyone.
+    pub fn lock_manifest(ctx: Context<LockManifest>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        require!(!manifest.locked(), ErrorCode::ManifestLocked);
+
+        manifest.set_locked(true);
+
+        emit_logged!(ManifestLocked {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            manifest_hash: manifest.manifest_hash,
+            owner: ctx.accounts.owner.key(),
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Approves `delegate` as the token delegate of the holder's object token
+    /// account on behalf of `marketplace_program`, and records both on the
+    /// manifest so listing integrations can tell a delegate sanctioned through
+    /// the ledger from one approved directly. Replaces any earlier approval.
+    pub fn approve_marketplace_delegate(ctx: Context<ApproveMarketplaceDelegate>) -> Result<()> {
+        ctx.accounts
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_MARKETPLACE)?;
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require!(
+            manifest.minted() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        require!(
+            ctx.accounts.marketplace_program.executable,
+            ErrorCode::InvalidMarketplaceProgram
+        );
+
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    delegate: ctx.accounts.delegate.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            ctx.accounts.owner_token_account.amount,
+        )?;
+
+        manifest.marketplace_delegate = ctx.accounts.delegate.key();
+        manifest.marketplace_program = ctx.accounts.marketplace_program.key();
+
+        emit_logged!(MarketplaceDelegateApproved {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            owner: ctx.accounts.owner.key(),
+            delegate: manifest.marketplace_delegate,
+            marketplace_program: manifest.marketplace_program,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Revokes the token delegate approved by `approve_marketplace_delegate`
+    /// and clears it from the manifest.
+    pub fn revoke_marketplace_delegate(ctx: Context<RevokeMarketplaceDelegate>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        require!(
+            manifest.marketplace_delegate != Pubkey::default(),
+            ErrorCode::MarketplaceDelegateNotSet
+        );
+
+        token::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ))?;
+
+        let delegate = manifest.marketplace_delegate;
+        let marketplace_program = manifest.marketplace_program;
+        manifest.clear_marketplace_delegate();
+
+        emit_logged!(MarketplaceDelegateRevoked {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            owner: ctx.accounts.owner.key(),
+            delegate,
+            marketplace_program,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Moves the holder's object NFT into the config's vault and mints them a
+    /// receipt token for it. Whoever holds the receipt can withdraw the
+    /// object; meanwhile the manifest records the vault as its owner.
+    pub fn deposit_to_vault(ctx: Context<DepositToVault>) -> Result<()> {
+        ctx.accounts
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_VAULT)?;
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.minted() && !manifest.mint_pending() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require!(!manifest.vaulted(), ErrorCode::ObjectVaulted);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+
+        token::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    to: ctx.accounts.vault_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            ctx.accounts.owner_token_account.amount,
+            ctx.accounts.object_mint.decimals,
+        )?;
+
+        let config_key = ctx.accounts.config.key();
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, config_key.as_ref(), &[ctx.bumps.vault]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    to: ctx.accounts.receipt_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            1,
+        )?;
+
+        manifest.set_vaulted(true);
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            ctx.accounts.vault.key(),
+        )?;
+
+        emit_logged!(ObjectVaulted {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            depositor: ctx.accounts.owner.key(),
+            vault: ctx.accounts.vault.key(),
+            receipt_mint: ctx.accounts.receipt_mint.key(),
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Burns a vault receipt and releases the object it stands for to the
+    /// receipt holder, who becomes the manifest's recorded owner.
+    pub fn withdraw_from_vault(ctx: Context<WithdrawFromVault>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.holder.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(manifest.vaulted(), ErrorCode::ObjectNotVaulted);
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    from: ctx.accounts.receipt_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let config_key = ctx.accounts.config.key();
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, config_key.as_ref(), &[ctx.bumps.vault]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            ctx.accounts.vault_token_account.amount,
+            ctx.accounts.object_mint.decimals,
+        )?;
+
+        manifest.set_vaulted(false);
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            ctx.accounts.holder.key(),
+        )?;
+
+        emit_logged!(ObjectWithdrawnFromVault {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            holder: ctx.accounts.holder.key(),
+            vault: ctx.accounts.vault.key(),
+            receipt_mint: ctx.accounts.receipt_mint.key(),
+            context: EventContext::signed_by(ctx.accounts.holder.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Escrows the holder's object NFT in a [`Gift`] that anyone holding the
+    /// preimage of `secret_hash` can redeem until `expires_at`, so an object
+    /// can be sent as a link to someone without a wallet address yet. The
+    /// sender pays the escrow rent, refunded when the gift is redeemed or
+    /// refunded.
+    pub fn create_gift(
+        ctx: Context<CreateGift>,
+        secret_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_VAULT)?;
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            ErrorCode::InvalidExpiry
+        );
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.sender.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.minted() && !manifest.mint_pending() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require!(!manifest.vaulted(), ErrorCode::ObjectVaulted);
+        require_object_holder(
+            &ctx.accounts.sender_token_account,
+            &ctx.accounts.sender.key(),
+            &manifest.mint,
+        )?;
+
+        token::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.sender_token_account.to_account_info(),
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.sender.to_account_info(),
+                },
+            ),
+            ctx.accounts.sender_token_account.amount,
+            ctx.accounts.object_mint.decimals,
+        )?;
+
+        let gift = &mut ctx.accounts.gift;
+        gift.config = manifest.config;
+        gift.manifest = manifest_info.key();
+        gift.sender = ctx.accounts.sender.key();
+        gift.secret_hash = secret_hash;
+        gift.expires_at = expires_at;
+        gift.bump = ctx.bumps.gift;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            gift.key(),
+        )?;
+
+        emit_logged!(GiftCreated {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            gift: gift.key(),
+            sender: gift.sender,
+            expires_at,
+            context: EventContext::signed_by(gift.sender)?,
+        });
+
+        Ok(())
+    }
+
+    /// Releases a gift to the signer when `secret` hashes to its
+    /// `secret_hash` and the gift has not expired. The escrow and gift rent
+    /// go back to the sender.
+    pub fn redeem_gift(ctx: Context<RedeemGift>, secret: [u8; 32]) -> Result<()> {
+        let gift = &ctx.accounts.gift;
+        require!(
+            keccak::hash(&secret).to_bytes() == gift.secret_hash,
+            ErrorCode::InvalidGiftSecret
+        );
+        require!(
+            Clock::get()?.unix_timestamp < gift.expires_at,
+            ErrorCode::GiftExpired
+        );
+        release_gift(
+            gift,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.destination_token_account,
+            &ctx.accounts.object_mint,
+            &ctx.accounts.sender,
+            &ctx.accounts.token_program,
+        )?;
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            ctx.accounts.recipient.key(),
+        )?;
+
+        emit_logged!(GiftReleased {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            gift: gift.key(),
+            recipient: ctx.accounts.recipient.key(),
+            refunded: false,
+            context: EventContext::signed_by(ctx.accounts.recipient.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Returns an expired, unredeemed gift to its sender.
+    pub fn refund_gift(ctx: Context<RefundGift>) -> Result<()> {
+        let gift = &ctx.accounts.gift;
+        require!(
+            Clock::get()?.unix_timestamp >= gift.expires_at,
+            ErrorCode::GiftNotExpired
+        );
+        release_gift(
+            gift,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.destination_token_account,
+            &ctx.accounts.object_mint,
+            &ctx.accounts.sender,
+            &ctx.accounts.token_program,
+        )?;
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            gift.sender,
+        )?;
+
+        emit_logged!(GiftReleased {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            gift: gift.key(),
+            recipient: gift.sender,
+            refunded: true,
+            context: EventContext::signed_by(gift.sender)?,
+        });
+
+        Ok(())
+    }
+
+    /// Escrows the holder's object NFT in the config's vault and mints them
+    /// `shares` fungible share tokens. Whoever later gathers every share can
+    /// redeem the object with `redeem_object`.
+    pub fn fractionalize_object(ctx: Context<FractionalizeObject>, shares: u64) -> Result<()> {
+        require!(shares > 0, ErrorCode::InvalidShareSupply);
+        ctx.accounts
+            .config
+            .load()?
+            .require_instruction_enabled(INSTRUCTION_VAULT)?;
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.minted() && !manifest.mint_pending() && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require!(!manifest.fractionalized(), ErrorCode::ObjectFractionalized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+
+        token::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    to: ctx.accounts.vault_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            ctx.accounts.owner_token_account.amount,
+            ctx.accounts.object_mint.decimals,
+        )?;
+
+        let config_key = ctx.accounts.config.key();
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, config_key.as_ref(), &[ctx.bumps.vault]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: ctx.accounts.owner_share_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            shares,
+        )?;
+
+        manifest.share_supply = shares;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            ctx.accounts.vault.key(),
+        )?;
+
+        emit_logged!(ObjectFractionalized {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            owner: ctx.accounts.owner.key(),
+            share_mint: ctx.accounts.share_mint.key(),
+            shares,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Burns every share of a fractionalized object and releases the object
+    /// to the redeemer, who becomes the manifest's recorded owner.
+    pub fn redeem_object(ctx: Context<RedeemObject>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.redeemer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            ctx.accounts.config.key(),
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.fractionalized(),
+            ErrorCode::ObjectNotFractionalized
+        );
+
+        let shares = manifest.share_supply;
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    from: ctx.accounts.redeemer_share_account.to_account_info(),
+                    authority: ctx.accounts.redeemer.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        let config_key = ctx.accounts.config.key();
+        let vault_seeds: &[&[u8]] = &[VAULT_SEED, config_key.as_ref(), &[ctx.bumps.vault]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vault_token_account.to_account_info(),
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    to: ctx.accounts.destination_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            ctx.accounts.vault_token_account.amount,
+            ctx.accounts.object_mint.decimals,
+        )?;
+
+        manifest.share_supply = 0;
+        record_owner_change(
+            &mut manifest,
+            manifest_info.key(),
+            ctx.accounts.custody_trail.as_ref(),
+            ctx.accounts.redeemer.key(),
+        )?;
+
+        emit_logged!(ObjectRedeemed {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            redeemer: ctx.accounts.redeemer.key(),
+            share_mint: ctx.accounts.share_mint.key(),
+            shares,
+            context: EventContext::signed_by(ctx.accounts.redeemer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Links a Solana Attestation Service attestation, such as an inspection
+    /// certificate, to the manifest. Only the holder may link attestations,
+    /// and each must name the manifest or its mint as its subject.
+    pub fn add_attestation(ctx: Context<AddAttestation>) -> Result<()> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        let manifest_key = ctx.accounts.object_manifest.key();
+        let attestation = parse_sas_attestation(&ctx.accounts.attestation.try_borrow_data()?)?;
+        require_linkable_attestation(
+            &attestation,
+            &manifest_key,
+            &manifest,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        let link = &mut ctx.accounts.manifest_attestation;
+        link.manifest = manifest_key;
+        link.attestation = ctx.accounts.attestation.key();
+        link.issuer = attestation.signer;
+        link.credential = attestation.credential;
+        link.schema = attestation.schema;
+        link.expiry = attestation.expiry;
+        link.bump = ctx.bumps.manifest_attestation;
+
+        emit_logged!(AttestationAdded {
+            config: manifest.config,
+            manifest: manifest_key,
+            object_id: manifest.object_id,
+            attestation: link.attestation,
+            issuer: link.issuer,
+            schema: link.schema,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Unlinks an attestation from the manifest and returns the link's rent
+    /// to the holder.
+    pub fn remove_attestation(ctx: Context<RemoveAttestation>) -> Result<()> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+
+        emit_logged!(AttestationRemoved {
+            config: manifest.config,
+            manifest: ctx.accounts.object_manifest.key(),
+            object_id: manifest.object_id,
+            attestation: ctx.accounts.manifest_attestation.attestation,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Binds the object to a decentralized identity. The holder supplies the
+    /// hash of the DID and its controller co-signs, so the binding shows that
+    /// the identity vouched for the object. Replaces any earlier binding.
+    pub fn verify_did(ctx: Context<VerifyDid>, did_hash: [u8; 32]) -> Result<()> {
+        require!(did_hash != [0u8; 32], ErrorCode::InvalidDidHash);
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+
+        manifest.did_hash = did_hash;
+        manifest.did_controller = ctx.accounts.did_controller.key();
+
+        emit_logged!(DidVerified {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            did_hash,
+            controller: manifest.did_controller,
+            owner: ctx.accounts.owner.key(),
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Removes the object's DID binding.
+    pub fn clear_did(ctx: Context<ClearDid>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        require!(manifest.did_hash != [0u8; 32], ErrorCode::DidNotSet);
+
+        let did_hash = manifest.did_hash;
+        manifest.did_hash = [0u8; 32];
+        manifest.did_controller = Pubkey::default();
+
+        emit_logged!(DidCleared {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            did_hash,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Replaces the mirror URIs recorded on a manifest. The primary URI, which
+    /// is mirrored to the Metaplex metadata, is unaffected.
+    pub fn set_mirror_uris(ctx: Context<SetMirrorUris>, mirror_uris: Vec<String>) -> Result<()> {
+        require!(
+            mirror_uris.len() <= MAX_MIRROR_URIS,
+            ErrorCode::TooManyMirrorUris
+        );
+        for uri in mirror_uris.iter() {
+            require!(!uri.is_empty(), ErrorCode::InvalidMirrorUri);
+            require!(uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+        }
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_object_holder(
+            &ctx.accounts.owner_token_account,
+            &ctx.accounts.owner.key(),
+            &manifest.mint,
+        )?;
+        require!(!manifest.locked(), ErrorCode::ManifestLocked);
+
+        manifest.set_mirror_uris(&mirror_uris);
+
+        emit_logged!(MirrorUrisUpdated {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            mirror_uris,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Lets the config authority rewrite a manifest URI whose stored bytes are
+    /// not valid UTF-8. Readable URIs stay under the holder's control through
+    /// `update_object_manifest`.
+    pub fn repair_manifest_uri(
+        ctx: Context<RepairManifestUri>,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require!(metadata_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+        require!(
+            metadata_uri.len() <= METADATA_MAX_URI_LENGTH,
+            ErrorCode::UriTooLong
+        );
+        validate_uri_rules(&ctx.accounts.config.load()?, &metadata_uri)?;
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_keys_eq!(
+            manifest.config,
+            ctx.accounts.config.key(),
+            ErrorCode::InvalidConfig
+        );
+        require!(
+            !manifest.metadata_uri_is_valid(),
+            ErrorCode::ManifestUriNotCorrupted
+        );
+
+        manifest.set_metadata_uri(&metadata_uri);
+        manifest.set_uri_live(false);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ManifestUriRepaired {
+                config: manifest.config,
+                manifest: manifest_info.key(),
+                object_id: manifest.object_id,
+                metadata_uri,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Toggles the SPL Memo CPI recorded by every mint under this config.
+    pub fn set_mint_memo(ctx: Context<SetMintMemo>, enabled: bool) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.memo_on_mint();
+        config.set_memo_on_mint(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            MintMemoUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Selects how new objects are minted: as Token Metadata NFTs through
+    /// `mint_object_nft`, or as Metaplex Core assets through
+    /// `mint_core_object`. Existing objects keep the backend they were minted
+    /// with.
+    pub fn set_asset_backend(ctx: Context<SetAssetBackend>, backend: u8) -> Result<()> {
+        let backend = AssetBackend::try_from(backend)?;
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_backend = config.asset_backend;
+        config.set_asset_backend(backend);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            AssetBackendUpdated {
+                config: config_key,
+                previous_backend,
+                backend: backend as u8,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets how many slots a queued recovery waits before `recover_object` can
+    /// execute it. A nonzero value also makes new Token-2022 objects
+    /// recoverable; zero stops new requests but leaves existing mints and
+    /// queued recoveries as they are.
+    pub fn set_recovery_delay_slots(ctx: Context<SetRecoveryDelaySlots>, slots: u64) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_slots = config.recovery_delay_slots;
+        config.recovery_delay_slots = slots;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            RecoveryDelayUpdated {
+                config: config_key,
+                previous_slots,
+                slots,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Points the config at another Token Metadata deployment, such as a test
+    /// environment or an audited fork. `Pubkey::default()` restores Metaplex.
+    pub fn set_metadata_program(
+        ctx: Context<SetMetadataProgram>,
+        metadata_program: Pubkey,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_program = config.metadata_program();
+        config.metadata_program_id = metadata_program;
+        let program = config.metadata_program();
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            MetadataProgramUpdated {
+                config: config_key,
+                previous_program,
+                program,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets the Wormhole core bridge `export_object` posts attestations to.
+    /// `Pubkey::default()` disables exports.
+    pub fn set_wormhole_program(
+        ctx: Context<SetWormholeProgram>,
+        wormhole_program: Pubkey,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_program = config.wormhole_program;
+        config.wormhole_program = wormhole_program;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            WormholeProgramUpdated {
+                config: config_key,
+                previous_program,
+                program: wormhole_program,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Burns the object NFT and posts a Wormhole message carrying the
+    /// manifest hash, object id, and owner, so another chain can represent
+    /// the object with a verifiable origin. The manifest is kept, marked
+    /// burned and exported, as the Solana side of the proof.
+    pub fn export_object(ctx: Context<ExportObject>, nonce: u32) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let config = ctx.accounts.config.load()?;
+        config.require_instruction_enabled(INSTRUCTION_BRIDGE)?;
+        let wormhole_program = config.wormhole_program;
+        drop(config);
+        require!(
+            wormhole_program != Pubkey::default(),
+            ErrorCode::WormholeNotConfigured
+        );
+        require_match!(
+            "wormhole_program",
+            ctx.accounts.wormhole_program.key(),
+            wormhole_program,
+            ErrorCode::InvalidWormholeAccount
+        );
+        let emitter = ctx.accounts.wormhole_emitter.key();
+        let (bridge, _) = Pubkey::find_program_address(&[WORMHOLE_BRIDGE_SEED], &wormhole_program);
+        require_match!(
+            "wormhole_bridge",
+            ctx.accounts.wormhole_bridge.key(),
+            bridge,
+            ErrorCode::InvalidWormholeAccount
+        );
+        let (fee_collector, _) =
+            Pubkey::find_program_address(&[WORMHOLE_FEE_COLLECTOR_SEED], &wormhole_program);
+        require_match!(
+            "wormhole_fee_collector",
+            ctx.accounts.wormhole_fee_collector.key(),
+            fee_collector,
+            ErrorCode::InvalidWormholeAccount
+        );
+        let (sequence_tracker, _) = Pubkey::find_program_address(
+            &[WORMHOLE_SEQUENCE_SEED, emitter.as_ref()],
+            &wormhole_program,
+        );
+        require_match!(
+            "wormhole_sequence",
+            ctx.accounts.wormhole_sequence.key(),
+            sequence_tracker,
+            ErrorCode::InvalidWormholeAccount
+        );
+
+        let owner = ctx.accounts.owner.key();
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        require_match!(
+            "config",
+            config_key,
+            manifest.config,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "mint",
+            ctx.accounts.object_mint.key(),
+            manifest.mint,
+            ErrorCode::MintMismatch
+        );
+        require!(
+            manifest.is_active_at(Clock::get()?.unix_timestamp)
+                && manifest.minted()
+                && !manifest.mint_pending()
+                && !manifest.burned(),
+            ErrorCode::ObjectInactive
+        );
+        require_object_holder(&ctx.accounts.owner_token_account, &owner, &manifest.mint)?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.object_mint.to_account_info(),
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let payload = export_payload(&manifest, &owner);
+        let object_id = manifest.object_id;
+        let manifest_hash = manifest.manifest_hash;
+        let mint = manifest.mint;
+        manifest.set_is_active(false);
+        manifest.set_burned(true);
+        manifest.set_exported(true);
+        manifest.owner = Pubkey::default();
+        drop(manifest);
+
+        let fee = read_wormhole_u64(&ctx.accounts.wormhole_bridge, WORMHOLE_FEE_OFFSET)?
+            .ok_or(ErrorCode::InvalidWormholeAccount)?;
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        // The tracker holds the next sequence and does not exist before the
+        // emitter's first message.
+        let sequence = read_wormhole_u64(&ctx.accounts.wormhole_sequence, 0)?.unwrap_or(0);
+
+        let mut data = vec![WORMHOLE_POST_MESSAGE];
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
+        data.push(WORMHOLE_CONSISTENCY_FINALIZED);
+        let message = ctx.accounts.wormhole_message.key();
+        let manifest_key = manifest_info.key();
+        invoke_signed(
+            &Instruction {
+                program_id: wormhole_program,
+                accounts: vec![
+                    AccountMeta::new(bridge, false),
+                    AccountMeta::new(message, true),
+                    AccountMeta::new_readonly(emitter, true),
+                    AccountMeta::new(sequence_tracker, false),
+                    AccountMeta::new(owner, true),
+                    AccountMeta::new(fee_collector, false),
+                    AccountMeta::new_readonly(sysvar::clock::ID, false),
+                    AccountMeta::new_readonly(sysvar::rent::ID, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                ],
+                data,
+            },
+            &[
+                ctx.accounts.wormhole_bridge.to_account_info(),
+                ctx.accounts.wormhole_message.to_account_info(),
+                ctx.accounts.wormhole_emitter.to_account_info(),
+                ctx.accounts.wormhole_sequence.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.wormhole_fee_collector.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.wormhole_program.to_account_info(),
+            ],
+            &[
+                &[
+                    EXPORT_MESSAGE_SEED,
+                    manifest_key.as_ref(),
+                    &[ctx.bumps.wormhole_message],
+                ],
+                &[
+                    WORMHOLE_EMITTER_SEED,
+                    config_key.as_ref(),
+                    &[ctx.bumps.wormhole_emitter],
+                ],
+            ],
+        )?;
+
+        record_config_stats(
+            &ctx.accounts.config,
+            &mut ctx.accounts.config_stats,
+            |stats| stats.total_burned = stats.total_burned.saturating_add(1),
+        )?;
+
+        emit_logged!(ObjectExported {
+            config: config_key,
+            manifest: manifest_key,
+            mint,
+            object_id,
+            manifest_hash,
+            owner,
+            emitter,
+            sequence,
+            context: EventContext::signed_by(owner)?,
+        });
+
+        Ok(())
+    }
+
+    /// Registers, or replaces, the Wormhole emitter on `chain` whose
+    /// attestations `import_object` accepts for this config.
+    pub fn register_foreign_emitter(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        require!(address != [0u8; 32], ErrorCode::InvalidForeignEmitter);
+        let config_key = ctx.accounts.config.key();
+        let emitter = &mut ctx.accounts.foreign_emitter;
+        let previous_address = emitter.address;
+        emitter.config = config_key;
+        emitter.chain = chain;
+        emitter.address = address;
+        emitter.bump = ctx.bumps.foreign_emitter;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ForeignEmitterRegistered {
+                config: config_key,
+                chain,
+                previous_address,
+                address,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Mints a ledger object for a foreign asset attested by a posted
+    /// Wormhole VAA from a registered emitter. The manifest hash, hash
+    /// algorithm, and recipient come from the attestation; the display data
+    /// and creators are supplied as for `mint_object_nft`. The manifest
+    /// records the origin chain and foreign identifier, and each VAA imports
+    /// once.
+    pub fn import_object<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ImportObject<'info>>,
+        object_id: u64,
+        manifest_uri: String,
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<()> {
+        let config_key = ctx.accounts.base.config.key();
+        let config = ctx.accounts.base.config.load()?;
+        config.require_instruction_enabled(INSTRUCTION_BRIDGE)?;
+        let wormhole_program = config.wormhole_program;
+        drop(config);
+        require!(
+            wormhole_program != Pubkey::default(),
+            ErrorCode::WormholeNotConfigured
+        );
+        let posted_vaa_info = ctx.accounts.posted_vaa.to_account_info();
+        require_match!(
+            "posted_vaa_owner",
+            *posted_vaa_info.owner,
+            wormhole_program,
+            ErrorCode::InvalidWormholeAccount
+        );
+        let (sequence, emitter_chain, payload) = {
+            let data = posted_vaa_info.try_borrow_data()?;
+            let vaa = parse_posted_vaa(&data)?;
+            let emitter = &ctx.accounts.foreign_emitter;
+            require!(
+                emitter.config == config_key
+                    && emitter.chain == vaa.emitter_chain
+                    && emitter.address == vaa.emitter_address,
+                ErrorCode::InvalidForeignEmitter
+            );
+            (
+                vaa.sequence,
+                vaa.emitter_chain,
+                parse_import_payload(vaa.payload)?,
+            )
+        };
+        require_match!(
+            "recipient",
+            ctx.accounts.base.recipient.key(),
+            payload.recipient,
+            ErrorCode::ImportRecipientMismatch
+        );
+
+        let receipt_info = ctx.accounts.import_receipt.to_account_info();
+        let posted_vaa = posted_vaa_info.key();
+        let (expected_receipt, receipt_bump) = Pubkey::find_program_address(
+            &[IMPORT_RECEIPT_SEED, posted_vaa.as_ref()],
+            ctx.program_id,
+        );
+        require_match!(
+            "import_receipt",
+            receipt_info.key(),
+            expected_receipt,
+            ErrorCode::InvalidImportReceipt
+        );
+        require!(receipt_info.data_len() == 0, ErrorCode::VaaAlreadyImported);
+        create_pda_account(
+            &receipt_info,
+            &ctx.accounts.base.rent_payer.to_account_info(),
+            &ctx.accounts.base.system_program.to_account_info(),
+            ctx.program_id,
+            ImportReceipt::LEN,
+            &[IMPORT_RECEIPT_SEED, posted_vaa.as_ref(), &[receipt_bump]],
+        )?;
+
+        let target = ObjectMintTarget::from_accounts(
+            &ctx.accounts.base,
+            &ctx.accounts.metadata,
+            &ctx.bumps.base,
+        );
+        process_mint_object(
+            &mut ctx.accounts.base,
+            &ctx.accounts.metadata,
+            target,
+            ctx.program_id,
+            ctx.remaining_accounts,
+            MintObjectArgs {
+                object_id,
+                manifest_uri: &manifest_uri,
+                manifest_hash: payload.manifest_hash,
+                metadata_name: &metadata_name,
+                metadata_symbol: &metadata_symbol,
+                seller_fee_basis_points,
+                creators: &creators,
+                hash_algorithm: ManifestHashAlgorithm::try_from(payload.hash_algorithm)?,
+                category,
+                tag_hash,
+                arweave_tx_id,
+                origin: ObjectOrigin::Imported,
+                provenance: &[],
+                creator: None,
+                stage: MintStage::Complete,
+            },
+        )?;
+
+        let manifest_key = ctx.accounts.base.object_manifest.key();
+        {
+            let mut manifest = ctx.accounts.base.object_manifest.load_mut()?;
+            manifest.origin_chain = emitter_chain;
+            manifest.foreign_id = payload.foreign_id;
+        }
+        let receipt = ImportReceipt {
+            config: config_key,
+            manifest: manifest_key,
+            emitter_chain,
+            sequence,
+            bump: receipt_bump,
+        };
+        receipt.try_serialize(&mut &mut receipt_info.try_borrow_mut_data()?[..])?;
+
+        emit_logged!(ObjectImported {
+            config: config_key,
+            manifest: manifest_key,
+            mint: ctx.accounts.base.object_mint.key(),
+            recipient: payload.recipient,
+            object_id,
+            origin_chain: emitter_chain,
+            foreign_id: payload.foreign_id,
+            manifest_hash: payload.manifest_hash,
+            sequence,
+            context: EventContext::new(
+                ctx.accounts.base.minter.key(),
+                ctx.accounts.base.authority.key()
+            )?,
+        });
+
+        Ok(())
+    }
+
+    /// Toggles whether objects may be created as compressed manifests, which
+    /// live in Light Protocol state trees instead of rent-paying PDAs.
+    pub fn set_compressed_manifests(
+        ctx: Context<SetCompressedManifests>,
+        enabled: bool,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.compressed_manifests();
+        config.set_compressed_manifests(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            CompressedManifestsUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Creates `object_id` as a compressed manifest owned by `owner`. The
+    /// validity proof shows that its compressed address, derived from the
+    /// config and object id, is unused in the address tree, so each id is
+    /// created once. The id must not have a manifest PDA either, but later
+    /// mints cannot see compressed ids, so registries using both kinds
+    /// should give them separate id ranges. The Merkle trees and queues are
+    /// the remaining accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_compressed_manifest<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateCompressedManifest<'info>>,
+        object_id: u64,
+        owner: Pubkey,
+        manifest_hash: [u8; 32],
+        hash_algorithm: u8,
+        manifest_uri: String,
+        proof: CompressedProof,
+        address_tree: LightAddressTreeInfo,
+        output_tree_index: u8,
+    ) -> Result<()> {
+        require_not_globally_paused(&ctx.accounts.global_pause)?;
+        let config_key = ctx.accounts.config.key();
+        {
+            let config = ctx.accounts.config.load()?;
+            require!(!config.paused(), ErrorCode::MintingPaused);
+            config.require_instruction_enabled(INSTRUCTION_MINT)?;
+            require!(
+                config.compressed_manifests(),
+                ErrorCode::CompressedManifestsDisabled
+            );
+            require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+            validate_uri_rules(&config, &manifest_uri)?;
+        }
+        ManifestHashAlgorithm::try_from(hash_algorithm)?;
+        require!(
+            ctx.accounts.object_manifest.data_is_empty(),
+            ErrorCode::ObjectAlreadyExists
+        );
+        require_object_reservation(&ctx.accounts.object_reservation, &ctx.accounts.minter.key())?;
+        require_approved_creator(
+            &ctx.accounts.config.load()?,
+            &config_key,
+            ctx.accounts.approved_creator.as_ref(),
+            &ctx.accounts.minter.key(),
+        )?;
+
+        let address_tree_account = ctx
+            .remaining_accounts
+            .get(address_tree.address_merkle_tree_account_index as usize)
+            .ok_or(ErrorCode::InvalidLightTreeIndex)?;
+        let seed = compressed_manifest_address_seed(&config_key, object_id);
+        let address = compressed_manifest_address(address_tree_account.key, &seed);
+        let slot = Clock::get()?.slot;
+        let manifest = CompressedManifest {
+            config: config_key,
+            object_id,
+            owner,
+            creator: ctx.accounts.minter.key(),
+            manifest_hash,
+            hash_algorithm,
+            created_slot: slot,
+            updated_slot: slot,
+            metadata_uri: manifest_uri,
+        };
+
+        invoke_light_system(
+            &ctx.accounts.light,
+            &ctx.accounts.payer.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.bumps.light.cpi_authority,
+            LightInvokeCpi {
+                proof: Some(proof),
+                new_address_params: vec![LightNewAddressParams {
+                    seed,
+                    address_queue_account_index: address_tree.address_queue_account_index,
+                    address_merkle_tree_account_index: address_tree
+                        .address_merkle_tree_account_index,
+                    address_merkle_tree_root_index: address_tree.address_merkle_tree_root_index,
+                }],
+                input_compressed_accounts: Vec::new(),
+                output_compressed_accounts: vec![LightOutputAccount {
+                    compressed_account: light_manifest_account(&manifest, address, true),
+                    merkle_tree_index: output_tree_index,
+                }],
+                relay_fee: None,
+                compress_or_decompress_lamports: None,
+                is_compress: false,
+                cpi_context: None,
+            },
+        )?;
+
+        {
+            let mut config = ctx.accounts.config.load_mut()?;
+            config.object_count = config.object_count.saturating_add(1);
+        }
+
+        emit_logged!(CompressedManifestCreated {
+            config: config_key,
+            address,
+            object_id,
+            owner,
+            creator: manifest.creator,
+            manifest_hash,
+            hash_algorithm,
+            metadata_uri: manifest.metadata_uri,
+            context: EventContext::new(ctx.accounts.payer.key(), manifest.creator)?,
+        });
+
+        Ok(())
+    }
+
+    /// Replaces the content of a compressed manifest. `manifest` is its
+    /// current state, which the validity proof shows is the leaf at `leaf`;
+    /// the updated manifest is written to the tree at `output_tree_index`
+    /// under the same address. Only the manifest's owner may update it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_compressed_manifest<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateCompressedManifest<'info>>,
+        manifest: CompressedManifest,
+        address: [u8; 32],
+        leaf: LightStateTreeInfo,
+        proof: CompressedProof,
+        output_tree_index: u8,
+        manifest_hash: [u8; 32],
+        hash_algorithm: u8,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require_not_globally_paused(&ctx.accounts.global_pause)?;
+        let config_key = ctx.accounts.config.key();
+        require_match!(
+            "config",
+            manifest.config,
+            config_key,
+            ErrorCode::InvalidConfig
+        );
+        require_match!(
+            "owner",
+            ctx.accounts.owner.key(),
+            manifest.owner,
+            ErrorCode::CompressedManifestOwnerMismatch
+        );
+        ManifestHashAlgorithm::try_from(hash_algorithm)?;
+        require!(metadata_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+        {
+            let config = ctx.accounts.config.load()?;
+            config.require_instruction_enabled(INSTRUCTION_UPDATE)?;
+            validate_uri_rules(&config, &metadata_uri)?;
+        }
+
+        let previous_manifest_hash = manifest.manifest_hash;
+        let updated = CompressedManifest {
+            manifest_hash,
+            hash_algorithm,
+            updated_slot: Clock::get()?.slot,
+            metadata_uri,
+            ..manifest.clone()
+        };
+
+        invoke_light_system(
+            &ctx.accounts.light,
+            &ctx.accounts.owner.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.bumps.light.cpi_authority,
+            LightInvokeCpi {
+                proof: Some(proof),
+                new_address_params: Vec::new(),
+                input_compressed_accounts: vec![LightInputAccount {
+                    compressed_account: light_manifest_account(&manifest, address, false),
+                    merkle_tree_account_index: leaf.merkle_tree_account_index,
+                    nullifier_queue_account_index: leaf.nullifier_queue_account_index,
+                    leaf_index: leaf.leaf_index,
+                    queue_index: None,
+                    root_index: leaf.root_index,
+                    read_only: false,
+                }],
+                output_compressed_accounts: vec![LightOutputAccount {
+                    compressed_account: light_manifest_account(&updated, address, true),
+                    merkle_tree_index: output_tree_index,
+                }],
+                relay_fee: None,
+                compress_or_decompress_lamports: None,
+                is_compress: false,
+                cpi_context: None,
+            },
+        )?;
+
+        emit_logged!(CompressedManifestUpdated {
+            config: config_key,
+            address,
+            object_id: updated.object_id,
+            previous_manifest_hash,
+            manifest_hash,
+            hash_algorithm,
+            metadata_uri: updated.metadata_uri,
+            context: EventContext::signed_by(ctx.accounts.owner.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Toggles whether mints may deliver to recipient token accounts other
+    /// than the recipient's ATA, for custodial wallets that do not use ATAs.
+    pub fn set_non_ata_recipients(ctx: Context<SetNonAtaRecipients>, enabled: bool) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.non_ata_recipients();
+        config.set_non_ata_recipients(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            NonAtaRecipientsUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Replaces the set of `INSTRUCTION_*` subsystems the config allows.
+    /// Disabling a subsystem only blocks new actions; exits such as
+    /// `withdraw_from_vault` and `revoke_marketplace_delegate` stay open.
+    pub fn set_enabled_instructions(
+        ctx: Context<SetEnabledInstructions>,
+        enabled: u16,
+    ) -> Result<()> {
+        require!(
+            enabled & !INSTRUCTION_ALL == 0,
+            ErrorCode::InvalidInstructionFlags
+        );
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.enabled_instructions();
+        config.set_enabled_instructions(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            EnabledInstructionsUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Toggles whether new Token-2022 objects are minted with the companion
+    /// transfer hook. Objects minted earlier keep the extensions they were
+    /// created with.
+    pub fn set_transfer_hook(ctx: Context<SetTransferHook>, enabled: bool) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_enabled = config.transfer_hook_enabled();
+        config.set_transfer_hook_enabled(enabled);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            TransferHookUpdated {
+                config: config_key,
+                previous_enabled,
+                enabled,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets the transfer fee, in basis points of each transfer, that new
+    /// Token-2022 objects withhold for their creator, or zero to mint them
+    /// without one. Objects minted earlier keep the fee they were created
+    /// with.
+    pub fn set_transfer_fee_royalties(
+        ctx: Context<SetTransferFeeRoyalties>,
+        seller_fee_basis_points: u16,
+    ) -> Result<()> {
+        require!(
+            seller_fee_basis_points < TRANSFER_FEE_BASIS_POINTS_LIMIT,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_basis_points = config.seller_fee_basis_points;
+        config.seller_fee_basis_points = seller_fee_basis_points;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            TransferFeeRoyaltiesUpdated {
+                config: config_key,
+                previous_basis_points,
+                basis_points: seller_fee_basis_points,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets the rules metadata URIs must satisfy at mint, update, and repair:
+    /// a bitmask of allowed `URI_SCHEME_*` values and up to two domain
+    /// prefixes. Zero schemes or no prefixes leave that rule unrestricted.
+    pub fn set_uri_rules(
+        ctx: Context<SetUriRules>,
+        allowed_schemes: u8,
+        prefixes: Vec<String>,
+    ) -> Result<()> {
+        require!(
+            allowed_schemes & !ALL_URI_SCHEMES == 0,
+            ErrorCode::InvalidUriSchemes
+        );
+        require!(
+            prefixes.len() <= MAX_URI_PREFIXES,
+            ErrorCode::TooManyUriPrefixes
+        );
+        for prefix in prefixes.iter() {
+            require!(
+                !prefix.is_empty() && prefix.len() <= MAX_URI_PREFIX_LENGTH,
+                ErrorCode::InvalidUriPrefix
+            );
+        }
+
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_allowed_schemes = config.allowed_uri_schemes;
+        config.allowed_uri_schemes = allowed_schemes;
+        config.set_uri_prefixes(&prefixes);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            UriRulesUpdated {
+                config: config_key,
+                previous_allowed_schemes,
+                allowed_schemes,
+                prefixes,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Sets how many slots a manifest may stay unminted before anyone can
+    /// close it with `gc_manifest`. Zero disables collection.
+    pub fn set_manifest_gc_slots(ctx: Context<SetManifestGcSlots>, slots: u64) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        let previous_slots = config.manifest_gc_slots;
+        config.manifest_gc_slots = slots;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ManifestGcSlotsUpdated {
+                config: config_key,
+                previous_slots,
+                slots,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless crank that closes a manifest which was created but never
+    /// minted once the config's `manifest_gc_slots` have passed, refunding its
+    /// rent to the payer that created it.
+    pub fn gc_manifest(ctx: Context<GcManifest>) -> Result<()> {
+        let gc_slots = ctx.accounts.config.load()?.manifest_gc_slots;
+        require!(gc_slots != 0, ErrorCode::ManifestGcDisabled);
+
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        let mut manifest = read_manifest(&manifest_info)?;
+        manifest.upgrade_layout();
+        require_keys_eq!(
+            manifest.config,
+            ctx.accounts.config.key(),
+            ErrorCode::InvalidConfig
+        );
+        require_keys_eq!(
+            manifest.rent_refund_account(),
+            ctx.accounts.rent_payer.key(),
+            ErrorCode::RentPayerMismatch
+        );
+        require!(
+            manifest.is_collectable_at(Clock::get()?.slot, gc_slots),
+            ErrorCode::ManifestNotCollectable
+        );
+
+        emit_logged!(ManifestCollected {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            creator: manifest.creator,
+            rent_payer: ctx.accounts.rent_payer.key(),
+            lamports: manifest_info.lamports(),
+            context: EventContext::signed_by(ctx.accounts.caller.key())?,
+        });
+
+        // The `close = rent_payer` constraint returns the rent once this
+        // succeeds.
+        Ok(())
+    }
+
+    /// Hands the creator slot of a manifest that has not been minted yet to
+    /// `new_creator`, who must then appear in the first mint's creators. Both
+    /// wallets sign.
+    pub fn transfer_manifest_creator(ctx: Context<TransferManifestCreator>) -> Result<()> {
+        let manifest_info = ctx.accounts.object_manifest.to_account_info();
+        ensure_manifest_capacity(
+            &manifest_info,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let mut manifest = ctx.accounts.object_manifest.load_mut()?;
+        require_keys_eq!(
+            manifest.config,
+            ctx.accounts.config.key(),
+            ErrorCode::InvalidConfig
+        );
+        require_keys_eq!(
+            manifest.creator,
+            ctx.accounts.creator.key(),
+            ErrorCode::ManifestCreatorMismatch
+        );
+        require!(
+            manifest.creator_transferable(),
+            ErrorCode::ManifestCreatorLocked
+        );
+
+        let previous_creator = manifest.creator;
+        manifest.creator = ctx.accounts.new_creator.key();
+
+        emit_logged!(ManifestCreatorTransferred {
+            config: manifest.config,
+            manifest: manifest_info.key(),
+            object_id: manifest.object_id,
+            previous_creator,
+            creator: manifest.creator,
+            context: EventContext::signed_by(previous_creator)?,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the per-config stats PDA. Once it exists, mints, burns, and
+    /// manifest updates under this config must pass it so the counters stay
+    /// complete from this point on.
+    pub fn initialize_config_stats(ctx: Context<InitializeConfigStats>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        ctx.accounts.config.load_mut()?.set_stats_enabled(true);
+
+        let stats = &mut ctx.accounts.config_stats;
+        stats.config = config_key;
+        stats.bump = ctx.bumps.config_stats;
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ConfigStatsInitialized {
+                config: config_key,
+                config_stats: stats.key(),
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Creates the config's [`ManifestChangelog`] and initializes `merkle_tree`,
+    /// which the caller has allocated for SPL Account Compression, with the
+    /// changelog PDA as its authority. From then on every mint and manifest
+    /// update appends a leaf hash of the manifest state.
+    pub fn init_manifest_changelog(
+        ctx: Context<InitManifestChangelog>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let tree = ctx.accounts.merkle_tree.key();
+        require_match!(
+            "merkle_tree_owner",
+            *ctx.accounts.merkle_tree.owner,
+            SPL_ACCOUNT_COMPRESSION_ID,
+            ErrorCode::InvalidChangelogAccount
+        );
+
+        let changelog = &mut ctx.accounts.manifest_changelog;
+        changelog.config = config_key;
+        changelog.tree = tree;
+        changelog.bump = ctx.bumps.manifest_changelog;
+
+        let mut data = compression_ix_data("init_empty_merkle_tree");
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        invoke_signed(
+            &Instruction {
+                program_id: SPL_ACCOUNT_COMPRESSION_ID,
+                accounts: vec![
+                    AccountMeta::new(tree, false),
+                    AccountMeta::new_readonly(changelog.key(), true),
+                    AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                ],
+                data,
+            },
+            &[
+                ctx.accounts.merkle_tree.to_account_info(),
+                changelog.to_account_info(),
+                ctx.accounts.noop_program.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+            ],
+            &[&[CHANGELOG_SEED, config_key.as_ref(), &[changelog.bump]]],
+        )?;
+        ctx.accounts.config.load_mut()?.set_changelog_enabled(true);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ManifestChangelogInitialized {
+                config: config_key,
+                changelog: ctx.accounts.manifest_changelog.key(),
+                tree,
+                max_depth,
+                max_buffer_size,
+                context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Creates the config's [`AuditLog`]. From then on every authority-gated
+    /// instruction must pass it and appends an entry for each event it emits.
+    pub fn init_audit_log(ctx: Context<InitAuditLog>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        ctx.accounts.config.load_mut()?.set_audit_log_enabled(true);
+
+        let mut audit_log = ctx.accounts.audit_log.load_init()?;
+        audit_log.config = config_key;
+        audit_log.bump = ctx.bumps.audit_log;
+
+        emit_logged!(AuditLogInitialized {
+            config: config_key,
+            audit_log: ctx.accounts.audit_log.key(),
+            context: EventContext::new(ctx.accounts.payer.key(), ctx.accounts.authority.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Starts appending every newly created object id to `ObjectIndexPage`
+    /// PDAs. Once enabled the index cannot be switched off, so the pages stay
+    /// a complete listing of objects created from this point on.
+    pub fn enable_object_index(ctx: Context<EnableObjectIndex>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        require!(
+            !config.object_index_enabled(),
+            ErrorCode::ObjectIndexAlreadyEnabled
+        );
+        config.set_object_index_enabled(true);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            ObjectIndexEnabled {
+                config: config_key,
+                object_count: config.object_count,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Starts setting a bit in the config's `MintedBitmap` chunks for every
+    /// object id minted from this point on. Earlier mints can be backfilled
+    /// with `record_minted_ids`.
+    pub fn enable_minted_bitmap(ctx: Context<EnableMintedBitmap>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let mut config = ctx.accounts.config.load_mut()?;
+        require!(
+            !config.minted_bitmap_enabled(),
+            ErrorCode::MintedBitmapAlreadyEnabled
+        );
+        config.set_minted_bitmap_enabled(true);
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            MintedBitmapEnabled {
+                config: config_key,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless crank that funds the config and the config-scoped PDAs
+    /// in the remaining accounts up to the rent-exempt minimum for their
+    /// current size, paid by `payer`. Accounts that are already exempt are
+    /// left alone.
+    pub fn top_up_rent<'info>(ctx: Context<'_, '_, 'info, 'info, TopUpRent<'info>>) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        let mut funded: u64 = 0;
+        for account in ctx.remaining_accounts {
+            require!(
+                account.is_writable && account.owner == ctx.program_id,
+                ErrorCode::InvalidRentAccount
+            );
+            require!(
+                belongs_to_config(&account.key(), &account.try_borrow_data()?, &config_key),
+                ErrorCode::RentAccountOutsideConfig
+            );
+            let before = account.lamports();
+            fund_rent_exemption(account, &payer, &system_program, account.data_len())?;
+            funded = funded.saturating_add(account.lamports() - before);
+        }
+
+        log_record(
+            "rent_top_up",
+            &[&config_key, &ctx.remaining_accounts.len(), &funded],
+        );
+        Ok(())
+    }
+
+    /// Moves lamports held above the rent-exempt minimum out of the config
+    /// and the config-scoped PDAs in the remaining accounts into `destination`.
+    /// Only the config authority may sweep.
+    pub fn sweep_rent_dust<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepRentDust<'info>>,
+    ) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let destination = ctx.accounts.destination.to_account_info();
+        let rent = Rent::get()?;
+
+        let mut swept: u64 = 0;
+        for account in ctx.remaining_accounts {
+            require!(
+                account.is_writable && account.owner == ctx.program_id,
+                ErrorCode::InvalidRentAccount
+            );
+            require!(
+                belongs_to_config(&account.key(), &account.try_borrow_data()?, &config_key),
+                ErrorCode::RentAccountOutsideConfig
+            );
+            let dust = account
+                .lamports()
+                .saturating_sub(rent.minimum_balance(account.data_len()));
+            if dust == 0 {
+                continue;
+            }
+            **account.try_borrow_mut_lamports()? -= dust;
+            **destination.try_borrow_mut_lamports()? += dust;
+            swept = swept.saturating_add(dust);
+        }
+
+        emit_audited!(
+            ctx.accounts.audit_log.as_ref(),
+            RentDustSwept {
+                config: config_key,
+                destination: destination.key(),
+                accounts: ctx.remaining_accounts.len() as u32,
+                lamports: swept,
+                context: EventContext::signed_by(ctx.accounts.authority.key())?,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless backfill of the minted bitmap from existing manifests.
+    /// Every manifest in the remaining accounts must belong to this config,
+    /// have been minted, and fall within the supplied bitmap chunk.
+    pub fn record_minted_ids<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordMintedIds<'info>>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.config.load()?.minted_bitmap_enabled(),
+            ErrorCode::MintedBitmapNotEnabled
+        );
+        let config_key = ctx.accounts.config.key();
+
+        let mut object_ids = Vec::with_capacity(ctx.remaining_accounts.len());
+        for manifest_info in ctx.remaining_accounts {
+            let manifest = read_manifest(manifest_info)?;
+            require_keys_eq!(manifest.config, config_key, ErrorCode::InvalidConfig);
+            require!(manifest.minted(), ErrorCode::ObjectNotMinted);
+            object_ids.push(manifest.object_id);
+        }
+
+        record_minted_ids(
+            &ctx.accounts.minted_bitmap.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+            &config_key,
+            &object_ids,
+        )?;
+
+        log_record("minted_ids", &[&config_key, &object_ids.len()]);
+        Ok(())
+    }
+
+    /// Opens a point-in-time holder snapshot over object ids
+    /// `start_object_id..=end_object_id`. Anyone may create a snapshot and
+    /// crank it with `snapshot_holders`.
+    pub fn create_holder_snapshot(
+        ctx: Context<CreateHolderSnapshot>,
+        snapshot_id: u64,
+        start_object_id: u64,
+        end_object_id: u64,
+    ) -> Result<()> {
+        require!(
+            end_object_id >= start_object_id
+                && end_object_id - start_object_id < HOLDER_SNAPSHOT_CAPACITY as u64,
+            ErrorCode::InvalidSnapshotRange
+        );
+
+        let mut snapshot = ctx.accounts.holder_snapshot.load_init()?;
+        snapshot.config = ctx.accounts.config.key();
+        snapshot.creator = ctx.accounts.payer.key();
+        snapshot.snapshot_id = snapshot_id;
+        snapshot.start_object_id = start_object_id;
+        snapshot.end_object_id = end_object_id;
+        snapshot.bump = ctx.bumps.holder_snapshot;
+
+        emit_logged!(HolderSnapshotCreated {
+            config: snapshot.config,
+            holder_snapshot: ctx.accounts.holder_snapshot.key(),
+            snapshot_id,
+            start_object_id,
+            end_object_id,
+            context: EventContext::signed_by(ctx.accounts.payer.key())?,
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless crank recording the current holder of each object in
+    /// the remaining accounts, passed as (manifest, mint, holder token account)
+    /// triplets. Each object can only be recorded once per snapshot.
+    pub fn snapshot_holders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SnapshotHolders<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 3 == 0,
+            ErrorCode::MissingSnapshotAccounts
+        );
+        let slot = Clock::get()?.slot;
+        let mut snapshot = ctx.accounts.holder_snapshot.load_mut()?;
+
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let manifest = read_manifest(&accounts[0])?;
+            require_keys_eq!(manifest.config, snapshot.config, ErrorCode::InvalidConfig);
+            require!(
+                manifest.minted() && !manifest.burned(),
+                ErrorCode::ObjectInactive
+            );
+            require_keys_eq!(manifest.mint, accounts[1].key(), ErrorCode::MintMismatch);
+
+            let mint = Account::<Mint>::try_from(&accounts[1])?;
+            let holder = Account::<TokenAccount>::try_from(&accounts[2])?;
+            require_keys_eq!(holder.mint, manifest.mint, ErrorCode::MintMismatch);
+            require!(
+                holder.amount > 0 && holder.amount == mint.supply,
+                ErrorCode::OwnerDoesNotHoldObjectNft
+            );
+
+            snapshot.record(HolderSnapshotEntry {
+                object_id: manifest.object_id,
+                slot,
+                owner: holder.owner,
+            })?;
+        }
+
+        log_record(
+            "snapshot_holders",
+            &[
+                &snapshot.config,
+                &snapshot.snapshot_id,
+                &snapshot.recorded_count,
+            ],
+        );
+        Ok(())
+    }
+
+    /// Read-only view of a manifest for CPI callers. The returned
+    /// `ManifestView` is Borsh-serialized into the transaction return data, so
+    /// other programs can query the ledger without depending on the
+    /// zero-copy manifest layout.
+    pub fn get_object_manifest(ctx: Context<GetObjectManifest>) -> Result<ManifestView> {
+        let manifest = read_manifest(&ctx.accounts.object_manifest)?;
+        require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+        ManifestView::from_manifest(&manifest, Clock::get()?.unix_timestamp)
+    }
+
+    /// Read-only preflight for `mint_object_nft`, taking the same arguments.
+    /// Runs the mint's preconditions without creating or writing anything and
+    /// returns a [`MintCheck`] naming the first one that fails, so frontends
+    /// can simulate it and show the precise reason before sending the mint.
+    pub fn check_mint(
+        ctx: Context<CheckMint>,
+        object_id: u64,
+        manifest_uri: String,
+        manifest_hash: [u8; 32],
+        metadata_name: String,
+        metadata_symbol: String,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorInput>,
+        hash_algorithm: u8,
+        category: u16,
+        tag_hash: [u8; 32],
+        arweave_tx_id: [u8; 32],
+    ) -> Result<MintCheck> {
+        let outcome = ManifestHashAlgorithm::try_from(hash_algorithm).and_then(|hash_algorithm| {
+            check_mint_preconditions(
+                &ctx.accounts,
+                ctx.program_id,
+                MintObjectArgs {
+                    object_id,
+                    manifest_uri: &manifest_uri,
+                    manifest_hash,
+                    metadata_name: &metadata_name,
+                    metadata_symbol: &metadata_symbol,
+                    seller_fee_basis_points,
+                    creators: &creators,
+                    hash_algorithm,
+                    category,
+                    tag_hash,
+                    arweave_tx_id,
+                    origin: ObjectOrigin::Minted,
+                    provenance: &[],
+                    creator: None,
+                    stage: MintStage::Complete,
+                },
+            )
+        });
+        let (first_mint, error_code) = match outcome {
+            Ok(first_mint) => (first_mint, 0),
+            Err(Error::AnchorError(error)) => (false, error.error_code_number),
+            Err(error) => return Err(error),
+        };
+        Ok(MintCheck {
+            ok: error_code == 0,
+            error_code,
+            first_mint,
+            manifest: ctx.accounts.object_manifest.key(),
+            mint: ctx.accounts.object_mint.key(),
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorInput {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// A new object minted by `split_object`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitPart {
+    pub object_id: u64,
+    pub manifest_uri: String,
+    pub manifest_hash: [u8; 32],
+    pub arweave_tx_id: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(namespace: Pubkey)]
+pub struct Initialize<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = Config::LEN,
+        seeds = [CONFIG_SEED, namespace.as_ref()],
+        bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = Auth::LEN,
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = NamespaceRegistry::LEN,
+        seeds = [NAMESPACE_REGISTRY_SEED],
+        bump
+    )]
+    pub namespace_registry: Account<'info, NamespaceRegistry>,
+    /// CHECK: `[b"namespace_registry_page", page]` PDA for the page holding
+    /// the next registry entry, verified and created within the instruction.
+    #[account(mut)]
+    pub registry_page: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct MintObjectNft<'info> {
+    pub base: MintObjectNftBase<'info>,
+    pub metadata: MintObjectNftMetadata<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct MintObjectNftBase<'info> {
+    /// CHECK: The config account enforces this matches its stored authority.
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    /// The minting wallet. It is recorded as the manifest creator and signs
+    /// as the creator and object holder.
+    pub minter: Signer<'info>,
+    /// Funds rent for every account the mint creates. A relayer and a creator
+    /// can split the two roles, or pass the same key for both.
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    /// Manifests created under an older, smaller layout fail the space check
+    /// until `migrate_manifest_v2` grows them. Anchor derives this and
+    /// `object_mint` with `find_program_address` on every call, so re-mints
+    /// do not reuse the bumps stored on the manifest.
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ObjectManifest::LEN,
+        seeds = [MANIFEST_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        mint::decimals = 0,
+        mint::authority = auth,
+        mint::freeze_authority = auth,
+        seeds = [MINT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub object_mint: Box<Account<'info, Mint>>,
+    /// CHECK: The recipient's ATA for `object_mint`, created if missing, or
+    /// once the config allows non-ATA recipients, any token account for the
+    /// mint owned by `recipient`. Verified within the instruction.
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+    /// CHECK: Recipient can be any account, including an off-curve PDA of
+    /// another program such as a vault; it never signs.
+    pub recipient: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    /// CHECK: `[b"creator_record", config, creator]` PDA for the manifest's
+    /// creator, verified and created within the instruction. Required
+    /// alongside `config_stats`.
+    #[account(mut)]
+    pub creator_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"epoch_stats", config, epoch]` PDA for the current epoch,
+    /// verified and created within the instruction. Required alongside
+    /// `config_stats`.
+    #[account(mut)]
+    pub epoch_stats: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"object_index", config, page]` PDA for the page holding the
+    /// next index entry, verified and created within the instruction.
+    /// Required once `enable_object_index` has run for this config.
+    #[account(mut)]
+    pub object_index_page: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"minted_bitmap", config, object_id / 8192]` PDA, verified and
+    /// created within the instruction. Required once `enable_minted_bitmap`
+    /// has run for this config.
+    #[account(mut)]
+    pub minted_bitmap: Option<UncheckedAccount<'info>>,
+    /// Required, with `price_update` and `treasury`, while the config charges
+    /// a USD mint price.
+    #[account(
+        seeds = [MINT_PRICE_SEED, config.key().as_ref()],
+        bump = mint_price.bump
+    )]
+    pub mint_price: Option<Box<Account<'info, MintPrice>>>,
+    /// CHECK: Pyth `PriceUpdateV2` account for the configured feed, verified
+    /// within the instruction.
+    pub price_update: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the treasury stored on `mint_price`.
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    /// Required, with `kyc_attestation`, when the object's category is one
+    /// the config's KYC gate restricts.
+    #[account(
+        seeds = [KYC_GATE_SEED, config.key().as_ref()],
+        bump = kyc_gate.bump
+    )]
+    pub kyc_gate: Option<Box<Account<'info, KycGate>>>,
+    /// CHECK: Solana Attestation Service attestation naming the recipient,
+    /// verified within the instruction.
+    pub kyc_attestation: Option<UncheckedAccount<'info>>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+    /// CHECK: [`ObjectReservation`] PDA for the object id, which usually does
+    /// not exist. A live reservation limits the first mint to its creator.
+    #[account(
+        seeds = [RESERVATION_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_reservation: UncheckedAccount<'info>,
+    /// CHECK: The creator's [`ApprovedCreator`] record, verified within the
+    /// instruction. Required for new manifests while the config's creator
+    /// allowlist is on.
+    pub approved_creator: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct MintCoreObject<'info> {
+    /// CHECK: The config account enforces this matches its stored authority.
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    /// The minting wallet, recorded as the manifest creator.
+    pub minter: Signer<'info>,
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    #[account(
+        init,
+        payer = rent_payer,
+        space = ObjectManifest::LEN,
+        seeds = [MANIFEST_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// CHECK: Created by the Core `CreateV1` CPI, which this PDA signs.
+    #[account(
+        mut,
+        seeds = [CORE_ASSET_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub core_asset: UncheckedAccount<'info>,
+    /// CHECK: Core collection whose update authority must be `auth`, verified
+    /// within the instruction.
+    #[account(mut)]
+    pub core_collection: UncheckedAccount<'info>,
+    /// CHECK: Recipient can be any account; it becomes the asset owner and
+    /// never signs.
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: Must be the Metaplex Core program.
+    #[account(address = MPL_CORE_ID @ ErrorCode::InvalidCoreProgram)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    /// CHECK: `[b"creator_record", config, minter]` PDA, verified and created
+    /// within the instruction. Required alongside `config_stats`.
+    #[account(mut)]
+    pub creator_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"epoch_stats", config, epoch]` PDA, verified and created
+    /// within the instruction. Required alongside `config_stats`.
+    #[account(mut)]
+    pub epoch_stats: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"object_index", config, page]` PDA, verified and created
+    /// within the instruction. Required once `enable_object_index` has run.
+    #[account(mut)]
+    pub object_index_page: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"minted_bitmap", config, object_id / 8192]` PDA, verified and
+    /// created within the instruction. Required once `enable_minted_bitmap`
+    /// has run.
+    #[account(mut)]
+    pub minted_bitmap: Option<UncheckedAccount<'info>>,
+    /// Required, with `price_update` and `treasury`, while the config charges
+    /// a USD mint price.
+    #[account(
+        seeds = [MINT_PRICE_SEED, config.key().as_ref()],
+        bump = mint_price.bump
+    )]
+    pub mint_price: Option<Box<Account<'info, MintPrice>>>,
+    /// CHECK: Pyth `PriceUpdateV2` account for the configured feed, verified
+    /// within the instruction.
+    pub price_update: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the treasury stored on `mint_price`.
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    /// Required, with `kyc_attestation`, when the object's category is one
+    /// the config's KYC gate restricts.
+    #[account(
+        seeds = [KYC_GATE_SEED, config.key().as_ref()],
+        bump = kyc_gate.bump
+    )]
+    pub kyc_gate: Option<Box<Account<'info, KycGate>>>,
+    /// CHECK: Solana Attestation Service attestation naming the recipient,
+    /// verified within the instruction.
+    pub kyc_attestation: Option<UncheckedAccount<'info>>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+    /// CHECK: [`ObjectReservation`] PDA for the object id, which usually does
+    /// not exist. A live reservation limits the first mint to its creator.
+    #[account(
+        seeds = [RESERVATION_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_reservation: UncheckedAccount<'info>,
+    /// CHECK: The creator's [`ApprovedCreator`] record, verified within the
+    /// instruction. Required for new manifests while the config's creator
+    /// allowlist is on.
+    pub approved_creator: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct MintToken2022Object<'info> {
+    /// CHECK: The config account enforces this matches its stored authority.
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    /// The minting wallet, recorded as the manifest creator.
+    pub minter: Signer<'info>,
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+    #[account(
+        init,
+        payer = rent_payer,
+        space = ObjectManifest::LEN,
+        seeds = [MANIFEST_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// CHECK: Created within the instruction with its metadata extensions,
+    /// which this PDA signs for.
+    #[account(
+        mut,
+        seeds = [MINT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub object_mint: UncheckedAccount<'info>,
+    /// CHECK: The recipient's Token-2022 ATA for `object_mint`, verified and
+    /// created within the instruction.
+    #[account(mut)]
+    pub recipient_token_account: UncheckedAccount<'info>,
+    /// CHECK: Recipient can be any account; it never signs.
+    pub recipient: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Must be the companion transfer-hook program. Required, with
+    /// `extra_account_metas`, while the config's transfer hook is enabled.
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: `["extra-account-metas", object_mint]` PDA of the transfer-hook
+    /// program, created by it within the instruction.
+    #[account(mut)]
+    pub extra_account_metas: Option<UncheckedAccount<'info>>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    /// CHECK: `[b"creator_record", config, minter]` PDA, verified and created
+    /// within the instruction. Required alongside `config_stats`.
+    #[account(mut)]
+    pub creator_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"epoch_stats", config, epoch]` PDA, verified and created
+    /// within the instruction. Required alongside `config_stats`.
+    #[account(mut)]
+    pub epoch_stats: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"object_index", config, page]` PDA, verified and created
+    /// within the instruction. Required once `enable_object_index` has run.
+    #[account(mut)]
+    pub object_index_page: Option<UncheckedAccount<'info>>,
+    /// CHECK: `[b"minted_bitmap", config, object_id / 8192]` PDA, verified and
+    /// created within the instruction. Required once `enable_minted_bitmap`
+    /// has run.
+    #[account(mut)]
+    pub minted_bitmap: Option<UncheckedAccount<'info>>,
+    /// Required, with `price_update` and `treasury`, while the config charges
+    /// a USD mint price.
+    #[account(
+        seeds = [MINT_PRICE_SEED, config.key().as_ref()],
+        bump = mint_price.bump
+    )]
+    pub mint_price: Option<Box<Account<'info, MintPrice>>>,
+    /// CHECK: Pyth `PriceUpdateV2` account for the configured feed, verified
+    /// within the instruction.
+    pub price_update: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the treasury stored on `mint_price`.
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+    /// Required, with `kyc_attestation`, when the object's category is one
+    /// the config's KYC gate restricts.
+    #[account(
+        seeds = [KYC_GATE_SEED, config.key().as_ref()],
+        bump = kyc_gate.bump
+    )]
+    pub kyc_gate: Option<Box<Account<'info, KycGate>>>,
+    /// CHECK: Solana Attestation Service attestation naming the recipient,
+    /// verified within the instruction.
+    pub kyc_attestation: Option<UncheckedAccount<'info>>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+    /// CHECK: [`ObjectReservation`] PDA for the object id, which usually does
+    /// not exist. A live reservation limits the first mint to its creator.
+    #[account(
+        seeds = [RESERVATION_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_reservation: UncheckedAccount<'info>,
+    /// CHECK: The creator's [`ApprovedCreator`] record, verified within the
+    /// instruction. Required for new manifests while the config's creator
+    /// allowlist is on.
+    pub approved_creator: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeObjectMint<'info> {
+    /// CHECK: The config account enforces this matches its stored authority.
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Owner, config, and pending flag verified within the instruction.
+    #[account(mut)]
+    pub object_manifest: UncheckedAccount<'info>,
+    /// CHECK: Verified against the manifest within the instruction.
+    #[account(mut)]
+    pub object_mint: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived Metaplex PDA
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Created via Metaplex CPI
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: Only used for PDA derivation and the verify CPI
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived Metaplex PDA
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived Metaplex PDA
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the Metaplex token metadata program id
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct FuseObjects<'info> {
+    pub base: MintObjectNftBase<'info>,
+    pub metadata: MintObjectNftMetadata<'info>,
+}
+
+/// The named mint accounts describe the first part, whose id leads the
+/// instruction data so their seeds can be checked; see `split_object` for the
+/// remaining account layout.
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct SplitObject<'info> {
+    pub base: MintObjectNftBase<'info>,
+    pub metadata: MintObjectNftMetadata<'info>,
+}
+
+#[derive(Accounts)]
+/// Additional remaining accounts expected (in order):
+/// 0. Collection metadata PDA (mut)
+/// 1. Collection master edition PDA (mut)
+/// 2. Rent sysvar account
+/// 3. Instructions sysvar account (optional, unused for unsized collections)
+/// 4. SPL Memo program (optional, required when `Config::memo_on_mint` is set)
+pub struct MintObjectNftMetadata<'info> {
+    #[account(mut)]
+    /// CHECK: Created via Metaplex CPI
+    pub metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Created via Metaplex CPI
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: Verified against expected seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Verified to match the Metaplex token metadata program id
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// The config's [`MetadataDefaults`], checked against the config within
+    /// the instruction. Without it, omitted values are not filled in.
+    pub metadata_defaults: Option<Box<Account<'info, MetadataDefaults>>>,
+    /// The manifest creator's [`CreatorProfile`], checked within the
+    /// instruction. When passed, the creator's royalty share is paid to its
+    /// payout wallet.
+    pub creator_profile: Option<Box<Account<'info, CreatorProfile>>>,
+    /// CHECK: The manifest creator's [`CreatorRoyalty`] PDA, re-derived within
+    /// the instruction, which usually does not exist. When it does, its split
+    /// replaces the seller fee and creators of the first mint.
+    pub creator_royalty: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateCollectionAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    #[account(mut)]
+    /// CHECK: Verified against derived PDA within the instruction
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Only used for PDA derivation
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the Metaplex token metadata program id
+    pub token_metadata_program: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateObjectMetadata<'info> {
+    pub authority: Signer<'info>,
+    /// Becomes, or already is, the metadata's update authority and approves
+    /// the auth PDA as its Data delegate.
+    pub update_authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// CHECK: Must be the manifest's mint.
+    pub object_mint: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived Metaplex PDA
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived delegate record and created by
+    /// Token Metadata.
+    #[account(mut)]
+    pub delegate_record: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the config's token metadata program
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Instructions sysvar, required by Token Metadata's `Delegate`.
+    #[account(address = sysvar::instructions::id() @ ErrorCode::InvalidInstructionsSysvar)]
+    pub instructions: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateCollectionMetadata<'info> {
+    pub authority: Signer<'info>,
+    /// Becomes, or already is, the collection's update authority and
+    /// approves the auth PDA as its Collection delegate.
+    pub update_authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    /// CHECK: Only used for PDA derivation and the delegate CPI
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived Metaplex PDA
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Verified against the derived delegate record and created by
+    /// Token Metadata.
+    #[account(mut)]
+    pub delegate_record: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the config's token metadata program
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Instructions sysvar, required by Token Metadata's `Delegate`.
+    #[account(address = sysvar::instructions::id() @ ErrorCode::InvalidInstructionsSysvar)]
+    pub instructions: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// Arguments for [`process_mint_object`], mirroring the `mint_object_nft`
+/// instruction arguments. Strings and lists are borrowed so instructions that
+/// mint several objects share one copy.
+struct MintObjectArgs<'a> {
+    object_id: u64,
+    manifest_uri: &'a str,
+    manifest_hash: [u8; 32],
+    metadata_name: &'a str,
+    metadata_symbol: &'a str,
+    seller_fee_basis_points: u16,
+    creators: &'a [CreatorInput],
+    hash_algorithm: ManifestHashAlgorithm,
+    category: u16,
+    tag_hash: [u8; 32],
+    arweave_tx_id: [u8; 32],
+    /// How the object came to exist. Anything other than
+    /// [`ObjectOrigin::Minted`] must create a brand new manifest.
+    origin: ObjectOrigin,
+    /// Object ids recorded as the new object's provenance.
+    provenance: &'a [u64],
+    /// Wallet recorded as the creator of a new manifest in place of the fee
+    /// payer, for cranks that mint on another wallet's behalf.
+    creator: Option<Pubkey>,
+    /// Whether a first mint runs to completion or stops before the master
+    /// edition and collection verification.
+    stage: MintStage,
+}
+
+/// How much of a first mint [`process_mint_object`] performs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MintStage {
+    /// Create the metadata, mint the token, create the master edition, and
+    /// verify the collection in one instruction.
+    Complete,
+    /// Stop after minting the token and leave the manifest pending until
+    /// `finalize_object_mint` runs.
+    Prepare,
+}
+
+/// Per-object accounts created or reused by [`process_mint_object`].
+struct ObjectMintTarget<'info> {
+    manifest: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    recipient_token_account: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    /// The object id's [`ObjectReservation`] PDA, which usually does not
+    /// exist.
+    reservation: AccountInfo<'info>,
+    /// `(manifest_bump, mint_bump)` for the named accounts, which Anchor's
+    /// constraints have already derived and created. Remaining-account
+    /// targets leave this unset and are checked by
+    /// [`ensure_remaining_target_accounts`]. The recipient token account is
+    /// always checked by [`ensure_recipient_token_account`].
+    bumps: Option<(u8, u8)>,
+}
+
+impl<'info> ObjectMintTarget<'info> {
+    /// Number of accounts consumed by [`ObjectMintTarget::from_remaining`].
+    const ACCOUNTS_LEN: usize = 6;
+
+    fn from_accounts(
+        base: &MintObjectNftBase<'info>,
+        metadata_accounts: &MintObjectNftMetadata<'info>,
+        bumps: &MintObjectNftBaseBumps,
+    ) -> Self {
+        Self {
+            manifest: base.object_manifest.to_account_info(),
+            mint: base.object_mint.to_account_info(),
+            recipient_token_account: base.recipient_token_account.to_account_info(),
+            metadata: metadata_accounts.metadata.to_account_info(),
+            master_edition: metadata_accounts.master_edition.to_account_info(),
+            reservation: base.object_reservation.to_account_info(),
+            bumps: Some((bumps.object_manifest, bumps.object_mint)),
+        }
+    }
+
+    /// Reads `(manifest, mint, recipient token account, metadata, master
+    /// edition, reservation)` from a slice of remaining accounts.
+    fn from_remaining(accounts: &[AccountInfo<'info>]) -> Self {
+        Self {
+            manifest: accounts[0].clone(),
+            mint: accounts[1].clone(),
+            recipient_token_account: accounts[2].clone(),
+            metadata: accounts[3].clone(),
+            master_edition: accounts[4].clone(),
+            reservation: accounts[5].clone(),
+            bumps: None,
+        }
+    }
+}
+
+/// Creates (or reuses) the manifest, mint, and recipient token account for an
+/// object and mints it, shared by `mint_object_nft` and the instructions that
+/// derive new objects from existing ones.
+///
+/// `remaining_accounts` follows the layout documented on
+/// [`MintObjectNftMetadata`].
+fn process_mint_object<'info>(
+    base: &mut MintObjectNftBase<'info>,
+    metadata_accounts: &MintObjectNftMetadata<'info>,
+    target: ObjectMintTarget<'info>,
+    program_id: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    args: MintObjectArgs<'_>,
+) -> Result<MintResult> {
+    let MintObjectArgs {
+        object_id,
+        manifest_uri,
+        manifest_hash,
+        metadata_name,
+        metadata_symbol,
+        seller_fee_basis_points,
+        creators,
+        hash_algorithm,
+        category,
+        tag_hash,
+        arweave_tx_id,
+        origin,
+        provenance,
+        creator,
+        stage,
+    } = args;
+    let (
+        collection_metadata_account,
+        collection_master_edition_account,
+        rent_sysvar_account,
+        instructions_sysvar_account,
+        memo_program_account,
+        changelog_remaining_accounts,
+        creator_remaining_accounts,
+    ) = metadata_remaining_accounts(remaining_accounts)?;
+    require!(
+        collection_metadata_account.is_writable,
+        ErrorCode::InvalidCollectionMetadataAccount
+    );
+    require!(
+        collection_master_edition_account.is_writable,
+        ErrorCode::InvalidCollectionMasterEditionAccount
+    );
+    require_distinct_accounts(&[
+        ("manifest", target.manifest.key()),
+        ("object_mint", target.mint.key()),
+        (
+            "recipient_token_account",
+            target.recipient_token_account.key(),
+        ),
+        ("metadata", target.metadata.key()),
+        ("master_edition", target.master_edition.key()),
+        ("collection_metadata", collection_metadata_account.key()),
+        (
+            "collection_master_edition",
+            collection_master_edition_account.key(),
+        ),
+    ])?;
+
+    require!(!base.config.load()?.paused(), ErrorCode::MintingPaused);
+    require_not_globally_paused(&base.global_pause)?;
+    base.config
+        .load()?
+        .require_instruction_enabled(INSTRUCTION_MINT)?;
+
+    let config_key = base.config.key();
+    let payer_key = base.minter.key();
+    let creator_key = creator.unwrap_or(payer_key);
+    let rent_payer_account_info = base.rent_payer.to_account_info();
+    let system_program_account_info = base.system_program.to_account_info();
+    let token_program_account_info = base.token_program.to_account_info();
+    let auth_account_info = base.auth.to_account_info();
+
+    let manifest_key = target.manifest.key();
+    let mint_key = target.mint.key();
+    let (manifest_bump, object_mint_bump) = match target.bumps {
+        Some(bumps) => bumps,
+        None => ensure_remaining_target_accounts(&target, base, program_id, object_id)?,
+    };
+    let non_ata_recipients = base.config.load()?.non_ata_recipients();
+    ensure_recipient_token_account(&target, base, non_ata_recipients)?;
+
+    require_match!(
+        "rent",
+        rent_sysvar_account.key(),
+        sysvar::rent::id(),
+        ErrorCode::InvalidRentSysvar
+    );
+    if let Some(ref account) = instructions_sysvar_account {
+        require_match!(
+            "instructions_sysvar",
+            account.key(),
+            sysvar::instructions::id(),
+            ErrorCode::InvalidInstructionsSysvar
+        );
+    }
+
+    let manifest_info = &target.manifest;
+    let object_mint_info = &target.mint;
+    let recipient_token_account_info = &target.recipient_token_account;
+
+    let mut increment_object_count = false;
+    let was_minted;
+    let stored_manifest_uri: String;
+    let manifest_creator: Pubkey;
+    let manifest_category: u16;
+    {
+        let mut data = manifest_info.try_borrow_mut_data()?;
+        require!(
+            data.len() >= ObjectManifest::LEN,
+            ErrorCode::ManifestAccountTooSmall
+        );
+        let (disc_bytes, rest) = data.split_at_mut(8);
+        if disc_bytes != ObjectManifest::discriminator() {
+            disc_bytes.copy_from_slice(&ObjectManifest::discriminator());
+        }
+        let manifest_slice = &mut rest[..core::mem::size_of::<ObjectManifest>()];
+        let manifest = from_bytes_mut::<ObjectManifest>(manifest_slice);
+
+        was_minted = manifest.minted();
+        require!(!manifest.mint_pending(), ErrorCode::MintPending);
+        require!(
+            stage == MintStage::Complete || !was_minted,
+            ErrorCode::ObjectAlreadyMinted
+        );
+
+        if !manifest.initialized() {
+            require!(
+                base.config.load()?.asset_backend()? == AssetBackend::TokenMetadata,
+                ErrorCode::WrongAssetBackend
+            );
+            require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+            require!(
+                manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
+                ErrorCode::UriTooLong
+            );
+            validate_uri_rules(&base.config.load()?, manifest_uri)?;
+
+            manifest.config = config_key;
+            manifest.object_id = object_id;
+            manifest.mint = mint_key;
+            manifest.bump = manifest_bump;
+            manifest.layout_version = MANIFEST_LAYOUT_VERSION;
+            manifest.mint_bump = object_mint_bump;
+            manifest.set_is_active(true);
+            manifest.set_initialized(true);
+            manifest.set_minted(false);
+            manifest.manifest_hash = manifest_hash;
+            manifest.set_hash_algorithm(hash_algorithm);
+            manifest.set_metadata_uri(manifest_uri);
+            manifest.set_content_addressed(validate_content_address(manifest_uri)?);
+            validate_arweave_tx_id(manifest_uri, &arweave_tx_id)?;
+            manifest.arweave_tx_id = arweave_tx_id;
+            manifest.creator = creator_key;
+            manifest.rent_payer = base.rent_payer.key();
+            let slot = Clock::get()?.slot;
+            manifest.created_slot = slot;
+            manifest.history_chain = next_history_chain(&[0u8; 32], &manifest_hash, slot);
+            require!(
+                base.config.load()?.is_category_enabled(category),
+                ErrorCode::CategoryNotEnabled
+            );
+            manifest.category = category;
+            manifest.tag_hash = tag_hash;
+            manifest.set_origin(origin);
+            manifest.set_provenance(provenance);
+            increment_object_count = true;
+        } else {
+            require!(
+                origin == ObjectOrigin::Minted,
+                ErrorCode::ObjectAlreadyExists
+            );
+            require!(
+                manifest.is_active_at(Clock::get()?.unix_timestamp),
+                ErrorCode::ObjectInactive
+            );
+            require_match!(
+                "object_id",
+                object_id,
+                manifest.object_id,
+                ErrorCode::ObjectIdMismatch
+            );
+            require_match!(
+                "config",
+                config_key,
+                manifest.config,
+                ErrorCode::InvalidConfig
+            );
+            require_match!("mint", mint_key, manifest.mint, ErrorCode::MintMismatch);
+            require_match!(
+                "manifest_hash",
+                Hex(&manifest_hash),
+                Hex(&manifest.manifest_hash),
+                ErrorCode::ManifestMismatch
+            );
+            require_match!(
+                "hash_algorithm",
+                hash_algorithm as u8,
+                manifest.hash_algorithm,
+                ErrorCode::ManifestMismatch
+            );
+            require!(
+                manifest.metadata_uri_len() <= METADATA_MAX_URI_LENGTH,
+                ErrorCode::UriTooLong
+            );
+            require!(
+                manifest.metadata_uri_is_valid(),
+                ErrorCode::ManifestUriCorrupted
+            );
+            if !manifest_uri.is_empty() {
+                require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+                require!(
+                    manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
+                    ErrorCode::UriTooLong
+                );
+                require_match!(
+                    "metadata_uri",
+                    manifest_uri,
+                    manifest.metadata_uri_string().as_str(),
+                    ErrorCode::ManifestMismatch
+                );
+            }
+        }
+
+        manifest_creator = manifest.creator;
+        manifest_category = manifest.category;
+        stored_manifest_uri = manifest.metadata_uri_string();
+    }
+
+    if increment_object_count {
+        require_object_reservation(&target.reservation, &creator_key)?;
+        require_approved_creator(
+            &base.config.load()?,
+            &config_key,
+            base.approved_creator.as_ref(),
+            &creator_key,
+        )?;
+        record_new_object(&mut base.tracking(), program_id, object_id, &creator_key)?;
+    }
+
+    let is_first_mint = !was_minted;
+    let (metadata_symbol, seller_fee_basis_points, creators) = apply_metadata_defaults(
+        metadata_accounts.metadata_defaults.as_deref(),
+        &config_key,
+        &manifest_creator,
+        metadata_symbol,
+        seller_fee_basis_points,
+        creators,
+    )?;
+    let (seller_fee_basis_points, creators) = if is_first_mint {
+        apply_creator_royalty(
+            load_creator_royalty(
+                &metadata_accounts.creator_royalty,
+                &config_key,
+                &manifest_creator,
+            )?
+            .as_ref(),
+            &manifest_creator,
+            seller_fee_basis_points,
+            creators,
+        )
+    } else {
+        (seller_fee_basis_points, creators)
+    };
+    let (metadata_symbol, creators) = (metadata_symbol.as_str(), creators.as_slice());
+
+    let recipient_mint = anchor_spl::token::accessor::mint(recipient_token_account_info)?;
+    require_match!(
+        "recipient_mint",
+        recipient_mint,
+        mint_key,
+        ErrorCode::MintMismatch
+    );
+    let recipient_owner = anchor_spl::token::accessor::authority(recipient_token_account_info)?;
+    require_match!(
+        "recipient_owner",
+        recipient_owner,
+        base.recipient.key(),
+        ErrorCode::RecipientMismatch
+    );
+
+    let signer_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[base.auth.bump]];
+    let auth_seeds = &[signer_seeds];
+
+    let mut signer_keys: HashSet<Pubkey> = HashSet::new();
+    signer_keys.insert(payer_key);
+    for account in creator_remaining_accounts {
+        if account.is_signer {
+            signer_keys.insert(account.key());
+        }
+    }
+
+    if is_first_mint {
+        require!(
+            metadata_name.as_bytes().len() <= MAX_NAME_LENGTH,
+            ErrorCode::MetadataNameTooLong
+        );
+        require!(
+            metadata_symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
+            ErrorCode::MetadataSymbolTooLong
+        );
+        require!(
+            seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        let metadata_program = base.config.load()?.metadata_program();
+        require_match!(
+            "metadata_program",
+            metadata_accounts.token_metadata_program.key(),
+            metadata_program,
+            ErrorCode::InvalidTokenMetadataProgram
+        );
+
+        validate_creators(creators, &base.auth.key(), &manifest_creator)?;
+        let creators = apply_creator_profile(
+            creators,
+            metadata_accounts.creator_profile.as_deref(),
+            &config_key,
+            &manifest_creator,
+            &base.auth.key(),
+        )?;
+
+        let collection_mint_key = metadata_accounts.collection_mint.key();
+        require_metaplex_pdas(
+            &metadata_program,
+            &mint_key,
+            &target.metadata.key(),
+            &target.master_edition.key(),
+            &collection_mint_key,
+            &collection_metadata_account.key(),
+            &collection_master_edition_account.key(),
+        )?;
+
+        let metadata_creators: Vec<MetadataCreator> = creators
+            .iter()
+            .map(|creator| -> Result<MetadataCreator> {
+                if creator.verified {
+                    require!(
+                        signer_keys.contains(&creator.address),
+                        ErrorCode::CreatorMustSign
+                    );
+                }
+                Ok(MetadataCreator {
+                    address: to_solana_pubkey(&creator.address),
+                    verified: creator.verified && signer_keys.contains(&creator.address),
+                    share: creator.share,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let data = DataV2 {
+            name: metadata_name.to_owned(),
+            symbol: metadata_symbol.to_owned(),
+            uri: stored_manifest_uri.clone(),
+            seller_fee_basis_points,
+            creators: Some(metadata_creators),
+            collection: Some(Collection {
+                key: to_solana_pubkey(&collection_mint_key),
+                verified: false,
+            }),
+            uses: None,
+        };
+
+        let metadata_program_info = metadata_accounts.token_metadata_program.to_account_info();
+
+        let mut creator_account_infos: Vec<(&AccountInfo<'info>, bool, bool)> =
+            Vec::with_capacity(creator_remaining_accounts.len());
+        for account in creator_remaining_accounts {
+            creator_account_infos.push((account, account.is_signer, account.is_writable));
+        }
+
+        CreateMetadataAccountV3Cpi::new(
+            &metadata_program_info,
+            CreateMetadataAccountV3CpiAccounts {
+                metadata: &target.metadata,
+                mint: object_mint_info,
+                mint_authority: &auth_account_info,
+                payer: &rent_payer_account_info,
+                update_authority: (&auth_account_info, true),
+                system_program: &system_program_account_info,
+                rent: Some(&rent_sysvar_account),
+            },
+            CreateMetadataAccountV3InstructionArgs {
+                data,
+                is_mutable: true,
+                collection_details: Option::<CollectionDetails>::None,
+            },
+        )
+        .invoke_signed_with_remaining_accounts(auth_seeds, &creator_account_infos)
+        .map_err(anchor_lang::error::Error::from)?;
+    }
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program_account_info.clone(),
+            MintTo {
+                mint: object_mint_info.clone(),
+                to: recipient_token_account_info.clone(),
+                authority: auth_account_info.clone(),
+            },
+            auth_seeds,
+        ),
+        1,
+    )?;
+    {
+        let recipient = base.recipient.key();
+        let mut tracking = base.tracking();
+        record_object_mint(&mut tracking, program_id, object_id)?;
+        if origin == ObjectOrigin::Minted {
+            charge_mint_price(&tracking, manifest_key, object_id)?;
+        }
+        check_kyc_gate(&tracking, manifest_category, &recipient)?;
+    }
+    log_mint_memo(
+        &base.config,
+        memo_program_account,
+        object_id,
+        &manifest_hash,
+    )?;
+
+    if is_first_mint && stage == MintStage::Complete {
+        finalize_first_mint(
+            FirstMintFinalizeAccounts {
+                metadata_program: &metadata_accounts.token_metadata_program.to_account_info(),
+                metadata: &target.metadata,
+                master_edition: &target.master_edition,
+                mint: object_mint_info,
+                auth: &auth_account_info,
+                payer: &rent_payer_account_info,
+                token_program: &token_program_account_info,
+                system_program: &system_program_account_info,
+                rent: &rent_sysvar_account,
+                collection_mint: &metadata_accounts.collection_mint.to_account_info(),
+                collection_metadata: &collection_metadata_account,
+                collection_master_edition: &collection_master_edition_account,
+                instructions: instructions_sysvar_account.as_ref(),
+                extra_accounts: creator_remaining_accounts,
+            },
+            auth_seeds,
+        )?;
+    }
+
+    let leaf = {
+        let mut data = manifest_info.try_borrow_mut_data()?;
+        let (_, rest) = data.split_at_mut(8);
+        let manifest =
+            from_bytes_mut::<ObjectManifest>(&mut rest[..core::mem::size_of::<ObjectManifest>()]);
+        if stage == MintStage::Prepare {
+            manifest.set_mint_pending(true);
+        } else {
+            manifest.set_minted(true);
+        }
+        manifest.owner = base.recipient.key();
+        if is_first_mint {
+            manifest.set_display_data(metadata_name, metadata_symbol);
+        }
+        changelog_leaf(&manifest_key, manifest, Clock::get()?.slot)
+    };
+
+    if stage == MintStage::Prepare {
+        log_record(
+            "mint_prepare",
+            &[&config_key, &object_id, &mint_key, &base.recipient.key()],
+        );
+        return Ok(MintResult {
+            mint: mint_key,
+            manifest: manifest_key,
+            object_id,
+            first_mint: true,
+        });
+    }
+
+    append_changelog_leaf(&base.config, changelog_remaining_accounts.as_ref(), leaf)?;
+
+    emit_logged!(ObjectMinted {
+        config: config_key,
+        manifest: manifest_key,
+        mint: mint_key,
+        recipient: base.recipient.key(),
+        object_id,
+        manifest_hash,
+        hash_algorithm: hash_algorithm as u8,
+        metadata_uri: stored_manifest_uri,
+        creator: manifest_creator,
+        context: EventContext::new(base.minter.key(), base.authority.key())?,
+    });
+
+    Ok(MintResult {
+        mint: mint_key,
+        manifest: manifest_key,
+        object_id,
+        first_mint: is_first_mint,
+    })
+}
+
+/// Accounts shared by `delegate_object_metadata` and
+/// `delegate_collection_metadata`.
+struct MetadataDelegation<'a, 'info> {
+    config: &'a AccountLoader<'info, Config>,
+    auth: &'a Account<'info, Auth>,
+    update_authority: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    delegate_record: AccountInfo<'info>,
+    metadata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    instructions: AccountInfo<'info>,
+    authority: Pubkey,
+    audit_log: Option<&'a AccountLoader<'info, AuditLog>>,
+}
+
+/// Moves the metadata's update authority from the auth PDA to the signing
+/// `update_authority`, if it is not there already, then has the new update
+/// authority approve the auth PDA as a `scope` delegate.
+fn approve_auth_delegate(accounts: MetadataDelegation, scope: MetadataDelegateScope) -> Result<()> {
+    let config_key = accounts.config.key();
+    let auth_key = accounts.auth.key();
+    let update_authority = accounts.update_authority.key();
+    let metadata_program = accounts.config.load()?.metadata_program();
+    require_match!(
+        "metadata_program",
+        accounts.metadata_program.key(),
+        metadata_program,
+        ErrorCode::InvalidTokenMetadataProgram
+    );
+    require_match!(
+        "metadata",
+        accounts.metadata.key(),
+        find_metadata_pda(&accounts.mint.key(), &metadata_program),
+        ErrorCode::InvalidMetadataAccount
+    );
+    let delegate_record = find_metadata_delegate_record(
+        &metadata_program,
+        &accounts.mint.key(),
+        scope,
+        &update_authority,
+        &auth_key,
+    );
+    require_match!(
+        "delegate_record",
+        accounts.delegate_record.key(),
+        delegate_record,
+        ErrorCode::InvalidMetadataDelegateRecord
+    );
+
+    let previous_update_authority = {
+        let data = accounts
+            .metadata
+            .try_borrow_data()
+            .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
+        let metadata = MetadataAccount::safe_deserialize(&data)
+            .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
+        Pubkey::new_from_array(metadata.update_authority.to_bytes())
+    };
+    if previous_update_authority == auth_key {
+        let auth_seeds: &[&[u8]] = &[AUTH_SEED, config_key.as_ref(), &[accounts.auth.bump]];
+        UpdateMetadataAccountV2Cpi::new(
+            &accounts.metadata_program,
+            UpdateMetadataAccountV2CpiAccounts {
+                metadata: &accounts.metadata,
+                update_authority: &accounts.auth.to_account_info(),
+            },
+            UpdateMetadataAccountV2InstructionArgs {
+                data: None,
+                new_update_authority: Some(to_solana_pubkey(&update_authority)),
+                primary_sale_happened: None,
+                is_mutable: None,
+            },
+        )
+        .invoke_signed(&[auth_seeds])
+        .map_err(anchor_lang::error::Error::from)?;
+    } else {
+        require_match!(
+            "update_authority",
+            update_authority,
+            previous_update_authority,
+            ErrorCode::InvalidMetadataUpdateAuthority
+        );
+    }
+
+    let auth_info = accounts.auth.to_account_info();
+    match scope {
+        MetadataDelegateScope::Data => DelegateDataV1Cpi::new(
+            &accounts.metadata_program,
+            DelegateDataV1CpiAccounts {
+                delegate_record: Some(&accounts.delegate_record),
+                delegate: &auth_info,
+                metadata: &accounts.metadata,
+                master_edition: None,
+                token_record: None,
+                mint: &accounts.mint,
+                token: None,
+                authority: &accounts.update_authority,
+                payer: &accounts.payer,
+                system_program: &accounts.system_program,
+                sysvar_instructions: &accounts.instructions,
+                spl_token_program: None,
+                authorization_rules_program: None,
+                authorization_rules: None,
+            },
+            DelegateDataV1InstructionArgs {
+                authorization_data: None,
+            },
+        )
+        .invoke(),
+        MetadataDelegateScope::Collection => DelegateCollectionV1Cpi::new(
+            &accounts.metadata_program,
+            DelegateCollectionV1CpiAccounts {
+                delegate_record: Some(&accounts.delegate_record),
+                delegate: &auth_info,
+                metadata: &accounts.metadata,
+                master_edition: None,
+                token_record: None,
+                mint: &accounts.mint,
+                token: None,
+                authority: &accounts.update_authority,
+                payer: &accounts.payer,
+                system_program: &accounts.system_program,
+                sysvar_instructions: &accounts.instructions,
+                spl_token_program: None,
+                authorization_rules_program: None,
+                authorization_rules: None,
+            },
+            DelegateCollectionV1InstructionArgs {
+                authorization_data: None,
+            },
+        )
+        .invoke(),
+    }
+    .map_err(anchor_lang::error::Error::from)?;
+
+    emit_audited!(
+        accounts.audit_log,
+        MetadataDelegateApproved {
+            config: config_key,
+            mint: accounts.mint.key(),
+            scope: scope as u8,
+            previous_update_authority,
+            update_authority,
+            delegate_record,
+            context: EventContext::new(accounts.payer.key(), accounts.authority)?,
+        }
+    );
+
+    Ok(())
+}
+
+/// Rejects writable mint accounts that alias one another. The address checks
+/// pin each account individually, but the collection accounts arrive through
+/// the unchecked remaining accounts, so aliasing is ruled out up front.
+fn require_distinct_accounts(accounts: &[(&str, Pubkey)]) -> Result<()> {
+    for (index, (name, key)) in accounts.iter().enumerate() {
+        if let Some((other, _)) = accounts[..index]
+            .iter()
+            .find(|(_, other_key)| other_key == key)
+        {
+            msg!("duplicate account {} passed as {} and {}", key, other, name);
+            return Err(ErrorCode::DuplicateMintAccount.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks the object and collection Metaplex PDAs used by a first mint,
+/// derived under the config's token metadata program.
+fn require_metaplex_pdas(
+    metadata_program: &Pubkey,
+    mint: &Pubkey,
+    metadata: &Pubkey,
+    master_edition: &Pubkey,
+    collection_mint: &Pubkey,
+    collection_metadata: &Pubkey,
+    collection_master_edition: &Pubkey,
+) -> Result<()> {
+    require_match!(
+        "metadata",
+        *metadata,
+        find_metadata_pda(mint, metadata_program),
+        ErrorCode::InvalidMetadataAccount
+    );
+    require_match!(
+        "master_edition",
+        *master_edition,
+        find_master_edition_pda(mint, metadata_program),
+        ErrorCode::InvalidMasterEditionAccount
+    );
+    require_match!(
+        "collection_metadata",
+        *collection_metadata,
+        find_metadata_pda(collection_mint, metadata_program),
+        ErrorCode::InvalidCollectionMetadataAccount
+    );
+    require_match!(
+        "collection_master_edition",
+        *collection_master_edition,
+        find_master_edition_pda(collection_mint, metadata_program),
+        ErrorCode::InvalidCollectionMasterEditionAccount
+    );
+    Ok(())
+}
+
+/// Accounts for the CPIs that complete a first mint.
+/// New manifest state applied by `update_object_manifest` and
+/// `execute_scheduled_update`.
+struct ManifestUpdateArgs {
+    manifest_hash: [u8; 32],
+    metadata_uri: String,
+    is_active: bool,
+    hash_algorithm: u8,
+    arweave_tx_id: [u8; 32],
+}
+
+/// Accounts a manifest update touches. `actor` is the holder the update is
+/// made for, already checked by the caller; `payer` funds any manifest
+/// growth.
+struct ManifestUpdateAccounts<'a, 'info> {
+    actor: Pubkey,
+    payer: AccountInfo<'info>,
+    config: &'a AccountLoader<'info, Config>,
+    auth: &'a Account<'info, Auth>,
+    object_manifest: &'a AccountLoader<'info, ObjectManifest>,
+    object_mint: AccountInfo<'info>,
+    metadata: ObjectMetadataAccounts<'info>,
+    instructions: Option<AccountInfo<'info>>,
+    system_program: AccountInfo<'info>,
+    manifest_history: Option<&'a AccountLoader<'info, ManifestHistory>>,
+    config_stats: &'a mut Option<Account<'info, ConfigStats>>,
+    global_pause: &'a AccountInfo<'info>,
+}
+
+/// Where a manifest update pushes the new URI, by the object's
+/// [`AssetBackend`].
+enum ObjectMetadataAccounts<'info> {
+    /// The object's Token Metadata account.
+    TokenMetadata {
+        metadata: AccountInfo<'info>,
+        metadata_program: AccountInfo<'info>,
+        rent: Pubkey,
+    },
+    /// The token metadata extension on the object's Token-2022 mint.
+    Token2022 { token_program: AccountInfo<'info> },
+}
+
+impl ObjectMetadataAccounts<'_> {
+    fn backend(&self) -> AssetBackend {
+        match self {
+            Self::TokenMetadata { .. } => AssetBackend::TokenMetadata,
+            Self::Token2022 { .. } => AssetBackend::Token2022,
+        }
+    }
+}
+
+/// Writes a new manifest hash, URI, and active flag, records history and the
+/// changelog leaf, and pushes the URI to the object's metadata.
+fn process_manifest_update<'info>(
+    accounts: ManifestUpdateAccounts<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    args: ManifestUpdateArgs,
+) -> Result<()> {
+    let ManifestUpdateArgs {
+        manifest_hash,
+        metadata_uri,
+        is_active,
+        hash_algorithm,
+        arweave_tx_id,
+    } = args;
+    require_not_globally_paused(accounts.global_pause)?;
+    accounts
+        .config
+        .load()?
+        .require_instruction_enabled(INSTRUCTION_UPDATE)?;
+    let hash_algorithm = ManifestHashAlgorithm::try_from(hash_algorithm)?;
+    require!(metadata_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+    validate_arweave_tx_id(&metadata_uri, &arweave_tx_id)?;
+    require!(
+        metadata_uri.len() <= METADATA_MAX_URI_LENGTH,
+        ErrorCode::UriTooLong
+    );
+    validate_uri_rules(&accounts.config.load()?, &metadata_uri)?;
+
+    let metadata_program = accounts.config.load()?.metadata_program();
+    if let ObjectMetadataAccounts::TokenMetadata {
+        metadata_program: program,
+        rent,
+        ..
+    } = &accounts.metadata
+    {
+        require_match!(
+            "metadata_program",
+            program.key(),
+            metadata_program,
+            ErrorCode::InvalidTokenMetadataProgram
+        );
+        require_match!(
+            "rent",
+            *rent,
+            sysvar::rent::id(),
+            ErrorCode::InvalidRentSysvar
+        );
+    }
+    if let Some(instructions_sysvar) = &accounts.instructions {
+        require_match!(
+            "instructions_sysvar",
+            instructions_sysvar.key(),
+            sysvar::instructions::id(),
+            ErrorCode::InvalidInstructionsSysvar
+        );
+    }
+
+    let manifest_info = accounts.object_manifest.to_account_info();
+    ensure_manifest_capacity(&manifest_info, &accounts.payer, &accounts.system_program)?;
+    let mut manifest = accounts.object_manifest.load_mut()?;
+
+    require!(manifest.initialized(), ErrorCode::ManifestNotInitialized);
+    require_match!(
+        "config",
+        accounts.config.key(),
+        manifest.config,
+        ErrorCode::InvalidConfig
+    );
+    require!(
+        !manifest.locked()
+            || (manifest.manifest_hash == manifest_hash
+                && manifest.hash_algorithm()? == hash_algorithm
+                && manifest.metadata_uri_equals(&metadata_uri)
+                && manifest.arweave_tx_id == arweave_tx_id),
+        ErrorCode::ManifestLocked
+    );
+
+    let (expected_manifest_key, expected_manifest_bump) = Pubkey::find_program_address(
+        &[
+            MANIFEST_SEED,
+            accounts.config.key().as_ref(),
+            &manifest.object_id.to_le_bytes(),
+        ],
+        &crate::ID,
+    );
+    require_match!(
+        "manifest",
+        manifest_info.key(),
+        expected_manifest_key,
+        ErrorCode::InvalidConfig
+    );
+    require!(
+        manifest.bump == expected_manifest_bump,
+        ErrorCode::InvalidConfig
+    );
+    require_match!(
+        "mint",
+        accounts.object_mint.key(),
+        manifest.mint,
+        ErrorCode::MintMismatch
+    );
+
+    require!(
+        manifest.asset_backend()? == accounts.metadata.backend(),
+        ErrorCode::WrongAssetBackend
+    );
+
+    let mint_key = accounts.object_mint.key();
+    if let ObjectMetadataAccounts::TokenMetadata { metadata, .. } = &accounts.metadata {
+        require_match!(
+            "metadata",
+            metadata.key(),
+            find_metadata_pda(&mint_key, &metadata_program),
+            ErrorCode::InvalidMetadataAccount
+        );
+    }
+
+    let clock = Clock::get()?;
+    require!(
+        !is_active || !manifest.is_expired(clock.unix_timestamp),
+        ErrorCode::ObjectExpired
+    );
+
+    let slot = clock.slot;
+    let previous_history_chain = manifest.history_chain;
+    let history_chain = next_history_chain(&previous_history_chain, &manifest_hash, slot);
+
+    if manifest.manifest_hash != manifest_hash || manifest.hash_algorithm()? != hash_algorithm {
+        manifest.set_verified_on_chain(false);
+    }
+    if manifest.manifest_hash != manifest_hash || !manifest.metadata_uri_equals(&metadata_uri) {
+        manifest.set_uri_live(false);
+    }
+    let previous_uri_len = manifest.metadata_uri_len();
+    manifest.manifest_hash = manifest_hash;
+    manifest.set_hash_algorithm(hash_algorithm);
+    manifest.set_metadata_uri(&metadata_uri);
+    manifest.set_content_addressed(validate_content_address(&metadata_uri)?);
+    manifest.arweave_tx_id = arweave_tx_id;
+    manifest.set_is_active(is_active);
+    manifest.history_chain = history_chain;
+
+    let history_enabled = manifest.history_enabled();
+    let display_data = manifest.has_display_data().then(|| {
+        (
+            manifest.metadata_name_string(),
+            manifest.metadata_symbol_string(),
+        )
+    });
+    let config_key = manifest.config;
+    let config_account_key = accounts.config.key();
+    let manifest_mint = manifest.mint;
+    let manifest_creator = manifest.creator;
+    let object_id = manifest.object_id;
+    let manifest_pubkey = manifest_info.key();
+    let leaf = changelog_leaf(&manifest_pubkey, &manifest, slot);
+
+    drop(manifest);
+
+    if history_enabled {
+        let history = accounts
+            .manifest_history
+            .ok_or(ErrorCode::ManifestHistoryRequired)?;
+        let mut history = history.load_mut()?;
+        require_match!(
+            "manifest_history",
+            manifest_pubkey,
+            history.manifest,
+            ErrorCode::InvalidManifestHistoryAccount
+        );
+        history.record(ManifestHistoryEntry {
+            slot,
+            manifest_hash,
+            uri_hash: hash(metadata_uri.as_bytes()).to_bytes(),
+            actor: accounts.actor,
+        });
+    }
+
+    let auth_info = accounts.auth.to_account_info();
+    let auth_seeds: &[&[u8]] = &[
+        AUTH_SEED,
+        config_account_key.as_ref(),
+        &[accounts.auth.bump],
+    ];
+    match &accounts.metadata {
+        ObjectMetadataAccounts::TokenMetadata {
+            metadata,
+            metadata_program: metadata_program_info,
+            ..
+        } => {
+            let metadata_info = metadata.clone();
+            let metadata_account = {
+                let metadata_data = metadata_info
+                    .try_borrow_data()
+                    .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
+                let metadata = MetadataAccount::safe_deserialize(&metadata_data)
+                    .map_err(|_| Error::from(ErrorCode::InvalidMetadataAccount))?;
+                drop(metadata_data);
+                metadata
+            };
+
+            let mut data = DataV2 {
+                name: metadata_account.name.clone(),
+                symbol: metadata_account.symbol.clone(),
+                uri: metadata_account.uri.clone(),
+                seller_fee_basis_points: metadata_account.seller_fee_basis_points,
+                creators: metadata_account.creators.clone(),
+                collection: metadata_account.collection.clone(),
+                uses: metadata_account.uses.clone(),
+            };
+            data.uri = metadata_uri.clone();
+            if let Some((name, symbol)) = display_data {
+                data.name = name;
+                data.symbol = symbol;
+            }
+
+            let update_authority =
+                Pubkey::new_from_array(metadata_account.update_authority.to_bytes());
+            if update_authority == accounts.auth.key() {
+                UpdateMetadataAccountV2Cpi::new(
+                    metadata_program_info,
+                    UpdateMetadataAccountV2CpiAccounts {
+                        metadata: &metadata_info,
+                        update_authority: &auth_info,
+                    },
+                    UpdateMetadataAccountV2InstructionArgs {
+                        data: Some(data),
+                        new_update_authority: None,
+                        primary_sale_happened: None,
+                        is_mutable: None,
+                    },
+                )
+                .invoke_signed(&[auth_seeds])
+                .map_err(anchor_lang::error::Error::from)?;
+            } else {
+                // An external update authority owns the metadata; the auth PDA
+                // updates it through its Data delegate record.
+                let delegate_record = find_metadata_delegate_record(
+                    &metadata_program,
+                    &mint_key,
+                    MetadataDelegateScope::Data,
+                    &update_authority,
+                    &accounts.auth.key(),
+                );
+                let delegate_record = remaining_accounts
+                    .iter()
+                    .find(|account| account.key() == delegate_record)
+                    .ok_or(ErrorCode::MetadataDelegateRequired)?;
+                let instructions = accounts
+                    .instructions
+                    .as_ref()
+                    .ok_or(ErrorCode::MetadataDelegateRequired)?;
+                UpdateAsDataDelegateV2Cpi::new(
+                    metadata_program_info,
+                    UpdateAsDataDelegateV2CpiAccounts {
+                        authority: &auth_info,
+                        delegate_record: Some(delegate_record),
+                        token: None,
+                        mint: &accounts.object_mint,
+                        metadata: &metadata_info,
+                        edition: None,
+                        payer: &accounts.payer,
+                        system_program: &accounts.system_program,
+                        sysvar_instructions: instructions,
+                        authorization_rules_program: None,
+                        authorization_rules: None,
+                    },
+                    UpdateAsDataDelegateV2InstructionArgs {
+                        data: Some(Data {
+                            name: data.name,
+                            symbol: data.symbol,
+                            uri: data.uri,
+                            seller_fee_basis_points: data.seller_fee_basis_points,
+                            creators: data.creators,
+                        }),
+                        authorization_data: None,
+                    },
+                )
+                .invoke_signed(&[auth_seeds])
+                .map_err(anchor_lang::error::Error::from)?;
+            }
+        }
+        ObjectMetadataAccounts::Token2022 { token_program } => {
+            // Token-2022 reallocates the mint for a longer URI but does not
+            // fund it, so top the mint up to the new rent-exempt balance.
+            let mint_info = &accounts.object_mint;
+            let grown_len =
+                mint_info.data_len() + metadata_uri.len().saturating_sub(previous_uri_len);
+            let shortfall = Rent::get()?
+                .minimum_balance(grown_len)
+                .saturating_sub(mint_info.lamports());
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        accounts.system_program.clone(),
+                        system_program::Transfer {
+                            from: accounts.payer.clone(),
+                            to: mint_info.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            token_2022_extensions::token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    token_2022_extensions::TokenMetadataUpdateField {
+                        token_program_id: token_program.clone(),
+                        metadata: mint_info.clone(),
+                        update_authority: auth_info,
+                    },
+                    &[auth_seeds],
+                ),
+                TokenMetadataField::Uri,
+                metadata_uri.clone(),
+            )?;
+        }
+    }
+
+    record_config_stats(accounts.config, accounts.config_stats, |stats| {
+        stats.total_updates = stats.total_updates.saturating_add(1)
+    })?;
+    append_changelog_leaf(
+        accounts.config,
+        changelog_accounts(remaining_accounts).as_ref(),
+        leaf,
+    )?;
+
+    emit_logged!(ManifestUpdated {
+        config: config_key,
+        manifest: manifest_pubkey,
+        mint: manifest_mint,
+        object_id,
+        is_active,
+        manifest_hash,
+        hash_algorithm: hash_algorithm as u8,
+        metadata_uri,
+        creator: manifest_creator,
+        previous_history_chain,
+        history_chain,
+        slot,
+        context: EventContext::new(accounts.payer.key(), accounts.actor)?,
+    });
+
+    Ok(())
+}
+
+struct FirstMintFinalizeAccounts<'a, 'info> {
+    metadata_program: &'a AccountInfo<'info>,
+    metadata: &'a AccountInfo<'info>,
+    master_edition: &'a AccountInfo<'info>,
+    mint: &'a AccountInfo<'info>,
+    auth: &'a AccountInfo<'info>,
+    payer: &'a AccountInfo<'info>,
+    token_program: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    rent: &'a AccountInfo<'info>,
+    collection_mint: &'a AccountInfo<'info>,
+    collection_metadata: &'a AccountInfo<'info>,
+    collection_master_edition: &'a AccountInfo<'info>,
+    /// Instructions sysvar, required once the collection is verified through
+    /// a Collection delegate.
+    instructions: Option<&'a AccountInfo<'info>>,
+    /// Accounts searched for the auth PDA's Collection delegate record.
+    extra_accounts: &'a [AccountInfo<'info>],
+}
+
+/// Creates the master edition and verifies the object into its collection,
+/// run by `mint_object_nft` itself or by `finalize_object_mint` after
+/// `prepare_object_mint`.
+fn finalize_first_mint(accounts: FirstMintFinalizeAccounts, auth_seeds: &[&[&[u8]]]) -> Result<()> {
+    CreateMasterEditionV3Cpi::new(
+        accounts.metadata_program,
+        CreateMasterEditionV3CpiAccounts {
+            edition: accounts.master_edition,
+            mint: accounts.mint,
+            update_authority: accounts.auth,
+            mint_authority: accounts.auth,
+            payer: accounts.payer,
+            metadata: accounts.metadata,
+            token_program: accounts.token_program,
+            system_program: accounts.system_program,
+            rent: Some(accounts.rent),
+        },
+        CreateMasterEditionV3InstructionArgs {
+            max_supply: Some(0),
+        },
+    )
+    .invoke_signed(auth_seeds)
+    .map_err(anchor_lang::error::Error::from)?;
+
+    let metadata_data = accounts
+        .collection_metadata
+        .try_borrow_data()
+        .map_err(|_| Error::from(ErrorCode::InvalidCollectionMetadataAccount))?;
+    let metadata = MetadataAccount::safe_deserialize(&metadata_data)
+        .map_err(|_| Error::from(ErrorCode::InvalidCollectionMetadataAccount))?;
+    let tlv_collection_details = read_collection_details_from_tlv(&metadata_data);
+    let is_sized_collection =
+        metadata.collection_details.is_some() || tlv_collection_details.is_some();
+    let collection_update_authority = Pubkey::new_from_array(metadata.update_authority.to_bytes());
+    drop(metadata_data);
+
+    if collection_update_authority != accounts.auth.key() {
+        let delegate_record = find_metadata_delegate_record(
+            &accounts.metadata_program.key(),
+            &accounts.collection_mint.key(),
+            MetadataDelegateScope::Collection,
+            &collection_update_authority,
+            &accounts.auth.key(),
+        );
+        let delegate_record = accounts
+            .extra_accounts
+            .iter()
+            .find(|account| account.key() == delegate_record)
+            .ok_or(ErrorCode::MetadataDelegateRequired)?;
+        let instructions = accounts
+            .instructions
+            .ok_or(ErrorCode::MetadataDelegateRequired)?;
+        VerifyCollectionV1Cpi::new(
+            accounts.metadata_program,
+            VerifyCollectionV1CpiAccounts {
+                authority: accounts.auth,
+                delegate_record: Some(delegate_record),
+                metadata: accounts.metadata,
+                collection_mint: accounts.collection_mint,
+                collection_metadata: Some(accounts.collection_metadata),
+                collection_master_edition: Some(accounts.collection_master_edition),
+                system_program: accounts.system_program,
+                sysvar_instructions: instructions,
+            },
+        )
+        .invoke_signed(auth_seeds)
+        .map_err(anchor_lang::error::Error::from)?;
+    } else if is_sized_collection {
+        VerifySizedCollectionItemCpi::new(
+            accounts.metadata_program,
+            VerifySizedCollectionItemCpiAccounts {
+                metadata: accounts.metadata,
+                collection_authority: accounts.auth,
+                payer: accounts.payer,
+                collection_mint: accounts.collection_mint,
+                collection: accounts.collection_metadata,
+                collection_master_edition_account: accounts.collection_master_edition,
+                collection_authority_record: None,
+            },
+        )
+        .invoke_signed(auth_seeds)
+        .map_err(anchor_lang::error::Error::from)?;
+    } else {
+        VerifyCollectionCpi::new(
+            accounts.metadata_program,
+            VerifyCollectionCpiAccounts {
+                metadata: accounts.metadata,
+                collection_authority: accounts.auth,
+                payer: accounts.payer,
+                collection_mint: accounts.collection_mint,
+                collection: accounts.collection_metadata,
+                collection_master_edition_account: accounts.collection_master_edition,
+                collection_authority_record: None,
+            },
+        )
+        .invoke_signed(auth_seeds)
+        .map_err(anchor_lang::error::Error::from)?;
+    }
+    Ok(())
+}
+
+/// Burns the payer's copy of an object and marks its manifest as burned and
+/// inactive, returning the consumed object id.
+fn consume_object<'info>(
+    base: &mut MintObjectNftBase<'info>,
+    manifest_info: &'info AccountInfo<'info>,
+    mint_info: &'info AccountInfo<'info>,
+    token_account_info: &'info AccountInfo<'info>,
+) -> Result<u64> {
+    require!(manifest_info.is_writable, ErrorCode::InvalidManifestAccount);
+    let consumed = read_manifest(manifest_info)?;
+    require!(consumed.initialized(), ErrorCode::ManifestNotInitialized);
+    require!(
+        consumed.is_active_at(Clock::get()?.unix_timestamp)
+            && consumed.minted()
+            && !consumed.burned(),
+        ErrorCode::ObjectInactive
+    );
+    require_match!(
+        "config",
+        base.config.key(),
+        consumed.config,
+        ErrorCode::InvalidConfig
+    );
+    require_match!(
+        "mint",
+        mint_info.key(),
+        consumed.mint,
+        ErrorCode::MintMismatch
+    );
+
+    let token_account = Account::<TokenAccount>::try_from(token_account_info)?;
+    require_object_holder(&token_account, &base.minter.key(), &consumed.mint)?;
+
+    token::burn(
+        CpiContext::new(
+            base.token_program.to_account_info(),
+            Burn {
+                mint: mint_info.clone(),
+                from: token_account_info.clone(),
+                authority: base.minter.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    ensure_manifest_capacity(
+        manifest_info,
+        &base.rent_payer.to_account_info(),
+        &base.system_program.to_account_info(),
+    )?;
+    let mut data = manifest_info.try_borrow_mut_data()?;
+    let (_, rest) = data.split_at_mut(8);
+    let manifest =
+        from_bytes_mut::<ObjectManifest>(&mut rest[..core::mem::size_of::<ObjectManifest>()]);
+    manifest.set_is_active(false);
+    manifest.set_burned(true);
+    manifest.owner = Pubkey::default();
+    drop(data);
+
+    record_config_stats(&base.config, &mut base.config_stats, |stats| {
+        stats.total_burned = stats.total_burned.saturating_add(1)
+    })?;
+
+    Ok(consumed.object_id)
+}
+
+/// Applies `update` to the config's stats PDA once stats tracking is enabled.
+fn record_config_stats<'info>(
+    config: &AccountLoader<'info, Config>,
+    config_stats: &mut Option<Account<'info, ConfigStats>>,
+    update: impl FnOnce(&mut ConfigStats),
+) -> Result<()> {
+    if !config.load()?.stats_enabled() {
+        return Ok(());
+    }
+    let stats = config_stats
+        .as_mut()
+        .ok_or(ErrorCode::ConfigStatsRequired)?;
+    require_match!(
+        "config_stats",
+        stats.config,
+        config.key(),
+        ErrorCode::InvalidConfigStatsAccount
+    );
+    update(stats);
+    Ok(())
+}
+
+/// Creates the `[b"creator_record", config, creator]` marker the first time
+/// `creator` opens a manifest under `config`, returning whether it was new.
+fn ensure_creator_record<'info>(
+    creator_record: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    creator: &Pubkey,
+) -> Result<bool> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[CREATOR_SEED, config_key.as_ref(), creator.as_ref()],
+        program_id,
+    );
+    require_match!(
+        "creator_record",
+        creator_record.key(),
+        expected,
+        ErrorCode::InvalidCreatorRecord
+    );
+    if creator_record.data_len() > 0 {
+        require!(
+            *creator_record.owner == *program_id,
+            ErrorCode::InvalidCreatorRecord
+        );
+        return Ok(false);
+    }
+
+    create_pda_account(
+        creator_record,
+        payer,
+        system_program,
+        program_id,
+        CreatorRecord::LEN,
+        &[CREATOR_SEED, config_key.as_ref(), creator.as_ref(), &[bump]],
+    )?;
+
+    let record = CreatorRecord {
+        config: *config_key,
+        creator: *creator,
+        bump,
+    };
+    let mut data = creator_record.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+
+    Ok(true)
+}
+
+/// Adds a mint to the `[b"epoch_stats", config, epoch]` counters, creating
+/// the PDA on the first mint of the epoch.
+fn record_epoch_mint<'info>(
+    epoch_stats: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    clock: &Clock,
+) -> Result<()> {
+    let epoch_bytes = clock.epoch.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[EPOCH_STATS_SEED, config_key.as_ref(), &epoch_bytes],
+        program_id,
+    );
+    require_match!(
+        "epoch_stats",
+        epoch_stats.key(),
+        expected,
+        ErrorCode::InvalidConfigStatsAccount
+    );
+
+    let mut stats = if epoch_stats.data_len() == 0 {
+        create_pda_account(
+            epoch_stats,
+            payer,
+            system_program,
+            program_id,
+            EpochStats::LEN,
+            &[EPOCH_STATS_SEED, config_key.as_ref(), &epoch_bytes, &[bump]],
+        )?;
+        EpochStats {
+            config: *config_key,
+            epoch: clock.epoch,
+            first_mint_slot: clock.slot,
+            bump,
+            ..EpochStats::default()
+        }
+    } else {
+        require!(
+            *epoch_stats.owner == *program_id,
+            ErrorCode::InvalidConfigStatsAccount
+        );
+        EpochStats::try_deserialize(&mut &epoch_stats.try_borrow_data()?[..])?
+    };
+
+    stats.record_mint(clock.slot);
+    let mut data = epoch_stats.try_borrow_mut_data()?;
+    stats.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Appends `entry` to the namespace registry, creating the registry page
+/// when the entry starts a new one.
+fn append_namespace_entry<'info>(
+    registry: &mut NamespaceRegistry,
+    registry_page: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    entry: NamespaceEntry,
+) -> Result<()> {
+    let position = registry.config_count;
+    let page = u32::try_from(position / NAMESPACE_REGISTRY_PAGE_CAPACITY as u64)
+        .map_err(|_| Error::from(ErrorCode::InvalidNamespaceRegistryPage))?;
+    let page_bytes = page.to_le_bytes();
+    let (expected, bump) =
+        Pubkey::find_program_address(&[NAMESPACE_REGISTRY_PAGE_SEED, &page_bytes], program_id);
+    require_match!(
+        "registry_page",
+        registry_page.key(),
+        expected,
+        ErrorCode::InvalidNamespaceRegistryPage
+    );
+
+    let is_new_page = registry_page.data_len() == 0;
+    if is_new_page {
+        create_pda_account(
+            registry_page,
+            payer,
+            system_program,
+            program_id,
+            NamespaceRegistryPage::LEN,
+            &[NAMESPACE_REGISTRY_PAGE_SEED, &page_bytes, &[bump]],
+        )?;
+    } else {
+        require!(
+            *registry_page.owner == *program_id,
+            ErrorCode::InvalidNamespaceRegistryPage
+        );
+    }
+
+    let mut data = registry_page.try_borrow_mut_data()?;
+    let (disc_bytes, rest) = data.split_at_mut(8);
+    if is_new_page {
+        disc_bytes.copy_from_slice(&NamespaceRegistryPage::discriminator());
+    }
+    require!(
+        disc_bytes == NamespaceRegistryPage::discriminator(),
+        ErrorCode::InvalidNamespaceRegistryPage
+    );
+    let entries = from_bytes_mut::<NamespaceRegistryPage>(
+        &mut rest[..core::mem::size_of::<NamespaceRegistryPage>()],
+    );
+    if is_new_page {
+        entries.page = page;
+        entries.bump = bump;
+    }
+    require!(
+        entries.count as u64 == position % NAMESPACE_REGISTRY_PAGE_CAPACITY as u64,
+        ErrorCode::InvalidNamespaceRegistryPage
+    );
+    entries.push(entry)?;
+    registry.config_count = position + 1;
+    Ok(())
+}
+
+/// Writes `object_id` at index position `indexed_count`, creating the page
+/// when the position starts a new one.
+fn append_object_index<'info>(
+    index_page: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    indexed_count: u64,
+    object_id: u64,
+) -> Result<()> {
+    let page = u32::try_from(indexed_count / OBJECT_INDEX_PAGE_CAPACITY as u64)
+        .map_err(|_| Error::from(ErrorCode::InvalidObjectIndexPage))?;
+    let page_bytes = page.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[OBJECT_INDEX_SEED, config_key.as_ref(), &page_bytes],
+        program_id,
+    );
+    require_match!(
+        "object_index_page",
+        index_page.key(),
+        expected,
+        ErrorCode::InvalidObjectIndexPage
+    );
+
+    let is_new_page = index_page.data_len() == 0;
+    if is_new_page {
+        create_pda_account(
+            index_page,
+            payer,
+            system_program,
+            program_id,
+            ObjectIndexPage::LEN,
+            &[OBJECT_INDEX_SEED, config_key.as_ref(), &page_bytes, &[bump]],
+        )?;
+    } else {
+        require!(
+            *index_page.owner == *program_id,
+            ErrorCode::InvalidObjectIndexPage
+        );
+    }
+
+    let mut data = index_page.try_borrow_mut_data()?;
+    let (disc_bytes, rest) = data.split_at_mut(8);
+    if is_new_page {
+        disc_bytes.copy_from_slice(&ObjectIndexPage::discriminator());
+    }
+    require!(
+        disc_bytes == ObjectIndexPage::discriminator(),
+        ErrorCode::InvalidObjectIndexPage
+    );
+    let entries =
+        from_bytes_mut::<ObjectIndexPage>(&mut rest[..core::mem::size_of::<ObjectIndexPage>()]);
+    if is_new_page {
+        entries.config = *config_key;
+        entries.page = page;
+        entries.bump = bump;
+    }
+    require!(
+        entries.count as u64 == indexed_count % OBJECT_INDEX_PAGE_CAPACITY as u64,
+        ErrorCode::InvalidObjectIndexPage
+    );
+    entries.push(object_id)
+}
+
+/// Sets the minted bit for each of `object_ids`, which must all fall in the
+/// chunk of `minted_bitmap`, creating the chunk on first use.
+fn record_minted_ids<'info>(
+    minted_bitmap: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    config_key: &Pubkey,
+    object_ids: &[u64],
+) -> Result<()> {
+    let chunk = MintedBitmap::chunk_for(*object_ids.first().ok_or(ErrorCode::InvalidMintedBitmap)?);
+    let chunk_bytes = chunk.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[MINTED_BITMAP_SEED, config_key.as_ref(), &chunk_bytes],
+        program_id,
+    );
+    require_match!(
+        "minted_bitmap",
+        minted_bitmap.key(),
+        expected,
+        ErrorCode::InvalidMintedBitmap
+    );
+
+    let is_new_chunk = minted_bitmap.data_len() == 0;
+    if is_new_chunk {
+        create_pda_account(
+            minted_bitmap,
+            payer,
+            system_program,
+            program_id,
+            MintedBitmap::LEN,
+            &[
+                MINTED_BITMAP_SEED,
+                config_key.as_ref(),
+                &chunk_bytes,
+                &[bump],
+            ],
+        )?;
+    } else {
+        require!(
+            *minted_bitmap.owner == *program_id,
+            ErrorCode::InvalidMintedBitmap
+        );
+    }
+
+    let mut data = minted_bitmap.try_borrow_mut_data()?;
+    let (disc_bytes, rest) = data.split_at_mut(8);
+    if is_new_chunk {
+        disc_bytes.copy_from_slice(&MintedBitmap::discriminator());
+    }
+    require!(
+        disc_bytes == MintedBitmap::discriminator(),
+        ErrorCode::InvalidMintedBitmap
+    );
+    let bitmap = from_bytes_mut::<MintedBitmap>(&mut rest[..core::mem::size_of::<MintedBitmap>()]);
+    if is_new_chunk {
+        bitmap.config = *config_key;
+        bitmap.chunk = chunk;
+        bitmap.bump = bump;
+    }
+    for object_id in object_ids {
+        bitmap.set_minted(*object_id)?;
+    }
+
+    Ok(())
+}
+
+/// Checks the creator list submitted with a first mint: between one and
+/// [`MAX_CREATOR_LIMIT`] distinct addresses, shares summing to
+/// [`CREATOR_TOTAL_SHARE`], a share for every unverified entry, the manifest
+/// creator included, and never the auth PDA, which signs the metadata as its
+/// update authority rather than earning royalties.
+fn validate_creators(
+    creators: &[CreatorInput],
+    auth: &Pubkey,
+    manifest_creator: &Pubkey,
+) -> Result<()> {
+    require!(
+        !creators.is_empty(),
+        ErrorCode::InvalidCreatorShareDistribution
+    );
+    require!(
+        creators.len() <= MAX_CREATOR_LIMIT,
+        ErrorCode::TooManyCreators
+    );
+    for (index, creator) in creators.iter().enumerate() {
+        require!(
+            !creators[..index]
+                .iter()
+                .any(|earlier| earlier.address == creator.address),
+            ErrorCode::DuplicateCreator
+        );
+        require!(
+            creator.verified || creator.share != 0,
+            ErrorCode::ZeroCreatorShare
+        );
+        require!(creator.address != *auth, ErrorCode::AuthCreatorNotAllowed);
+    }
+
+    let total_shares: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+    require!(
+        total_shares == CREATOR_TOTAL_SHARE,
+        ErrorCode::InvalidCreatorShareDistribution
+    );
+    require!(
+        creators
+            .iter()
+            .any(|creator| creator.address == *manifest_creator),
+        ErrorCode::MissingManifestCreator
+    );
+    Ok(())
+}
+
+/// Checks a [`MetadataDefaults`] creators template: at most one entry fewer
+/// than [`MAX_CREATOR_LIMIT`], leaving room for the minting creator, distinct
+/// addresses other than the auth PDA, a share for every unverified entry, and
+/// shares summing to at most [`CREATOR_TOTAL_SHARE`].
+fn validate_creator_template(creators: &[CreatorInput], auth: &Pubkey) -> Result<()> {
+    require!(
+        creators.len() < MAX_CREATOR_LIMIT,
+        ErrorCode::TooManyCreators
+    );
+    for (index, creator) in creators.iter().enumerate() {
+        require!(
+            !creators[..index]
+                .iter()
+                .any(|earlier| earlier.address == creator.address),
+            ErrorCode::DuplicateCreator
+        );
+        require!(
+            creator.verified || creator.share != 0,
+            ErrorCode::ZeroCreatorShare
+        );
+        require!(creator.address != *auth, ErrorCode::AuthCreatorNotAllowed);
+    }
+    let total_shares: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+    require!(
+        total_shares <= CREATOR_TOTAL_SHARE,
+        ErrorCode::InvalidCreatorShareDistribution
+    );
+    Ok(())
+}
+
+/// Symbol, seller fee, and creators for a Token Metadata mint, taking each
+/// one the caller left out from `defaults`: an empty symbol, a fee of
+/// [`DEFAULT_SELLER_FEE_BASIS_POINTS`], or no creators.
+fn apply_metadata_defaults(
+    defaults: Option<&MetadataDefaults>,
+    config: &Pubkey,
+    manifest_creator: &Pubkey,
+    symbol: &str,
+    seller_fee_basis_points: u16,
+    creators: &[CreatorInput],
+) -> Result<(String, u16, Vec<CreatorInput>)> {
+    let Some(defaults) = defaults else {
+        return Ok((
+            symbol.to_owned(),
+            seller_fee_basis_points,
+            creators.to_vec(),
+        ));
+    };
+    require_keys_eq!(defaults.config, *config, ErrorCode::InvalidMetadataDefaults);
+    let symbol = match symbol {
+        "" => defaults.symbol.clone(),
+        symbol => symbol.to_owned(),
+    };
+    let seller_fee_basis_points = match seller_fee_basis_points {
+        DEFAULT_SELLER_FEE_BASIS_POINTS => defaults.seller_fee_basis_points,
+        fee => fee,
+    };
+    let creators = match creators {
+        [] => defaults.creators_for(manifest_creator),
+        creators => creators.to_vec(),
+    };
+    Ok((symbol, seller_fee_basis_points, creators))
+}
+
+/// Reads the [`CreatorRoyalty`] registered for `creator`, after checking that
+/// `account` is its PDA. An account that was never created means the creator
+/// has no override.
+fn load_creator_royalty(
+    account: &AccountInfo,
+    config: &Pubkey,
+    creator: &Pubkey,
+) -> Result<Option<CreatorRoyalty>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[CREATOR_ROYALTY_SEED, config.as_ref(), creator.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(account.key(), expected, ErrorCode::InvalidCreatorRoyalty);
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(CreatorRoyalty::try_deserialize(
+        &mut &account.try_borrow_data()?[..],
+    )?))
+}
+
+/// Seller fee and creators for a first mint by `manifest_creator`. A
+/// registered [`CreatorRoyalty`] replaces whatever the caller sent or the
+/// config defaults supplied, so the split cannot be bypassed.
+fn apply_creator_royalty(
+    royalty: Option<&CreatorRoyalty>,
+    manifest_creator: &Pubkey,
+    seller_fee_basis_points: u16,
+    creators: Vec<CreatorInput>,
+) -> (u16, Vec<CreatorInput>) {
+    match royalty {
+        Some(royalty) => (
+            royalty.seller_fee_basis_points,
+            royalty.creators_for(manifest_creator),
+        ),
+        None => (seller_fee_basis_points, creators),
+    }
+}
+
+/// Moves the manifest creator's share of a validated creator list to the
+/// payout wallet of their [`CreatorProfile`], as an unverified entry. A
+/// zero-share entry, which only records the creator's signature, stays.
+fn apply_creator_profile(
+    creators: &[CreatorInput],
+    profile: Option<&CreatorProfile>,
+    config: &Pubkey,
+    manifest_creator: &Pubkey,
+    auth: &Pubkey,
+) -> Result<Vec<CreatorInput>> {
+    let Some(profile) = profile else {
+        return Ok(creators.to_vec());
+    };
+    require!(
+        profile.config == *config && profile.creator == *manifest_creator,
+        ErrorCode::InvalidCreatorProfile
+    );
+    let payout = profile.payout_wallet;
+    if payout == *manifest_creator {
+        return Ok(creators.to_vec());
+    }
+    require!(payout != *auth, ErrorCode::AuthCreatorNotAllowed);
+    require!(
+        !creators.iter().any(|creator| creator.address == payout),
+        ErrorCode::DuplicateCreator
+    );
+    Ok(creators
+        .iter()
+        .map(|creator| {
+            if creator.address == *manifest_creator && creator.share != 0 {
+                CreatorInput {
+                    address: payout,
+                    verified: false,
+                    share: creator.share,
+                }
+            } else {
+                creator.clone()
+            }
+        })
+        .collect())
+}
+
+/// Every config-scoped account stores its config as the first field after the
+/// discriminator; the config itself is matched by address.
+fn belongs_to_config(key: &Pubkey, data: &[u8], config_key: &Pubkey) -> bool {
+    key == config_key || data.get(8..40) == Some(config_key.as_ref())
+}
+
+/// Bumps recorded in an initialized, program-owned manifest, as
+/// `(manifest_bump, mint_bump)`.
+fn stored_manifest_bumps(manifest_info: &AccountInfo, program_id: &Pubkey) -> Option<(u8, u8)> {
+    if manifest_info.owner != program_id {
+        return None;
+    }
+    let data = manifest_info.try_borrow_data().ok()?;
+    if data.get(..8)? != ObjectManifest::discriminator() {
+        return None;
+    }
+    // Read the bumps in place rather than copying the whole manifest.
+    let manifest = bytemuck::try_from_bytes::<ObjectManifest>(
+        data.get(8..8 + core::mem::size_of::<ObjectManifest>())?,
+    )
+    .ok()?;
+    manifest
+        .initialized()
+        .then_some((manifest.bump, manifest.mint_bump))
+}
+
+/// Requires `signer` to be the config's authority or, when the config has a
+/// parent and `parent_config` is that parent, the parent's authority.
+fn require_config_admin(
+    config: &AccountLoader<Config>,
+    parent_config: Option<&AccountLoader<Config>>,
+    signer: &Pubkey,
+) -> Result<()> {
+    let config = config.load()?;
+    let parent = match parent_config {
+        Some(parent_config) => Some((parent_config.key(), parent_config.load()?.authority)),
+        None => None,
+    };
+    require!(
+        is_config_admin(&config, parent, signer),
+        ErrorCode::InvalidAuthority
+    );
+    Ok(())
+}
+
+/// Whether `signer` administers `config`, given the key and authority of the
+/// parent config passed alongside it, if any.
+fn is_config_admin(config: &Config, parent: Option<(Pubkey, Pubkey)>, signer: &Pubkey) -> bool {
+    if config.authority == *signer {
+        return true;
+    }
+    parent.is_some_and(|(parent_key, parent_authority)| {
+        config.parent_config != Pubkey::default()
+            && config.parent_config == parent_key
+            && parent_authority == *signer
+    })
+}
+
+/// Derives the PDA for `seeds`. A bump stored when the account was created is
+/// checked with `create_program_address`, which is far cheaper than
+/// `find_program_address`. A stored bump that does not yield a valid address
+/// returns the default key, so the caller's address check fails.
+fn derive_pda(seeds: &[&[u8]], stored_bump: Option<u8>, program_id: &Pubkey) -> (Pubkey, u8) {
+    let Some(bump) = stored_bump else {
+        return Pubkey::find_program_address(seeds, program_id);
+    };
+    let bump_seed = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump_seed);
+    let key = Pubkey::create_program_address(&seeds_with_bump, program_id).unwrap_or_default();
+    (key, bump)
+}
+
+/// Inscription account of `mint` and its inscription metadata account, as
+/// derived by the Metaplex Inscription program.
+fn find_inscription_pdas(mint: &Pubkey) -> (Pubkey, Pubkey) {
+    let (inscription, _) = Pubkey::find_program_address(
+        &[INSCRIPTION_SEED, MPL_INSCRIPTION_ID.as_ref(), mint.as_ref()],
+        &MPL_INSCRIPTION_ID,
+    );
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            INSCRIPTION_SEED,
+            MPL_INSCRIPTION_ID.as_ref(),
+            inscription.as_ref(),
+        ],
+        &MPL_INSCRIPTION_ID,
+    );
+    (inscription, metadata)
+}
+
+/// `WriteData` instruction data: no associated tag, then the offset and the
+/// Borsh-encoded bytes.
+fn inscription_write_data(offset: u64, value: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + 1 + 8 + 4 + value.len());
+    data.push(INSCRIPTION_WRITE_DATA);
+    data.push(0);
+    data.extend_from_slice(&offset.to_le_bytes());
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value);
+    data
+}
+
+/// Core `CreateV1` instruction data: account-state data, the name and URI,
+/// and no plugins.
+fn core_create_v1_data(name: &str, uri: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(2 + 4 + name.len() + 4 + uri.len() + 1);
+    data.push(CORE_CREATE_V1);
+    data.push(0);
+    for value in [name, uri] {
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+    }
+    data.push(0);
+    data
+}
+
+/// Update authority of a Metaplex Core collection account.
+fn core_collection_update_authority(collection: &AccountInfo) -> Result<Pubkey> {
+    require_match!(
+        "core_collection_owner",
+        *collection.owner,
+        MPL_CORE_ID,
+        ErrorCode::InvalidCoreCollection
+    );
+    let data = collection.try_borrow_data()?;
+    require!(
+        data.len() >= 33 && data[0] == CORE_KEY_COLLECTION_V1,
+        ErrorCode::InvalidCoreCollection
+    );
+    Ok(Pubkey::new_from_array(data[1..33].try_into().unwrap()))
+}
+
+/// Price fields of a fully verified Pyth `PriceUpdateV2` account.
+struct PythPrice {
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+fn parse_pyth_price(data: &[u8]) -> Result<PythPrice> {
+    const MESSAGE: usize = PYTH_VERIFICATION_LEVEL_OFFSET + 1;
+    require!(
+        data.len() >= MESSAGE + 60
+            && data[..8] == hash(b"account:PriceUpdateV2").to_bytes()[..8]
+            && data[PYTH_VERIFICATION_LEVEL_OFFSET] == PYTH_VERIFICATION_FULL,
+        ErrorCode::InvalidPriceUpdate
+    );
+    let field = |offset: usize, len: usize| &data[MESSAGE + offset..MESSAGE + offset + len];
+    Ok(PythPrice {
+        feed_id: field(0, 32).try_into().unwrap(),
+        price: i64::from_le_bytes(field(32, 8).try_into().unwrap()),
+        conf: u64::from_le_bytes(field(40, 8).try_into().unwrap()),
+        exponent: i32::from_le_bytes(field(48, 4).try_into().unwrap()),
+        publish_time: i64::from_le_bytes(field(52, 8).try_into().unwrap()),
+    })
+}
+
+/// Converts a USD price in cents to lamports at `price * 10^exponent` USD
+/// per SOL, rounding up so the treasury is never short.
+fn usd_cents_to_lamports(usd_cents: u64, price: i64, exponent: i32) -> Result<u64> {
+    require!(
+        price > 0 && (-18..=0).contains(&exponent),
+        ErrorCode::InvalidPriceUpdate
+    );
+    // lamports = cents / 100 * 10^9 / (price * 10^exponent)
+    let numerator = (usd_cents as u128)
+        .checked_mul(10u128.pow((7 - exponent) as u32))
+        .ok_or(ErrorCode::MintPriceOverflow)?;
+    let price = price as u128;
+    u64::try_from(numerator.div_ceil(price)).map_err(|_| error!(ErrorCode::MintPriceOverflow))
+}
+
+/// Checks the Pyth price against the config's feed, age, and confidence
+/// band, and returns the mint price in lamports.
+fn mint_price_lamports(mint_price: &MintPrice, pyth: &PythPrice, now: i64) -> Result<u64> {
+    require!(
+        pyth.feed_id == mint_price.feed_id,
+        ErrorCode::InvalidPriceUpdate
+    );
+    require!(
+        now.saturating_sub(pyth.publish_time) <= mint_price.max_price_age_secs as i64,
+        ErrorCode::PriceUpdateStale
+    );
+    require!(
+        pyth.price > 0
+            && (pyth.conf as u128) * 10_000
+                <= (pyth.price as u128) * mint_price.max_confidence_bps as u128,
+        ErrorCode::PriceConfidenceTooWide
+    );
+    usd_cents_to_lamports(mint_price.usd_cents, pyth.price, pyth.exponent)
+}
+
+/// Charges the config's USD mint price to the rent payer, converted with the
+/// Pyth price update passed to the mint, and pays it to the treasury.
+fn charge_mint_price(
+    tracking: &MintTrackingAccounts,
+    manifest_key: Pubkey,
+    object_id: u64,
+) -> Result<()> {
+    if !tracking.config.load()?.usd_price_enabled() {
+        return Ok(());
+    }
+    let mint_price = tracking.mint_price.ok_or(ErrorCode::MintPriceRequired)?;
+    let price_update = tracking
+        .price_update
+        .as_ref()
+        .ok_or(ErrorCode::MintPriceRequired)?;
+    let treasury = tracking
+        .treasury
+        .as_ref()
+        .ok_or(ErrorCode::MintPriceRequired)?;
+    require_match!(
+        "price_update_owner",
+        *price_update.owner,
+        PYTH_RECEIVER_ID,
+        ErrorCode::InvalidPriceUpdate
+    );
+    require_match!(
+        "treasury",
+        treasury.key(),
+        mint_price.treasury,
+        ErrorCode::InvalidTreasury
+    );
+
+    let pyth = parse_pyth_price(&price_update.try_borrow_data()?)?;
+    let lamports = mint_price_lamports(mint_price, &pyth, Clock::get()?.unix_timestamp)?;
+    system_program::transfer(
+        CpiContext::new(
+            tracking.system_program.clone(),
+            system_program::Transfer {
+                from: tracking.rent_payer.clone(),
+                to: treasury.clone(),
+            },
+        ),
+        lamports,
+    )?;
+
+    emit_logged!(MintPricePaid {
+        config: tracking.config.key(),
+        manifest: manifest_key,
+        object_id,
+        treasury: treasury.key(),
+        usd_cents: mint_price.usd_cents,
+        lamports,
+        price: pyth.price,
+        exponent: pyth.exponent,
+        context: EventContext::new(tracking.rent_payer.key(), tracking.minter)?,
+    });
+
+    Ok(())
+}
+
+/// Requires the KYC attestation passed to a mint when `category` is
+/// restricted by the config's KYC gate.
+fn check_kyc_gate(
+    tracking: &MintTrackingAccounts,
+    category: u16,
+    recipient: &Pubkey,
+) -> Result<()> {
+    if !tracking.config.load()?.kyc_gate_enabled() {
+        return Ok(());
+    }
+    let kyc_gate = tracking.kyc_gate.ok_or(ErrorCode::KycAttestationRequired)?;
+    if !kyc_gate.is_restricted(category) {
+        return Ok(());
+    }
+    let attestation = tracking
+        .kyc_attestation
+        .as_ref()
+        .ok_or(ErrorCode::KycAttestationRequired)?;
+    require_match!(
+        "kyc_attestation_owner",
+        *attestation.owner,
+        SAS_PROGRAM_ID,
+        ErrorCode::InvalidAttestationAccount
+    );
+    let attestation = parse_sas_attestation(&attestation.try_borrow_data()?)?;
+    require_kyc_attestation(
+        kyc_gate,
+        &attestation,
+        recipient,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// Tracking, pricing, and KYC accounts shared by the mint paths, each
+/// required once the config flag that uses it is set.
+struct MintTrackingAccounts<'a, 'info> {
+    config: &'a AccountLoader<'info, Config>,
+    minter: Pubkey,
+    rent_payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    config_stats: &'a mut Option<Account<'info, ConfigStats>>,
+    creator_record: Option<AccountInfo<'info>>,
+    epoch_stats: Option<AccountInfo<'info>>,
+    object_index_page: Option<AccountInfo<'info>>,
+    minted_bitmap: Option<AccountInfo<'info>>,
+    mint_price: Option<&'a Account<'info, MintPrice>>,
+    price_update: Option<AccountInfo<'info>>,
+    treasury: Option<AccountInfo<'info>>,
+    kyc_gate: Option<&'a Account<'info, KycGate>>,
+    kyc_attestation: Option<AccountInfo<'info>>,
+}
+
+/// Implements `tracking()` for mint account structs that carry the optional
+/// tracking, pricing, and KYC accounts under their usual names.
+macro_rules! impl_mint_tracking {
+    ($($accounts:ident),+ $(,)?) => {
+        $(
+            impl<'info> $accounts<'info> {
+                fn tracking(&mut self) -> MintTrackingAccounts<'_, 'info> {
+                    MintTrackingAccounts {
+                        config: &self.config,
+                        minter: self.minter.key(),
+                        rent_payer: self.rent_payer.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                        config_stats: &mut self.config_stats,
+                        creator_record: self.creator_record.as_ref().map(|a| a.to_account_info()),
+                        epoch_stats: self.epoch_stats.as_ref().map(|a| a.to_account_info()),
+                        object_index_page: self
+                            .object_index_page
+                            .as_ref()
+                            .map(|a| a.to_account_info()),
+                        minted_bitmap: self.minted_bitmap.as_ref().map(|a| a.to_account_info()),
+                        mint_price: self.mint_price.as_deref(),
+                        price_update: self.price_update.as_ref().map(|a| a.to_account_info()),
+                        treasury: self.treasury.as_ref().map(|a| a.to_account_info()),
+                        kyc_gate: self.kyc_gate.as_deref(),
+                        kyc_attestation: self.kyc_attestation.as_ref().map(|a| a.to_account_info()),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_mint_tracking!(MintObjectNftBase, MintCoreObject, MintToken2022Object);
+
+/// Checks a new object minted outside `process_mint_object` against the
+/// config before its manifest is written.
+fn validate_new_object(
+    config: &Config,
+    backend: AssetBackend,
+    manifest_uri: &str,
+    metadata_name: &str,
+    metadata_symbol: &str,
+    category: u16,
+    arweave_tx_id: &[u8; 32],
+) -> Result<()> {
+    require!(!config.paused(), ErrorCode::MintingPaused);
+    config.require_instruction_enabled(INSTRUCTION_MINT)?;
+    require!(
+        config.asset_backend()? == backend,
+        ErrorCode::WrongAssetBackend
+    );
+    require!(manifest_uri.len() <= MAX_URI_LENGTH, ErrorCode::UriTooLong);
+    require!(
+        manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
+        ErrorCode::UriTooLong
+    );
+    validate_uri_rules(config, manifest_uri)?;
+    require!(
+        config.is_category_enabled(category),
+        ErrorCode::CategoryNotEnabled
+    );
+    require!(
+        metadata_name.as_bytes().len() <= MAX_NAME_LENGTH,
+        ErrorCode::MetadataNameTooLong
+    );
+    require!(
+        metadata_symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
+        ErrorCode::MetadataSymbolTooLong
+    );
+    validate_arweave_tx_id(manifest_uri, arweave_tx_id)
+}
+
+/// Initial state of a manifest minted in one step outside
+/// `process_mint_object`.
+struct NewManifest<'a> {
+    config: Pubkey,
+    object_id: u64,
+    mint: Pubkey,
+    bump: u8,
+    mint_bump: u8,
+    backend: AssetBackend,
+    manifest_uri: &'a str,
+    manifest_hash: [u8; 32],
+    hash_algorithm: ManifestHashAlgorithm,
+    arweave_tx_id: [u8; 32],
+    creator: Pubkey,
+    owner: Pubkey,
+    category: u16,
+    tag_hash: [u8; 32],
+    name: &'a str,
+    symbol: &'a str,
+}
+
+fn write_new_manifest(manifest: &mut ObjectManifest, new: NewManifest, slot: u64) -> Result<()> {
+    manifest.config = new.config;
+    manifest.object_id = new.object_id;
+    manifest.mint = new.mint;
+    manifest.bump = new.bump;
+    manifest.layout_version = MANIFEST_LAYOUT_VERSION;
+    manifest.mint_bump = new.mint_bump;
+    manifest.set_asset_backend(new.backend);
+    manifest.set_is_active(true);
+    manifest.set_initialized(true);
+    manifest.set_minted(true);
+    manifest.manifest_hash = new.manifest_hash;
+    manifest.set_hash_algorithm(new.hash_algorithm);
+    manifest.set_metadata_uri(new.manifest_uri);
+    manifest.set_content_addressed(validate_content_address(new.manifest_uri)?);
+    manifest.arweave_tx_id = new.arweave_tx_id;
+    manifest.creator = new.creator;
+    manifest.created_slot = slot;
+    manifest.history_chain = next_history_chain(&[0u8; 32], &new.manifest_hash, slot);
+    manifest.category = new.category;
+    manifest.tag_hash = new.tag_hash;
+    manifest.set_origin(ObjectOrigin::Minted);
+    manifest.owner = new.owner;
+    manifest.set_display_data(new.name, new.symbol);
+    Ok(())
+}
+
+/// Remaining accounts of `mint_core_object` and `mint_token2022_object`: the
+/// optional memo program, then the optional changelog accounts.
+fn single_mint_remaining_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+) -> (Option<AccountInfo<'info>>, Option<ChangelogAccounts<'info>>) {
+    let memo_program = remaining_accounts
+        .first()
+        .filter(|account| account.key() == MEMO_PROGRAM_ID)
+        .cloned();
+    let changelog = changelog_accounts(&remaining_accounts[usize::from(memo_program.is_some())..]);
+    (memo_program, changelog)
+}
+
+/// Bytes Token-2022 appends to a mint for a token metadata extension with no
+/// additional fields: the extension header, the update authority and mint,
+/// and the length-prefixed name, symbol, and URI.
+fn token_metadata_extension_len(name: &str, symbol: &str, uri: &str) -> usize {
+    4 + 32 + 32 + 4 + name.len() + 4 + symbol.len() + 4 + uri.len() + 4
+}
+
+/// Counts a newly created object and, when enabled, records its creator and
+/// appends it to the object index. `creator` is the manifest's creator, which
+/// airdrops set apart from the minter.
+fn record_new_object(
+    tracking: &mut MintTrackingAccounts,
+    program_id: &Pubkey,
+    object_id: u64,
+    creator: &Pubkey,
+) -> Result<()> {
+    let config_key = tracking.config.key();
+    {
+        let mut config = tracking.config.load_mut()?;
+        config.object_count = config.object_count.saturating_add(1);
+    }
+    if tracking.config.load()?.stats_enabled() {
+        let creator_record = tracking
+            .creator_record
+            .as_ref()
+            .ok_or(ErrorCode::ConfigStatsRequired)?;
+        if ensure_creator_record(
+            creator_record,
+            &tracking.rent_payer,
+            &tracking.system_program,
+            program_id,
+            &config_key,
+            creator,
+        )? {
+            record_config_stats(tracking.config, &mut *tracking.config_stats, |stats| {
+                stats.unique_creators = stats.unique_creators.saturating_add(1)
+            })?;
+        }
+    }
+    if tracking.config.load()?.object_index_enabled() {
+        let index_page = tracking
+            .object_index_page
+            .as_ref()
+            .ok_or(ErrorCode::ObjectIndexPageRequired)?;
+        let indexed_object_count = tracking.config.load()?.indexed_object_count;
+        append_object_index(
+            index_page,
+            &tracking.rent_payer,
+            &tracking.system_program,
+            program_id,
+            &config_key,
+            indexed_object_count,
+            object_id,
+        )?;
+        tracking.config.load_mut()?.indexed_object_count = indexed_object_count.saturating_add(1);
+    }
+    Ok(())
+}
+
+/// Records a mint in the config and epoch stats and the minted bitmap, for
+/// whichever of them the config has enabled.
+fn record_object_mint(
+    tracking: &mut MintTrackingAccounts,
+    program_id: &Pubkey,
+    object_id: u64,
+) -> Result<()> {
+    let config_key = tracking.config.key();
+    let clock = Clock::get()?;
+    record_config_stats(tracking.config, &mut *tracking.config_stats, |stats| {
+        stats.record_mint(clock.slot)
+    })?;
+    if tracking.config.load()?.stats_enabled() {
+        let epoch_stats = tracking
+            .epoch_stats
+            .as_ref()
+            .ok_or(ErrorCode::ConfigStatsRequired)?;
+        record_epoch_mint(
+            epoch_stats,
+            &tracking.rent_payer,
+            &tracking.system_program,
+            program_id,
+            &config_key,
+            &clock,
+        )?;
+    }
+    if tracking.config.load()?.minted_bitmap_enabled() {
+        let minted_bitmap = tracking
+            .minted_bitmap
+            .as_ref()
+            .ok_or(ErrorCode::MintedBitmapRequired)?;
+        record_minted_ids(
+            minted_bitmap,
+            &tracking.rent_payer,
+            &tracking.system_program,
+            program_id,
+            &config_key,
+            &[object_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Logs the compact mint memo through the memo program when the config asks
+/// for it.
+fn log_mint_memo(
+    config: &AccountLoader<Config>,
+    memo_program: Option<AccountInfo>,
+    object_id: u64,
+    manifest_hash: &[u8; 32],
+) -> Result<()> {
+    if !config.load()?.memo_on_mint() {
+        return Ok(());
+    }
+    let memo_program = memo_program.ok_or(ErrorCode::MissingMemoProgram)?;
+    invoke(
+        &Instruction {
+            program_id: MEMO_PROGRAM_ID,
+            accounts: Vec::new(),
+            data: mint_memo(object_id, manifest_hash).into_bytes(),
+        },
+        &[memo_program],
+    )?;
+    Ok(())
+}
+
+/// Checks that `attestation` comes from the oracle's current attester,
+/// matches the manifest's hash and URI, and is within the oracle's age limit.
+fn require_current_uri_attestation(
+    attestation: &UriAttestation,
+    oracle: &LivenessOracle,
+    manifest: &ObjectManifest,
+    slot: u64,
+) -> Result<()> {
+    require!(
+        oracle.attester != Pubkey::default(),
+        ErrorCode::InvalidLivenessAttester
+    );
+    require_match!(
+        "attester",
+        attestation.attester,
+        oracle.attester,
+        ErrorCode::InvalidLivenessAttester
+    );
+    require!(
+        attestation.manifest_hash == manifest.manifest_hash
+            && attestation.uri_hash == hash(manifest.metadata_uri_bytes()).to_bytes(),
+        ErrorCode::UriAttestationMismatch
+    );
+    require!(
+        oracle.max_age_slots == 0 || slot.saturating_sub(attestation.slot) <= oracle.max_age_slots,
+        ErrorCode::UriAttestationStale
+    );
+    Ok(())
+}
+
+/// Leaf appended to the changelog tree for a manifest state: the hash of the
+/// manifest address, object id, manifest hash, hash algorithm, active flag,
+/// URI hash, owner, and slot. Anyone holding the state can recompute it and
+/// prove it against the tree.
+fn changelog_leaf(manifest_key: &Pubkey, manifest: &ObjectManifest, slot: u64) -> [u8; 32] {
+    hashv(&[
+        manifest_key.as_ref(),
+        &manifest.object_id.to_le_bytes(),
+        &manifest.manifest_hash,
+        &[manifest.hash_algorithm, manifest.is_active],
+        &hash(manifest.metadata_uri_bytes()).to_bytes(),
+        manifest.owner.as_ref(),
+        &slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// SPL Account Compression instruction data starts with the Anchor
+/// discriminator of the instruction name.
+fn compression_ix_data(name: &str) -> Vec<u8> {
+    hash(format!("global:{name}").as_bytes()).to_bytes()[..8].to_vec()
+}
+
+/// Accounts a changelog append needs, passed as a group of four remaining
+/// accounts led by the SPL Account Compression program.
+struct ChangelogAccounts<'info> {
+    compression_program: AccountInfo<'info>,
+    noop_program: AccountInfo<'info>,
+    changelog: AccountInfo<'info>,
+    tree: AccountInfo<'info>,
+}
+
+fn changelog_accounts<'info>(accounts: &[AccountInfo<'info>]) -> Option<ChangelogAccounts<'info>> {
+    match accounts {
+        [program, noop, changelog, tree, ..] if program.key() == SPL_ACCOUNT_COMPRESSION_ID => {
+            Some(ChangelogAccounts {
+                compression_program: program.clone(),
+                noop_program: noop.clone(),
+                changelog: changelog.clone(),
+                tree: tree.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Appends `leaf` to the config's changelog tree, signed by the
+/// [`ManifestChangelog`] PDA. Does nothing until `init_manifest_changelog`
+/// has run for the config.
+fn append_changelog_leaf(
+    config: &AccountLoader<Config>,
+    accounts: Option<&ChangelogAccounts>,
+    leaf: [u8; 32],
+) -> Result<()> {
+    if !config.load()?.changelog_enabled() {
+        return Ok(());
+    }
+    let accounts = accounts.ok_or(ErrorCode::ChangelogRequired)?;
+    let changelog = Account::<ManifestChangelog>::try_from(&accounts.changelog)?;
+    let config_key = config.key();
+    require_match!(
+        "changelog",
+        changelog.config,
+        config_key,
+        ErrorCode::InvalidChangelogAccount
+    );
+    require_match!(
+        "changelog_tree",
+        accounts.tree.key(),
+        changelog.tree,
+        ErrorCode::InvalidChangelogAccount
+    );
+    require_match!(
+        "noop_program",
+        accounts.noop_program.key(),
+        SPL_NOOP_ID,
+        ErrorCode::InvalidChangelogAccount
+    );
+
+    let mut data = compression_ix_data("append");
+    data.extend_from_slice(&leaf);
+    invoke_signed(
+        &Instruction {
+            program_id: SPL_ACCOUNT_COMPRESSION_ID,
+            accounts: vec![
+                AccountMeta::new(changelog.tree, false),
+                AccountMeta::new_readonly(accounts.changelog.key(), true),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+            ],
+            data,
+        },
+        &[
+            accounts.tree.clone(),
+            accounts.changelog.clone(),
+            accounts.noop_program.clone(),
+            accounts.compression_program.clone(),
+        ],
+        &[&[CHANGELOG_SEED, config_key.as_ref(), &[changelog.bump]]],
+    )?;
+
+    Ok(())
+}
+
+/// Compact memo recorded at mint, e.g. `ogal:mint:42:<hex manifest hash>`.
+fn mint_memo(object_id: u64, manifest_hash: &[u8; 32]) -> String {
+    format!("ogal:mint:{}:{}", object_id, Hex(manifest_hash))
+}
+
+fn metadata_remaining_accounts<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<(
+    AccountInfo<'info>,
+    AccountInfo<'info>,
+    AccountInfo<'info>,
+    Option<AccountInfo<'info>>,
+    Option<AccountInfo<'info>>,
+    Option<ChangelogAccounts<'info>>,
+    &'info [AccountInfo<'info>],
+)> {
+    require!(
+        remaining_accounts.len() >= 3,
+        ErrorCode::MissingMintMetadataAccounts
+    );
+
+    let mut extra_index = 3;
+    let instructions_sysvar_account = if let Some(account) = remaining_accounts.get(3) {
+        if account.key() == sysvar::instructions::id() {
+            extra_index = 4;
+            Some(account.clone())
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let memo_program_account = match remaining_accounts.get(extra_index) {
+        Some(account) if account.key() == MEMO_PROGRAM_ID => {
+            extra_index += 1;
+            Some(account.clone())
+        }
+        _ => None,
+    };
+
+    let changelog_accounts =
+        changelog_accounts(&remaining_accounts[extra_index.min(remaining_accounts.len())..]);
+    if changelog_accounts.is_some() {
+        extra_index += 4;
+    }
+
+    let extra_accounts = if extra_index < remaining_accounts.len() {
+        &remaining_accounts[extra_index..]
+    } else {
+        &[]
+    };
+
+    Ok((
+        remaining_accounts[0].clone(),
+        remaining_accounts[1].clone(),
+        remaining_accounts[2].clone(),
+        instructions_sysvar_account,
+        memo_program_account,
+        changelog_accounts,
+        extra_accounts,
+    ))
+}
+
+/// Derives, creates, or reuses the manifest and mint of a target passed
+/// through the remaining accounts, which Anchor's constraints do not cover.
+/// Returns `(manifest_bump, mint_bump)`.
+fn ensure_remaining_target_accounts<'info>(
+    target: &ObjectMintTarget<'info>,
+    base: &MintObjectNftBase<'info>,
+    program_id: &Pubkey,
+    object_id: u64,
+) -> Result<(u8, u8)> {
+    let config_key = base.config.key();
+    let payer = base.rent_payer.to_account_info();
+    let system_program = base.system_program.to_account_info();
+    let object_id_bytes = object_id.to_le_bytes();
+    let manifest_key = target.manifest.key();
+    // Re-mints reuse the bumps recorded when the manifest was created instead
+    // of searching for them again.
+    let stored_bumps = stored_manifest_bumps(&target.manifest, program_id);
+    let (expected_manifest_key, manifest_bump) = derive_pda(
+        &[MANIFEST_SEED, config_key.as_ref(), &object_id_bytes],
+        stored_bumps.map(|(manifest_bump, _)| manifest_bump),
+        program_id,
+    );
+    require_match!(
+        "manifest",
+        manifest_key,
+        expected_manifest_key,
+        ErrorCode::InvalidManifestAccount
+    );
+    if target.manifest.data_len() == 0 {
+        // Only a new object consults its reservation, so re-mints skip the
+        // derivation.
+        let (expected_reservation_key, _) = derive_pda(
+            &[RESERVATION_SEED, config_key.as_ref(), &object_id_bytes],
+            None,
+            program_id,
+        );
+        require_match!(
+            "object_reservation",
+            target.reservation.key(),
+            expected_reservation_key,
+            ErrorCode::InvalidObjectReservation
+        );
+        create_pda_account(
+            &target.manifest,
+            &payer,
+            &system_program,
+            program_id,
+            ObjectManifest::LEN,
+            &[
+                MANIFEST_SEED,
+                config_key.as_ref(),
+                &object_id_bytes,
+                &[manifest_bump],
+            ],
+        )?;
+    } else {
+        require!(
+            target.manifest.owner == program_id,
+            ErrorCode::InvalidManifestAccount
+        );
+        ensure_manifest_capacity(&target.manifest, &payer, &system_program)?;
+    }
+
+    let (expected_mint_key, mint_bump) = derive_pda(
+        &[MINT_SEED, manifest_key.as_ref()],
+        stored_bumps.map(|(_, mint_bump)| mint_bump),
+        program_id,
+    );
+    require_match!(
+        "object_mint",
+        target.mint.key(),
+        expected_mint_key,
+        ErrorCode::InvalidObjectMintAccount
+    );
+    if target.mint.data_len() == 0 {
+        let auth = base.auth.key();
+        create_pda_account(
+            &target.mint,
+            &payer,
+            &system_program,
+            &token::ID,
+            Mint::LEN,
+            &[MINT_SEED, manifest_key.as_ref(), &[mint_bump]],
+        )?;
+        token::initialize_mint2(
+            CpiContext::new(
+                base.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: target.mint.clone(),
+                },
+            ),
+            0,
+            &auth,
+            Some(&auth),
+        )?;
+    } else {
+        require!(
+            target.mint.owner == &token::ID,
+            ErrorCode::InvalidObjectMintAccount
+        );
+    }
+
+    Ok((manifest_bump, mint_bump))
+}
+
+/// Creates the recipient's ATA for the target mint if it is missing, or
+/// checks that an existing recipient token account is an SPL token account.
+/// With `allow_non_ata`, an existing account at any address is accepted; the
+/// caller then checks its mint and owner like it does for an ATA.
+fn ensure_recipient_token_account<'info>(
+    target: &ObjectMintTarget<'info>,
+    base: &MintObjectNftBase<'info>,
+    allow_non_ata: bool,
+) -> Result<()> {
+    let account = &target.recipient_token_account;
+    if check_recipient_token_account(
+        account,
+        &base.recipient.key(),
+        &target.mint.key(),
+        allow_non_ata,
+    )? {
+        associated_token::create(CpiContext::new(
+            base.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: base.rent_payer.to_account_info(),
+                associated_token: account.clone(),
+                authority: base.recipient.to_account_info(),
+                mint: target.mint.clone(),
+                system_program: base.system_program.to_account_info(),
+                token_program: base.token_program.to_account_info(),
+            },
+        ))?;
+    }
+    Ok(())
+}
+
+/// Checks the recipient token account's address and, if it exists, that it
+/// is an SPL token account. Returns true when it is the missing ATA, which
+/// the mint creates.
+fn check_recipient_token_account(
+    account: &AccountInfo,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    allow_non_ata: bool,
+) -> Result<bool> {
+    let expected_recipient_ata = associated_token::get_associated_token_address(recipient, mint);
+    let is_ata = account.key() == expected_recipient_ata;
+    if !allow_non_ata {
+        require_match!(
+            "recipient_token_account",
+            account.key(),
+            expected_recipient_ata,
+            ErrorCode::InvalidRecipientTokenAccount
+        );
+    }
+
+    if account.data_len() == 0 {
+        // Only the ATA can be created by the mint; other token accounts need
+        // the recipient's signature, so custodians must create them beforehand.
+        require!(is_ata, ErrorCode::RecipientTokenAccountMissing);
+        return Ok(true);
+    }
+    require!(
+        account.owner == &token::ID && account.data_len() == TokenAccount::LEN,
+        ErrorCode::InvalidRecipientTokenAccount
+    );
+    Ok(false)
+}
+
+/// Runs the preconditions `process_mint_object` enforces for a plain
+/// `mint_object_nft` without changing any account, returning whether it
+/// would be a first mint. Creator signatures and the optional tracking
+/// accounts are not checked, since a simulation may omit them.
+fn check_mint_preconditions(
+    accounts: &CheckMint,
+    program_id: &Pubkey,
+    args: MintObjectArgs<'_>,
+) -> Result<bool> {
+    let MintObjectArgs {
+        object_id,
+        manifest_uri,
+        manifest_hash,
+        metadata_name,
+        metadata_symbol,
+        seller_fee_basis_points,
+        creators,
+        hash_algorithm,
+        category,
+        arweave_tx_id,
+        ..
+    } = args;
+    let config = accounts.config.load()?;
+    let config_key = accounts.config.key();
+    require!(!config.paused(), ErrorCode::MintingPaused);
+    config.require_instruction_enabled(INSTRUCTION_MINT)?;
+
+    let manifest_key = accounts.object_manifest.key();
+    let (expected_manifest_key, _) = Pubkey::find_program_address(
+        &[MANIFEST_SEED, config_key.as_ref(), &object_id.to_le_bytes()],
+        program_id,
+    );
+    require_match!(
+        "manifest",
+        manifest_key,
+        expected_manifest_key,
+        ErrorCode::InvalidManifestAccount
+    );
+    let mint_key = accounts.object_mint.key();
+    let (expected_mint_key, _) =
+        Pubkey::find_program_address(&[MINT_SEED, manifest_key.as_ref()], program_id);
+    require_match!(
+        "object_mint",
+        mint_key,
+        expected_mint_key,
+        ErrorCode::InvalidObjectMintAccount
+    );
+
+    let existing = if accounts.object_manifest.data_len() == 0 {
+        None
+    } else {
+        require!(
+            accounts.object_manifest.data_len() == ObjectManifest::LEN,
+            ErrorCode::ManifestAccountTooSmall
+        );
+        Some(read_manifest(&accounts.object_manifest)?).filter(|manifest| manifest.initialized())
+    };
+    let (first_mint, manifest_creator) = match existing {
+        None => {
+            require!(
+                manifest_uri.len() <= MAX_URI_LENGTH
+                    && manifest_uri.len() <= METADATA_MAX_URI_LENGTH,
+                ErrorCode::UriTooLong
+            );
+            validate_uri_rules(&config, manifest_uri)?;
+            validate_content_address(manifest_uri)?;
+            validate_arweave_tx_id(manifest_uri, &arweave_tx_id)?;
+            require!(
+                config.is_category_enabled(category),
+                ErrorCode::CategoryNotEnabled
+            );
+            (true, accounts.minter.key())
+        }
+        Some(manifest) => {
+            require!(!manifest.mint_pending(), ErrorCode::MintPending);
+            require!(
+                manifest.is_active_at(Clock::get()?.unix_timestamp),
+                ErrorCode::ObjectInactive
+            );
+            require_match!(
+                "object_id",
+                object_id,
+                manifest.object_id,
+                ErrorCode::ObjectIdMismatch
+            );
+            require_match!(
+                "config",
+                config_key,
+                manifest.config,
+                ErrorCode::InvalidConfig
+            );
+            require_match!("mint", mint_key, manifest.mint, ErrorCode::MintMismatch);
+            require!(
+                manifest_hash == manifest.manifest_hash
+                    && hash_algorithm as u8 == manifest.hash_algorithm,
+                ErrorCode::ManifestMismatch
+            );
+            require!(
+                manifest.metadata_uri_len() <= METADATA_MAX_URI_LENGTH,
+                ErrorCode::UriTooLong
+            );
+            require!(
+                manifest.metadata_uri_is_valid(),
+                ErrorCode::ManifestUriCorrupted
+            );
+            require!(
+                manifest_uri.is_empty() || manifest.metadata_uri_equals(manifest_uri),
+                ErrorCode::ManifestMismatch
+            );
+            (!manifest.minted(), manifest.creator)
+        }
+    };
+
+    let recipient_token_account = &accounts.recipient_token_account;
+    let recipient = accounts.recipient.key();
+    if !check_recipient_token_account(
+        recipient_token_account,
+        &recipient,
+        &mint_key,
+        config.non_ata_recipients(),
+    )? {
+        require_match!(
+            "recipient_mint",
+            anchor_spl::token::accessor::mint(recipient_token_account)?,
+            mint_key,
+            ErrorCode::MintMismatch
+        );
+        require_match!(
+            "recipient_owner",
+            anchor_spl::token::accessor::authority(recipient_token_account)?,
+            recipient,
+            ErrorCode::RecipientMismatch
+        );
+    }
+
+    if first_mint {
+        let (metadata_symbol, seller_fee_basis_points, creators) = apply_metadata_defaults(
+            accounts.metadata_defaults.as_deref(),
+            &accounts.config.key(),
+            &manifest_creator,
+            metadata_symbol,
+            seller_fee_basis_points,
+            creators,
+        )?;
+        let (seller_fee_basis_points, creators) = apply_creator_royalty(
+            load_creator_royalty(
+                &accounts.creator_royalty,
+                &accounts.config.key(),
+                &manifest_creator,
+            )?
+            .as_ref(),
+            &manifest_creator,
+            seller_fee_basis_points,
+            creators,
+        );
+        require!(
+            metadata_name.as_bytes().len() <= MAX_NAME_LENGTH,
+            ErrorCode::MetadataNameTooLong
+        );
+        require!(
+            metadata_symbol.as_bytes().len() <= MAX_SYMBOL_LENGTH,
+            ErrorCode::MetadataSymbolTooLong
+        );
+        require!(
+            seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidSellerFeeBasisPoints
+        );
+        validate_creators(&creators, &accounts.auth.key(), &manifest_creator)?;
+        let metadata_program = config.metadata_program();
+        require_metaplex_pdas(
+            &metadata_program,
+            &mint_key,
+            &accounts.metadata.key(),
+            &accounts.master_edition.key(),
+            &accounts.collection_mint.key(),
+            &accounts.collection_metadata.key(),
+            &accounts.collection_master_edition.key(),
+        )?;
+        require!(
+            accounts.collection_metadata.owner == &metadata_program,
+            ErrorCode::InvalidCollectionMetadataAccount
+        );
+    }
+    Ok(first_mint)
+}
+
+/// Allocates a rent-exempt PDA of `space` bytes owned by `owner`. Like
+/// Anchor's `init`, an address that already holds lamports is topped up,
+/// allocated, and assigned instead, so a transfer to it beforehand cannot
+/// block creation.
+fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            owner,
+        );
+    }
+
+    fund_rent_exemption(account, payer, system_program, space)?;
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        owner,
+    )
+}
+
+/// Transfers whatever `account` lacks of the rent-exempt minimum for `len`
+/// bytes from `payer` through the System Program.
+fn fund_rent_exemption<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(len);
+    if account.lamports() >= required_lamports {
+        return Ok(());
+    }
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        ),
+        required_lamports - account.lamports(),
+    )
+}
+
+/// Raises `subtree_height` on each manifest of `chain`, nearest first, so it
+/// covers a subtree reaching `height` levels below the first of them.
+fn raise_subtree_heights<'a, 'info: 'a>(
+    chain: impl Iterator<Item = &'a AccountInfo<'info>>,
+    mut height: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    for manifest_info in chain {
+        ensure_manifest_capacity(manifest_info, payer, system_program)?;
+        let mut data = manifest_info.try_borrow_mut_data()?;
+        let (_, rest) = data.split_at_mut(8);
+        let manifest =
+            from_bytes_mut::<ObjectManifest>(&mut rest[..core::mem::size_of::<ObjectManifest>()]);
+        manifest.subtree_height = manifest.subtree_height.max(height as u8);
+        height += 1;
+    }
+    Ok(())
+}
+
+/// Grows manifests created under an older, smaller layout so they can be
+/// loaded with the current [`ObjectManifest`] definition, topping up rent from
+/// `payer` as required.
+fn ensure_manifest_capacity<'info>(
+    manifest: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if manifest.data_len() >= ObjectManifest::LEN {
+        return Ok(());
+    }
+
+    fund_rent_exemption(manifest, payer, system_program, ObjectManifest::LEN)?;
+    manifest.realloc(ObjectManifest::LEN, true)?;
+
+    Ok(())
+}
+
+/// Appends `event` to the config's audit log when one is passed. Accounts
+/// structs require the log once the config has enabled it.
+fn record_audit_entry<E: anchor_lang::Event>(
+    audit_log: Option<&AccountLoader<AuditLog>>,
+    event: &E,
+    context: &EventContext,
+) -> Result<()> {
+    let Some(audit_log) = audit_log else {
+        return Ok(());
+    };
+    audit_log.load_mut()?.record(AuditEntry {
+        slot: context.slot,
+        tag: E::DISCRIMINATOR,
+        actor: context.authority,
+        summary_hash: keccak::hash(&event.data()).to_bytes(),
+    });
+    Ok(())
+}
+
+/// Grows a config created by an older program to [`Config::LEN`], with
+/// `payer` covering the extra rent. The realloc zero-fills appended fields;
+/// `Config::upgrade_layout` then sets any that default to something else.
+fn ensure_config_capacity<'info>(
+    config: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if config.data_len() >= Config::LEN {
+        return Ok(());
+    }
+
+    fund_rent_exemption(config, payer, system_program, Config::LEN)?;
+    config.realloc(Config::LEN, true)?;
+
+    Ok(())
+}
+
+/// Closes a program-owned account the way Anchor's `close` constraint does,
+/// moving all of its lamports to `destination`.
+fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateObjectManifest<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Verified against the expected Metaplex metadata PDA
+    #[account(mut)]
+    pub object_metadata: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the Metaplex token metadata program id
+    pub metadata_program: UncheckedAccount<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Optional sysvar, only used when present
+    pub instructions: Option<AccountInfo<'info>>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has opted into on-chain history.
+    #[account(mut)]
+    pub manifest_history: Option<AccountLoader<'info, ManifestHistory>>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateToken2022Manifest<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// Token-2022 object mint; its token metadata extension is rewritten.
+    #[account(mut, mint::token_program = token_program)]
+    pub object_mint: InterfaceAccount<'info, InterfaceMint>,
+    #[account(token::token_program = token_program)]
+    pub owner_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+    /// CHECK: Optional sysvar, only used when present
+    pub instructions: Option<AccountInfo<'info>>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has opted into on-chain history.
+    #[account(mut)]
+    pub manifest_history: Option<AccountLoader<'info, ManifestHistory>>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ScheduleManifestUpdate<'info> {
+    /// Holder of the object NFT; pays the scheduled update's rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = ScheduledUpdate::LEN,
+        seeds = [SCHEDULED_UPDATE_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub scheduled_update: Account<'info, ScheduledUpdate>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelScheduledUpdate<'info> {
+    #[account(mut)]
+    pub scheduler: Signer<'info>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        mut,
+        close = scheduler,
+        seeds = [SCHEDULED_UPDATE_SEED, object_manifest.key().as_ref()],
+        bump = scheduled_update.bump,
+        has_one = scheduler @ ErrorCode::InvalidScheduledUpdate
+    )]
+    pub scheduled_update: Account<'info, ScheduledUpdate>,
+}
+
+/// The update accounts are laid out as for `update_object_manifest`, with
+/// the scheduler's token account in place of the owner's.
+#[derive(Accounts)]
+pub struct ExecuteScheduledUpdate<'info> {
+    /// Cranker funding any manifest growth.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Receives the scheduled update's rent; must be its scheduler.
+    #[account(mut)]
+    pub scheduler: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub scheduler_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Verified against the expected Metaplex metadata PDA
+    #[account(mut)]
+    pub object_metadata: UncheckedAccount<'info>,
+    /// CHECK: Validated to match the Metaplex token metadata program id
+    pub metadata_program: UncheckedAccount<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Optional sysvar, only used when present
+    pub instructions: Option<AccountInfo<'info>>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has opted into on-chain history.
+    #[account(mut)]
+    pub manifest_history: Option<AccountLoader<'info, ManifestHistory>>,
+    /// Required once `initialize_config_stats` has run for this config.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+    #[account(
+        mut,
+        close = scheduler,
+        seeds = [SCHEDULED_UPDATE_SEED, object_manifest.key().as_ref()],
+        bump = scheduled_update.bump,
+        has_one = scheduler @ ErrorCode::InvalidScheduledUpdate
+    )]
+    pub scheduled_update: Account<'info, ScheduledUpdate>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    /// This config's authority, or its parent config's authority.
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// Parent config, when its authority signs instead of this config's.
+    #[account(
+        seeds = [CONFIG_SEED, parent_config.load()?.namespace.as_ref()],
+        bump = parent_config.load()?.config_bump
+    )]
+    pub parent_config: Option<AccountLoader<'info, Config>>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct TransferNamespace<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = NamespaceTransfer::LEN,
+        seeds = [NAMESPACE_TRANSFER_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub namespace_transfer: Account<'info, NamespaceTransfer>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptNamespaceTransfer<'info> {
+    pub new_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        close = previous_authority,
+        seeds = [NAMESPACE_TRANSFER_SEED, config.key().as_ref()],
+        bump = namespace_transfer.bump,
+        has_one = new_authority @ ErrorCode::InvalidNamespaceTransfer
+    )]
+    pub namespace_transfer: Account<'info, NamespaceTransfer>,
+    /// CHECK: Authority that proposed the transfer; receives the proposal's
+    /// rent.
+    #[account(
+        mut,
+        address = namespace_transfer.current_authority @ ErrorCode::NamespaceTransferStale
+    )]
+    pub previous_authority: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelNamespaceTransfer<'info> {
+    /// Current authority; receives the proposal's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [NAMESPACE_TRANSFER_SEED, config.key().as_ref()],
+        bump = namespace_transfer.bump
+    )]
+    pub namespace_transfer: Account<'info, NamespaceTransfer>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetParentConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// New parent, or omitted to detach the config.
+    #[account(
+        seeds = [CONFIG_SEED, parent_config.load()?.namespace.as_ref()],
+        bump = parent_config.load()?.config_bump
+    )]
+    pub parent_config: Option<AccountLoader<'info, Config>>,
+    /// Authority of `parent_config`; required with it.
+    pub parent_authority: Option<Signer<'info>>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// Same accounts as [`SetAuthority`], without the parent config, plus the
+/// governance the config is handed to.
+#[derive(Accounts)]
+pub struct SetGovernanceAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// CHECK: Must be owned by `governance_program`; only its key is used.
+    pub governance: UncheckedAccount<'info>,
+    /// CHECK: Any executable program; DAOs may run their own governance
+    /// deployment.
+    pub governance_program: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfigLayout<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: A config still in the Borsh layout; its discriminator, PDA, and
+    /// authority are verified within the instruction.
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidConfig)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeConfig<'info> {
+    /// Pays for any config layout growth.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub config: AccountLoader<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    /// This config's authority, or its parent config's authority.
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// Parent config, when its authority signs instead of this config's.
+    #[account(
+        seeds = [CONFIG_SEED, parent_config.load()?.namespace.as_ref()],
+        bump = parent_config.load()?.config_bump
+    )]
+    pub parent_config: Option<AccountLoader<'info, Config>>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintMemo<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetAssetBackend<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetRecoveryDelaySlots<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataProgram<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetWormholeProgram<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct InscribeObject<'info> {
+    /// Holder of the object NFT; pays the inscription rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Verified against the expected metadata PDA
+    pub object_metadata: UncheckedAccount<'info>,
+    /// CHECK: Inscription of the object mint, derived and checked in the
+    /// handler.
+    #[account(mut)]
+    pub inscription: UncheckedAccount<'info>,
+    /// CHECK: Inscription metadata account, derived and checked in the
+    /// handler.
+    #[account(mut)]
+    pub inscription_metadata: UncheckedAccount<'info>,
+    /// CHECK: Inscription shard chosen by the client; the Inscription program
+    /// validates it.
+    #[account(mut)]
+    pub inscription_shard: UncheckedAccount<'info>,
+    /// CHECK: Metaplex Inscription program.
+    #[account(address = MPL_INSCRIPTION_ID)]
+    pub inscription_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExportObject<'info> {
+    /// Holder of the object NFT; pays the Wormhole fee and message rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mut)]
+    pub object_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Must be the core bridge stored on the config.
+    pub wormhole_program: UncheckedAccount<'info>,
+    /// CHECK: Core bridge config PDA, derived and checked in the handler.
+    #[account(mut)]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+    /// CHECK: Message account the core bridge creates; OGAL signs for it.
+    #[account(
+        mut,
+        seeds = [EXPORT_MESSAGE_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub wormhole_message: UncheckedAccount<'info>,
+    /// CHECK: Emitter PDA for the config; OGAL signs for it.
+    #[account(seeds = [WORMHOLE_EMITTER_SEED, config.key().as_ref()], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+    /// CHECK: Core bridge sequence tracker for the emitter, checked in the
+    /// handler.
+    #[account(mut)]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+    /// CHECK: Core bridge fee collector, checked in the handler.
+    #[account(mut)]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    /// Required once the authority has called `initialize_config_stats`.
+    #[account(mut)]
+    pub config_stats: Option<Account<'info, ConfigStats>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintPrice<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = MintPrice::LEN,
+        seeds = [MINT_PRICE_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub mint_price: Account<'info, MintPrice>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataDefaults<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = MetadataDefaults::LEN,
+        seeds = [METADATA_DEFAULTS_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub metadata_defaults: Account<'info, MetadataDefaults>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct SetCreatorRoyalty<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CreatorRoyalty::LEN,
+        seeds = [CREATOR_ROYALTY_SEED, config.key().as_ref(), creator.as_ref()],
+        bump
+    )]
+    pub creator_royalty: Account<'info, CreatorRoyalty>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct ClearCreatorRoyalty<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = config @ ErrorCode::InvalidCreatorRoyalty,
+        seeds = [
+            CREATOR_ROYALTY_SEED,
+            config.key().as_ref(),
+            creator_royalty.creator.as_ref()
+        ],
+        bump = creator_royalty.bump
+    )]
+    pub creator_royalty: Account<'info, CreatorRoyalty>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorProfile<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorProfile::LEN,
+        seeds = [CREATOR_PROFILE_SEED, config.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyCreatorProfile<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        has_one = config @ ErrorCode::InvalidCreatorProfile,
+        seeds = [
+            CREATOR_PROFILE_SEED,
+            config.key().as_ref(),
+            creator_profile.creator.as_ref()
+        ],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorAllowlist<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddApprovedCreator<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = ApprovedCreator::LEN,
+        seeds = [APPROVED_CREATOR_SEED, config.key().as_ref(), creator.as_ref()],
+        bump
+    )]
+    pub approved_creator: Account<'info, ApprovedCreator>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveApprovedCreator<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = config @ ErrorCode::CreatorNotApproved,
+        seeds = [
+            APPROVED_CREATOR_SEED,
+            config.key().as_ref(),
+            approved_creator.creator.as_ref()
+        ],
+        bump = approved_creator.bump
+    )]
+    pub approved_creator: Account<'info, ApprovedCreator>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetKycGate<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = KycGate::LEN,
+        seeds = [KYC_GATE_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub kyc_gate: Account<'info, KycGate>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetLivenessOracle<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = LivenessOracle::LEN,
+        seeds = [LIVENESS_ORACLE_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub liveness_oracle: Account<'info, LivenessOracle>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct RecordUriAttestation<'info> {
+    /// Switchboard function signer registered on the oracle; pays the
+    /// attestation rent.
+    #[account(mut)]
+    pub attester: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [LIVENESS_ORACLE_SEED, config.key().as_ref()],
+        bump = liveness_oracle.bump,
+        has_one = attester @ ErrorCode::InvalidLivenessAttester
+    )]
+    pub liveness_oracle: Account<'info, LivenessOracle>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        init_if_needed,
+        payer = attester,
+        space = UriAttestation::LEN,
+        seeds = [URI_ATTESTATION_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub uri_attestation: Account<'info, UriAttestation>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUriLiveness<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [LIVENESS_ORACLE_SEED, config.key().as_ref()],
+        bump = liveness_oracle.bump,
+    )]
+    pub liveness_oracle: Account<'info, LivenessOracle>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        seeds = [URI_ATTESTATION_SEED, object_manifest.key().as_ref()],
+        bump = uri_attestation.bump,
+    )]
+    pub uri_attestation: Account<'info, UriAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterForeignEmitter<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ForeignEmitter::LEN,
+        seeds = [FOREIGN_EMITTER_SEED, config.key().as_ref(), &chain.to_le_bytes()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// The mint accounts are laid out as for `mint_object_nft`, including the
+/// remaining accounts tail.
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct ImportObject<'info> {
+    pub base: MintObjectNftBase<'info>,
+    pub metadata: MintObjectNftMetadata<'info>,
+    /// CHECK: Core bridge `PostedVAA` account; its owner and layout are
+    /// verified within the instruction.
+    pub posted_vaa: UncheckedAccount<'info>,
+    /// Emitter registered for the VAA's chain, checked against the VAA.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    /// CHECK: `[b"import_receipt", posted_vaa]` PDA, created within the
+    /// instruction; an existing receipt means the VAA was already imported.
+    #[account(mut)]
+    pub import_receipt: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCompressedManifests<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// Light Protocol accounts passed to the Light system program by the
+/// compressed manifest instructions, ahead of the Merkle trees and queues in
+/// the remaining accounts.
+#[derive(Accounts)]
+pub struct LightSystemAccounts<'info> {
+    /// CHECK: `[b"cpi_authority"]` PDA the program signs Light CPIs with.
+    #[account(seeds = [LIGHT_CPI_AUTHORITY_SEED], bump)]
+    pub cpi_authority: UncheckedAccount<'info>,
+    /// CHECK: The account compression program's registration of the Light
+    /// system program.
+    #[account(
+        seeds = [LIGHT_SYSTEM_PROGRAM_ID.as_ref()],
+        bump,
+        seeds::program = LIGHT_ACCOUNT_COMPRESSION_PROGRAM_ID
+    )]
+    pub registered_program_pda: UncheckedAccount<'info>,
+    /// CHECK: Noop program Light logs compressed account data through.
+    #[account(address = SPL_NOOP_ID @ ErrorCode::InvalidLightAccount)]
+    pub noop_program: UncheckedAccount<'info>,
+    /// CHECK: The Light system program's authority over the account
+    /// compression program.
+    #[account(
+        seeds = [LIGHT_CPI_AUTHORITY_SEED],
+        bump,
+        seeds::program = LIGHT_SYSTEM_PROGRAM_ID
+    )]
+    pub account_compression_authority: UncheckedAccount<'info>,
+    /// CHECK: Light account compression program.
+    #[account(address = LIGHT_ACCOUNT_COMPRESSION_PROGRAM_ID @ ErrorCode::InvalidLightAccount)]
+    pub account_compression_program: UncheckedAccount<'info>,
+    /// CHECK: This program, which Light derives `cpi_authority` under.
+    #[account(address = crate::ID @ ErrorCode::InvalidLightAccount)]
+    pub invoking_program: UncheckedAccount<'info>,
+    /// CHECK: Light system program.
+    #[account(address = LIGHT_SYSTEM_PROGRAM_ID @ ErrorCode::InvalidLightAccount)]
+    pub light_system_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct CreateCompressedManifest<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// Recorded as the manifest creator.
+    pub minter: Signer<'info>,
+    /// Pays the Light Protocol tree fees.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: The id's manifest PDA, which must not exist.
+    #[account(seeds = [MANIFEST_SEED, config.key().as_ref(), &object_id.to_le_bytes()], bump)]
+    pub object_manifest: UncheckedAccount<'info>,
+    pub light: LightSystemAccounts<'info>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+    /// CHECK: [`ObjectReservation`] PDA for the object id, which usually does
+    /// not exist. A live reservation limits creation to its creator.
+    #[account(
+        seeds = [RESERVATION_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_reservation: UncheckedAccount<'info>,
+    /// CHECK: The minter's [`ApprovedCreator`] record, verified within the
+    /// instruction. Required while the config's creator allowlist is on.
+    pub approved_creator: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCompressedManifest<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The manifest's owner, who also pays the Light Protocol tree fees.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub light: LightSystemAccounts<'info>,
+    /// CHECK: Program-wide [`GlobalPause`] PDA, which may not exist yet.
+    #[account(seeds = [GLOBAL_PAUSE_SEED], bump)]
+    pub global_pause: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetNonAtaRecipients<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetEnabledInstructions<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetTransferHook<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetTransferFeeRoyalties<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetUriRules<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetManifestGcSlots<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct GcManifest<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut, close = rent_payer)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// CHECK: Must match the manifest's recorded rent payer, verified within
+    /// the instruction; receives the manifest's rent.
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferManifestCreator<'info> {
+    /// The manifest's recorded creator, which also covers any growth of an
+    /// older manifest.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub new_creator: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGlobalPause<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = GlobalPause::LEN,
+        seeds = [GLOBAL_PAUSE_SEED],
+        bump
+    )]
+    pub global_pause: Account<'info, GlobalPause>,
+    /// The program's upgradeable loader data account, required when
+    /// `authority` is the upgrade authority rather than an allowed deployer.
+    pub program_data: Option<Account<'info, ProgramData>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_namespace: Pubkey)]
+pub struct MigrateConfigNamespace<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, old_config.load()?.namespace.as_ref()],
+        bump = old_config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !old_config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub old_config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = Config::LEN,
+        seeds = [CONFIG_SEED, new_namespace.as_ref()],
+        bump
+    )]
+    pub new_config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, old_config.key().as_ref()],
+        bump = old_config.load()?.auth_bump,
+        constraint = old_auth.config == old_config.key() @ ErrorCode::InvalidConfig
+    )]
+    pub old_auth: Account<'info, Auth>,
+    #[account(
+        init,
+        payer = authority,
+        space = Auth::LEN,
+        seeds = [AUTH_SEED, new_config.key().as_ref()],
+        bump
+    )]
+    pub new_auth: Account<'info, Auth>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = NamespaceRegistry::LEN,
+        seeds = [NAMESPACE_REGISTRY_SEED],
+        bump
+    )]
+    pub namespace_registry: Account<'info, NamespaceRegistry>,
+    /// CHECK: `[b"namespace_registry_page", page]` PDA for the page holding
+    /// the next registry entry, verified and created within the instruction.
+    #[account(mut)]
+    pub registry_page: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, old_config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateManifests<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, old_config.load()?.namespace.as_ref()],
+        bump = old_config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !old_config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub old_config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, old_config.key().as_ref()],
+        bump = old_config.load()?.auth_bump,
+        constraint = old_auth.config == old_config.key() @ ErrorCode::InvalidConfig
+    )]
+    pub old_auth: Box<Account<'info, Auth>>,
+    #[account(
+        seeds = [CONFIG_SEED, new_config.load()?.namespace.as_ref()],
+        bump = new_config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub new_config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, new_config.key().as_ref()],
+        bump = new_config.load()?.auth_bump,
+        constraint = new_auth.config == new_config.key() @ ErrorCode::InvalidConfig
+    )]
+    pub new_auth: Box<Account<'info, Auth>>,
+    /// CHECK: Validated to match the config's token metadata program id
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, old_config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct EnableManifestHistory<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = ManifestHistory::LEN,
+        seeds = [HISTORY_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub manifest_history: AccountLoader<'info, ManifestHistory>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteManifestPayload<'info> {
+    #[account(mut)]
+    pub writer: Signer<'info>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = ManifestPayloadBuffer::space(0),
+        seeds = [PAYLOAD_SEED, object_manifest.key().as_ref(), writer.key().as_ref()],
+        bump
+    )]
+    pub payload_buffer: Account<'info, ManifestPayloadBuffer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyManifestPayload<'info> {
+    #[account(mut)]
+    pub writer: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// Staged content written via `write_manifest_payload`, if any.
+    #[account(mut, close = writer)]
+    pub payload_buffer: Option<Account<'info, ManifestPayloadBuffer>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategories<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct SetObjectExpiry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct ReserveObjectId<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// CHECK: The object's manifest PDA, which must not exist yet.
+    #[account(
+        seeds = [MANIFEST_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_manifest: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = creator,
+        space = ObjectReservation::LEN,
+        seeds = [RESERVATION_SEED, config.key().as_ref(), &object_id.to_le_bytes()],
+        bump
+    )]
+    pub object_reservation: Account<'info, ObjectReservation>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelObjectReservation<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ ErrorCode::InvalidAuthority,
+        seeds = [
+            RESERVATION_SEED,
+            object_reservation.config.as_ref(),
+            &object_reservation.object_id.to_le_bytes()
+        ],
+        bump = object_reservation.bump
+    )]
+    pub object_reservation: Account<'info, ObjectReservation>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireObjectReservation<'info> {
+    pub caller: Signer<'info>,
+    /// CHECK: The reservation's creator, refunded its rent.
+    #[account(mut, address = object_reservation.creator @ ErrorCode::InvalidAuthority)]
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            RESERVATION_SEED,
+            object_reservation.config.as_ref(),
+            &object_reservation.object_id.to_le_bytes()
+        ],
+        bump = object_reservation.bump
+    )]
+    pub object_reservation: Account<'info, ObjectReservation>,
+}
+
+#[derive(Accounts)]
+#[instruction(airdrop_id: u64, merkle_root: [u8; 32], recipient_count: u32)]
+pub struct CreateAirdrop<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = Airdrop::space(recipient_count),
+        seeds = [AIRDROP_SEED, config.key().as_ref(), &airdrop_id.to_le_bytes()],
+        bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+#[instruction(object_id: u64)]
+pub struct DistributeAirdrop<'info> {
+    pub base: MintObjectNftBase<'info>,
+    pub metadata: MintObjectNftMetadata<'info>,
+    #[account(
+        mut,
+        seeds = [AIRDROP_SEED, base.config.key().as_ref(), &airdrop.airdrop_id.to_le_bytes()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateManifestV2<'info> {
+    /// Pays for the manifest realloc.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireObject<'info> {
+    /// Pays for any manifest layout growth.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncOwner<'info> {
+    /// Pays for any manifest layout growth.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Account<'info, Mint>,
+    pub holder_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct SyncHookedOwner<'info> {
+    #[account(
+        seeds = [HOOK_AUTHORITY_SEED],
+        bump,
+        seeds::program = TRANSFER_HOOK_PROGRAM_ID
+    )]
+    pub hook_authority: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: InterfaceAccount<'info, InterfaceMint>,
+    pub destination_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    /// CHECK: Owner or delegate that signed the transfer; only recorded.
+    pub transfer_authority: UncheckedAccount<'info>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct RequestObjectRecovery<'info> {
+    /// Config authority; pays the queued recovery's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mint::token_program = token_program)]
+    pub object_mint: InterfaceAccount<'info, InterfaceMint>,
+    /// Token account currently holding the object.
+    #[account(token::token_program = token_program)]
+    pub source_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    /// Token account the object is recovered to.
+    #[account(token::token_program = token_program)]
+    pub destination_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = ObjectRecovery::LEN,
+        seeds = [RECOVERY_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub object_recovery: Account<'info, ObjectRecovery>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelObjectRecovery<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RECOVERY_SEED, object_manifest.key().as_ref()],
+        bump = object_recovery.bump
+    )]
+    pub object_recovery: Account<'info, ObjectRecovery>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct RecoverObject<'info> {
+    /// Config authority; receives the queued recovery's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mint::token_program = token_program)]
+    pub object_mint: InterfaceAccount<'info, InterfaceMint>,
+    #[account(
+        mut,
+        address = object_recovery.source @ ErrorCode::InvalidObjectRecovery,
+        token::token_program = token_program
+    )]
+    pub source_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    #[account(
+        mut,
+        address = object_recovery.destination @ ErrorCode::InvalidObjectRecovery,
+        token::token_program = token_program
+    )]
+    pub destination_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RECOVERY_SEED, object_manifest.key().as_ref()],
+        bump = object_recovery.bump
+    )]
+    pub object_recovery: Account<'info, ObjectRecovery>,
+    pub token_program: Program<'info, Token2022>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestRoyalties<'info> {
+    /// Pays for the creator's token account when it does not exist yet.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Account<'info, Auth>,
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mut, mint::token_program = token_program)]
+    pub object_mint: InterfaceAccount<'info, InterfaceMint>,
+    /// CHECK: Must be the manifest's recorded creator; it never signs.
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = object_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, InterfaceTokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableCustodyTrail<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(
+        init,
+        payer = payer,
+        space = CustodyTrail::LEN,
+        seeds = [CUSTODY_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub custody_trail: AccountLoader<'info, CustodyTrail>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        space = HolderProposal::LEN,
+        seeds = [PROPOSAL_SEED, config.key().as_ref(), &proposal_id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, HolderProposal>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    /// Object holder; pays for the vote record.
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        has_one = config @ ErrorCode::InvalidConfig,
+        seeds = [PROPOSAL_SEED, config.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, HolderProposal>,
+    /// CHECK: Owner and layout are verified by `read_manifest`; read-only, so
+    /// manifests of any layout can vote.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub voter_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = voter,
+        space = HolderVote::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), object_manifest.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, HolderVote>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.config.as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, HolderProposal>,
+}
+
+#[derive(Accounts)]
+pub struct LockManifest<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMarketplaceDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mut)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Any account the marketplace program uses as its delegate,
+    /// typically one of its PDAs.
+    pub delegate: UncheckedAccount<'info>,
+    /// CHECK: Only required to be executable; recorded on the manifest.
+    pub marketplace_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        constraint = config.key() == object_manifest.load()?.config @ ErrorCode::InvalidConfig
+    )]
+    pub config: AccountLoader<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeMarketplaceDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(mut)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositToVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Data-less PDA owning every object vaulted under the config.
+    #[account(seeds = [VAULT_SEED, config.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = object_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    /// Receipt for this object, reused across deposits.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = vault,
+        seeds = [VAULT_RECEIPT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = owner
+    )]
+    pub receipt_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromVault<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Data-less PDA owning every object vaulted under the config.
+    #[account(seeds = [VAULT_SEED, config.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = object_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [VAULT_RECEIPT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = holder
+    )]
+    pub receipt_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = object_mint,
+        associated_token::authority = holder
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGift<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub sender_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = sender,
+        space = Gift::LEN,
+        seeds = [GIFT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub gift: Account<'info, Gift>,
+    #[account(
+        init,
+        payer = sender,
+        associated_token::mint = object_mint,
+        associated_token::authority = gift
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemGift<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    /// CHECK: The gift's sender, refunded the escrow rent.
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        constraint = object_manifest.key() == gift.manifest @ ErrorCode::InvalidManifestAccount
+    )]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(address = object_manifest.load()?.mint @ ErrorCode::MintMismatch)]
+    pub object_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        close = sender,
+        has_one = config @ ErrorCode::InvalidConfig,
+        has_one = sender @ ErrorCode::InvalidAuthority,
+        seeds = [GIFT_SEED, gift.manifest.as_ref()],
+        bump = gift.bump
+    )]
+    pub gift: Account<'info, Gift>,
+    #[account(
+        mut,
+        associated_token::mint = object_mint,
+        associated_token::authority = gift
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = object_mint,
+        associated_token::authority = recipient
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct RefundGift<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        mut,
+        constraint = object_manifest.key() == gift.manifest @ ErrorCode::InvalidManifestAccount
+    )]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    #[account(address = object_manifest.load()?.mint @ ErrorCode::MintMismatch)]
+    pub object_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        close = sender,
+        has_one = config @ ErrorCode::InvalidConfig,
+        has_one = sender @ ErrorCode::InvalidAuthority,
+        seeds = [GIFT_SEED, gift.manifest.as_ref()],
+        bump = gift.bump
+    )]
+    pub gift: Account<'info, Gift>,
+    #[account(
+        mut,
+        associated_token::mint = object_mint,
+        associated_token::authority = gift
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = object_mint,
+        associated_token::authority = sender
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct FractionalizeObject<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Box<Account<'info, Mint>>,
+    #[account(mut)]
+    pub owner_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Data-less PDA that escrows the object and mints its shares.
+    #[account(seeds = [VAULT_SEED, config.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = object_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    /// Share mint for this object, reused if it is fractionalized again.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = vault,
+        seeds = [SHARE_MINT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = share_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_share_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemObject<'info> {
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub object_mint: Box<Account<'info, Mint>>,
+    /// CHECK: Data-less PDA that escrows the object and mints its shares.
+    #[account(seeds = [VAULT_SEED, config.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = object_mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [SHARE_MINT_SEED, object_manifest.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = redeemer
+    )]
+    pub redeemer_share_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = redeemer,
+        associated_token::mint = object_mint,
+        associated_token::authority = redeemer
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// Required when the manifest has a custody trail.
+    #[account(mut)]
+    pub custody_trail: Option<AccountLoader<'info, CustodyTrail>>,
+}
+
+#[derive(Accounts)]
+pub struct AddAttestation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Owner and layout are verified by `read_manifest`.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Parsed as a Solana Attestation Service `Attestation` within the
+    /// instruction.
+    #[account(owner = SAS_PROGRAM_ID @ ErrorCode::InvalidAttestationAccount)]
+    pub attestation: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = ManifestAttestation::LEN,
+        seeds = [
+            MANIFEST_ATTESTATION_SEED,
+            object_manifest.key().as_ref(),
+            attestation.key().as_ref()
+        ],
+        bump
+    )]
+    pub manifest_attestation: Account<'info, ManifestAttestation>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAttestation<'info> {
+    /// Current holder; receives the link's rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Owner and layout are verified by `read_manifest`.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            MANIFEST_ATTESTATION_SEED,
+            object_manifest.key().as_ref(),
+            manifest_attestation.attestation.as_ref()
+        ],
+        bump = manifest_attestation.bump
+    )]
+    pub manifest_attestation: Account<'info, ManifestAttestation>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyDid<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Key controlling the DID, as listed in its DID document.
+    pub did_controller: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearDid<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMirrorUris<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RepairManifestUri<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct LinkChildObject<'info> {
+    /// Holder of the child object; pays for any manifest realloc.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub child_manifest: AccountLoader<'info, ObjectManifest>,
+    pub parent_owner: Signer<'info>,
+    pub parent_owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Decoded and validated as an `ObjectManifest` within the instruction.
+    #[account(mut)]
+    pub parent_manifest: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlinkChildObject<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub child_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetLevelDelegate<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LevelDelegate::LEN,
+        seeds = [LEVEL_DELEGATE_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub level_delegate: Account<'info, LevelDelegate>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct LevelUp<'info> {
+    /// The config's level delegate, or the holder when the authority
+    /// co-signs. Pays for growing a manifest from an older layout.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    /// Config authority, required unless `signer` is the level delegate.
+    pub authority: Option<Signer<'info>>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [LEVEL_DELEGATE_SEED, config.key().as_ref()],
+        bump = level_delegate.bump
+    )]
+    pub level_delegate: Option<Account<'info, LevelDelegate>>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    /// The holder's token account, required unless `signer` is the level
+    /// delegate.
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetXpScorer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = XpScorer::LEN,
+        seeds = [XP_SCORER_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub xp_scorer: Account<'info, XpScorer>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueXp<'info> {
+    /// The config's registered scorer. Pays for growing a manifest from an
+    /// older layout.
+    #[account(mut)]
+    pub scorer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [XP_SCORER_SEED, config.key().as_ref()],
+        bump = xp_scorer.bump,
+        constraint = xp_scorer.is_scorer(&scorer.key()) @ ErrorCode::InvalidXpScorer
+    )]
+    pub xp_scorer: Account<'info, XpScorer>,
+    #[account(mut)]
+    pub object_manifest: AccountLoader<'info, ObjectManifest>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LinkExternalObject<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Owner and layout are verified by `read_manifest`.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Owner and layout are verified by `read_manifest`, and its
+    /// address is checked against the foreign config and object id.
+    pub foreign_manifest: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = ExternalObjectLink::LEN,
+        seeds = [
+            EXTERNAL_LINK_SEED,
+            object_manifest.key().as_ref(),
+            foreign_manifest.key().as_ref()
+        ],
+        bump
+    )]
+    pub external_link: Account<'info, ExternalObjectLink>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlinkExternalObject<'info> {
+    /// Current holder; receives the link's rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Owner and layout are verified by `read_manifest`.
+    pub object_manifest: UncheckedAccount<'info>,
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            EXTERNAL_LINK_SEED,
+            object_manifest.key().as_ref(),
+            external_link.foreign_manifest.as_ref()
+        ],
+        bump = external_link.bump
+    )]
+    pub external_link: Account<'info, ExternalObjectLink>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfigStats<'info> {
     pub authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
     #[account(
         init,
         payer = payer,
-        space = Config::LEN,
-        seeds = [CONFIG_SEED, namespace.as_ref()],
+        space = ConfigStats::LEN,
+        seeds = [STATS_SEED, config.key().as_ref()],
         bump
     )]
-    pub config: Account<'info, Config>,
+    pub config_stats: Account<'info, ConfigStats>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct InitManifestChangelog<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
     #[account(
         init,
         payer = payer,
-        space = Auth::LEN,
-        seeds = [AUTH_SEED, config.key().as_ref()],
+        space = ManifestChangelog::LEN,
+        seeds = [CHANGELOG_SEED, config.key().as_ref()],
         bump
     )]
-    pub auth: Account<'info, Auth>,
+    pub manifest_changelog: Account<'info, ManifestChangelog>,
+    /// CHECK: Allocated by the caller and owned by SPL Account Compression,
+    /// which initializes it.
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: SPL Account Compression program.
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: SPL Noop program, which records the tree's changelog.
+    #[account(address = SPL_NOOP_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct InitAuditLog<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableObjectIndex<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct EnableMintedBitmap<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+/// Remaining accounts: the config, or PDAs whose data starts with this config's
+/// key, to fund (all writable).
+#[derive(Accounts)]
+pub struct TopUpRent<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts: the config, or PDAs whose data starts with this config's
+/// key, to sweep (all writable).
+#[derive(Accounts)]
+pub struct SweepRentDust<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        constraint = audit_log.is_some() || !config.load()?.audit_log_enabled()
+            @ ErrorCode::AuditLogRequired
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// CHECK: Any account chosen by the authority to receive the dust.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    /// The config's [`AuditLog`], required once `init_audit_log` has run.
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, config.key().as_ref()],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+}
+
+#[derive(Accounts)]
+pub struct RecordMintedIds<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    /// CHECK: Verified against the chunk covering the manifests and created
+    /// within the instruction when missing.
+    #[account(mut)]
+    pub minted_bitmap: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(snapshot_id: u64)]
+pub struct CreateHolderSnapshot<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump,
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = HolderSnapshot::LEN,
+        seeds = [SNAPSHOT_SEED, config.key().as_ref(), &snapshot_id.to_le_bytes()],
+        bump
+    )]
+    pub holder_snapshot: AccountLoader<'info, HolderSnapshot>,
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-#[instruction(object_id: u64)]
-pub struct MintObjectNft<'info> {
-    pub base: MintObjectNftBase<'info>,
-    pub metadata: MintObjectNftMetadata<'info>,
+#[derive(Accounts)]
+pub struct SnapshotHolders<'info> {
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub holder_snapshot: AccountLoader<'info, HolderSnapshot>,
+}
+
+/// Accounts for `check_mint`: the named accounts of `mint_object_nft` that
+/// its preconditions read, none of which need to sign or be writable.
+#[derive(Accounts)]
+pub struct CheckMint<'info> {
+    #[account(
+        seeds = [CONFIG_SEED, config.load()?.namespace.as_ref()],
+        bump = config.load()?.config_bump
+    )]
+    pub config: AccountLoader<'info, Config>,
+    #[account(
+        seeds = [AUTH_SEED, config.key().as_ref()],
+        bump = config.load()?.auth_bump,
+        has_one = config @ ErrorCode::InvalidConfig
+    )]
+    pub auth: Box<Account<'info, Auth>>,
+    /// CHECK: The wallet that will sign the mint as `minter`.
+    pub minter: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the config and object id.
+    pub object_manifest: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the manifest.
+    pub object_mint: UncheckedAccount<'info>,
+    /// CHECK: Checked like `mint_object_nft` checks it.
+    pub recipient_token_account: UncheckedAccount<'info>,
+    /// CHECK: Recipient can be any account.
+    pub recipient: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the object mint.
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the object mint.
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: Collection mint the object will be verified into.
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the collection mint and checked for ownership.
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Re-derived from the collection mint.
+    pub collection_master_edition: UncheckedAccount<'info>,
+    #[account(
+        seeds = [METADATA_DEFAULTS_SEED, config.key().as_ref()],
+        bump = metadata_defaults.bump
+    )]
+    pub metadata_defaults: Option<Box<Account<'info, MetadataDefaults>>>,
+    /// CHECK: Re-derived from the config and manifest creator.
+    pub creator_royalty: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetObjectManifest<'info> {
+    /// CHECK: Ownership and discriminator are verified by `read_manifest`.
+    pub object_manifest: UncheckedAccount<'info>,
+}
+
+/// Per-namespace settings. Zero-copy with explicit padding. The reserved
+/// bytes are spent, so new fields are appended and existing configs grow in
+/// place through `upgrade_config`, keyed on `layout_version`.
+#[account(zero_copy)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    pub authority: Pubkey,
+    pub namespace: Pubkey,
+    pub object_count: u64,
+    /// Number of object ids written to the index, which locates the next
+    /// page and slot.
+    pub indexed_object_count: u64,
+    /// Authority-curated categories, the first `category_count` entries are
+    /// in use.
+    pub categories: [u16; MAX_CATEGORIES],
+    pub config_bump: u8,
+    pub auth_bump: u8,
+    pub paused: u8,
+    pub category_count: u8,
+    /// When set, every mint CPIs the SPL Memo program with the object id and
+    /// manifest hash.
+    pub memo_on_mint: u8,
+    /// Set by `initialize_config_stats`; from then on mints, burns, and
+    /// manifest updates must pass the [`ConfigStats`] PDA.
+    pub stats_enabled: u8,
+    /// Set by `enable_object_index`; new objects are then appended to
+    /// [`ObjectIndexPage`] PDAs.
+    pub object_index_enabled: u8,
+    /// Set by `enable_minted_bitmap`; mints then set their bit in the
+    /// [`MintedBitmap`] chunk covering the object id.
+    pub minted_bitmap_enabled: u8,
+    /// Slots a manifest may stay unminted before `gc_manifest` can close it,
+    /// or zero to disable collection. Set with `set_manifest_gc_slots`.
+    pub manifest_gc_slots: u64,
+    /// When set, mints accept any SPL token account owned by the recipient
+    /// for the object mint instead of only the recipient's ATA. Set with
+    /// `set_non_ata_recipients`.
+    pub non_ata_recipients: u8,
+    /// Bitmask of `URI_SCHEME_*` values metadata URIs must use, or zero for
+    /// any scheme. Set with `set_uri_rules`.
+    pub allowed_uri_schemes: u8,
+    pub uri_prefix_lengths: [u8; MAX_URI_PREFIXES],
+    /// When set, `create_compressed_manifest` may store objects in Light
+    /// Protocol compressed accounts. Set with `set_compressed_manifests`.
+    pub compressed_manifests: u8,
+    /// Set by `init_manifest_changelog`; mints and manifest updates then
+    /// append a leaf to the config's [`ManifestChangelog`] tree.
+    pub changelog_enabled: u8,
+    /// Set while the config's [`MintPrice`] charges a USD price; mints then
+    /// take the price accounts.
+    pub usd_price_enabled: u8,
+    /// [`AssetBackend`] new objects are minted as. Set with
+    /// `set_asset_backend`.
+    pub asset_backend: u8,
+    /// When set, Token-2022 objects are minted with the companion transfer
+    /// hook, which keeps the manifest's cached owner current. Set with
+    /// `set_transfer_hook`.
+    pub transfer_hook_enabled: u8,
+    /// Set while the config's [`KycGate`] restricts at least one category;
+    /// mints of those categories then take a KYC attestation. Set with
+    /// `set_kyc_gate`.
+    pub kyc_gate_enabled: u8,
+    /// Layout the config was written with; see `upgrade_config`.
+    pub layout_version: u8,
+    /// Set by `init_audit_log`; authority-gated instructions must then pass
+    /// the config's [`AuditLog`].
+    pub audit_log_enabled: u8,
+    /// `INSTRUCTION_*` subsystems the authority has switched off. Stored
+    /// inverted so configs written before the field existed keep everything
+    /// enabled. Set with `set_enabled_instructions`.
+    pub disabled_instructions: u16,
+    /// Transfer fee, in basis points of each transfer, that new Token-2022
+    /// objects withhold for their creator, or zero for none. Set with
+    /// `set_transfer_fee_royalties`.
+    pub seller_fee_basis_points: u16,
+    /// Slots `request_object_recovery` must wait before `recover_object` can
+    /// move the object, or zero while recovery is off. While set, Token-2022
+    /// objects are minted with the auth PDA as permanent delegate. Set with
+    /// `set_recovery_delay_slots`.
+    pub recovery_delay_slots: u64,
+    /// Token Metadata program every metadata CPI must target, or zero for
+    /// Metaplex. Set with `set_metadata_program`.
+    pub metadata_program_id: Pubkey,
+    /// Domain prefixes metadata URIs must start with; unused slots have a
+    /// zero length, and no prefixes at all means any domain.
+    pub uri_prefixes: [[u8; MAX_URI_PREFIX_LENGTH]; MAX_URI_PREFIXES],
+    /// Wormhole core bridge `export_object` posts to, or zero while exports
+    /// are disabled. Set with `set_wormhole_program`.
+    pub wormhole_program: Pubkey,
+    /// Config whose authority may also pause this config and rotate its
+    /// authority, or zero. Set with `set_parent_config`. Occupies what used
+    /// to be the reserved tail.
+    pub parent_config: Pubkey,
+    /// When set, only the config authority and wallets with an
+    /// [`ApprovedCreator`] record may create new manifests. Set with
+    /// `set_creator_allowlist`. Added in layout version 2.
+    pub creator_allowlist_enabled: u8,
+    pub flag_reserved: [u8; 7],
+}
+
+impl Config {
+    pub const LEN: usize = 8 + core::mem::size_of::<Config>();
+    /// Account data offset (including the discriminator) of `authority`.
+    pub const AUTHORITY_OFFSET: usize = 8;
+    /// Account data offset (including the discriminator) of `namespace`.
+    pub const NAMESPACE_OFFSET: usize = 40;
+    /// Account data offset (including the discriminator) of
+    /// `indexed_object_count`.
+    pub const INDEXED_OBJECT_COUNT_OFFSET: usize = 80;
+    /// Account data offset (including the discriminator) of
+    /// `object_index_enabled`.
+    pub const OBJECT_INDEX_ENABLED_OFFSET: usize = 126;
+    /// Account data offset (including the discriminator) of
+    /// `minted_bitmap_enabled`.
+    pub const MINTED_BITMAP_ENABLED_OFFSET: usize = 127;
+    /// Account data offset (including the discriminator) of `layout_version`.
+    pub const LAYOUT_VERSION_OFFSET: usize = 146;
+    /// Account data offset (including the discriminator) of
+    /// `disabled_instructions`.
+    pub const DISABLED_INSTRUCTIONS_OFFSET: usize = 148;
+    /// Account data offset (including the discriminator) of `parent_config`.
+    pub const PARENT_CONFIG_OFFSET: usize = 352;
+
+    /// Decodes a config from raw account data, rejecting configs still in the
+    /// layout that predates `migrate_config_layout`.
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() > 8 && data[..8] == Config::discriminator(),
+            ErrorCode::InvalidConfig
+        );
+        require!(data.len() >= Config::LEN, ErrorCode::ConfigLayoutOutdated);
+        Ok(bytemuck::pod_read_unaligned(&data[8..Config::LEN]))
+    }
+
+    /// Rewrites a config written under an older layout into the current one.
+    /// Each layout change that needs a non-zero default adds a step here, so
+    /// a config several versions behind is brought forward one step at a
+    /// time.
+    pub fn upgrade_layout(&mut self) {
+        // Version 1 only introduces `layout_version` itself, in bytes that
+        // were already zeroed reserve. Version 2 appends
+        // `creator_allowlist_enabled`, which defaults to off.
+        self.layout_version = CONFIG_LAYOUT_VERSION;
+    }
+
+    pub fn is_category_enabled(&self, category: u16) -> bool {
+        category == 0
+            || self.categories[..self.category_count as usize]
+                .iter()
+                .any(|enabled| *enabled == category)
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    pub fn set_paused(&mut self, value: bool) {
+        self.paused = value.into();
+    }
+
+    /// The configured Token Metadata program, defaulting to Metaplex.
+    pub fn metadata_program(&self) -> Pubkey {
+        if self.metadata_program_id == Pubkey::default() {
+            mpl_program_id()
+        } else {
+            self.metadata_program_id
+        }
+    }
+
+    pub fn non_ata_recipients(&self) -> bool {
+        self.non_ata_recipients != 0
+    }
+
+    pub fn set_non_ata_recipients(&mut self, value: bool) {
+        self.non_ata_recipients = value.into();
+    }
+
+    pub fn compressed_manifests(&self) -> bool {
+        self.compressed_manifests != 0
+    }
+
+    pub fn set_compressed_manifests(&mut self, value: bool) {
+        self.compressed_manifests = value.into();
+    }
+
+    pub fn changelog_enabled(&self) -> bool {
+        self.changelog_enabled != 0
+    }
+
+    pub fn set_changelog_enabled(&mut self, value: bool) {
+        self.changelog_enabled = value.into();
+    }
+
+    pub fn usd_price_enabled(&self) -> bool {
+        self.usd_price_enabled != 0
+    }
+
+    pub fn set_usd_price_enabled(&mut self, value: bool) {
+        self.usd_price_enabled = value.into();
+    }
+
+    pub fn asset_backend(&self) -> Result<AssetBackend> {
+        AssetBackend::try_from(self.asset_backend)
+    }
+
+    pub fn set_asset_backend(&mut self, value: AssetBackend) {
+        self.asset_backend = value as u8;
+    }
+
+    pub fn transfer_hook_enabled(&self) -> bool {
+        self.transfer_hook_enabled != 0
+    }
+
+    pub fn set_transfer_hook_enabled(&mut self, value: bool) {
+        self.transfer_hook_enabled = value.into();
+    }
+
+    pub fn kyc_gate_enabled(&self) -> bool {
+        self.kyc_gate_enabled != 0
+    }
+
+    pub fn set_kyc_gate_enabled(&mut self, value: bool) {
+        self.kyc_gate_enabled = value.into();
+    }
+
+    pub fn audit_log_enabled(&self) -> bool {
+        self.audit_log_enabled != 0
+    }
+
+    pub fn set_audit_log_enabled(&mut self, value: bool) {
+        self.audit_log_enabled = value.into();
+    }
+
+    pub fn creator_allowlist_enabled(&self) -> bool {
+        self.creator_allowlist_enabled != 0
+    }
+
+    pub fn set_creator_allowlist_enabled(&mut self, value: bool) {
+        self.creator_allowlist_enabled = value.into();
+    }
+
+    pub fn enabled_instructions(&self) -> u16 {
+        INSTRUCTION_ALL & !self.disabled_instructions
+    }
+
+    pub fn set_enabled_instructions(&mut self, enabled: u16) {
+        self.disabled_instructions = INSTRUCTION_ALL & !enabled;
+    }
+
+    /// Fails with `InstructionDisabled` unless the `INSTRUCTION_*` subsystem
+    /// `flag` is enabled.
+    pub fn require_instruction_enabled(&self, flag: u16) -> Result<()> {
+        require!(
+            self.disabled_instructions & flag == 0,
+            ErrorCode::InstructionDisabled
+        );
+        Ok(())
+    }
+
+    pub fn uri_prefixes(&self) -> impl Iterator<Item = &[u8]> {
+        self.uri_prefixes
+            .iter()
+            .zip(self.uri_prefix_lengths)
+            .filter(|(_, len)| *len != 0)
+            .map(|(prefix, len)| &prefix[..len as usize])
+    }
+
+    pub fn set_uri_prefixes(&mut self, prefixes: &[String]) {
+        self.uri_prefixes = [[0u8; MAX_URI_PREFIX_LENGTH]; MAX_URI_PREFIXES];
+        self.uri_prefix_lengths = [0u8; MAX_URI_PREFIXES];
+        for (index, prefix) in prefixes.iter().enumerate() {
+            self.uri_prefixes[index][..prefix.len()].copy_from_slice(prefix.as_bytes());
+            self.uri_prefix_lengths[index] = prefix.len() as u8;
+        }
+    }
+
+    pub fn memo_on_mint(&self) -> bool {
+        self.memo_on_mint != 0
+    }
+
+    pub fn set_memo_on_mint(&mut self, value: bool) {
+        self.memo_on_mint = value.into();
+    }
+
+    pub fn stats_enabled(&self) -> bool {
+        self.stats_enabled != 0
+    }
+
+    pub fn set_stats_enabled(&mut self, value: bool) {
+        self.stats_enabled = value.into();
+    }
+
+    pub fn object_index_enabled(&self) -> bool {
+        self.object_index_enabled != 0
+    }
+
+    pub fn set_object_index_enabled(&mut self, value: bool) {
+        self.object_index_enabled = value.into();
+    }
+
+    pub fn minted_bitmap_enabled(&self) -> bool {
+        self.minted_bitmap_enabled != 0
+    }
+
+    pub fn set_minted_bitmap_enabled(&mut self, value: bool) {
+        self.minted_bitmap_enabled = value.into();
+    }
+}
+
+/// Borsh layout used by configs created before [`Config`] became zero-copy,
+/// read once by `migrate_config_layout`. Fields were only ever appended, so
+/// older, shorter accounts decode with the missing fields zeroed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+struct LegacyConfig {
+    authority: Pubkey,
+    config_bump: u8,
+    auth_bump: u8,
+    object_count: u64,
+    namespace: Pubkey,
+    paused: bool,
+    categories: [u16; MAX_CATEGORIES],
+    category_count: u8,
+    memo_on_mint: bool,
+    stats_enabled: bool,
+    object_index_enabled: bool,
+    indexed_object_count: u64,
+    minted_bitmap_enabled: bool,
+}
+
+impl LegacyConfig {
+    /// Serialized size without the discriminator.
+    const LEN: usize = 32 + 1 + 1 + 8 + 32 + 1 + 2 * MAX_CATEGORIES + 1 + 1 + 1 + 1 + 8 + 1;
+
+    fn from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() > 8 && data[..8] == Config::discriminator(),
+            ErrorCode::InvalidConfig
+        );
+        let mut stored = [0u8; LegacyConfig::LEN];
+        let available = (data.len() - 8).min(LegacyConfig::LEN);
+        stored[..available].copy_from_slice(&data[8..8 + available]);
+        LegacyConfig::deserialize(&mut &stored[..])
+            .map_err(|_| Error::from(ErrorCode::InvalidConfig))
+    }
+
+    fn write_to(&self, config: &mut Config) {
+        config.authority = self.authority;
+        config.namespace = self.namespace;
+        config.object_count = self.object_count;
+        config.indexed_object_count = self.indexed_object_count;
+        config.categories = self.categories;
+        config.config_bump = self.config_bump;
+        config.auth_bump = self.auth_bump;
+        config.set_paused(self.paused);
+        config.category_count = self.category_count;
+        config.set_memo_on_mint(self.memo_on_mint);
+        config.set_stats_enabled(self.stats_enabled);
+        config.set_object_index_enabled(self.object_index_enabled);
+        config.set_minted_bitmap_enabled(self.minted_bitmap_enabled);
+    }
+}
+
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Auth {
+    pub config: Pubkey,
+    pub bump: u8,
+}
+
+impl Auth {
+    pub const LEN: usize = 8 + 32 + 1;
+}
+
+/// Game server allowed to call `level_up` for any of a config's objects
+/// without the holder, set with `set_level_delegate`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelDelegate {
+    pub config: Pubkey,
+    /// Delegate key, or the default key while none is registered.
+    pub delegate: Pubkey,
+    pub bump: u8,
+}
+
+impl LevelDelegate {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+
+    pub fn is_delegate(&self, signer: &Pubkey) -> bool {
+        self.delegate != Pubkey::default() && self.delegate == *signer
+    }
+}
+
+/// Role allowed to grant experience points to a config's objects with
+/// `accrue_xp`, set with `set_xp_scorer`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XpScorer {
+    pub config: Pubkey,
+    /// Scorer key, or the default key while accrual is off.
+    pub scorer: Pubkey,
+    /// Most points a single `accrue_xp` call may grant.
+    pub max_xp_per_call: u64,
+    /// Most points one object may receive within a single slot.
+    pub max_xp_per_slot: u64,
+    pub bump: u8,
+}
+
+impl XpScorer {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn is_scorer(&self, signer: &Pubkey) -> bool {
+        self.scorer != Pubkey::default() && self.scorer == *signer
+    }
+}
+
+/// Running totals for a config, so dashboards can read a single account
+/// instead of scanning every manifest. Counts start when the PDA is created.
+#[account]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigStats {
+    pub config: Pubkey,
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub total_updates: u64,
+    pub last_mint_slot: u64,
+    /// Distinct manifest creators, counted through their [`CreatorRecord`].
+    pub unique_creators: u64,
+    pub bump: u8,
+}
+
+impl ConfigStats {
+    pub const LEN: usize = 8 + 32 + 8 * 5 + 1;
+
+    pub fn record_mint(&mut self, slot: u64) {
+        self.total_minted = self.total_minted.saturating_add(1);
+        self.last_mint_slot = slot;
+    }
+}
+
+/// Marks that `creator` has created a manifest under `config`, so
+/// [`ConfigStats::unique_creators`] only counts them once.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorRecord {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+impl CreatorRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+/// Manifest update queued by `schedule_manifest_update`, applied by
+/// `execute_scheduled_update` once `trigger_slot` is reached.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledUpdate {
+    pub manifest: Pubkey,
+    /// Holder who queued the update and receives the rent back.
+    pub scheduler: Pubkey,
+    pub trigger_slot: u64,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub is_active: bool,
+    pub arweave_tx_id: [u8; 32],
+    pub metadata_uri: String,
+    pub bump: u8,
+}
+
+impl ScheduledUpdate {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 1 + 1 + 32 + 4 + MAX_URI_LENGTH + 1;
+}
+
+/// Recovery queued by `request_object_recovery`, executed by
+/// `recover_object` once `executable_slot` is reached.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectRecovery {
+    pub manifest: Pubkey,
+    /// Token account the object is moved from.
+    pub source: Pubkey,
+    /// Token account the object is moved to.
+    pub destination: Pubkey,
+    /// Hash of the off-chain record justifying the recovery.
+    pub reason_hash: [u8; 32],
+    pub requested_slot: u64,
+    pub executable_slot: u64,
+    pub bump: u8,
+}
+
+impl ObjectRecovery {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// Holder vote on a config change, opened by the authority with
+/// `create_proposal`. Each object votes at most once, see [`HolderVote`].
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderProposal {
+    pub config: Pubkey,
+    pub proposal_id: u64,
+    /// Hash of the off-chain description of the proposed change.
+    pub description_hash: [u8; 32],
+    /// First slot at which votes are no longer accepted.
+    pub voting_ends_slot: u64,
+    /// Minimum number of votes cast, for and against, for the proposal to
+    /// pass.
+    pub quorum: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub finalized: bool,
+    /// Set by `finalize_proposal` when the proposal passed.
+    pub passed: bool,
+    pub bump: u8,
+}
+
+impl HolderProposal {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+
+    /// Whether the votes cast so far would pass the proposal: a strict
+    /// majority in favour with at least `quorum` votes cast.
+    pub fn passes(&self) -> bool {
+        self.yes_votes > self.no_votes
+            && self.yes_votes.saturating_add(self.no_votes) >= self.quorum
+    }
+}
+
+/// One object's vote on a [`HolderProposal`], derived from the proposal and
+/// the manifest so each object can vote only once.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderVote {
+    pub proposal: Pubkey,
+    pub manifest: Pubkey,
+    /// Holder that cast the vote.
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub bump: u8,
+}
+
+impl HolderVote {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 1;
+}
+
+/// Concurrent Merkle tree recording a leaf per manifest state of a config,
+/// so historical states can be proven without trusting the event stream.
+/// The PDA is the tree's authority.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestChangelog {
+    pub config: Pubkey,
+    pub tree: Pubkey,
+    pub bump: u8,
+}
+
+impl ManifestChangelog {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+/// USD mint price of a config and the Pyth feed used to convert it.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintPrice {
+    pub config: Pubkey,
+    /// Price per mint in USD cents; zero disables pricing.
+    pub usd_cents: u64,
+    pub treasury: Pubkey,
+    /// Pyth feed id of the SOL/USD price.
+    pub feed_id: [u8; 32],
+    /// Widest accepted confidence interval, in basis points of the price.
+    pub max_confidence_bps: u16,
+    pub max_price_age_secs: u32,
+    pub bump: u8,
+}
+
+impl MintPrice {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 2 + 4 + 1;
+}
+
+/// Categories a config restricts to KYC-attested recipients, and the Solana
+/// Attestation Service credential whose attestations clear them.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KycGate {
+    pub config: Pubkey,
+    /// Credential of the KYC issuer.
+    pub credential: Pubkey,
+    /// Schema attestations must use; the default key accepts any.
+    pub schema: Pubkey,
+    pub restricted_categories: [u16; MAX_CATEGORIES],
+    pub restricted_count: u8,
+    pub bump: u8,
+}
+
+impl KycGate {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 * MAX_CATEGORIES + 1 + 1;
+
+    pub fn restricted_categories(&self) -> &[u16] {
+        &self.restricted_categories[..self.restricted_count as usize]
+    }
+
+    pub fn is_restricted(&self, category: u16) -> bool {
+        self.restricted_categories().contains(&category)
+    }
+}
+
+/// Metadata values Token Metadata mints under a config fall back to, set with
+/// `set_metadata_defaults`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataDefaults {
+    pub config: Pubkey,
+    pub symbol: String,
+    pub seller_fee_basis_points: u16,
+    /// Creators template; the minting creator is appended unless listed.
+    pub creators: Vec<CreatorInput>,
+    pub bump: u8,
+}
+
+impl MetadataDefaults {
+    pub const LEN: usize =
+        8 + 32 + (4 + MAX_SYMBOL_LENGTH) + 2 + (4 + (MAX_CREATOR_LIMIT - 1) * (32 + 1 + 1)) + 1;
+
+    /// The creators template with `manifest_creator` appended, verified and
+    /// holding whatever share the template leaves unassigned.
+    pub fn creators_for(&self, manifest_creator: &Pubkey) -> Vec<CreatorInput> {
+        creators_with_minter(&self.creators, manifest_creator)
+    }
+}
+
+/// A royalty split the config authority has fixed for one creator, set with
+/// `set_creator_royalty`. It takes precedence over [`MetadataDefaults`] and
+/// the mint's own arguments on that creator's first mints.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorRoyalty {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub seller_fee_basis_points: u16,
+    /// Creators template, such as a platform wallet with a 20 share; the
+    /// creator is appended with the rest unless listed.
+    pub creators: Vec<CreatorInput>,
+    pub bump: u8,
+}
+
+impl CreatorRoyalty {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + (4 + (MAX_CREATOR_LIMIT - 1) * (32 + 1 + 1)) + 1;
+
+    /// The creators template with `manifest_creator` appended, as
+    /// [`MetadataDefaults::creators_for`] does.
+    pub fn creators_for(&self, manifest_creator: &Pubkey) -> Vec<CreatorInput> {
+        creators_with_minter(&self.creators, manifest_creator)
+    }
+}
+
+fn creators_with_minter(template: &[CreatorInput], manifest_creator: &Pubkey) -> Vec<CreatorInput> {
+    let mut creators = template.to_vec();
+    if !creators
+        .iter()
+        .any(|creator| creator.address == *manifest_creator)
+    {
+        let assigned: u16 = creators.iter().map(|creator| creator.share as u16).sum();
+        creators.push(CreatorInput {
+            address: *manifest_creator,
+            verified: true,
+            share: CREATOR_TOTAL_SHARE.saturating_sub(assigned) as u8,
+        });
+    }
+    creators
+}
+
+/// A creator's public identity under a config: a hash of their display
+/// name, the wallet their royalties are paid to, and whether the config
+/// authority has reviewed it. Set with `set_creator_profile`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorProfile {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub display_name_hash: [u8; 32],
+    pub payout_wallet: Pubkey,
+    /// Set by the config authority with `verify_creator_profile`.
+    pub verified: bool,
+    pub bump: u8,
+}
+
+impl CreatorProfile {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 1 + 1;
+}
+
+/// Lets `creator` create new manifests under `config` while its creator
+/// allowlist is on. Added with `add_approved_creator` and closed with
+/// `remove_approved_creator`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApprovedCreator {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+impl ApprovedCreator {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+
+    pub fn approves(&self, config: &Pubkey, creator: &Pubkey) -> bool {
+        self.config == *config && self.creator == *creator
+    }
+}
+
+/// Signer of the Switchboard function trusted to attest that a config's
+/// manifest URIs serve their content.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LivenessOracle {
+    pub config: Pubkey,
+    pub attester: Pubkey,
+    /// Slots an attestation stays usable by `verify_uri_liveness`, or zero
+    /// for no limit.
+    pub max_age_slots: u64,
+    pub bump: u8,
+}
+
+impl LivenessOracle {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Latest URI attestation for a manifest. `slot` gives marketplaces a
+/// freshness signal independent of the manifest's `uri_live` flag.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UriAttestation {
+    pub manifest: Pubkey,
+    pub attester: Pubkey,
+    pub manifest_hash: [u8; 32],
+    /// SHA-256 of the URI the attester fetched.
+    pub uri_hash: [u8; 32],
+    pub slot: u64,
+    pub bump: u8,
+}
+
+impl UriAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+}
+
+/// Link between a manifest and a Solana Attestation Service attestation
+/// about the object, created by the holder with `add_attestation`. The
+/// issuer, credential, schema, and expiry are copied from the attestation
+/// when it is linked.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestAttestation {
+    pub manifest: Pubkey,
+    pub attestation: Pubkey,
+    /// Signer of the attestation.
+    pub issuer: Pubkey,
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+    /// Unix timestamp after which the attestation lapses, or zero.
+    pub expiry: i64,
+    pub bump: u8,
+}
+
+impl ManifestAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 1;
+}
+
+/// Reference from a manifest to an object in another config, recorded by
+/// `link_external_object`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalObjectLink {
+    pub manifest: Pubkey,
+    pub foreign_config: Pubkey,
+    pub foreign_object_id: u64,
+    pub foreign_manifest: Pubkey,
+    pub linked_slot: u64,
+    pub bump: u8,
+}
+
+impl ExternalObjectLink {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 8 + 1;
+}
+
+/// Link from a manifest left behind by a namespace migration to the manifest
+/// `migrate_manifests` re-created under the new config.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestMigration {
+    pub old_config: Pubkey,
+    pub new_config: Pubkey,
+    pub old_manifest: Pubkey,
+    pub new_manifest: Pubkey,
+    pub object_id: u64,
+    pub migrated_slot: u64,
+    pub bump: u8,
+}
+
+impl ManifestMigration {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// Program-wide pause set with `set_global_pause`. While `paused`, mints and
+/// manifest updates fail in every namespace.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalPause {
+    pub paused: bool,
+    pub updated_by: Pubkey,
+    pub updated_slot: u64,
+    pub bump: u8,
+}
+
+impl GlobalPause {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 1;
+}
+
+/// Claim on an object id made with `reserve_object_id` before its content is
+/// uploaded. Until `expires_slot`, only `creator` can mint the object.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectReservation {
+    pub config: Pubkey,
+    pub object_id: u64,
+    pub creator: Pubkey,
+    pub reserved_slot: u64,
+    pub expires_slot: u64,
+    pub bump: u8,
+}
+
+impl ObjectReservation {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 8 + 1;
+
+    /// Whether the reservation stops `creator` from minting at `slot`.
+    pub fn blocks(&self, creator: &Pubkey, slot: u64) -> bool {
+        slot < self.expires_slot && *creator != self.creator
+    }
+}
+
+/// Batch of objects the config authority has assigned to recipients, minted
+/// by the permissionless `distribute_airdrop` crank. Assignments live
+/// off-chain as leaves of `merkle_root` (see [`airdrop_leaf`]).
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Airdrop {
+    pub config: Pubkey,
+    pub airdrop_id: u64,
+    /// Config authority that created the airdrop, recorded as the creator
+    /// of every object it mints.
+    pub authority: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub recipient_count: u32,
+    pub distributed_count: u32,
+    pub metadata_name: String,
+    pub metadata_symbol: String,
+    pub seller_fee_basis_points: u16,
+    pub category: u16,
+    pub bump: u8,
+    /// One bit per assignment index, set once it has been minted.
+    pub distributed: Vec<u8>,
+}
+
+impl Airdrop {
+    /// Account size for an airdrop of `recipient_count` assignments.
+    pub fn space(recipient_count: u32) -> usize {
+        8 + 32
+            + 8
+            + 32
+            + 32
+            + 4
+            + 4
+            + (4 + MAX_NAME_LENGTH)
+            + (4 + MAX_SYMBOL_LENGTH)
+            + 2
+            + 2
+            + 1
+            + (4 + (recipient_count as usize).div_ceil(8))
+    }
+
+    pub fn is_distributed(&self, index: u32) -> bool {
+        self.distributed
+            .get(index as usize / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Marks assignment `index` minted, failing if it is out of range or
+    /// already minted.
+    pub fn mark_distributed(&mut self, index: u32) -> Result<()> {
+        require!(index < self.recipient_count, ErrorCode::InvalidAirdropIndex);
+        require!(
+            !self.is_distributed(index),
+            ErrorCode::AirdropAlreadyDistributed
+        );
+        self.distributed[index as usize / 8] |= 1 << (index % 8);
+        self.distributed_count += 1;
+        Ok(())
+    }
+}
+
+/// Object escrowed by `create_gift` until someone reveals the preimage of
+/// `secret_hash` or the sender reclaims it after `expires_at`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gift {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub sender: Pubkey,
+    /// keccak256 of the 32-byte secret shared in the gift link.
+    pub secret_hash: [u8; 32],
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Gift {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+}
+
+/// Wormhole emitter on a foreign chain whose attestations `import_object`
+/// accepts for a config.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignEmitter {
+    pub config: Pubkey,
+    pub chain: u16,
+    pub address: [u8; 32],
+    pub bump: u8,
+}
+
+impl ForeignEmitter {
+    pub const LEN: usize = 8 + 32 + 2 + 32 + 1;
+}
+
+/// Marks a posted VAA as imported, so each attestation mints one object.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportReceipt {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub bump: u8,
+}
+
+impl ImportReceipt {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 8 + 1;
+}
+
+/// Manifest of an object stored in a Light Protocol compressed account rather
+/// than an [`ObjectManifest`] PDA. These objects have no mint. Only the
+/// account hash is kept on chain, so clients read the state from a Light
+/// indexer and pass it back to update it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedManifest {
+    pub config: Pubkey,
+    pub object_id: u64,
+    /// Wallet that signs updates to the manifest.
+    pub owner: Pubkey,
+    pub creator: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub created_slot: u64,
+    pub updated_slot: u64,
+    pub metadata_uri: String,
+}
+
+impl CompressedManifest {
+    /// Discriminator Light stores alongside the account data.
+    pub const DISCRIMINATOR: [u8; 8] = *b"ogal_cm1";
+
+    pub fn data(&self) -> Vec<u8> {
+        self.try_to_vec()
+            .expect("compressed manifests always serialize")
+    }
+}
+
+/// Mint counters for a single epoch of a config, created by the first mint of
+/// the epoch once the config tracks stats.
+#[account]
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochStats {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub total_minted: u64,
+    pub first_mint_slot: u64,
+    pub last_mint_slot: u64,
+    pub bump: u8,
+}
+
+impl EpochStats {
+    pub const LEN: usize = 8 + 32 + 8 * 4 + 1;
+
+    pub fn record_mint(&mut self, slot: u64) {
+        self.total_minted = self.total_minted.saturating_add(1);
+        self.last_mint_slot = slot;
+    }
+}
+
+/// Object manifest PDA data layout used by mint and update flows.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct ObjectManifest {
+    pub config: Pubkey,
+    pub object_id: u64,
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub mint_bump: u8,
+    pub is_active: u8,
+    pub minted: u8,
+    pub initialized: u8,
+    pub manifest_hash: [u8; 32],
+    pub metadata_uri: [u8; MAX_URI_LENGTH],
+    pub metadata_uri_padding: u8,
+    pub metadata_uri_length: u16,
+    pub creator: Pubkey,
+    /// Running hash chain over every recorded manifest hash, see
+    /// [`next_history_chain`].
+    pub history_chain: [u8; 32],
+    pub history_enabled: u8,
+    /// [`ManifestHashAlgorithm`] used to produce `manifest_hash`.
+    pub hash_algorithm: u8,
+    /// Set once `manifest_hash` has been checked against content supplied to
+    /// `verify_manifest_payload`; cleared when the hash changes.
+    pub verified_on_chain: u8,
+    /// [`ObjectOrigin`] describing how the object was created.
+    pub origin: u8,
+    /// Set once the object has been consumed by `fuse_objects` or
+    /// `split_object`.
+    pub burned: u8,
+    /// Number of populated entries in `provenance`.
+    pub provenance_count: u8,
+    /// Set once a [`CustodyTrail`] has been created for this manifest.
+    pub custody_trail_enabled: u8,
+    /// Set permanently by the holder through `lock_manifest`; the manifest
+    /// hash and URI can no longer change.
+    pub locked: u8,
+    /// Curated category id, see [`Config::categories`]. Stored at the fixed
+    /// account offset [`ObjectManifest::CATEGORY_OFFSET`] for memcmp filters.
+    pub category: u16,
+    /// Hash of a free-form tag, stored at [`ObjectManifest::TAG_HASH_OFFSET`].
+    pub tag_hash: [u8; 32],
+    /// Layout the manifest was written with, stored at
+    /// [`ObjectManifest::LAYOUT_VERSION_OFFSET`]; see `migrate_manifest_v2`.
+    pub layout_version: u8,
+    /// Set once `export_object` has burned the object and posted its
+    /// Wormhole attestation.
+    pub exported: u8,
+    /// Set once `inscribe_object` has written the manifest content to the
+    /// mint's Metaplex inscription, whose address derives from `mint`.
+    pub inscribed: u8,
+    /// Set by `verify_uri_liveness` from a current [`UriAttestation`];
+    /// cleared when the manifest hash or URI changes.
+    pub uri_live: u8,
+    /// [`AssetBackend`] the object was minted as. For Core objects `mint`
+    /// holds the Core asset address.
+    pub asset_backend: u8,
+    /// Set while the object sits in the config's vault after
+    /// `deposit_to_vault`. Occupies what used to be a reserved byte.
+    pub vaulted: u8,
+    /// Parent manifest when this object is linked into a composite object,
+    /// stored at [`ObjectManifest::PARENT_OFFSET`].
+    pub parent: Pubkey,
+    /// Levels of descendants linked below this object, raised by
+    /// `link_child_object` on the parent and its ancestors. Unlinking does
+    /// not lower it, so it is an upper bound.
+    pub subtree_height: u8,
+    pub link_reserved: [u8; 7],
+    /// Object ids (within the same config) this object was derived from.
+    pub provenance: [u64; MAX_PROVENANCE],
+    /// Stage of an evolving object, stored at [`ObjectManifest::LEVEL_OFFSET`]
+    /// and raised by `level_up`. Zero until the first level up.
+    pub level: u16,
+    pub level_reserved: [u8; 6],
+    /// Experience points granted by the config's [`XpScorer`] through
+    /// `accrue_xp`.
+    pub xp: u64,
+    /// Slot of the latest accrual and the points granted in it, which
+    /// enforce [`XpScorer::max_xp_per_slot`].
+    pub xp_slot: u64,
+    pub xp_slot_accrued: u64,
+    /// Unix timestamp after which the object is treated as inactive, or zero
+    /// when it never expires.
+    pub expires_at: i64,
+    /// Holder recorded at mint and refreshed by `sync_owner`, stored at
+    /// [`ObjectManifest::OWNER_OFFSET`].
+    pub owner: Pubkey,
+    /// Display name and symbol supplied at first mint, re-asserted on the
+    /// Metaplex metadata by `update_object_manifest`.
+    pub metadata_name: [u8; MAX_NAME_LENGTH],
+    pub metadata_symbol: [u8; MAX_SYMBOL_LENGTH],
+    pub metadata_name_length: u8,
+    pub metadata_symbol_length: u8,
+    pub display_reserved: [u8; 4],
+    /// Alternate locations (e.g. Arweave, IPFS, HTTPS) serving the same
+    /// content as `metadata_uri`, maintained by the holder.
+    pub mirror_uris: [[u8; MAX_URI_LENGTH]; MAX_MIRROR_URIS],
+    pub mirror_uri_lengths: [u16; MAX_MIRROR_URIS],
+    /// Set when `metadata_uri` is an `ipfs://` URI with a well-formed CID.
+    pub content_addressed: u8,
+    /// Set by `prepare_object_mint` until `finalize_object_mint` creates the
+    /// master edition and verifies the collection.
+    pub mint_pending: u8,
+    /// Arweave transaction holding the permanent copy of the manifest
+    /// content, or zero when none was supplied.
+    pub arweave_tx_id: [u8; 32],
+    /// Slot the manifest was created in, or zero for manifests created before
+    /// it was recorded. Occupies what used to be trailing padding, which
+    /// `upgrade_layout` clears on manifests from before layout version 2.
+    pub created_slot: u64,
+    /// Account that funded the manifest's rent when it was created, which
+    /// `gc_manifest` refunds. Zero on manifests created before it was
+    /// recorded, which refund their creator instead.
+    pub rent_payer: Pubkey,
+    /// Identifier of the foreign asset an imported object represents, and
+    /// the Wormhole chain id it came from. Both are zero for objects that
+    /// originated on Solana.
+    pub foreign_id: [u8; 32],
+    pub origin_chain: u16,
+    pub import_reserved: [u8; 6],
+    /// Token delegate the holder approved through
+    /// `approve_marketplace_delegate`, stored at
+    /// [`ObjectManifest::MARKETPLACE_DELEGATE_OFFSET`], and the marketplace
+    /// program it was approved for. Both are cleared on revoke and whenever
+    /// the recorded owner changes.
+    pub marketplace_delegate: Pubkey,
+    pub marketplace_program: Pubkey,
+    /// Number of share tokens minted by `fractionalize_object`, nonzero
+    /// while the object is fractionalized and escrowed in the config's vault.
+    pub share_supply: u64,
+    /// Hash of the DID the object is bound to, stored at
+    /// [`ObjectManifest::DID_HASH_OFFSET`], and the controller key that
+    /// co-signed `verify_did`. Both are zero when no DID is bound.
+    pub did_hash: [u8; 32],
+    pub did_controller: Pubkey,
+}
+
+impl ObjectManifest {
+    pub const LEN: usize = 8 + core::mem::size_of::<ObjectManifest>();
+    /// Account data offset (including the discriminator) of `config`.
+    pub const CONFIG_OFFSET: usize = 8;
+    /// Account data offset (including the discriminator) of `object_id`.
+    pub const OBJECT_ID_OFFSET: usize = 40;
+    /// Account data offset (including the discriminator) of `mint`.
+    pub const MINT_OFFSET: usize = 48;
+    /// Account data offset (including the discriminator) of `is_active`.
+    pub const IS_ACTIVE_OFFSET: usize = 82;
+    /// Account data offset (including the discriminator) of `minted`.
+    pub const MINTED_OFFSET: usize = 83;
+    /// Account data offset (including the discriminator) of `manifest_hash`.
+    pub const MANIFEST_HASH_OFFSET: usize = 85;
+    /// Account data offset (including the discriminator) of `creator`.
+    pub const CREATOR_OFFSET: usize = 248;
+    /// Account data offset (including the discriminator) of `origin`.
+    pub const ORIGIN_OFFSET: usize = 315;
+    /// Account data offset (including the discriminator) of `burned`.
+    pub const BURNED_OFFSET: usize = 316;
+    /// Account data offset (including the discriminator) of `locked`.
+    pub const LOCKED_OFFSET: usize = 319;
+    /// Account data offset (including the discriminator) of `category`.
+    pub const CATEGORY_OFFSET: usize = 320;
+    /// Account data offset (including the discriminator) of `tag_hash`.
+    pub const TAG_HASH_OFFSET: usize = 322;
+    /// Account data offset (including the discriminator) of `layout_version`.
+    pub const LAYOUT_VERSION_OFFSET: usize = 354;
+    /// Account data offset (including the discriminator) of `parent`.
+    pub const PARENT_OFFSET: usize = 360;
+    /// Account data offset (including the discriminator) of `level`.
+    pub const LEVEL_OFFSET: usize = 464;
+    /// Account data offset (including the discriminator) of `owner`.
+    pub const OWNER_OFFSET: usize = 504;
+    /// Account data offset (including the discriminator) of
+    /// `marketplace_delegate`.
+    pub const MARKETPLACE_DELEGATE_OFFSET: usize = 1088;
+    /// Account data offset (including the discriminator) of `did_hash`.
+    pub const DID_HASH_OFFSET: usize = 1160;
+
+    /// Copies a manifest out of raw account data, zero-filling fields that a
+    /// manifest created under an older, smaller layout does not have yet.
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        let mut manifest = ObjectManifest::zeroed();
+        manifest.copy_from_account_data(data)?;
+        Ok(manifest)
+    }
+
+    /// Overwrites `self` with the manifest in `data`, zero-filling fields
+    /// missing from older layouts.
+    pub fn copy_from_account_data(&mut self, data: &[u8]) -> Result<()> {
+        require!(
+            data.len() > 8 && data[..8] == ObjectManifest::discriminator(),
+            ErrorCode::InvalidManifestAccount
+        );
+
+        let stored = &data[8..];
+        let available = stored.len().min(core::mem::size_of::<ObjectManifest>());
+        let bytes = bytemuck::bytes_of_mut(self);
+        bytes[..available].copy_from_slice(&stored[..available]);
+        bytes[available..].fill(0);
+        Ok(())
+    }
+
+    /// Rewrites a manifest written under an older layout into the current
+    /// one. Each layout change adds a step here, so a manifest several
+    /// versions behind is brought forward one step at a time.
+    pub fn upgrade_layout(&mut self) {
+        if self.layout_version < 2 {
+            // Version 2 claims bytes that older programs left as padding, so
+            // clear whatever they held before the fields are given meaning.
+            self.metadata_uri_padding = 0;
+            self.exported = 0;
+            self.inscribed = 0;
+            self.uri_live = 0;
+            self.asset_backend = 0;
+            self.vaulted = 0;
+            self.display_reserved = [0u8; 4];
+            self.created_slot = 0;
+        }
+        // Versions 3 to 7 append the rent payer, import, marketplace
+        // delegate, share supply, and DID fields, which the realloc
+        // zero-fills.
+        self.layout_version = MANIFEST_LAYOUT_VERSION;
+    }
+
+    pub fn metadata_uri_len(&self) -> usize {
+        (self.metadata_uri_length as usize).min(MAX_URI_LENGTH)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active != 0
+    }
+
+    pub fn set_is_active(&mut self, value: bool) {
+        self.is_active = value.into();
+    }
+
+    pub fn custody_trail_enabled(&self) -> bool {
+        self.custody_trail_enabled != 0
+    }
+
+    pub fn set_custody_trail_enabled(&mut self, value: bool) {
+        self.custody_trail_enabled = value.into();
+    }
+
+    pub fn content_addressed(&self) -> bool {
+        self.content_addressed != 0
+    }
+
+    pub fn set_content_addressed(&mut self, value: bool) {
+        self.content_addressed = value.into();
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked != 0
+    }
+
+    pub fn set_locked(&mut self, value: bool) {
+        self.locked = value.into();
+    }
+
+    pub fn vaulted(&self) -> bool {
+        self.vaulted != 0
+    }
+
+    pub fn set_vaulted(&mut self, value: bool) {
+        self.vaulted = value.into();
+    }
+
+    pub fn fractionalized(&self) -> bool {
+        self.share_supply != 0
+    }
+
+    /// Whether `delegate` is the marketplace delegate the holder approved
+    /// through `approve_marketplace_delegate`. The default key is never
+    /// sanctioned.
+    pub fn is_marketplace_delegate(&self, delegate: &Pubkey) -> bool {
+        *delegate != Pubkey::default() && self.marketplace_delegate == *delegate
+    }
+
+    pub fn clear_marketplace_delegate(&mut self) {
+        self.marketplace_delegate = Pubkey::default();
+        self.marketplace_program = Pubkey::default();
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Whether the object is active and has not passed its expiry.
+    pub fn is_active_at(&self, now: i64) -> bool {
+        self.is_active() && !self.is_expired(now)
+    }
+
+    /// Account `gc_manifest` returns the rent to: the recorded rent payer, or
+    /// the creator on manifests that predate it.
+    pub fn rent_refund_account(&self) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            self.creator
+        } else {
+            self.rent_payer
+        }
+    }
+
+    /// Whether `transfer_manifest_creator` may hand the creator slot on: the
+    /// manifest exists but no mint has created its metadata yet.
+    pub fn creator_transferable(&self) -> bool {
+        self.initialized() && !self.minted() && !self.mint_pending() && !self.burned()
+    }
+
+    /// Whether `gc_manifest` may close this manifest at `slot`: it was never
+    /// minted or prepared, its creation slot is known, and `gc_slots` have
+    /// passed since.
+    pub fn is_collectable_at(&self, slot: u64, gc_slots: u64) -> bool {
+        gc_slots != 0
+            && self.initialized()
+            && !self.minted()
+            && !self.mint_pending()
+            && !self.burned()
+            && self.created_slot != 0
+            && slot >= self.created_slot.saturating_add(gc_slots)
+    }
+
+    pub fn minted(&self) -> bool {
+        self.minted != 0
+    }
+
+    pub fn set_minted(&mut self, value: bool) {
+        self.minted = value.into();
+    }
+
+    pub fn mint_pending(&self) -> bool {
+        self.mint_pending != 0
+    }
+
+    pub fn set_mint_pending(&mut self, value: bool) {
+        self.mint_pending = value.into();
+    }
+
+    pub fn initialized(&self) -> bool {
+        self.initialized != 0
+    }
+
+    pub fn set_initialized(&mut self, value: bool) {
+        self.initialized = value.into();
+    }
+
+    pub fn history_enabled(&self) -> bool {
+        self.history_enabled != 0
+    }
+
+    pub fn set_history_enabled(&mut self, value: bool) {
+        self.history_enabled = value.into();
+    }
+
+    /// Manifests created before the algorithm was recorded read back as
+    /// [`ManifestHashAlgorithm::Sha256`].
+    pub fn hash_algorithm(&self) -> Result<ManifestHashAlgorithm> {
+        ManifestHashAlgorithm::try_from(self.hash_algorithm)
+    }
+
+    pub fn set_hash_algorithm(&mut self, value: ManifestHashAlgorithm) {
+        self.hash_algorithm = value as u8;
+    }
+
+    pub fn verified_on_chain(&self) -> bool {
+        self.verified_on_chain != 0
+    }
+
+    pub fn set_verified_on_chain(&mut self, value: bool) {
+        self.verified_on_chain = value.into();
+    }
+
+    /// Manifests created before origins were recorded read back as
+    /// [`ObjectOrigin::Minted`].
+    pub fn origin(&self) -> Result<ObjectOrigin> {
+        ObjectOrigin::try_from(self.origin)
+    }
+
+    pub fn set_origin(&mut self, value: ObjectOrigin) {
+        self.origin = value as u8;
+    }
+
+    pub fn asset_backend(&self) -> Result<AssetBackend> {
+        AssetBackend::try_from(self.asset_backend)
+    }
+
+    pub fn set_asset_backend(&mut self, value: AssetBackend) {
+        self.asset_backend = value as u8;
+    }
+
+    pub fn burned(&self) -> bool {
+        self.burned != 0
+    }
+
+    pub fn set_burned(&mut self, value: bool) {
+        self.burned = value.into();
+    }
+
+    pub fn exported(&self) -> bool {
+        self.exported != 0
+    }
+
+    pub fn set_exported(&mut self, value: bool) {
+        self.exported = value.into();
+    }
+
+    pub fn inscribed(&self) -> bool {
+        self.inscribed != 0
+    }
+
+    pub fn set_inscribed(&mut self, value: bool) {
+        self.inscribed = value.into();
+    }
+
+    pub fn uri_live(&self) -> bool {
+        self.uri_live != 0
+    }
+
+    pub fn set_uri_live(&mut self, value: bool) {
+        self.uri_live = value.into();
+    }
+
+    pub fn provenance(&self) -> &[u64] {
+        &self.provenance[..self.provenance_count as usize]
+    }
+
+    pub fn set_provenance(&mut self, object_ids: &[u64]) {
+        self.provenance = [0u64; MAX_PROVENANCE];
+        self.provenance[..object_ids.len()].copy_from_slice(object_ids);
+        self.provenance_count = object_ids.len() as u8;
+    }
+
+    /// Adds `amount` points within `scorer`'s caps: at most
+    /// `max_xp_per_call` per call and `max_xp_per_slot` summed over the calls
+    /// in one slot.
+    pub fn accrue_xp(&mut self, amount: u64, slot: u64, scorer: &XpScorer) -> Result<()> {
+        require!(
+            amount != 0 && amount <= scorer.max_xp_per_call,
+            ErrorCode::XpCapExceeded
+        );
+        if self.xp_slot != slot {
+            self.xp_slot = slot;
+            self.xp_slot_accrued = 0;
+        }
+        let slot_accrued = self
+            .xp_slot_accrued
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        require!(
+            slot_accrued <= scorer.max_xp_per_slot,
+            ErrorCode::XpCapExceeded
+        );
+        self.xp = self
+            .xp
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.xp_slot_accrued = slot_accrued;
+        Ok(())
+    }
+
+    pub fn metadata_uri_equals(&self, uri: &str) -> bool {
+        self.metadata_uri_bytes() == uri.as_bytes()
+    }
+
+    /// Stored URIs that are not valid UTF-8 come back lossily converted, with
+    /// U+FFFD in place of the bad bytes; `repair_manifest_uri` rewrites them.
+    pub fn metadata_uri_string(&self) -> String {
+        String::from_utf8_lossy(self.metadata_uri_bytes()).into_owned()
+    }
+
+    pub fn metadata_uri_is_valid(&self) -> bool {
+        core::str::from_utf8(self.metadata_uri_bytes()).is_ok()
+    }
+
+    /// Callers validate `uri` against `MAX_URI_LENGTH` first; longer values
+    /// panic.
+    pub fn set_metadata_uri(&mut self, uri: &str) {
+        let bytes = uri.as_bytes();
+        let len = bytes.len();
+        self.metadata_uri[..len].copy_from_slice(bytes);
+        for byte in self.metadata_uri[len..].iter_mut() {
+            *byte = 0;
+        }
+        self.metadata_uri_padding = 0;
+        self.metadata_uri_length = len as u16;
+    }
+
+    pub fn metadata_name_string(&self) -> String {
+        String::from_utf8_lossy(&self.metadata_name[..self.metadata_name_length as usize])
+            .into_owned()
+    }
+
+    pub fn metadata_symbol_string(&self) -> String {
+        String::from_utf8_lossy(&self.metadata_symbol[..self.metadata_symbol_length as usize])
+            .into_owned()
+    }
+
+    /// Stores the display name and symbol; callers validate them against
+    /// `MAX_NAME_LENGTH` and `MAX_SYMBOL_LENGTH` first.
+    pub fn set_display_data(&mut self, name: &str, symbol: &str) {
+        self.metadata_name = [0u8; MAX_NAME_LENGTH];
+        self.metadata_name[..name.len()].copy_from_slice(name.as_bytes());
+        self.metadata_name_length = name.len() as u8;
+        self.metadata_symbol = [0u8; MAX_SYMBOL_LENGTH];
+        self.metadata_symbol[..symbol.len()].copy_from_slice(symbol.as_bytes());
+        self.metadata_symbol_length = symbol.len() as u8;
+    }
+
+    pub fn mirror_uri_strings(&self) -> Vec<String> {
+        self.mirror_uris
+            .iter()
+            .zip(self.mirror_uri_lengths.iter())
+            .filter(|(_, len)| **len != 0)
+            .map(|(uri, len)| String::from_utf8_lossy(&uri[..*len as usize]).into_owned())
+            .collect()
+    }
+
+    /// Replaces every mirror URI; callers validate count and lengths first.
+    pub fn set_mirror_uris(&mut self, uris: &[String]) {
+        self.mirror_uris = [[0u8; MAX_URI_LENGTH]; MAX_MIRROR_URIS];
+        self.mirror_uri_lengths = [0u16; MAX_MIRROR_URIS];
+        for (index, uri) in uris.iter().enumerate() {
+            self.mirror_uris[index][..uri.len()].copy_from_slice(uri.as_bytes());
+            self.mirror_uri_lengths[index] = uri.len() as u16;
+        }
+    }
+
+    /// Manifests minted before display data was recorded leave it empty.
+    pub fn has_display_data(&self) -> bool {
+        self.metadata_name_length != 0
+    }
+
+    fn metadata_uri_bytes(&self) -> &[u8] {
+        &self.metadata_uri[..self.metadata_uri_len()]
+    }
+}
+
+/// Append-only ring buffer of the most recent manifest updates, maintained for
+/// manifests whose owner opted in via `enable_manifest_history`.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct ManifestHistory {
+    pub manifest: Pubkey,
+    /// Total number of updates recorded; the next entry is written at
+    /// `total_updates % MANIFEST_HISTORY_CAPACITY`.
+    pub total_updates: u64,
+    pub bump: u8,
+    pub reserved: [u8; 7],
+    pub entries: [ManifestHistoryEntry; MANIFEST_HISTORY_CAPACITY],
+}
+
+impl ManifestHistory {
+    pub const LEN: usize = 8 + core::mem::size_of::<ManifestHistory>();
+
+    pub fn record(&mut self, entry: ManifestHistoryEntry) {
+        let index = (self.total_updates % MANIFEST_HISTORY_CAPACITY as u64) as usize;
+        self.entries[index] = entry;
+        self.total_updates = self.total_updates.saturating_add(1);
+    }
+}
+
+#[zero_copy]
+#[repr(C)]
+pub struct ManifestHistoryEntry {
+    pub slot: u64,
+    pub manifest_hash: [u8; 32],
+    pub uri_hash: [u8; 32],
+    pub actor: Pubkey,
+}
+
+/// Bounded admin trail of a config, appended to by every authority-gated
+/// instruction once `init_audit_log` has run. `head_hash` chains every entry
+/// ever recorded, so entries the ring has since overwritten stay committed
+/// to.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct AuditLog {
+    pub config: Pubkey,
+    /// Total number of entries recorded; the next entry is written at
+    /// `total_entries % AUDIT_LOG_CAPACITY`.
+    pub total_entries: u64,
+    /// keccak256 of the previous `head_hash` and the new entry's bytes, or
+    /// zero while the log is empty.
+    pub head_hash: [u8; 32],
+    pub bump: u8,
+    pub reserved: [u8; 7],
+    pub entries: [AuditEntry; AUDIT_LOG_CAPACITY],
+}
+
+impl AuditLog {
+    pub const LEN: usize = 8 + core::mem::size_of::<AuditLog>();
+
+    pub fn record(&mut self, entry: AuditEntry) {
+        self.head_hash = keccak::hashv(&[&self.head_hash, bytemuck::bytes_of(&entry)]).to_bytes();
+        let index = (self.total_entries % AUDIT_LOG_CAPACITY as u64) as usize;
+        self.entries[index] = entry;
+        self.total_entries = self.total_entries.saturating_add(1);
+    }
+}
+
+#[zero_copy]
+#[repr(C)]
+pub struct AuditEntry {
+    pub slot: u64,
+    /// Discriminator of the event the instruction emitted.
+    pub tag: [u8; 8],
+    /// The authority recorded in the event's context.
+    pub actor: Pubkey,
+    /// keccak256 of the event data as emitted in the `Program data:` log,
+    /// so the entry can be matched against the transaction's logs.
+    pub summary_hash: [u8; 32],
+}
+
+/// Bounded chain-of-custody log of the previous holders of an object, written
+/// whenever `sync_owner` observes a new owner.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct CustodyTrail {
+    pub manifest: Pubkey,
+    /// Total number of ownership changes recorded; the next entry is written
+    /// at `total_transfers % CUSTODY_TRAIL_CAPACITY`.
+    pub total_transfers: u64,
+    pub bump: u8,
+    pub reserved: [u8; 7],
+    pub entries: [CustodyEntry; CUSTODY_TRAIL_CAPACITY],
+}
+
+/// Namespace handover awaiting the new authority's signature, created by
+/// `transfer_namespace`.
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceTransfer {
+    pub config: Pubkey,
+    /// Authority that proposed the transfer; the proposal lapses once the
+    /// config's authority differs.
+    pub current_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub proposed_slot: u64,
+    pub bump: u8,
+}
+
+impl NamespaceTransfer {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
+
+/// Program-wide count of configs created by `initialize` and
+/// `migrate_config_namespace`, each listed in a [`NamespaceRegistryPage`].
+#[account]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceRegistry {
+    pub config_count: u64,
+    pub bump: u8,
+}
+
+impl NamespaceRegistry {
+    pub const LEN: usize = 8 + 8 + 1;
+}
+
+/// Fixed-capacity page of registered configs, in creation order. Page `n`
+/// holds the configs registered at positions
+/// `n * NAMESPACE_REGISTRY_PAGE_CAPACITY..`.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct NamespaceRegistryPage {
+    pub page: u32,
+    /// Number of populated entries in `entries`.
+    pub count: u32,
+    pub entries: [NamespaceEntry; NAMESPACE_REGISTRY_PAGE_CAPACITY],
+    pub bump: u8,
+    pub reserved: [u8; 7],
+}
+
+impl NamespaceRegistryPage {
+    pub const LEN: usize = 8 + core::mem::size_of::<NamespaceRegistryPage>();
+
+    pub fn entries(&self) -> &[NamespaceEntry] {
+        &self.entries[..self.count as usize]
+    }
+
+    pub fn push(&mut self, entry: NamespaceEntry) -> Result<()> {
+        let index = self.count as usize;
+        require!(
+            index < NAMESPACE_REGISTRY_PAGE_CAPACITY,
+            ErrorCode::InvalidNamespaceRegistryPage
+        );
+        self.entries[index] = entry;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// A config as it was registered. `authority` is not updated when the
+/// config's authority later changes.
+#[zero_copy]
+#[repr(C)]
+pub struct NamespaceEntry {
+    pub namespace: Pubkey,
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub created_slot: u64,
+}
+
+/// Fixed-capacity page of object ids, in creation order. Page `n` holds the
+/// ids indexed at positions `n * OBJECT_INDEX_PAGE_CAPACITY..`, so clients can
+/// enumerate a config by walking pages from zero until one is missing.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct ObjectIndexPage {
+    pub config: Pubkey,
+    pub page: u32,
+    /// Number of populated entries in `object_ids`.
+    pub count: u32,
+    pub object_ids: [u64; OBJECT_INDEX_PAGE_CAPACITY],
+    pub bump: u8,
+    pub reserved: [u8; 7],
+}
+
+impl ObjectIndexPage {
+    pub const LEN: usize = 8 + core::mem::size_of::<ObjectIndexPage>();
+
+    pub fn object_ids(&self) -> &[u64] {
+        &self.object_ids[..self.count as usize]
+    }
+
+    pub fn push(&mut self, object_id: u64) -> Result<()> {
+        let index = self.count as usize;
+        require!(
+            index < OBJECT_INDEX_PAGE_CAPACITY,
+            ErrorCode::InvalidObjectIndexPage
+        );
+        self.object_ids[index] = object_id;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// One bit per object id for ids `chunk * MINTED_BITMAP_BITS..`, set once the
+/// id has been minted. Bits are never cleared, so a set bit means the id was
+/// minted at some point even if the object was later burned.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct MintedBitmap {
+    pub config: Pubkey,
+    pub chunk: u64,
+    pub bump: u8,
+    pub reserved: [u8; 7],
+    pub bits: [u8; MINTED_BITMAP_BITS / 8],
+}
+
+impl MintedBitmap {
+    pub const LEN: usize = 8 + core::mem::size_of::<MintedBitmap>();
+
+    pub fn chunk_for(object_id: u64) -> u64 {
+        object_id / MINTED_BITMAP_BITS as u64
+    }
+
+    fn bit_position(&self, object_id: u64) -> Option<(usize, u8)> {
+        if Self::chunk_for(object_id) != self.chunk {
+            return None;
+        }
+        let bit = (object_id % MINTED_BITMAP_BITS as u64) as usize;
+        Some((bit / 8, 1 << (bit % 8)))
+    }
+
+    pub fn is_minted(&self, object_id: u64) -> bool {
+        self.bit_position(object_id)
+            .map(|(byte, mask)| self.bits[byte] & mask != 0)
+            .unwrap_or(false)
+    }
+
+    pub fn set_minted(&mut self, object_id: u64) -> Result<()> {
+        let (byte, mask) = self
+            .bit_position(object_id)
+            .ok_or(ErrorCode::InvalidMintedBitmap)?;
+        self.bits[byte] |= mask;
+        Ok(())
+    }
+}
+
+/// Point-in-time ownership set for a range of object ids, filled in by the
+/// `snapshot_holders` crank. Entry `i` belongs to object id
+/// `start_object_id + i` and is unset while its `slot` is zero.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct HolderSnapshot {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub snapshot_id: u64,
+    pub start_object_id: u64,
+    pub end_object_id: u64,
+    /// Number of entries recorded so far.
+    pub recorded_count: u32,
+    pub bump: u8,
+    pub reserved: [u8; 3],
+    pub entries: [HolderSnapshotEntry; HOLDER_SNAPSHOT_CAPACITY],
+}
+
+impl HolderSnapshot {
+    pub const LEN: usize = 8 + core::mem::size_of::<HolderSnapshot>();
+
+    pub fn record(&mut self, entry: HolderSnapshotEntry) -> Result<()> {
+        require!(
+            (self.start_object_id..=self.end_object_id).contains(&entry.object_id),
+            ErrorCode::InvalidSnapshotRange
+        );
+        let index = (entry.object_id - self.start_object_id) as usize;
+        require!(
+            self.entries[index].slot == 0,
+            ErrorCode::SnapshotEntryExists
+        );
+        self.entries[index] = entry;
+        self.recorded_count += 1;
+        Ok(())
+    }
+}
+
+#[zero_copy]
+#[repr(C)]
+pub struct HolderSnapshotEntry {
+    pub object_id: u64,
+    pub slot: u64,
+    pub owner: Pubkey,
+}
+
+impl CustodyTrail {
+    pub const LEN: usize = 8 + core::mem::size_of::<CustodyTrail>();
+
+    pub fn record(&mut self, entry: CustodyEntry) {
+        let index = (self.total_transfers % CUSTODY_TRAIL_CAPACITY as u64) as usize;
+        self.entries[index] = entry;
+        self.total_transfers = self.total_transfers.saturating_add(1);
+    }
+}
+
+#[zero_copy]
+#[repr(C)]
+pub struct CustodyEntry {
+    pub slot: u64,
+    pub previous_owner: Pubkey,
+}
+
+/// Hash function used to derive `manifest_hash` from the content referenced by
+/// the manifest URI, so verifiers know how to recompute it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ManifestHashAlgorithm {
+    Sha256 = 0,
+    Keccak256 = 1,
+    Blake3 = 2,
+}
+
+impl TryFrom<u8> for ManifestHashAlgorithm {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Sha256),
+            1 => Ok(Self::Keccak256),
+            2 => Ok(Self::Blake3),
+            _ => err!(ErrorCode::UnsupportedHashAlgorithm),
+        }
+    }
+}
+
+/// How an object came to exist, recorded on its manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ObjectOrigin {
+    Minted = 0,
+    Fused = 1,
+    Split = 2,
+    Imported = 3,
+}
+
+impl TryFrom<u8> for ObjectOrigin {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Minted),
+            1 => Ok(Self::Fused),
+            2 => Ok(Self::Split),
+            3 => Ok(Self::Imported),
+            _ => err!(ErrorCode::InvalidObjectOrigin),
+        }
+    }
+}
+
+/// How objects are issued on chain, chosen per config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AssetBackend {
+    /// An SPL mint with Token Metadata metadata and a master edition.
+    TokenMetadata = 0,
+    /// A single Metaplex Core asset in a Core collection.
+    Core = 1,
+    /// A Token-2022 mint holding its name, symbol, and URI in its own
+    /// metadata pointer and token metadata extensions.
+    Token2022 = 2,
+}
+
+impl TryFrom<u8> for AssetBackend {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::TokenMetadata),
+            1 => Ok(Self::Core),
+            2 => Ok(Self::Token2022),
+            _ => err!(ErrorCode::InvalidAssetBackend),
+        }
+    }
+}
+
+/// Scope of a Token Metadata delegate the auth PDA holds in place of the
+/// update authority.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MetadataDelegateScope {
+    /// Rewrites an object's name, symbol, and URI.
+    Data = 0,
+    /// Verifies items into a collection.
+    Collection = 1,
+}
+
+impl MetadataDelegateScope {
+    /// Role seed Token Metadata derives the delegate record with.
+    pub fn role_seed(self) -> &'static [u8] {
+        match self {
+            Self::Data => b"data_delegate",
+            Self::Collection => b"collection_delegate",
+        }
+    }
+}
+
+/// Manifest content staged across multiple transactions for
+/// `verify_manifest_payload`.
+#[account]
+pub struct ManifestPayloadBuffer {
+    pub manifest: Pubkey,
+    pub writer: Pubkey,
+    pub bump: u8,
+    pub data: Vec<u8>,
+}
+
+impl ManifestPayloadBuffer {
+    pub const fn space(data_len: usize) -> usize {
+        8 + 32 + 32 + 1 + 4 + data_len
+    }
+}
+
+/// When and by whom an event was produced, recorded on every event so
+/// indexers do not need to re-fetch the transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventContext {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    /// Account that paid for the instruction's fees or rent.
+    pub payer: Pubkey,
+    /// Account whose permission authorized the change: the config authority
+    /// for administrative and mint flows, the holder for owner actions, and
+    /// the caller for permissionless cranks.
+    pub authority: Pubkey,
+}
+
+impl EventContext {
+    /// Context for instructions where a single signer both pays and
+    /// authorizes.
+    pub fn signed_by(signer: Pubkey) -> Result<Self> {
+        Self::new(signer, signer)
+    }
+
+    pub fn new(payer: Pubkey, authority: Pubkey) -> Result<Self> {
+        let clock = Clock::get()?;
+        Ok(Self {
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            payer,
+            authority,
+        })
+    }
+}
+
+/// Stable, Borsh-serialized snapshot of a manifest returned by
+/// `get_object_manifest`. New fields are only ever appended, alongside a bump
+/// of `version`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestView {
+    pub version: u8,
+    pub config: Pubkey,
+    pub object_id: u64,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub owner: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub metadata_uri: String,
+    pub mirror_uris: Vec<String>,
+    pub arweave_tx_id: [u8; 32],
+    pub metadata_name: String,
+    pub metadata_symbol: String,
+    /// Whether the object is active and not past its expiry.
+    pub is_active: bool,
+    pub minted: bool,
+    pub burned: bool,
+    pub locked: bool,
+    pub content_addressed: bool,
+    pub origin: u8,
+    pub provenance: Vec<u64>,
+    pub category: u16,
+    pub tag_hash: [u8; 32],
+    pub parent: Pubkey,
+    pub expires_at: i64,
+}
+
+impl ManifestView {
+    pub fn from_manifest(manifest: &ObjectManifest, now: i64) -> Result<Self> {
+        Ok(Self {
+            version: MANIFEST_VIEW_VERSION,
+            config: manifest.config,
+            object_id: manifest.object_id,
+            mint: manifest.mint,
+            creator: manifest.creator,
+            owner: manifest.owner,
+            manifest_hash: manifest.manifest_hash,
+            hash_algorithm: manifest.hash_algorithm()? as u8,
+            metadata_uri: manifest.metadata_uri_string(),
+            mirror_uris: manifest.mirror_uri_strings(),
+            arweave_tx_id: manifest.arweave_tx_id,
+            metadata_name: manifest.metadata_name_string(),
+            metadata_symbol: manifest.metadata_symbol_string(),
+            is_active: manifest.is_active_at(now),
+            minted: manifest.minted(),
+            burned: manifest.burned(),
+            locked: manifest.locked(),
+            content_addressed: manifest.content_addressed(),
+            origin: manifest.origin()? as u8,
+            provenance: manifest.provenance().to_vec(),
+            category: manifest.category,
+            tag_hash: manifest.tag_hash,
+            parent: manifest.parent,
+            expires_at: manifest.expires_at,
+        })
+    }
+}
+
+/// Returned by `mint_object_nft` through the transaction return data, so CPI
+/// callers and simulating clients need not re-derive the PDAs.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintResult {
+    pub mint: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    /// False when an existing manifest was re-minted.
+    pub first_mint: bool,
+}
+
+/// Returned by `check_mint` through the transaction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintCheck {
+    /// True when every checked precondition passed.
+    pub ok: bool,
+    /// `ErrorCode` number of the first failed precondition, or zero.
+    pub error_code: u32,
+    /// Whether the mint would create the metadata and master edition. Only
+    /// meaningful when `ok` is set.
+    pub first_mint: bool,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectMinted {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub object_id: u64,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub metadata_uri: String,
+    pub creator: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestUpdated {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+    pub object_id: u64,
+    pub is_active: bool,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub metadata_uri: String,
+    pub creator: Pubkey,
+    pub previous_history_chain: [u8; 32],
+    pub history_chain: [u8; 32],
+    pub slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParentConfigUpdated {
+    pub config: Pubkey,
+    pub previous_parent: Pubkey,
+    pub parent: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseStatusUpdated {
+    pub config: Pubkey,
+    pub paused: bool,
+    pub previous_paused: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigInitialized {
+    pub config: Pubkey,
+    pub namespace: Pubkey,
+    pub authority: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorityUpdated {
+    pub config: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceTransferProposed {
+    pub config: Pubkey,
+    pub namespace: Pubkey,
+    pub current_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceTransferred {
+    pub config: Pubkey,
+    pub namespace: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub proposed_slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceTransferCancelled {
+    pub config: Pubkey,
+    pub new_authority: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GovernanceAuthoritySet {
+    pub config: Pubkey,
+    pub previous_authority: Pubkey,
+    pub governance_program: Pubkey,
+    pub governance: Pubkey,
+    pub native_treasury: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollectionAuthorityRotated {
+    pub config: Pubkey,
+    pub collection_mint: Pubkey,
+    pub previous_update_authority: Pubkey,
+    pub new_update_authority: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataDelegateApproved {
+    pub config: Pubkey,
+    pub mint: Pubkey,
+    /// [`MetadataDelegateScope`] the auth PDA was approved for.
+    pub scope: u8,
+    pub previous_update_authority: Pubkey,
+    pub update_authority: Pubkey,
+    pub delegate_record: Pubkey,
+    pub context: EventContext,
+}
+
+/// Emitted when `migrate_config_layout` rewrites a Borsh config in place.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigLayoutMigrated {
+    pub config: Pubkey,
+    /// Account size before the migration, including the discriminator.
+    pub previous_len: u64,
+    pub context: EventContext,
+}
+
+/// Emitted when `upgrade_config` brings a config up to the current layout.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigLayoutUpgraded {
+    pub config: Pubkey,
+    pub previous_version: u8,
+    pub layout_version: u8,
+    /// Account size before the upgrade, including the discriminator.
+    pub previous_len: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigNamespaceMigrated {
+    pub old_config: Pubkey,
+    pub new_config: Pubkey,
+    pub old_namespace: Pubkey,
+    pub new_namespace: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestMigrated {
+    pub old_config: Pubkey,
+    pub new_config: Pubkey,
+    pub object_id: u64,
+    pub old_manifest: Pubkey,
+    pub new_manifest: Pubkey,
+    pub closed: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalPauseUpdated {
+    pub global_pause: Pubkey,
+    pub previous_paused: bool,
+    pub paused: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestHistoryEnabled {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub history: Pubkey,
+    pub object_id: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestUpdateScheduled {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub scheduled_update: Pubkey,
+    pub scheduler: Pubkey,
+    pub trigger_slot: u64,
+    pub manifest_hash: [u8; 32],
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledUpdateCancelled {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub scheduled_update: Pubkey,
+    pub trigger_slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduledUpdateExecuted {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub scheduled_update: Pubkey,
+    pub trigger_slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectInscribed {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub inscription: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub inscription_len: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestPayloadVerified {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub verifier: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub payload_len: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CategoriesUpdated {
+    pub config: Pubkey,
+    pub previous_categories: Vec<u16>,
+    pub categories: Vec<u16>,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectLinked {
+    pub config: Pubkey,
+    pub parent: Pubkey,
+    pub child: Pubkey,
+    pub previous_parent: Pubkey,
+    pub parent_object_id: u64,
+    pub child_object_id: u64,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-#[instruction(object_id: u64)]
-pub struct MintObjectNftBase<'info> {
-    /// CHECK: The config account enforces this matches its stored authority.
-    pub authority: UncheckedAccount<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, config.namespace.as_ref()],
-        bump = config.config_bump,
-        has_one = authority @ ErrorCode::InvalidAuthority
-    )]
-    pub config: Box<Account<'info, Config>>,
-    #[account(
-        mut,
-        seeds = [AUTH_SEED, config.key().as_ref()],
-        bump = config.auth_bump,
-        has_one = config @ ErrorCode::InvalidConfig
-    )]
-    pub auth: Box<Account<'info, Auth>>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    /// CHECK: Created and size-checked within the instruction.
-    #[account(mut)]
-    pub object_manifest: UncheckedAccount<'info>,
-    /// CHECK: Created and initialized within the instruction.
-    #[account(mut)]
-    pub object_mint: UncheckedAccount<'info>,
-    /// CHECK: Created and verified within the instruction.
-    #[account(mut)]
-    pub recipient_token_account: UncheckedAccount<'info>,
-    /// CHECK: Recipient can be any account
-    pub recipient: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectUnlinked {
+    pub config: Pubkey,
+    pub parent: Pubkey,
+    pub child: Pubkey,
+    pub child_object_id: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalObjectLinked {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub foreign_config: Pubkey,
+    pub foreign_manifest: Pubkey,
+    pub foreign_object_id: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalObjectUnlinked {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub foreign_config: Pubkey,
+    pub foreign_manifest: Pubkey,
+    pub foreign_object_id: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectsFused {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+    pub object_id: u64,
+    pub consumed_object_ids: Vec<u64>,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectSplit {
+    pub config: Pubkey,
+    pub parent_manifest: Pubkey,
+    pub parent_object_id: u64,
+    pub part_object_ids: Vec<u64>,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelDelegateUpdated {
+    pub config: Pubkey,
+    pub previous_delegate: Pubkey,
+    pub delegate: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectLeveledUp {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub previous_level: u16,
+    pub level: u16,
+    /// Whether the level delegate signed rather than the holder.
+    pub by_delegate: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XpScorerUpdated {
+    pub config: Pubkey,
+    pub previous_scorer: Pubkey,
+    pub scorer: Pubkey,
+    pub max_xp_per_call: u64,
+    pub max_xp_per_slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XpAccrued {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub amount: u64,
+    /// Total points after the accrual.
+    pub xp: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectExpirySet {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub previous_expires_at: i64,
+    pub expires_at: i64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectIdReserved {
+    pub config: Pubkey,
+    pub reservation: Pubkey,
+    pub object_id: u64,
+    pub creator: Pubkey,
+    pub expires_slot: u64,
+    pub context: EventContext,
+}
+
+/// Emitted when a reservation is cancelled by its creator or closed after
+/// it lapsed.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectReservationReleased {
+    pub config: Pubkey,
+    pub reservation: Pubkey,
+    pub object_id: u64,
+    pub creator: Pubkey,
+    pub expired: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropCreated {
+    pub config: Pubkey,
+    pub airdrop: Pubkey,
+    pub airdrop_id: u64,
+    pub merkle_root: [u8; 32],
+    pub recipient_count: u32,
+    pub context: EventContext,
+}
+
+/// Emitted for each assignment `distribute_airdrop` or `claim_airdrop`
+/// mints, alongside the mint's own `ObjectMinted`.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropDistributed {
+    pub config: Pubkey,
+    pub airdrop: Pubkey,
+    pub index: u32,
+    pub object_id: u64,
+    pub recipient: Pubkey,
+    /// Whether the recipient minted it with `claim_airdrop`.
+    pub claimed: bool,
+    /// Assignments still to be minted.
+    pub remaining: u32,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GiftCreated {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub gift: Pubkey,
+    pub sender: Pubkey,
+    pub expires_at: i64,
+    pub context: EventContext,
+}
+
+/// Emitted when a gift is redeemed, or refunded to its sender after it
+/// expired.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GiftReleased {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub gift: Pubkey,
+    pub recipient: Pubkey,
+    pub refunded: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestLayoutMigrated {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub previous_version: u8,
+    pub layout_version: u8,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectExpired {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub expires_at: i64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnerSynced {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectRecoveryRequested {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub object_recovery: Pubkey,
+    pub source_owner: Pubkey,
+    pub destination_owner: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub executable_slot: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectRecoveryCancelled {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub object_recovery: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectRecovered {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub object_recovery: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub reason_hash: [u8; 32],
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoyaltiesHarvested {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub creator: Pubkey,
+    pub creator_token_account: Pubkey,
+    /// Units of the object withdrawn to the creator.
+    pub amount: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustodyTrailEnabled {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub custody_trail: Pubkey,
+    pub object_id: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderProposalCreated {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub description_hash: [u8; 32],
+    pub voting_ends_slot: u64,
+    pub quorum: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderVoteCast {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderProposalFinalized {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub passed: bool,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestLocked {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub manifest_hash: [u8; 32],
+    pub owner: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketplaceDelegateApproved {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub marketplace_program: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketplaceDelegateRevoked {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub marketplace_program: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectVaulted {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub depositor: Pubkey,
+    pub vault: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectWithdrawnFromVault {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub holder: Pubkey,
+    pub vault: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectFractionalized {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub owner: Pubkey,
+    pub share_mint: Pubkey,
+    pub shares: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectRedeemed {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub redeemer: Pubkey,
+    pub share_mint: Pubkey,
+    pub shares: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttestationAdded {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub attestation: Pubkey,
+    pub issuer: Pubkey,
+    pub schema: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttestationRemoved {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub attestation: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DidVerified {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub did_hash: [u8; 32],
+    pub controller: Pubkey,
+    pub owner: Pubkey,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DidCleared {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub did_hash: [u8; 32],
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MirrorUrisUpdated {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub mirror_uris: Vec<String>,
+    pub context: EventContext,
 }
 
-#[derive(Accounts, Clone)]
-/// Additional remaining accounts expected (in order):
-/// 0. Collection metadata PDA (mut)
-/// 1. Collection master edition PDA (mut)
-/// 2. Rent sysvar account
-/// 3. Instructions sysvar account (optional, unused for unsized collections)
-pub struct MintObjectNftMetadata<'info> {
-    #[account(mut)]
-    /// CHECK: Created via Metaplex CPI
-    pub metadata: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: Created via Metaplex CPI
-    pub master_edition: UncheckedAccount<'info>,
-    /// CHECK: Verified against expected seeds
-    pub collection_mint: UncheckedAccount<'info>,
-    /// CHECK: Verified to match the Metaplex token metadata program id
-    pub token_metadata_program: UncheckedAccount<'info>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataProgramUpdated {
+    pub config: Pubkey,
+    pub previous_program: Pubkey,
+    pub program: Pubkey,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-pub struct RotateCollectionAuthority<'info> {
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, config.namespace.as_ref()],
-        bump = config.config_bump,
-        has_one = authority @ ErrorCode::InvalidAuthority
-    )]
-    pub config: Box<Account<'info, Config>>,
-    #[account(
-        seeds = [AUTH_SEED, config.key().as_ref()],
-        bump = config.auth_bump,
-        has_one = config @ ErrorCode::InvalidConfig
-    )]
-    pub auth: Box<Account<'info, Auth>>,
-    #[account(mut)]
-    /// CHECK: Verified against derived PDA within the instruction
-    pub collection_metadata: UncheckedAccount<'info>,
-    /// CHECK: Only used for PDA derivation
-    pub collection_mint: UncheckedAccount<'info>,
-    /// CHECK: Validated to match the Metaplex token metadata program id
-    pub token_metadata_program: UncheckedAccount<'info>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonAtaRecipientsUpdated {
+    pub config: Pubkey,
+    pub previous_enabled: bool,
+    pub enabled: bool,
+    pub context: EventContext,
 }
 
-fn metadata_remaining_accounts<'info>(
-    remaining_accounts: &'info [AccountInfo<'info>],
-) -> Result<(
-    AccountInfo<'info>,
-    AccountInfo<'info>,
-    AccountInfo<'info>,
-    Option<AccountInfo<'info>>,
-    &'info [AccountInfo<'info>],
-)> {
-    require!(
-        remaining_accounts.len() >= 3,
-        ErrorCode::MissingMintMetadataAccounts
-    );
-
-    let mut extra_index = 3;
-    let instructions_sysvar_account = if let Some(account) = remaining_accounts.get(3) {
-        if account.key() == sysvar::instructions::id() {
-            extra_index = 4;
-            Some(account.clone())
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnabledInstructionsUpdated {
+    pub config: Pubkey,
+    /// `INSTRUCTION_*` bitmask before and after the change.
+    pub previous_enabled: u16,
+    pub enabled: u16,
+    pub context: EventContext,
+}
 
-    let extra_accounts = if extra_index < remaining_accounts.len() {
-        &remaining_accounts[extra_index..]
-    } else {
-        &[]
-    };
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferHookUpdated {
+    pub config: Pubkey,
+    pub previous_enabled: bool,
+    pub enabled: bool,
+    pub context: EventContext,
+}
 
-    Ok((
-        remaining_accounts[0].clone(),
-        remaining_accounts[1].clone(),
-        remaining_accounts[2].clone(),
-        instructions_sysvar_account,
-        extra_accounts,
-    ))
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferFeeRoyaltiesUpdated {
+    pub config: Pubkey,
+    pub previous_basis_points: u16,
+    pub basis_points: u16,
+    pub context: EventContext,
 }
 
-fn ensure_object_manifest_account<'info>(
-    manifest: &AccountInfo<'info>,
-    payer: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    program_id: &Pubkey,
-    signer_seeds: &[&[u8]],
-) -> Result<()> {
-    let rent = Rent::get()?;
-    let required_lamports = rent.minimum_balance(ObjectManifest::LEN);
-
-    if manifest.data_len() == 0 {
-        let create_ix = system_instruction::create_account(
-            payer.key,
-            manifest.key,
-            required_lamports,
-            ObjectManifest::LEN as u64,
-            program_id,
-        );
-        invoke_signed(
-            &create_ix,
-            &[payer.clone(), manifest.clone(), system_program.clone()],
-            &[signer_seeds],
-        )?;
-    } else {
-        require!(
-            *manifest.owner == *program_id,
-            ErrorCode::InvalidManifestAccount
-        );
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WormholeProgramUpdated {
+    pub config: Pubkey,
+    pub previous_program: Pubkey,
+    pub program: Pubkey,
+    pub context: EventContext,
+}
 
-        if manifest.lamports() < required_lamports {
-            let additional = required_lamports.saturating_sub(manifest.lamports());
-            **payer.try_borrow_mut_lamports()? -= additional;
-            **manifest.try_borrow_mut_lamports()? += additional;
-        }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectExported {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+    pub object_id: u64,
+    pub manifest_hash: [u8; 32],
+    pub owner: Pubkey,
+    /// Wormhole emitter and sequence identifying the posted message, which
+    /// together locate its VAA.
+    pub emitter: Pubkey,
+    pub sequence: u64,
+    pub context: EventContext,
+}
 
-        if manifest.data_len() < ObjectManifest::LEN {
-            manifest.realloc(ObjectManifest::LEN, true)?;
-        }
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintPriceUpdated {
+    pub config: Pubkey,
+    pub previous_usd_cents: u64,
+    pub usd_cents: u64,
+    pub treasury: Pubkey,
+    pub feed_id: [u8; 32],
+    pub max_confidence_bps: u16,
+    pub max_price_age_secs: u32,
+    pub context: EventContext,
+}
 
-    Ok(())
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KycGateUpdated {
+    pub config: Pubkey,
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+    pub previous_categories: Vec<u16>,
+    pub restricted_categories: Vec<u16>,
+    pub context: EventContext,
 }
 
-fn ensure_object_mint_account<'info>(
-    mint: &AccountInfo<'info>,
-    payer: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    signer_seeds: &[&[u8]],
-    authority: &AccountInfo<'info>,
-) -> Result<()> {
-    let rent = Rent::get()?;
-    let required_lamports = rent.minimum_balance(Mint::LEN);
-
-    if mint.data_len() == 0 {
-        let create_ix = system_instruction::create_account(
-            payer.key,
-            mint.key,
-            required_lamports,
-            Mint::LEN as u64,
-            &token::ID,
-        );
-        invoke_signed(
-            &create_ix,
-            &[payer.clone(), mint.clone(), system_program.clone()],
-            &[signer_seeds],
-        )?;
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataDefaultsUpdated {
+    pub config: Pubkey,
+    pub symbol: String,
+    pub seller_fee_basis_points: u16,
+    pub creator_count: u8,
+    pub context: EventContext,
+}
 
-        token::initialize_mint2(
-            CpiContext::new_with_signer(
-                token_program.clone(),
-                InitializeMint2 { mint: mint.clone() },
-                &[signer_seeds],
-            ),
-            0,
-            authority.key,
-            Some(authority.key),
-        )?;
-    } else {
-        require!(
-            mint.owner == &token::ID,
-            ErrorCode::InvalidObjectMintAccount
-        );
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorRoyaltyUpdated {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub seller_fee_basis_points: u16,
+    pub creator_count: u8,
+    pub context: EventContext,
+}
 
-    if mint.lamports() < required_lamports {
-        let additional = required_lamports.saturating_sub(mint.lamports());
-        **payer.try_borrow_mut_lamports()? -= additional;
-        **mint.try_borrow_mut_lamports()? += additional;
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorRoyaltyCleared {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub context: EventContext,
+}
 
-    Ok(())
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorProfileUpdated {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub profile: Pubkey,
+    pub display_name_hash: [u8; 32],
+    pub payout_wallet: Pubkey,
+    pub verified: bool,
+    pub context: EventContext,
 }
 
-fn ensure_recipient_token_account<'info>(
-    token_account: &AccountInfo<'info>,
-    authority: &AccountInfo<'info>,
-    payer: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    associated_token_program: &AccountInfo<'info>,
-    mint: &AccountInfo<'info>,
-) -> Result<()> {
-    if token_account.data_len() == 0 {
-        let cpi_accounts = associated_token::Create {
-            payer: payer.clone(),
-            associated_token: token_account.clone(),
-            authority: authority.clone(),
-            mint: mint.clone(),
-            system_program: system_program.clone(),
-            token_program: token_program.clone(),
-        };
-        associated_token::create(CpiContext::new(
-            associated_token_program.clone(),
-            cpi_accounts,
-        ))?;
-    } else {
-        require!(
-            token_account.owner == &token::ID,
-            ErrorCode::InvalidRecipientTokenAccount
-        );
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorProfileVerified {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub profile: Pubkey,
+    pub verified: bool,
+    pub context: EventContext,
+}
 
-    Ok(())
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorAllowlistUpdated {
+    pub config: Pubkey,
+    pub previous_enabled: bool,
+    pub enabled: bool,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-pub struct UpdateObjectManifest<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, config.namespace.as_ref()],
-        bump = config.config_bump,
-    )]
-    pub config: Account<'info, Config>,
-    #[account(
-        seeds = [AUTH_SEED, config.key().as_ref()],
-        bump = config.auth_bump,
-        has_one = config @ ErrorCode::InvalidConfig
-    )]
-    pub auth: Account<'info, Auth>,
-    #[account(mut)]
-    pub object_manifest: AccountLoader<'info, ObjectManifest>,
-    pub object_mint: Account<'info, Mint>,
-    pub owner_token_account: Account<'info, TokenAccount>,
-    /// CHECK: Verified against the expected Metaplex metadata PDA
-    #[account(mut)]
-    pub object_metadata: UncheckedAccount<'info>,
-    /// CHECK: Validated to match the Metaplex token metadata program id
-    pub metadata_program: UncheckedAccount<'info>,
-    pub rent: Sysvar<'info, Rent>,
-    /// CHECK: Optional sysvar, only used when present
-    pub instructions: Option<AccountInfo<'info>>,
+/// Emitted when a creator is added to or removed from the config's creator
+/// allowlist.
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreatorApprovalUpdated {
+    pub config: Pubkey,
+    pub creator: Pubkey,
+    pub approved: bool,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-pub struct SetAuthority<'info> {
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, config.namespace.as_ref()],
-        bump = config.config_bump,
-        has_one = authority @ ErrorCode::InvalidAuthority
-    )]
-    pub config: Account<'info, Config>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintPricePaid {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub treasury: Pubkey,
+    pub usd_cents: u64,
+    pub lamports: u64,
+    pub price: i64,
+    pub exponent: i32,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-pub struct SetPaused<'info> {
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, config.namespace.as_ref()],
-        bump = config.config_bump,
-        has_one = authority @ ErrorCode::InvalidAuthority
-    )]
-    pub config: Account<'info, Config>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LivenessOracleUpdated {
+    pub config: Pubkey,
+    pub previous_attester: Pubkey,
+    pub attester: Pubkey,
+    pub max_age_slots: u64,
+    pub context: EventContext,
 }
 
-#[derive(Accounts)]
-#[instruction(new_namespace: Pubkey)]
-pub struct MigrateConfigNamespace<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED, old_config.namespace.as_ref()],
-        bump = old_config.config_bump,
-        has_one = authority @ ErrorCode::InvalidAuthority
-    )]
-    pub old_config: Account<'info, Config>,
-    #[account(
-        init,
-        payer = authority,
-        space = Config::LEN,
-        seeds = [CONFIG_SEED, new_namespace.as_ref()],
-        bump
-    )]
-    pub new_config: Account<'info, Config>,
-    #[account(
-        seeds = [AUTH_SEED, old_config.key().as_ref()],
-        bump = old_config.auth_bump,
-        constraint = old_auth.config == old_config.key() @ ErrorCode::InvalidConfig
-    )]
-    pub old_auth: Account<'info, Auth>,
-    #[account(
-        init,
-        payer = authority,
-        space = Auth::LEN,
-        seeds = [AUTH_SEED, new_config.key().as_ref()],
-        bump
-    )]
-    pub new_auth: Account<'info, Auth>,
-    pub system_program: Program<'info, System>,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UriAttestationRecorded {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub attester: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub uri_hash: [u8; 32],
+    pub slot: u64,
+    pub context: EventContext,
 }
 
-#[account]
-pub struct Config {
-    pub authority: Pubkey,
-    pub config_bump: u8,
-    pub auth_bump: u8,
-    pub object_count: u64,
-    pub namespace: Pubkey,
-    pub paused: bool,
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UriLivenessVerified {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub attester: Pubkey,
+    pub attested_slot: u64,
+    pub context: EventContext,
 }
 
-impl Config {
-    pub const LEN: usize = 8 + 32 + 1 + 1 + 8 + 32 + 1;
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignEmitterRegistered {
+    pub config: Pubkey,
+    pub chain: u16,
+    pub previous_address: [u8; 32],
+    pub address: [u8; 32],
+    pub context: EventContext,
 }
 
-#[account]
-pub struct Auth {
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectImported {
     pub config: Pubkey,
-    pub bump: u8,
+    pub manifest: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub object_id: u64,
+    pub origin_chain: u16,
+    pub foreign_id: [u8; 32],
+    pub manifest_hash: [u8; 32],
+    /// Sequence of the imported VAA from the registered emitter.
+    pub sequence: u64,
+    pub context: EventContext,
 }
 
-impl Auth {
-    pub const LEN: usize = 8 + 32 + 1;
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedManifestsUpdated {
+    pub config: Pubkey,
+    pub previous_enabled: bool,
+    pub enabled: bool,
+    pub context: EventContext,
 }
 
-/// Object manifest PDA data layout used by mint and update flows.
-#[account(zero_copy)]
-#[repr(C)]
-pub struct ObjectManifest {
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedManifestCreated {
     pub config: Pubkey,
+    /// Compressed address of the manifest's account.
+    pub address: [u8; 32],
     pub object_id: u64,
-    pub mint: Pubkey,
-    pub bump: u8,
-    pub mint_bump: u8,
-    pub is_active: u8,
-    pub minted: u8,
-    pub initialized: u8,
-    pub manifest_hash: [u8; 32],
-    pub metadata_uri: [u8; MAX_URI_LENGTH],
-    pub metadata_uri_padding: u8,
-    pub metadata_uri_length: u16,
+    pub owner: Pubkey,
     pub creator: Pubkey,
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub metadata_uri: String,
+    pub context: EventContext,
 }
 
-impl ObjectManifest {
-    pub const LEN: usize = 8 + core::mem::size_of::<ObjectManifest>() + MANIFEST_PADDING;
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompressedManifestUpdated {
+    pub config: Pubkey,
+    pub address: [u8; 32],
+    pub object_id: u64,
+    pub previous_manifest_hash: [u8; 32],
+    pub manifest_hash: [u8; 32],
+    pub hash_algorithm: u8,
+    pub metadata_uri: String,
+    pub context: EventContext,
+}
 
-    pub fn metadata_uri_len(&self) -> usize {
-        self.metadata_uri_length as usize
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestChangelogInitialized {
+    pub config: Pubkey,
+    pub changelog: Pubkey,
+    pub tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub context: EventContext,
+}
 
-    pub fn is_active(&self) -> bool {
-        self.is_active != 0
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UriRulesUpdated {
+    pub config: Pubkey,
+    pub previous_allowed_schemes: u8,
+    pub allowed_schemes: u8,
+    pub prefixes: Vec<String>,
+    pub context: EventContext,
+}
 
-    pub fn set_is_active(&mut self, value: bool) {
-        self.is_active = value.into();
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestGcSlotsUpdated {
+    pub config: Pubkey,
+    pub previous_slots: u64,
+    pub slots: u64,
+    pub context: EventContext,
+}
 
-    pub fn minted(&self) -> bool {
-        self.minted != 0
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestCollected {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub creator: Pubkey,
+    /// Account the rent was returned to.
+    pub rent_payer: Pubkey,
+    /// Rent returned to `rent_payer`.
+    pub lamports: u64,
+    pub context: EventContext,
+}
 
-    pub fn set_minted(&mut self, value: bool) {
-        self.minted = value.into();
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestCreatorTransferred {
+    pub config: Pubkey,
+    pub manifest: Pubkey,
+    pub object_id: u64,
+    pub previous_creator: Pubkey,
+    pub creator: Pubkey,
+    pub context: EventContext,
+}
 
-    pub fn initialized(&self) -> bool {
-        self.initialized != 0
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintMemoUpdated {
+    pub config: Pubkey,
+    pub previous_enabled: bool,
+    pub enabled: bool,
+    pub context: EventContext,
+}
 
-    pub fn set_initialized(&mut self, value: bool) {
-        self.initialized = value.into();
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetBackendUpdated {
+    pub config: Pubkey,
+    pub previous_backend: u8,
+    pub backend: u8,
+    pub context: EventContext,
+}
 
-    pub fn metadata_uri_equals(&self, uri: &str) -> bool {
-        self.metadata_uri_str() == uri
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryDelayUpdated {
+    pub config: Pubkey,
+    pub previous_slots: u64,
+    pub slots: u64,
+    pub context: EventContext,
+}
 
-    pub fn metadata_uri_string(&self) -> String {
-        self.metadata_uri_str().to_string()
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigStatsInitialized {
+    pub config: Pubkey,
+    pub config_stats: Pubkey,
+    pub context: EventContext,
+}
 
-    pub fn set_metadata_uri(&mut self, uri: &str) {
-        let bytes = uri.as_bytes();
-        let len = bytes.len();
-        self.metadata_uri[..len].copy_from_slice(bytes);
-        for byte in self.metadata_uri[len..].iter_mut() {
-            *byte = 0;
-        }
-        self.metadata_uri_padding = 0;
-        self.metadata_uri_length = len as u16;
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditLogInitialized {
+    pub config: Pubkey,
+    pub audit_log: Pubkey,
+    pub context: EventContext,
+}
 
-    fn metadata_uri_str(&self) -> &str {
-        let len = self.metadata_uri_len();
-        // Safety: the URI bytes are always written from a valid UTF-8 string via
-        // `set_metadata_uri`.
-        unsafe { core::str::from_utf8_unchecked(&self.metadata_uri[..len]) }
-    }
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectIndexEnabled {
+    pub config: Pubkey,
+    /// Objects created before the index was enabled are not listed in it.
+    pub object_count: u64,
+    pub context: EventContext,
 }
 
 #[event]
-pub struct ObjectMinted {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintedBitmapEnabled {
     pub config: Pubkey,
-    pub manifest: Pubkey,
-    pub mint: Pubkey,
-    pub recipient: Pubkey,
-    pub object_id: u64,
+    pub context: EventContext,
 }
 
 #[event]
-pub struct ManifestUpdated {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RentDustSwept {
+    pub config: Pubkey,
+    pub destination: Pubkey,
+    /// Number of accounts passed to the sweep, including those with no dust.
+    pub accounts: u32,
+    pub lamports: u64,
+    pub context: EventContext,
+}
+
+#[event]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestUriRepaired {
     pub config: Pubkey,
     pub manifest: Pubkey,
-    pub mint: Pubkey,
     pub object_id: u64,
-    pub is_active: bool,
+    pub metadata_uri: String,
+    pub context: EventContext,
 }
 
 #[event]
-pub struct PauseStatusUpdated {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HolderSnapshotCreated {
     pub config: Pubkey,
-    pub paused: bool,
+    pub holder_snapshot: Pubkey,
+    pub snapshot_id: u64,
+    pub start_object_id: u64,
+    pub end_object_id: u64,
+    pub context: EventContext,
+}
+
+log_record_layouts! {
+    ConfigInitialized(e) => "init" [e.config, e.namespace, e.authority];
+    AuthorityUpdated(e) => "authority" [e.config, e.new_authority];
+    GovernanceAuthoritySet(e) => "governance_authority" [e.config, e.governance, e.native_treasury];
+    NamespaceTransferProposed(e) => "namespace_transfer"
+        [e.config, e.current_authority, e.new_authority];
+    NamespaceTransferred(e) => "namespace_transferred"
+        [e.config, e.previous_authority, e.new_authority];
+    NamespaceTransferCancelled(e) => "namespace_transfer_cancel" [e.config, e.new_authority];
+    CollectionAuthorityRotated(e) => "collection_authority" [
+        e.config,
+        e.collection_mint,
+        e.new_update_authority,
+    ];
+    MetadataDelegateApproved(e) => "metadata_delegate" [e.config, e.mint, e.scope, e.update_authority];
+    ConfigNamespaceMigrated(e) => "migrate" [e.old_config, e.new_config, e.new_namespace];
+    ManifestMigrated(e) => "manifest_migrate" [e.new_config, e.object_id, e.closed];
+    ConfigLayoutMigrated(e) => "config_layout" [e.config, e.previous_len];
+    ConfigLayoutUpgraded(e) => "config_upgrade" [e.config, e.previous_version, e.layout_version];
+    PauseStatusUpdated(e) => "pause" [e.config, e.paused];
+    GlobalPauseUpdated(e) => "global_pause" [e.global_pause, e.paused];
+    ParentConfigUpdated(e) => "parent_config" [e.config, e.parent];
+    CategoriesUpdated(e) => "categories" [e.config, e.categories.len()];
+    MintMemoUpdated(e) => "memo" [e.config, e.enabled];
+    AssetBackendUpdated(e) => "asset_backend" [e.config, e.backend];
+    RecoveryDelayUpdated(e) => "recovery_delay" [e.config, e.slots];
+    NonAtaRecipientsUpdated(e) => "non_ata" [e.config, e.enabled];
+    CompressedManifestsUpdated(e) => "compressed" [e.config, e.enabled];
+    EnabledInstructionsUpdated(e) => "instructions" [e.config, e.enabled];
+    TransferHookUpdated(e) => "transfer_hook" [e.config, e.enabled];
+    TransferFeeRoyaltiesUpdated(e) => "transfer_fee" [e.config, e.basis_points];
+    UriRulesUpdated(e) => "uri_rules" [e.config, e.allowed_schemes];
+    ManifestChangelogInitialized(e) => "changelog" [e.config, e.tree];
+    WormholeProgramUpdated(e) => "wormhole_program" [e.config, e.program];
+    ForeignEmitterRegistered(e) => "foreign_emitter" [e.config, e.chain, Hex(&e.address)];
+    MintPriceUpdated(e) => "mint_price" [e.config, e.usd_cents, e.treasury];
+    MintPricePaid(e) => "mint_paid" [e.config, e.object_id, e.usd_cents, e.lamports];
+    KycGateUpdated(e) => "kyc_gate" [e.config, e.credential, e.restricted_categories.len()];
+    MetadataDefaultsUpdated(e) => "metadata_defaults"
+        [e.config, e.seller_fee_basis_points, e.creator_count];
+    CreatorRoyaltyUpdated(e) => "creator_royalty"
+        [e.config, e.creator, e.seller_fee_basis_points, e.creator_count];
+    CreatorRoyaltyCleared(e) => "creator_royalty_clear" [e.config, e.creator];
+    CreatorProfileUpdated(e) => "creator_profile"
+        [e.config, e.creator, e.payout_wallet, e.verified];
+    CreatorProfileVerified(e) => "creator_verified" [e.config, e.creator, e.verified];
+    CreatorAllowlistUpdated(e) => "creator_allowlist" [e.config, e.enabled];
+    CreatorApprovalUpdated(e) => "creator_approval" [e.config, e.creator, e.approved];
+    LivenessOracleUpdated(e) => "liveness_oracle" [e.config, e.attester, e.max_age_slots];
+    UriAttestationRecorded(e) => "uri_attestation" [e.config, e.object_id, e.slot];
+    UriLivenessVerified(e) => "uri_live" [e.config, e.object_id, e.attested_slot];
+    MetadataProgramUpdated(e) => "metadata_program" [e.config, e.program];
+    ManifestGcSlotsUpdated(e) => "gc_slots" [e.config, e.slots];
+    ManifestCollected(e) => "gc" [e.config, e.object_id, e.creator];
+    ManifestCreatorTransferred(e) => "creator_transfer"
+        [e.config, e.object_id, e.previous_creator, e.creator];
+    ConfigStatsInitialized(e) => "stats" [e.config, e.config_stats];
+    AuditLogInitialized(e) => "audit_log" [e.config, e.audit_log];
+    ObjectIndexEnabled(e) => "object_index" [e.config, e.object_count];
+    MintedBitmapEnabled(e) => "minted_bitmap" [e.config];
+    RentDustSwept(e) => "rent_sweep" [e.config, e.destination, e.lamports];
+    ObjectMinted(e) => "mint" [e.config, e.object_id, e.mint, e.recipient];
+    ManifestUpdated(e) => "update" [
+        e.config,
+        e.object_id,
+        e.mint,
+        e.is_active,
+        Hex(&e.manifest_hash),
+    ];
+    ManifestHistoryEnabled(e) => "history" [e.config, e.object_id, e.history];
+    ManifestPayloadVerified(e) => "payload_verified" [
+        e.config,
+        e.object_id,
+        Hex(&e.manifest_hash),
+    ];
+    ManifestUpdateScheduled(e) => "schedule" [e.config, e.object_id, e.trigger_slot];
+    ScheduledUpdateCancelled(e) => "schedule_cancel" [e.config, e.object_id];
+    ScheduledUpdateExecuted(e) => "schedule_execute" [e.config, e.object_id, e.trigger_slot];
+    ObjectInscribed(e) => "inscribe" [e.config, e.object_id, e.inscription];
+    ObjectLinked(e) => "link" [e.config, e.parent_object_id, e.child_object_id];
+    ExternalObjectLinked(e) => "external_link"
+        [e.config, e.object_id, e.foreign_config, e.foreign_object_id];
+    ExternalObjectUnlinked(e) => "external_unlink"
+        [e.config, e.object_id, e.foreign_config, e.foreign_object_id];
+    ObjectUnlinked(e) => "unlink" [e.config, e.child_object_id];
+    ObjectsFused(e) => "fuse" [e.config, e.object_id, e.consumed_object_ids.len()];
+    ObjectSplit(e) => "split" [e.config, e.parent_object_id, e.part_object_ids.len()];
+    ObjectExpirySet(e) => "expiry" [e.config, e.object_id, e.expires_at];
+    ObjectIdReserved(e) => "reserve" [e.config, e.object_id, e.creator, e.expires_slot];
+    ObjectReservationReleased(e) => "reservation_release" [e.config, e.object_id, e.expired];
+    AirdropCreated(e) => "airdrop" [e.config, e.airdrop_id, e.recipient_count];
+    AirdropDistributed(e) => "airdrop_mint"
+        [e.config, e.index, e.object_id, e.recipient, e.claimed];
+    ObjectExpired(e) => "expired" [e.config, e.object_id];
+    ManifestLayoutMigrated(e) => "manifest_layout" [e.config, e.object_id, e.layout_version];
+    OwnerSynced(e) => "owner" [e.config, e.object_id, e.owner];
+    ObjectRecoveryRequested(e) => "recovery_request" [e.config, e.object_id, e.executable_slot];
+    ObjectRecoveryCancelled(e) => "recovery_cancel" [e.config, e.object_id];
+    ObjectRecovered(e) => "recover" [e.config, e.object_id, e.owner];
+    RoyaltiesHarvested(e) => "royalties" [e.config, e.object_id, e.creator, e.amount];
+    CustodyTrailEnabled(e) => "custody" [e.config, e.object_id];
+    HolderProposalCreated(e) => "proposal" [e.config, e.proposal_id, e.voting_ends_slot, e.quorum];
+    HolderVoteCast(e) => "vote" [e.config, e.proposal, e.object_id, e.approve];
+    HolderProposalFinalized(e) => "proposal_final" [
+        e.config,
+        e.proposal_id,
+        e.yes_votes,
+        e.no_votes,
+        e.passed,
+    ];
+    ManifestLocked(e) => "lock" [e.config, e.object_id, Hex(&e.manifest_hash)];
+    LevelDelegateUpdated(e) => "level_delegate" [e.config, e.delegate];
+    ObjectLeveledUp(e) => "level_up" [e.config, e.object_id, e.level, e.by_delegate];
+    XpScorerUpdated(e) => "xp_scorer"
+        [e.config, e.scorer, e.max_xp_per_call, e.max_xp_per_slot];
+    XpAccrued(e) => "xp" [e.config, e.object_id, e.amount, e.xp];
+    MarketplaceDelegateApproved(e) => "marketplace_delegate" [
+        e.config,
+        e.object_id,
+        e.delegate,
+        e.marketplace_program,
+    ];
+    MarketplaceDelegateRevoked(e) => "marketplace_revoke" [e.config, e.object_id, e.delegate];
+    ObjectVaulted(e) => "vault_deposit" [e.config, e.object_id, e.depositor];
+    ObjectWithdrawnFromVault(e) => "vault_withdraw" [e.config, e.object_id, e.holder];
+    GiftCreated(e) => "gift" [e.config, e.object_id, e.sender, e.expires_at];
+    GiftReleased(e) => "gift_release" [e.config, e.object_id, e.recipient, e.refunded];
+    ObjectFractionalized(e) => "fractionalize" [e.config, e.object_id, e.share_mint, e.shares];
+    ObjectRedeemed(e) => "redeem" [e.config, e.object_id, e.redeemer];
+    AttestationAdded(e) => "attestation" [e.config, e.object_id, e.attestation, e.issuer];
+    AttestationRemoved(e) => "attestation_remove" [e.config, e.object_id, e.attestation];
+    DidVerified(e) => "did" [e.config, e.object_id, Hex(&e.did_hash), e.controller];
+    DidCleared(e) => "did_clear" [e.config, e.object_id];
+    MirrorUrisUpdated(e) => "mirrors" [e.config, e.object_id, e.mirror_uris.len()];
+    ManifestUriRepaired(e) => "uri_repair" [e.config, e.object_id];
+    ObjectExported(e) => "export" [e.config, e.object_id, e.owner, e.sequence];
+    ObjectImported(e) => "import" [
+        e.config,
+        e.object_id,
+        e.origin_chain,
+        Hex(&e.foreign_id),
+        e.sequence,
+    ];
+    CompressedManifestCreated(e) => "compressed_create" [
+        e.config,
+        e.object_id,
+        e.owner,
+        Hex(&e.address),
+    ];
+    CompressedManifestUpdated(e) => "compressed_update" [
+        e.config,
+        e.object_id,
+        Hex(&e.manifest_hash),
+    ];
+    HolderSnapshotCreated(e) => "snapshot" [
+        e.config,
+        e.snapshot_id,
+        e.start_object_id,
+        e.end_object_id,
+    ];
 }
 
 #[error_code]
@@ -1510,8 +19223,864 @@ pub enum ErrorCode {
     InvalidRecipientTokenAccount,
     #[msg("All verified metadata creators must sign the transaction.")]
     CreatorMustSign,
+    #[msg("The manifest history account must be supplied for this manifest.")]
+    ManifestHistoryRequired,
+    #[msg("The manifest history account does not belong to the supplied manifest.")]
+    InvalidManifestHistoryAccount,
+    #[msg("The manifest hash algorithm is not supported.")]
+    UnsupportedHashAlgorithm,
+    #[msg("The payload buffer does not belong to the supplied manifest and writer.")]
+    InvalidPayloadBuffer,
+    #[msg("The staged manifest payload exceeds the permitted size.")]
+    PayloadTooLarge,
+    #[msg("The supplied payload does not hash to the recorded manifest hash.")]
+    ManifestPayloadMismatch,
+    #[msg("Too many categories supplied for the configuration.")]
+    TooManyCategories,
+    #[msg("Categories must be non-zero and unique.")]
+    InvalidCategory,
+    #[msg("The requested category is not enabled for this configuration.")]
+    CategoryNotEnabled,
+    #[msg("The config already uses the zero-copy layout.")]
+    ConfigAlreadyMigrated,
+    #[msg("Linking these objects would create a cycle.")]
+    ObjectLinkCycle,
+    #[msg("The object hierarchy exceeds the permitted depth.")]
+    ObjectHierarchyTooDeep,
+    #[msg("Every ancestor manifest of the parent must be supplied in order.")]
+    MissingAncestorAccounts,
+    #[msg("The object is not linked to a parent.")]
+    ObjectNotLinked,
+    #[msg("Unknown object origin.")]
+    InvalidObjectOrigin,
+    #[msg("Derived objects must be minted into a new manifest.")]
+    ObjectAlreadyExists,
+    #[msg("Fusion requires between 2 and 8 consumed objects.")]
+    InvalidFusionInputCount,
+    #[msg("Each consumed object requires its manifest, mint, and token account.")]
+    MissingConsumedObjectAccounts,
+    #[msg("The same object cannot be consumed twice.")]
+    DuplicateConsumedObject,
+    #[msg("Splitting requires between 2 and 4 parts.")]
+    InvalidSplitPartCount,
+    #[msg(
+        "Each additional part requires its manifest, mint, token, metadata, and edition accounts."
+    )]
+    MissingSplitPartAccounts,
+    #[msg("Split parts must use distinct object ids.")]
+    DuplicateSplitPart,
+    #[msg("Levels can only be raised.")]
+    InvalidLevel,
+    #[msg("Level ups need the level delegate, or the holder with the config authority.")]
+    LevelUpUnauthorized,
+    #[msg("The signer is not the config's XP scorer.")]
+    InvalidXpScorer,
+    #[msg("The per-call XP cap cannot exceed the per-slot cap.")]
+    InvalidXpCaps,
+    #[msg("The XP amount is zero or exceeds the scorer's caps.")]
+    XpCapExceeded,
+    #[msg("Expiry timestamps cannot be negative.")]
+    InvalidExpiry,
+    #[msg("The object has expired and cannot be reactivated.")]
+    ObjectExpired,
+    #[msg("The object has not reached its expiry.")]
+    ObjectNotExpired,
+    #[msg("The manifest's custody trail must be supplied.")]
+    CustodyTrailRequired,
+    #[msg("The custody trail does not belong to this manifest.")]
+    InvalidCustodyTrailAccount,
+    #[msg("The manifest has been locked by its owner.")]
+    ManifestLocked,
+    #[msg("A manifest can carry at most three mirror URIs.")]
+    TooManyMirrorUris,
+    #[msg("Mirror URIs cannot be empty.")]
+    InvalidMirrorUri,
+    #[msg("The ipfs:// URI does not contain a well-formed CID.")]
+    InvalidIpfsCid,
+    #[msg("Arweave URIs require the Arweave transaction id.")]
+    MissingArweaveTxId,
+    #[msg("The SPL Memo program account must follow the mint sysvars when memos are enabled.")]
+    MissingMemoProgram,
+    #[msg("The config stats, creator record, and epoch stats accounts are required once stats are enabled.")]
+    ConfigStatsRequired,
+    #[msg("The config's audit log is required once it has been initialized.")]
+    AuditLogRequired,
+    #[msg("The config stats or epoch stats account does not belong to this config.")]
+    InvalidConfigStatsAccount,
+    #[msg("The creator record is not the PDA for this config and creator.")]
+    InvalidCreatorRecord,
+    #[msg("The object index page for the next index entry is required once the index is enabled.")]
+    ObjectIndexPageRequired,
+    #[msg("The object index page does not match the next index entry for this config.")]
+    InvalidObjectIndexPage,
+    #[msg("The object index is already enabled for this config.")]
+    ObjectIndexAlreadyEnabled,
+    #[msg(
+        "The minted bitmap chunk covering the object id is required once the bitmap is enabled."
+    )]
+    MintedBitmapRequired,
+    #[msg("The minted bitmap chunk does not cover this object id for this config.")]
+    InvalidMintedBitmap,
+    #[msg("The minted bitmap is already enabled for this config.")]
+    MintedBitmapAlreadyEnabled,
+    #[msg("The minted bitmap is not enabled for this config.")]
+    MintedBitmapNotEnabled,
+    #[msg("The object has not been minted yet.")]
+    ObjectNotMinted,
+    #[msg("Snapshots cover at most 64 consecutive object ids.")]
+    InvalidSnapshotRange,
+    #[msg("Snapshot cranks take (manifest, mint, holder token account) triplets.")]
+    MissingSnapshotAccounts,
+    #[msg("The object has already been recorded in this snapshot.")]
+    SnapshotEntryExists,
+    #[msg("The object's first mint is waiting for finalize_object_mint.")]
+    MintPending,
+    #[msg("The object has no prepared mint to finalize.")]
+    MintNotPending,
+    #[msg("prepare_object_mint only applies to objects that have never been minted.")]
+    ObjectAlreadyMinted,
+    #[msg("The config predates the zero-copy layout; run migrate_config_layout first.")]
+    ConfigLayoutOutdated,
+    #[msg("The config already uses the current layout.")]
+    ConfigAlreadyUpgraded,
+    #[msg("The same account was passed for two writable mint accounts.")]
+    DuplicateMintAccount,
+    #[msg("The stored manifest URI is not valid UTF-8; repair it with repair_manifest_uri.")]
+    ManifestUriCorrupted,
+    #[msg("The stored manifest URI is valid and does not need repair.")]
+    ManifestUriNotCorrupted,
+    #[msg("The manifest already uses the current layout.")]
+    ManifestAlreadyMigrated,
+    #[msg("object_id must match the first split part.")]
+    SplitObjectIdMismatch,
+    #[msg("Rent accounts must be writable accounts owned by this program.")]
+    InvalidRentAccount,
+    #[msg("Only the config and the accounts scoped to it can be topped up or swept.")]
+    RentAccountOutsideConfig,
+    #[msg("Manifest garbage collection is disabled for this config.")]
+    ManifestGcDisabled,
+    #[msg(
+        "The manifest was minted, has no recorded creation slot, or is still within its window."
+    )]
+    ManifestNotCollectable,
+    #[msg("The refund account does not match the manifest's rent payer.")]
+    RentPayerMismatch,
+    #[msg("The same address appears more than once in the creator list.")]
+    DuplicateCreator,
+    #[msg("Unverified creators must have a non-zero share.")]
+    ZeroCreatorShare,
+    #[msg("The auth PDA cannot be listed as a creator.")]
+    AuthCreatorNotAllowed,
+    #[msg("Recipient token accounts other than the ATA must exist before minting.")]
+    RecipientTokenAccountMissing,
+    #[msg("Metadata URI contains control characters")]
+    UriContainsControlCharacters,
+    #[msg("Metadata URI scheme is not allowed by the config")]
+    UriSchemeNotAllowed,
+    #[msg("Metadata URI does not start with an allowed prefix")]
+    UriPrefixNotAllowed,
+    #[msg("Unknown URI scheme bits")]
+    InvalidUriSchemes,
+    #[msg("Too many URI prefixes")]
+    TooManyUriPrefixes,
+    #[msg("URI prefixes must be non-empty and at most 64 bytes")]
+    InvalidUriPrefix,
+    #[msg("No Wormhole core bridge is configured for exports")]
+    WormholeNotConfigured,
+    #[msg("Wormhole account does not match the configured core bridge")]
+    InvalidWormholeAccount,
+    #[msg("Posted VAA account is malformed")]
+    InvalidVaa,
+    #[msg("Foreign emitter is not registered for this config and chain")]
+    InvalidForeignEmitter,
+    #[msg("Import payload is malformed")]
+    InvalidImportPayload,
+    #[msg("Recipient does not match the import attestation")]
+    ImportRecipientMismatch,
+    #[msg("Import receipt account does not match the posted VAA")]
+    InvalidImportReceipt,
+    #[msg("This VAA has already been imported")]
+    VaaAlreadyImported,
+    #[msg("Compressed manifests are not enabled for this config")]
+    CompressedManifestsDisabled,
+    #[msg("Light Protocol account does not match the expected program")]
+    InvalidLightAccount,
+    #[msg("Light Protocol tree index is outside the remaining accounts")]
+    InvalidLightTreeIndex,
+    #[msg("The signer is not the compressed manifest's owner")]
+    CompressedManifestOwnerMismatch,
+    #[msg("The config keeps a changelog; pass the changelog accounts")]
+    ChangelogRequired,
+    #[msg("Changelog account does not match the config")]
+    InvalidChangelogAccount,
+    #[msg("Inscription account does not match the object mint")]
+    InvalidInscriptionAccount,
+    #[msg("Object content is already inscribed")]
+    ObjectAlreadyInscribed,
+    #[msg("Trigger slot must be in the future")]
+    InvalidTriggerSlot,
+    #[msg("Scheduled update has not reached its trigger slot")]
+    ScheduledUpdateNotDue,
+    #[msg("Scheduled update does not match the scheduler")]
+    InvalidScheduledUpdate,
+    #[msg("Signer is not the registered liveness attester")]
+    InvalidLivenessAttester,
+    #[msg("URI attestation does not match the manifest hash and URI")]
+    UriAttestationMismatch,
+    #[msg("URI attestation is older than the oracle allows")]
+    UriAttestationStale,
+    #[msg("The config charges a USD mint price; pass the price accounts")]
+    MintPriceRequired,
+    #[msg("Mint price confidence, age, or treasury settings are invalid")]
+    InvalidMintPrice,
+    #[msg("Treasury does not match the mint price")]
+    InvalidTreasury,
+    #[msg("Price update is not a verified Pyth price for the configured feed")]
+    InvalidPriceUpdate,
+    #[msg("Price update is older than the mint price allows")]
+    PriceUpdateStale,
+    #[msg("Price confidence interval is wider than the mint price allows")]
+    PriceConfidenceTooWide,
+    #[msg("Mint price does not fit in lamports")]
+    MintPriceOverflow,
+    #[msg("The object's category requires a KYC attestation for the recipient")]
+    KycAttestationRequired,
+    #[msg("KYC gate needs an issuer credential")]
+    InvalidKycGate,
+    #[msg("Attestation was not issued under the KYC gate's credential and schema")]
+    InvalidKycAttestation,
+    #[msg("Unknown asset backend")]
+    InvalidAssetBackend,
+    #[msg("The config mints objects with a different asset backend")]
+    WrongAssetBackend,
+    #[msg("Core collection is not governed by the config's auth PDA")]
+    InvalidCoreCollection,
+    #[msg("Core program does not match Metaplex Core")]
+    InvalidCoreProgram,
+    #[msg("Delegate record is not the auth PDA's Token Metadata delegate record")]
+    InvalidMetadataDelegateRecord,
+    #[msg("Metadata belongs to another update authority")]
+    InvalidMetadataUpdateAuthority,
+    #[msg("Metadata is delegated; pass the delegate record and instructions sysvar")]
+    MetadataDelegateRequired,
+    #[msg("Object recovery is disabled for this configuration.")]
+    RecoveryDisabled,
+    #[msg("The object mint does not name the auth PDA as its permanent delegate.")]
+    ObjectNotRecoverable,
+    #[msg("The token accounts do not match the queued recovery.")]
+    InvalidObjectRecovery,
+    #[msg("The recovery waiting period has not elapsed yet.")]
+    RecoveryNotDue,
+    #[msg("The transfer hook program and extra account metas are required while the transfer hook is enabled.")]
+    TransferHookAccountsRequired,
+    #[msg("The provided transfer hook program is invalid.")]
+    InvalidTransferHookProgram,
+    #[msg("The creator account is not the manifest's recorded creator.")]
+    InvalidRoyaltyCreator,
+    #[msg("The object mint does not charge a transfer fee.")]
+    ObjectHasNoTransferFee,
+    #[msg("The object has no withheld transfer fees to harvest.")]
+    NoRoyaltiesWithheld,
+    #[msg("The marketplace program must be an executable program account.")]
+    InvalidMarketplaceProgram,
+    #[msg("No marketplace delegate is recorded on this manifest.")]
+    MarketplaceDelegateNotSet,
+    #[msg("The object is held in the config's vault.")]
+    ObjectVaulted,
+    #[msg("The object is not held in the config's vault.")]
+    ObjectNotVaulted,
+    #[msg("A fractionalized object must be split into at least one share.")]
+    InvalidShareSupply,
+    #[msg("The object is already fractionalized.")]
+    ObjectFractionalized,
+    #[msg("The object is not fractionalized.")]
+    ObjectNotFractionalized,
+    #[msg("The governance program must be an executable program account.")]
+    InvalidGovernanceProgram,
+    #[msg("The governance account is not owned by the governance program.")]
+    InvalidGovernanceAccount,
+    #[msg("Proposals must stay open for at least one slot.")]
+    InvalidVotingPeriod,
+    #[msg("The proposal has already been finalized.")]
+    ProposalFinalized,
+    #[msg("Voting on the proposal has ended.")]
+    VotingEnded,
+    #[msg("Voting on the proposal is still open.")]
+    VotingNotEnded,
+    #[msg("The account is not a Solana Attestation Service attestation.")]
+    InvalidAttestationAccount,
+    #[msg("The attestation's subject is neither this manifest nor its mint.")]
+    AttestationSubjectMismatch,
+    #[msg("The attestation has expired.")]
+    AttestationExpired,
+    #[msg("A DID hash must be nonzero.")]
+    InvalidDidHash,
+    #[msg("No DID is bound to this manifest.")]
+    DidNotSet,
+    #[msg("Objects in the same config are linked with link_child_object")]
+    ExternalObjectSameConfig,
+    #[msg("Foreign manifest is not the given config's object")]
+    ExternalObjectMismatch,
+    #[msg("Namespace registry page does not match the registry's next entry")]
+    InvalidNamespaceRegistryPage,
+    #[msg("A config cannot be its own parent or its parent's parent")]
+    InvalidParentConfig,
+    #[msg("Namespace transfer must name a different, non-default authority")]
+    InvalidNamespaceTransfer,
+    #[msg("The config authority changed since the namespace transfer was proposed")]
+    NamespaceTransferStale,
+    #[msg("Migration accounts must be (old manifest, new manifest, record, metadata) groups")]
+    MissingManifestMigrationAccounts,
+    #[msg("Only minted, unburned Token Metadata objects outside vaults and shares can migrate")]
+    ManifestNotMigratable,
+    #[msg("Manifest migration record does not match the old manifest")]
+    InvalidManifestMigration,
+    #[msg("Pause the old config before migrating its manifests")]
+    ManifestMigrationRequiresPause,
+    #[msg("Metadata defaults belong to another config")]
+    InvalidMetadataDefaults,
+    #[msg("The program is paused for every namespace")]
+    GloballyPaused,
+    #[msg("The instruction flags contain unknown bits.")]
+    InvalidInstructionFlags,
+    #[msg("This instruction is disabled for the config.")]
+    InstructionDisabled,
+    #[msg("Reservations must last between one slot and MAX_RESERVATION_SLOTS.")]
+    InvalidReservationDuration,
+    #[msg("The object id is reserved for another creator.")]
+    ObjectIdReserved,
+    #[msg("The reservation has not expired yet.")]
+    ReservationNotExpired,
+    #[msg("The object reservation account does not match the object id.")]
+    InvalidObjectReservation,
+    #[msg("Airdrops must assign between one and MAX_AIRDROP_RECIPIENTS objects.")]
+    InvalidAirdropRecipientCount,
+    #[msg("The airdrop assignment index is out of range.")]
+    InvalidAirdropIndex,
+    #[msg("The airdrop assignment has already been minted.")]
+    AirdropAlreadyDistributed,
+    #[msg("The airdrop assignment does not match the airdrop's merkle root.")]
+    InvalidAirdropProof,
+    #[msg("Only the assignment's recipient can claim it.")]
+    ClaimerNotRecipient,
+    #[msg("The secret does not match the gift.")]
+    InvalidGiftSecret,
+    #[msg("The gift has expired.")]
+    GiftExpired,
+    #[msg("The gift has not expired yet.")]
+    GiftNotExpired,
+    #[msg("The manifest's creator can only change before its first mint.")]
+    ManifestCreatorLocked,
+    #[msg("The signer is not the manifest's recorded creator.")]
+    ManifestCreatorMismatch,
+    #[msg("The creator profile does not belong to the config or the manifest creator.")]
+    InvalidCreatorProfile,
+    #[msg("A creator profile needs a payout wallet.")]
+    InvalidPayoutWallet,
+    #[msg("The creator is not on the config's creator allowlist.")]
+    CreatorNotApproved,
+    #[msg("The creator royalty account is not the manifest creator's PDA.")]
+    InvalidCreatorRoyalty,
 }
 
 fn is_allowed_deployer(authority: &Pubkey) -> bool {
     ALLOWED_DEPLOYERS.iter().any(|allowed| allowed == authority)
 }
+
+/// Program data account the upgradeable loader keeps for this program.
+fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(
+        &[crate::ID.as_ref()],
+        &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )
+    .0
+}
+
+/// Rejects mints and manifest updates while the [`GlobalPause`] is set. Until
+/// `set_global_pause` first runs the PDA does not exist, which reads as
+/// unpaused.
+fn require_not_globally_paused(global_pause: &AccountInfo) -> Result<()> {
+    if global_pause.owner != &crate::ID || global_pause.data_is_empty() {
+        return Ok(());
+    }
+    let state = GlobalPause::try_deserialize(&mut &global_pause.try_borrow_data()?[..])?;
+    require!(!state.paused, ErrorCode::GloballyPaused);
+    Ok(())
+}
+
+/// Leaf committing an [`Airdrop`] to minting `object_id` with the given
+/// manifest to `recipient`. `index` is the assignment's bit in the airdrop's
+/// bitmap. Leaves carry a `0` prefix and inner nodes a `1`, so a node can
+/// never be passed off as a leaf.
+pub fn airdrop_leaf(
+    index: u32,
+    recipient: &Pubkey,
+    object_id: u64,
+    manifest_hash: &[u8; 32],
+    hash_algorithm: u8,
+    manifest_uri: &str,
+    arweave_tx_id: &[u8; 32],
+) -> [u8; 32] {
+    keccak::hashv(&[
+        &[0u8],
+        &index.to_le_bytes(),
+        recipient.as_ref(),
+        &object_id.to_le_bytes(),
+        manifest_hash,
+        &[hash_algorithm],
+        &keccak::hash(manifest_uri.as_bytes()).to_bytes(),
+        arweave_tx_id,
+    ])
+    .to_bytes()
+}
+
+/// Node above two sibling hashes, ordered so proofs need no direction bits.
+fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[&[1u8], low, high]).to_bytes()
+}
+
+/// Whether `proof` leads from `leaf` to `root`.
+fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_node(&node, sibling))
+        == *root
+}
+
+/// One airdrop assignment as passed to `distribute_airdrop` or
+/// `claim_airdrop`, with its Merkle proof.
+struct AirdropAssignment<'a> {
+    index: u32,
+    object_id: u64,
+    manifest_uri: &'a str,
+    manifest_hash: [u8; 32],
+    hash_algorithm: u8,
+    arweave_tx_id: [u8; 32],
+    proof: &'a [[u8; 32]],
+}
+
+/// Checks `assignment` against the airdrop's root, marks it in the bitmap,
+/// and mints it to the recipient with the airdrop's metadata.
+fn mint_airdrop_assignment<'info>(
+    accounts: &mut DistributeAirdrop<'info>,
+    bumps: &MintObjectNftBaseBumps,
+    program_id: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    assignment: AirdropAssignment<'_>,
+    claimed: bool,
+) -> Result<()> {
+    let AirdropAssignment {
+        index,
+        object_id,
+        manifest_uri,
+        manifest_hash,
+        hash_algorithm,
+        arweave_tx_id,
+        proof,
+    } = assignment;
+    let recipient = accounts.base.recipient.key();
+    let leaf = airdrop_leaf(
+        index,
+        &recipient,
+        object_id,
+        &manifest_hash,
+        hash_algorithm,
+        manifest_uri,
+        &arweave_tx_id,
+    );
+    require!(
+        verify_merkle_proof(proof, &accounts.airdrop.merkle_root, leaf),
+        ErrorCode::InvalidAirdropProof
+    );
+    accounts.airdrop.mark_distributed(index)?;
+    let airdrop = &accounts.airdrop;
+    let creators = [CreatorInput {
+        address: airdrop.authority,
+        verified: false,
+        share: CREATOR_TOTAL_SHARE as u8,
+    }];
+
+    let target = ObjectMintTarget::from_accounts(&accounts.base, &accounts.metadata, bumps);
+    process_mint_object(
+        &mut accounts.base,
+        &accounts.metadata,
+        target,
+        program_id,
+        remaining_accounts,
+        MintObjectArgs {
+            object_id,
+            manifest_uri,
+            manifest_hash,
+            metadata_name: &airdrop.metadata_name,
+            metadata_symbol: &airdrop.metadata_symbol,
+            seller_fee_basis_points: airdrop.seller_fee_basis_points,
+            creators: &creators,
+            hash_algorithm: ManifestHashAlgorithm::try_from(hash_algorithm)?,
+            category: airdrop.category,
+            tag_hash: [0u8; 32],
+            arweave_tx_id,
+            origin: ObjectOrigin::Minted,
+            provenance: &[],
+            creator: Some(airdrop.authority),
+            stage: MintStage::Complete,
+        },
+    )?;
+
+    emit_logged!(AirdropDistributed {
+        config: airdrop.config,
+        airdrop: airdrop.key(),
+        index,
+        object_id,
+        recipient,
+        claimed,
+        remaining: airdrop.recipient_count - airdrop.distributed_count,
+        context: EventContext::new(accounts.base.minter.key(), accounts.base.authority.key())?,
+    });
+
+    Ok(())
+}
+
+/// Fails with `ObjectIdReserved` when `reservation` is a live
+/// [`ObjectReservation`] held by someone other than `creator`. A missing
+/// reservation, or one past its expiry, leaves the object id open.
+fn require_object_reservation(reservation: &AccountInfo, creator: &Pubkey) -> Result<()> {
+    if reservation.owner != &crate::ID || reservation.data_is_empty() {
+        return Ok(());
+    }
+    let state = ObjectReservation::try_deserialize(&mut &reservation.try_borrow_data()?[..])?;
+    require!(
+        !state.blocks(creator, Clock::get()?.slot),
+        ErrorCode::ObjectIdReserved
+    );
+    Ok(())
+}
+
+/// Fails with `CreatorNotApproved` while the config's creator allowlist is on
+/// and `creator` is neither the config authority nor the holder of the
+/// [`ApprovedCreator`] record passed in `approved_creator`.
+fn require_approved_creator(
+    config: &Config,
+    config_key: &Pubkey,
+    approved_creator: Option<&UncheckedAccount>,
+    creator: &Pubkey,
+) -> Result<()> {
+    if !config.creator_allowlist_enabled() || *creator == config.authority {
+        return Ok(());
+    }
+    let account = approved_creator.ok_or(ErrorCode::CreatorNotApproved)?;
+    require!(
+        account.owner == &crate::ID && !account.data_is_empty(),
+        ErrorCode::CreatorNotApproved
+    );
+    let record = ApprovedCreator::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    require!(
+        record.approves(config_key, creator),
+        ErrorCode::CreatorNotApproved
+    );
+    Ok(())
+}
+
+fn require_object_holder(
+    owner_token_account: &TokenAccount,
+    owner: &Pubkey,
+    object_mint: &Pubkey,
+) -> Result<()> {
+    require_match!(
+        "token_account_owner",
+        owner_token_account.owner,
+        *owner,
+        ErrorCode::InvalidOwnerTokenAccount
+    );
+    require_match!(
+        "mint",
+        owner_token_account.mint,
+        *object_mint,
+        ErrorCode::MintMismatch
+    );
+    require!(
+        owner_token_account.amount > 0,
+        ErrorCode::OwnerDoesNotHoldObjectNft
+    );
+    Ok(())
+}
+
+/// [`require_object_holder`] for Token-2022 token accounts, which must hold
+/// the object as [`holds_object`] defines it.
+fn require_token2022_holder(
+    owner_token_account: &InterfaceTokenAccount,
+    owner: &Pubkey,
+    object_mint: &InterfaceAccount<InterfaceMint>,
+) -> Result<()> {
+    require_match!(
+        "token_account_owner",
+        owner_token_account.owner,
+        *owner,
+        ErrorCode::InvalidOwnerTokenAccount
+    );
+    require_match!(
+        "mint",
+        owner_token_account.mint,
+        object_mint.key(),
+        ErrorCode::MintMismatch
+    );
+    require!(
+        holds_object(owner_token_account.amount, object_mint.supply),
+        ErrorCode::OwnerDoesNotHoldObjectNft
+    );
+    Ok(())
+}
+
+/// Whether a token account with `amount` of a Token-2022 object holds it:
+/// more than half of the mint's `supply`. That is the single token of most
+/// objects, and the bulk of a transfer fee object, whose fees pass some of
+/// its units to the creator.
+fn holds_object(amount: u64, supply: u64) -> bool {
+    u128::from(amount) * 2 > u128::from(supply)
+}
+
+/// Decodes a manifest from any program-owned account, zero-filling fields that
+/// are absent from manifests created under an older, smaller layout.
+/// Copies a manifest onto the heap, keeping its 960 bytes off the SBF stack.
+fn read_manifest(account: &AccountInfo) -> Result<Box<ObjectManifest>> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidManifestAccount);
+    let data = account.try_borrow_data()?;
+    let mut manifest = Box::new(ObjectManifest::zeroed());
+    manifest.copy_from_account_data(&data)?;
+    Ok(manifest)
+}
+
+/// Copy of `manifest` re-keyed to `new_config`, as `migrate_manifests` writes
+/// it. History and custody trail PDAs are keyed by the old manifest, so the
+/// copy starts without them; objects whose vault receipt or share mint is
+/// keyed by the old manifest cannot move.
+fn migrated_manifest(
+    manifest: &ObjectManifest,
+    new_config: Pubkey,
+    bump: u8,
+) -> Result<Box<ObjectManifest>> {
+    require!(
+        manifest.initialized()
+            && manifest.minted()
+            && !manifest.burned()
+            && !manifest.mint_pending()
+            && !manifest.vaulted()
+            && !manifest.fractionalized()
+            && manifest.asset_backend()? == AssetBackend::TokenMetadata,
+        ErrorCode::ManifestNotMigratable
+    );
+    let mut migrated = Box::new(*manifest);
+    migrated.upgrade_layout();
+    migrated.config = new_config;
+    migrated.bump = bump;
+    migrated.set_history_enabled(false);
+    migrated.set_custody_trail_enabled(false);
+    Ok(migrated)
+}
+
+/// Checks that `foreign` is an initialized manifest of another config and
+/// that `foreign_key` is its PDA for `foreign_config` and
+/// `foreign_object_id`.
+fn require_external_reference(
+    manifest: &ObjectManifest,
+    foreign_key: &Pubkey,
+    foreign: &ObjectManifest,
+    foreign_config: &Pubkey,
+    foreign_object_id: u64,
+) -> Result<()> {
+    require!(foreign.initialized(), ErrorCode::ManifestNotInitialized);
+    require_keys_neq!(
+        *foreign_config,
+        manifest.config,
+        ErrorCode::ExternalObjectSameConfig
+    );
+    require_match!(
+        "foreign_config",
+        foreign.config,
+        *foreign_config,
+        ErrorCode::ExternalObjectMismatch
+    );
+    require_match!(
+        "foreign_object_id",
+        foreign.object_id,
+        foreign_object_id,
+        ErrorCode::ExternalObjectMismatch
+    );
+    let (expected_key, _) = derive_pda(
+        &[
+            MANIFEST_SEED,
+            foreign_config.as_ref(),
+            &foreign_object_id.to_le_bytes(),
+        ],
+        Some(foreign.bump),
+        &crate::ID,
+    );
+    require_match!(
+        "foreign_manifest",
+        *foreign_key,
+        expected_key,
+        ErrorCode::ExternalObjectMismatch
+    );
+    Ok(())
+}
+
+/// Caches `owner` on the manifest, appending the previous owner to its
+/// custody trail when the manifest keeps one and the owner changed. A new
+/// owner also drops the previous owner's marketplace delegate, which the
+/// token program revokes on transfer.
+/// Moves a gift's object from its escrow to `destination` and closes the
+/// escrow, refunding its rent to the sender. The gift account itself is
+/// closed by the instruction's constraints.
+fn release_gift<'info>(
+    gift: &Account<'info, Gift>,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    destination_token_account: &Account<'info, TokenAccount>,
+    object_mint: &Account<'info, Mint>,
+    sender: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let gift_seeds: &[&[u8]] = &[GIFT_SEED, gift.manifest.as_ref(), &[gift.bump]];
+    token::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: escrow_token_account.to_account_info(),
+                mint: object_mint.to_account_info(),
+                to: destination_token_account.to_account_info(),
+                authority: gift.to_account_info(),
+            },
+            &[gift_seeds],
+        ),
+        escrow_token_account.amount,
+        object_mint.decimals,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_token_account.to_account_info(),
+            destination: sender.clone(),
+            authority: gift.to_account_info(),
+        },
+        &[gift_seeds],
+    ))
+}
+
+fn record_owner_change(
+    manifest: &mut ObjectManifest,
+    manifest_key: Pubkey,
+    custody_trail: Option<&AccountLoader<CustodyTrail>>,
+    owner: Pubkey,
+) -> Result<()> {
+    let previous_owner = manifest.owner;
+    manifest.owner = owner;
+    if previous_owner != owner {
+        manifest.clear_marketplace_delegate();
+    }
+
+    if manifest.custody_trail_enabled() && previous_owner != owner {
+        let trail = custody_trail.ok_or(ErrorCode::CustodyTrailRequired)?;
+        let mut trail = trail.load_mut()?;
+        require_keys_eq!(
+            trail.manifest,
+            manifest_key,
+            ErrorCode::InvalidCustodyTrailAccount
+        );
+        trail.record(CustodyEntry {
+            slot: Clock::get()?.slot,
+            previous_owner,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the permanent delegate of a Token-2022 mint, if it has one.
+fn mint_permanent_delegate(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    use spl_token_2022::extension::{
+        permanent_delegate::PermanentDelegate, BaseStateWithExtensions, StateWithExtensions,
+    };
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state
+        .get_extension::<PermanentDelegate>()
+        .ok()
+        .and_then(|extension| Option::<Pubkey>::from(extension.delegate)))
+}
+
+/// Transfer fees withheld on a Token-2022 mint, or `None` when the mint has
+/// no transfer fee.
+fn mint_withheld_fees(mint: &AccountInfo) -> Result<Option<u64>> {
+    use spl_token_2022::extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+    };
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeConfig>()
+        .ok()
+        .map(|config| u64::from(config.withheld_amount)))
+}
+
+/// Has the companion transfer-hook program create the object mint's extra
+/// account metas while the auth PDA is still its mint authority.
+fn initialize_object_hook(accounts: &MintToken2022Object, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+    let (Some(hook_program), Some(extra_account_metas)) = (
+        accounts.transfer_hook_program.as_ref(),
+        accounts.extra_account_metas.as_ref(),
+    ) else {
+        return err!(ErrorCode::TransferHookAccountsRequired);
+    };
+    require_match!(
+        "transfer_hook_program",
+        hook_program.key(),
+        TRANSFER_HOOK_PROGRAM_ID,
+        ErrorCode::InvalidTransferHookProgram
+    );
+    let mint_key = accounts.object_mint.key();
+    let (expected_metas, _) = Pubkey::find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, mint_key.as_ref()],
+        &TRANSFER_HOOK_PROGRAM_ID,
+    );
+    require_match!(
+        "extra_account_metas",
+        extra_account_metas.key(),
+        expected_metas,
+        ErrorCode::TransferHookAccountsRequired
+    );
+
+    invoke_signed(
+        &Instruction {
+            program_id: TRANSFER_HOOK_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(accounts.rent_payer.key(), true),
+                AccountMeta::new(expected_metas, false),
+                AccountMeta::new_readonly(mint_key, false),
+                AccountMeta::new_readonly(accounts.auth.key(), true),
+                AccountMeta::new_readonly(accounts.object_manifest.key(), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: TRANSFER_HOOK_INITIALIZE.to_vec(),
+        },
+        &[
+            accounts.rent_payer.to_account_info(),
+            extra_account_metas.to_account_info(),
+            accounts.object_mint.to_account_info(),
+            accounts.auth.to_account_info(),
+            accounts.object_manifest.to_account_info(),
+            accounts.system_program.to_account_info(),
+            hook_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Native treasury PDA of an SPL Governance `governance` account.
+fn governance_native_treasury(governance_program: &Pubkey, governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[GOVERNANCE_NATIVE_TREASURY_SEED, governance.as_ref()],
+        governance_program,
+    )
+    .0
+}
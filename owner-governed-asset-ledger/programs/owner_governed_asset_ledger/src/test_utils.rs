@@ -0,0 +1,517 @@
+//! `solana-program-test` fixtures, enabled with the `test-utils` feature: a
+//! `ProgramTest` with OGAL, SPL Token, the associated token program, and a
+//! Token Metadata mock loaded, plus helpers to fabricate collection metadata
+//! and initialize a config. Programs that call OGAL through CPI can reuse these
+//! in their own integration tests.
+
+use crate::{
+    accounts, instruction, NamespaceRegistry, AUTH_SEED, CONFIG_SEED,
+    NAMESPACE_REGISTRY_PAGE_CAPACITY, NAMESPACE_REGISTRY_PAGE_SEED, NAMESPACE_REGISTRY_SEED,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        account_info::next_account_info, entrypoint::ProgramResult, program_error::ProgramError,
+        sysvar,
+    },
+    system_program, InstructionData, ToAccountMetas,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    accounts::{MasterEdition as MetadataMasterEdition, Metadata as MetadataAccount},
+    instructions::SetCollectionSizeInstructionArgs,
+    types::{CollectionDetails, Key},
+};
+use once_cell::sync::Lazy;
+use solana_program_test::{processor, BanksClient, BanksClientError, ProgramTest};
+use solana_sdk::{
+    account::Account, instruction::Instruction, program_pack::Pack, rent::Rent, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+use spl_discriminator::ArrayDiscriminator;
+use std::{path::Path, sync::Mutex};
+
+/// Collection verification instruction seen by the metadata mock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyKind {
+    Sized,
+    Unsized,
+}
+
+/// Raw collection metadata account data and the collection mint it describes.
+#[derive(Clone, Debug)]
+pub struct PrebakedCollectionMetadata {
+    pub data: Vec<u8>,
+    pub mint: Pubkey,
+}
+
+/// Addresses of a collection registered with [`add_collection`].
+#[derive(Clone, Copy, Debug)]
+pub struct CollectionAccounts {
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub master_edition: Pubkey,
+}
+
+/// Config and auth PDAs created by [`initialize_config`].
+#[derive(Clone, Copy, Debug)]
+pub struct ConfigAccounts {
+    pub namespace: Pubkey,
+    pub config: Pubkey,
+    pub auth: Pubkey,
+}
+
+fn process_instruction_adapter<'a, 'b, 'c, 'd>(
+    program_id: &'a Pubkey,
+    accounts: &'b [AccountInfo<'c>],
+    data: &'d [u8],
+) -> ProgramResult {
+    // Anchor's generated entrypoint requires a homogeneous lifetime across the
+    // account slice and each `AccountInfo`. The adapter safely coerces the
+    // slice reference to satisfy that requirement for the duration of the
+    // invocation.
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    crate::entry(program_id, accounts, data)
+}
+
+/// Builds a `ProgramTest` with OGAL, SPL Token, the associated token program,
+/// and [`metadata_mock`] registered as Token Metadata.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "owner-governed-asset-ledger",
+        crate::ID,
+        processor!(process_instruction_adapter),
+    );
+    add_dependency_programs(&mut program_test);
+    program_test
+}
+
+/// Like [`program_test`], but loads OGAL from its compiled SBF binary so that
+/// compute units are metered. The binary is looked up in `SBF_OUT_DIR`, which
+/// defaults to this workspace's `target/deploy`. Returns `None` when it has
+/// not been built with `anchor build`.
+pub fn sbf_program_test() -> Option<ProgramTest> {
+    if std::env::var_os("SBF_OUT_DIR").is_none() && std::env::var_os("BPF_OUT_DIR").is_none() {
+        let deploy_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy");
+        std::env::set_var("SBF_OUT_DIR", deploy_dir);
+    }
+    let out_dir = std::env::var_os("SBF_OUT_DIR").or_else(|| std::env::var_os("BPF_OUT_DIR"))?;
+    if !Path::new(&out_dir)
+        .join("owner_governed_asset_ledger.so")
+        .exists()
+    {
+        return None;
+    }
+
+    let mut program_test = ProgramTest::new("owner_governed_asset_ledger", crate::ID, None);
+    add_dependency_programs(&mut program_test);
+    Some(program_test)
+}
+
+fn add_dependency_programs(program_test: &mut ProgramTest) {
+    program_test.add_program(
+        "spl_token",
+        spl_token::ID,
+        processor!(spl_token::processor::Processor::process),
+    );
+    program_test.add_program(
+        "spl_associated_token_account",
+        spl_associated_token_account::ID,
+        processor!(spl_associated_token_account::processor::process_instruction),
+    );
+    program_test.add_program(
+        "mpl_token_metadata",
+        mpl_token_metadata::ID,
+        processor!(metadata_mock::process_instruction),
+    );
+}
+
+/// Appends `details` to metadata account data as the TLV entry newer Token
+/// Metadata releases write after `SetCollectionSize`.
+pub fn append_collection_details_tlv(buffer: &mut Vec<u8>, details: &CollectionDetails) {
+    let mut value = Vec::new();
+    details.serialize(&mut value).unwrap();
+    let discriminator = ArrayDiscriminator::new_with_hash_input("collection_details");
+    buffer.extend_from_slice(discriminator.as_ref());
+    buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(&value);
+}
+
+/// Returns a collection `Metadata` with no optional fields set.
+pub fn collection_metadata_state(
+    mint: Pubkey,
+    update_authority: Pubkey,
+    collection_details: Option<CollectionDetails>,
+) -> MetadataAccount {
+    MetadataAccount {
+        key: Key::MetadataV1,
+        update_authority,
+        mint,
+        name: "Collection".into(),
+        symbol: "COLL".into(),
+        uri: "https://example.com/collection.json".into(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        collection: None,
+        uses: None,
+        collection_details,
+        programmable_config: None,
+    }
+}
+
+/// Serializes collection metadata for a fresh mint. `trailing_space` zero
+/// bytes are appended so the mock's `SetCollectionSize` can write a TLV entry.
+pub fn prebaked_collection_metadata(
+    update_authority: Pubkey,
+    collection_details: Option<CollectionDetails>,
+    trailing_space: usize,
+) -> PrebakedCollectionMetadata {
+    let mint = Pubkey::new_unique();
+    let mut data = Vec::new();
+    collection_metadata_state(mint, update_authority, collection_details)
+        .serialize(&mut data)
+        .unwrap();
+    data.resize(data.len() + trailing_space, 0);
+    PrebakedCollectionMetadata { data, mint }
+}
+
+/// Collection metadata encodings the mint's size detection must handle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollectionMetadataVariant {
+    /// No collection details, so mints use `VerifyCollection`.
+    Unsized,
+    /// Size stored in the Borsh `collection_details` field.
+    Sized,
+    /// Size stored only as a TLV entry after empty optional fields.
+    Tlv,
+    /// Size stored as a TLV entry, with the trailing `collection_details` and
+    /// `programmable_config` options omitted entirely.
+    TruncatedOptions,
+}
+
+impl CollectionMetadataVariant {
+    pub const ALL: [Self; 4] = [
+        Self::Unsized,
+        Self::Sized,
+        Self::Tlv,
+        Self::TruncatedOptions,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Unsized => "unsized",
+            Self::Sized => "sized",
+            Self::Tlv => "tlv",
+            Self::TruncatedOptions => "truncated-options",
+        }
+    }
+
+    /// Serializes the variant's metadata account data. `size` is ignored for
+    /// [`CollectionMetadataVariant::Unsized`].
+    pub fn account_data(self, mint: Pubkey, update_authority: Pubkey, size: u64) -> Vec<u8> {
+        let details = (self == Self::Sized).then_some(CollectionDetails::V1 { size });
+        let mut data = Vec::new();
+        collection_metadata_state(mint, update_authority, details)
+            .serialize(&mut data)
+            .unwrap();
+
+        if self == Self::TruncatedOptions {
+            let programmable_marker = data.pop().expect("programmable config option");
+            assert_eq!(programmable_marker, 0);
+            let collection_marker = data.pop().expect("collection details option");
+            assert_eq!(collection_marker, 0);
+        }
+        if matches!(self, Self::Tlv | Self::TruncatedOptions) {
+            append_collection_details_tlv(&mut data, &CollectionDetails::V1 { size });
+        }
+        data
+    }
+}
+
+/// Collection metadata whose trailing `collection_details` and
+/// `programmable_config` options are omitted entirely, with the size stored
+/// only as a TLV entry.
+pub fn prebaked_collection_metadata_with_truncated_options(
+    size: u64,
+) -> PrebakedCollectionMetadata {
+    let mint = Pubkey::new_unique();
+    PrebakedCollectionMetadata {
+        data: CollectionMetadataVariant::TruncatedOptions.account_data(
+            mint,
+            Pubkey::new_unique(),
+            size,
+        ),
+        mint,
+    }
+}
+
+/// Runs the real `SetCollectionSize` instruction builder against the metadata
+/// mock and returns the resulting account data.
+pub async fn prebaked_collection_metadata_sized_by_cpi(size: u64) -> PrebakedCollectionMetadata {
+    let collection_authority = Keypair::new();
+    let mut program_test = ProgramTest::new(
+        "mpl_token_metadata",
+        mpl_token_metadata::ID,
+        processor!(metadata_mock::process_instruction),
+    );
+    let collection = add_collection(
+        &mut program_test,
+        prebaked_collection_metadata(collection_authority.pubkey(), None, 64),
+    );
+    program_test.add_account(
+        collection_authority.pubkey(),
+        Account::new(1_000_000_000, 0, &system_program::ID),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    set_collection_size(
+        &mut banks_client,
+        &payer,
+        &collection_authority,
+        &collection,
+        size,
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let metadata_account = banks_client
+        .get_account(collection.metadata)
+        .await
+        .unwrap()
+        .unwrap();
+    PrebakedCollectionMetadata {
+        data: metadata_account.data,
+        mint: collection.mint,
+    }
+}
+
+/// Registers a collection mint, its metadata, and an empty master edition.
+pub fn add_collection(
+    program_test: &mut ProgramTest,
+    metadata: PrebakedCollectionMetadata,
+) -> CollectionAccounts {
+    let rent = Rent::default();
+    let (metadata_pda, _) = MetadataAccount::find_pda(&metadata.mint);
+    let (master_edition_pda, _) = MetadataMasterEdition::find_pda(&metadata.mint);
+
+    program_test.add_account(
+        metadata_pda,
+        Account {
+            lamports: rent.minimum_balance(metadata.data.len()),
+            data: metadata.data,
+            owner: mpl_token_metadata::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_account(
+        metadata.mint,
+        Account {
+            lamports: rent.minimum_balance(spl_token::state::Mint::LEN),
+            data: vec![0; spl_token::state::Mint::LEN],
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    program_test.add_account(
+        master_edition_pda,
+        Account {
+            lamports: rent.minimum_balance(0),
+            data: Vec::new(),
+            owner: mpl_token_metadata::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    CollectionAccounts {
+        mint: metadata.mint,
+        metadata: metadata_pda,
+        master_edition: master_edition_pda,
+    }
+}
+
+/// Registers an instructions sysvar account so mints can read it.
+pub fn add_instructions_sysvar(program_test: &mut ProgramTest) {
+    program_test.add_account(
+        sysvar::instructions::ID,
+        Account::new(1, 0, &sysvar::instructions::ID),
+    );
+}
+
+/// Sends `SetCollectionSize` for `collection`, signed by its update authority.
+pub async fn set_collection_size(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    collection_authority: &Keypair,
+    collection: &CollectionAccounts,
+    size: u64,
+    recent_blockhash: solana_sdk::hash::Hash,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = mpl_token_metadata::instructions::SetCollectionSize {
+        collection_metadata: collection.metadata,
+        collection_authority: collection_authority.pubkey(),
+        collection_mint: collection.mint,
+        collection_authority_record: None,
+    }
+    .instruction(SetCollectionSizeInstructionArgs {
+        set_collection_size_args: mpl_token_metadata::types::SetCollectionSizeArgs { size },
+    });
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, collection_authority],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(tx).await
+}
+
+/// Initializes a config under a fresh namespace with `payer` as authority.
+pub async fn initialize_config(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+) -> std::result::Result<ConfigAccounts, BanksClientError> {
+    let namespace = Pubkey::new_unique();
+    let (config, _) = Pubkey::find_program_address(&[CONFIG_SEED, namespace.as_ref()], &crate::ID);
+    let (auth, _) = Pubkey::find_program_address(&[AUTH_SEED, config.as_ref()], &crate::ID);
+    let (namespace_registry, _) =
+        Pubkey::find_program_address(&[NAMESPACE_REGISTRY_SEED], &crate::ID);
+    let config_count = match banks_client.get_account(namespace_registry).await? {
+        Some(account) => NamespaceRegistry::try_deserialize(&mut &account.data[..])
+            .map(|registry| registry.config_count)
+            .unwrap_or_default(),
+        None => 0,
+    };
+    let page = (config_count / NAMESPACE_REGISTRY_PAGE_CAPACITY as u64) as u32;
+    let (registry_page, _) = Pubkey::find_program_address(
+        &[NAMESPACE_REGISTRY_PAGE_SEED, &page.to_le_bytes()],
+        &crate::ID,
+    );
+
+    let initialize_ix = Instruction {
+        program_id: crate::ID,
+        accounts: accounts::Initialize {
+            authority: payer.pubkey(),
+            payer: payer.pubkey(),
+            config,
+            auth,
+            namespace_registry,
+            registry_page,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::Initialize { namespace }.data(),
+    };
+    let latest_blockhash = banks_client.get_latest_blockhash().await?;
+    let mut tx = Transaction::new_with_payer(&[initialize_ix], Some(&payer.pubkey()));
+    tx.sign(&[payer], latest_blockhash);
+    banks_client.process_transaction(tx).await?;
+
+    Ok(ConfigAccounts {
+        namespace,
+        config,
+        auth,
+    })
+}
+
+/// Token Metadata stand-in. It records `VerifySizedCollectionItem` and
+/// `VerifyCollection` calls, applies `SetCollectionSize` as a TLV entry, and
+/// accepts every other instruction without touching accounts.
+pub mod metadata_mock {
+    use super::*;
+
+    static VERIFY_CALLS: Lazy<Mutex<Vec<VerifyKind>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+    pub fn reset() {
+        VERIFY_CALLS.lock().unwrap().clear();
+    }
+
+    pub fn verify_calls() -> Vec<VerifyKind> {
+        VERIFY_CALLS.lock().unwrap().clone()
+    }
+
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        match instruction_data.first().copied() {
+            Some(30) => {
+                VERIFY_CALLS.lock().unwrap().push(VerifyKind::Sized);
+                Ok(())
+            }
+            Some(18) => {
+                VERIFY_CALLS.lock().unwrap().push(VerifyKind::Unsized);
+                Ok(())
+            }
+            Some(34) => process_set_collection_size(program_id, accounts, instruction_data),
+            _ => Ok(()),
+        }
+    }
+
+    fn process_set_collection_size(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let mut args_data = &instruction_data[1..];
+        let args = SetCollectionSizeInstructionArgs::deserialize(&mut args_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let account_info_iter = &mut accounts.iter();
+        let metadata_info = next_account_info(account_info_iter)?;
+        let collection_authority_info = next_account_info(account_info_iter)?;
+        let collection_mint_info = next_account_info(account_info_iter)?;
+
+        if metadata_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if *collection_mint_info.owner != spl_token::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if !collection_authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let metadata_data = metadata_info.try_borrow_data()?;
+        let mut metadata = MetadataAccount::deserialize(&mut &metadata_data[..])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        drop(metadata_data);
+
+        if metadata.update_authority != *collection_authority_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if metadata.collection_details.is_some() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut serialized = Vec::new();
+        metadata.collection_details = None;
+        metadata
+            .serialize(&mut serialized)
+            .map_err(|_| ProgramError::AccountDataTooSmall)?;
+        append_collection_details_tlv(
+            &mut serialized,
+            &CollectionDetails::V1 {
+                size: args.set_collection_size_args.size,
+            },
+        );
+
+        let mut metadata_data = metadata_info.try_borrow_mut_data()?;
+        if serialized.len() > metadata_data.len() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        metadata_data[..serialized.len()].copy_from_slice(&serialized);
+        metadata_data[serialized.len()..].fill(0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+//! Compute unit regression gates. These run OGAL from its SBF binary, so build
+//! it with `anchor build` first; without the binary the tests are skipped.
+//! Each test prints the measured usage so budgets can be updated alongside the
+//! change that needs them.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use borsh::BorshSerialize;
+use mpl_token_metadata::accounts::{
+    MasterEdition as MetadataMasterEdition, Metadata as MetadataAccount,
+};
+use owner_governed_asset_ledger::test_utils::{self, CollectionAccounts, ConfigAccounts};
+use owner_governed_asset_ledger::{self, CreatorInput};
+use serial_test::serial;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::ID as ASSOCIATED_TOKEN_ID;
+use spl_token::ID as TOKEN_ID;
+
+const MANIFEST_SEED: &[u8] = b"object_manifest";
+const MINT_SEED: &[u8] = b"object_mint";
+const GLOBAL_PAUSE_SEED: &[u8] = b"global_pause";
+const RESERVATION_SEED: &[u8] = b"object_reservation";
+const CREATOR_ROYALTY_SEED: &[u8] = b"creator_royalty";
+
+/// Budgets for a mint that creates the manifest, mint, metadata, and master
+/// edition, a mint of an already minted object, and a manifest update. The
+/// Token Metadata CPIs run against the mock, so only OGAL's own usage counts.
+/// Re-mints pay for `find_program_address` on the manifest and mint, since
+/// Anchor's `init_if_needed` constraints derive both on every call.
+const FIRST_MINT_BUDGET: u64 = 140_000;
+const REMINT_BUDGET: u64 = 80_000;
+const UPDATE_BUDGET: u64 = 40_000;
+
+/// Size Token Metadata allocates for every metadata account.
+const METADATA_ACCOUNT_LEN: usize = 679;
+
+struct Fixture {
+    context: ProgramTestContext,
+    config: ConfigAccounts,
+    collection: CollectionAccounts,
+}
+
+async fn setup() -> Option<Fixture> {
+    let Some(mut program_test) = test_utils::sbf_program_test() else {
+        eprintln!("skipping compute unit gates: owner_governed_asset_ledger.so not built");
+        return None;
+    };
+    let collection = test_utils::add_collection(
+        &mut program_test,
+        test_utils::prebaked_collection_metadata(Pubkey::new_unique(), None, 0),
+    );
+    test_utils::add_instructions_sysvar(&mut program_test);
+
+    let mut context = program_test.start_with_context().await;
+    let config = test_utils::initialize_config(&mut context.banks_client, &context.payer)
+        .await
+        .unwrap();
+    Some(Fixture {
+        context,
+        config,
+        collection,
+    })
+}
+
+fn object_addresses(config: &Pubkey, object_id: u64) -> (Pubkey, Pubkey) {
+    let (manifest, _) = Pubkey::find_program_address(
+        &[MANIFEST_SEED, config.as_ref(), &object_id.to_le_bytes()],
+        &owner_governed_asset_ledger::id(),
+    );
+    let (mint, _) = Pubkey::find_program_address(
+        &[MINT_SEED, manifest.as_ref()],
+        &owner_governed_asset_ledger::id(),
+    );
+    (manifest, mint)
+}
+
+fn global_pause_address() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_PAUSE_SEED], &owner_governed_asset_ledger::id()).0
+}
+
+fn reservation_address(config: &Pubkey, object_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[RESERVATION_SEED, config.as_ref(), &object_id.to_le_bytes()],
+        &owner_governed_asset_ledger::id(),
+    )
+    .0
+}
+
+fn creator_royalty_address(config: &Pubkey, creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CREATOR_ROYALTY_SEED, config.as_ref(), creator.as_ref()],
+        &owner_governed_asset_ledger::id(),
+    )
+    .0
+}
+
+fn mint_ix(fixture: &Fixture, object_id: u64) -> Instruction {
+    let payer = fixture.context.payer.pubkey();
+    let (manifest, object_mint) = object_addresses(&fixture.config.config, object_id);
+    let (metadata, _) = MetadataAccount::find_pda(&object_mint);
+    let (master_edition, _) = MetadataMasterEdition::find_pda(&object_mint);
+
+    let accounts = owner_governed_asset_ledger::accounts::MintObjectNft {
+        base: owner_governed_asset_ledger::accounts::MintObjectNftBase {
+            authority: payer,
+            config: fixture.config.config,
+            auth: fixture.config.auth,
+            minter: payer,
+            rent_payer: payer,
+            object_manifest: manifest,
+            object_mint,
+            recipient_token_account: get_associated_token_address(&payer, &object_mint),
+            recipient: payer,
+            token_program: TOKEN_ID,
+            associated_token_program: ASSOCIATED_TOKEN_ID,
+            system_program: system_program::ID,
+            config_stats: None,
+            creator_record: None,
+            epoch_stats: None,
+            object_index_page: None,
+            minted_bitmap: None,
+            mint_price: None,
+            price_update: None,
+            treasury: None,
+            kyc_gate: None,
+            kyc_attestation: None,
+            global_pause: global_pause_address(),
+            object_reservation: reservation_address(&fixture.config.config, object_id),
+            approved_creator: None,
+        },
+        metadata: owner_governed_asset_ledger::accounts::MintObjectNftMetadata {
+            metadata,
+            master_edition,
+            collection_mint: fixture.collection.mint,
+            token_metadata_program: mpl_token_metadata::ID,
+            metadata_defaults: None,
+            creator_profile: None,
+            creator_royalty: creator_royalty_address(&fixture.config.config, &payer),
+        },
+    };
+    let mut ix = Instruction {
+        program_id: owner_governed_asset_ledger::id(),
+        accounts: accounts.to_account_metas(None),
+        data: owner_governed_asset_ledger::instruction::MintObjectNft {
+            object_id,
+            manifest_uri: "https://example.com/manifest.json".into(),
+            manifest_hash: [7u8; 32],
+            metadata_name: "Token Toss UGC Level".into(),
+            metadata_symbol: "TT".into(),
+            seller_fee_basis_points: 0,
+            creators: vec![CreatorInput {
+                address: payer,
+                verified: true,
+                share: 100,
+            }],
+            hash_algorithm: 0,
+            category: 0,
+            tag_hash: [0u8; 32],
+            arweave_tx_id: [0u8; 32],
+        }
+        .data(),
+    };
+    ix.accounts.extend_from_slice(&[
+        AccountMeta::new(fixture.collection.metadata, false),
+        AccountMeta::new(fixture.collection.master_edition, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]);
+    ix
+}
+
+fn update_ix(fixture: &Fixture, object_id: u64) -> Instruction {
+    let owner = fixture.context.payer.pubkey();
+    let (manifest, object_mint) = object_addresses(&fixture.config.config, object_id);
+    let (object_metadata, _) = MetadataAccount::find_pda(&object_mint);
+
+    Instruction {
+        program_id: owner_governed_asset_ledger::id(),
+        accounts: owner_governed_asset_ledger::accounts::UpdateObjectManifest {
+            owner,
+            config: fixture.config.config,
+            auth: fixture.config.auth,
+            object_manifest: manifest,
+            object_mint,
+            owner_token_account: get_associated_token_address(&owner, &object_mint),
+            object_metadata,
+            metadata_program: mpl_token_metadata::ID,
+            rent: sysvar::rent::id(),
+            instructions: None,
+            system_program: system_program::ID,
+            manifest_history: None,
+            config_stats: None,
+            global_pause: global_pause_address(),
+        }
+        .to_account_metas(None),
+        data: owner_governed_asset_ledger::instruction::UpdateObjectManifest {
+            manifest_hash: [8u8; 32],
+            metadata_uri: "https://example.com/manifest-v2.json".into(),
+            is_active: true,
+            hash_algorithm: 0,
+            arweave_tx_id: [0u8; 32],
+        }
+        .data(),
+    }
+}
+
+/// The metadata mock does not create object metadata, so updates need a
+/// stand-in account owned by Token Metadata with the auth PDA as authority.
+fn install_object_metadata(fixture: &mut Fixture, object_id: u64) {
+    let (_, object_mint) = object_addresses(&fixture.config.config, object_id);
+    let (metadata_pda, _) = MetadataAccount::find_pda(&object_mint);
+    let mut data = Vec::new();
+    test_utils::collection_metadata_state(object_mint, fixture.config.auth, None)
+        .serialize(&mut data)
+        .unwrap();
+    data.resize(METADATA_ACCOUNT_LEN, 0);
+    fixture.context.set_account(
+        &metadata_pda,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: mpl_token_metadata::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
+
+async fn measure(fixture: &mut Fixture, ix: Instruction) -> u64 {
+    let blockhash = fixture
+        .context
+        .banks_client
+        .get_new_latest_blockhash(&fixture.context.last_blockhash)
+        .await
+        .unwrap();
+    fixture.context.last_blockhash = blockhash;
+    let payer = &fixture.context.payer;
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ix,
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+    let outcome = fixture
+        .context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    outcome.result.unwrap();
+    outcome
+        .metadata
+        .expect("transaction metadata")
+        .compute_units_consumed
+}
+
+fn assert_within_budget(label: &str, used: u64, budget: u64) {
+    println!("{label}: {used} CU (budget {budget})");
+    assert!(
+        used <= budget,
+        "{label} used {used} CU, over its {budget} CU budget"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[serial]
+async fn mint_object_nft_stays_within_budget() {
+    let Some(mut fixture) = setup().await else {
+        return;
+    };
+
+    let ix = mint_ix(&fixture, 1);
+    let first_mint = measure(&mut fixture, ix).await;
+    assert_within_budget("first mint", first_mint, FIRST_MINT_BUDGET);
+
+    let ix = mint_ix(&fixture, 1);
+    let remint = measure(&mut fixture, ix).await;
+    assert_within_budget("re-mint", remint, REMINT_BUDGET);
+}
+
+#[tokio::test(flavor = "current_thread")]
+#[serial]
+async fn update_object_manifest_stays_within_budget() {
+    let Some(mut fixture) = setup().await else {
+        return;
+    };
+
+    let ix = mint_ix(&fixture, 1);
+    measure(&mut fixture, ix).await;
+    install_object_metadata(&mut fixture, 1);
+
+    let ix = update_ix(&fixture, 1);
+    let update = measure(&mut fixture, ix).await;
+    assert_within_budget("update", update, UPDATE_BUDGET);
+}
@@ -1,54 +1,35 @@
-use anchor_lang::solana_program::{entrypoint::ProgramResult, sysvar};
-use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use anchor_lang::prelude::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
 use anchor_spl::associated_token::get_associated_token_address;
-use borsh::BorshSerialize;
 use mpl_token_metadata::{
     accounts::{MasterEdition as MetadataMasterEdition, Metadata as MetadataAccount},
-    instructions::{SetCollectionSize, SetCollectionSizeInstructionArgs},
-    types::{CollectionDetails, Key, SetCollectionSizeArgs},
+    types::CollectionDetails,
+};
+use owner_governed_asset_ledger::test_utils::{
+    self, metadata_mock, PrebakedCollectionMetadata, VerifyKind,
 };
 use owner_governed_asset_ledger::{self, CreatorInput, ErrorCode, ObjectManifest};
 use serial_test::serial;
-use solana_program_test::{processor, BanksClientError, ProgramTest};
+use solana_program_test::BanksClientError;
 use solana_sdk::{
     account::Account,
     instruction::{AccountMeta, Instruction, InstructionError},
     program_pack::Pack,
-    rent::Rent,
     signer::keypair::Keypair,
     signer::Signer,
-    system_program,
+    system_program, sysvar,
     transaction::{Transaction, TransactionError},
 };
 use spl_associated_token_account::ID as ASSOCIATED_TOKEN_ID;
 use spl_token::ID as TOKEN_ID;
 use std::mem;
 
-use spl_discriminator::ArrayDiscriminator;
-
-fn process_instruction_adapter<'a, 'b, 'c, 'd>(
-    program_id: &'a Pubkey,
-    accounts: &'b [AccountInfo<'c>],
-    data: &'d [u8],
-) -> ProgramResult {
-    // Anchor's generated entrypoint requires a homogeneous lifetime across the
-    // account slice and each `AccountInfo`. The adapter safely coerces the
-    // slice reference to satisfy that requirement for the duration of the
-    // invocation.
-    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
-    owner_governed_asset_ledger::entry(program_id, accounts, data)
-}
-
-const CONFIG_SEED: &[u8] = b"config";
-const AUTH_SEED: &[u8] = b"auth";
 const MANIFEST_SEED: &[u8] = b"object_manifest";
 const MINT_SEED: &[u8] = b"object_mint";
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum VerifyKind {
-    Sized,
-    Unsized,
-}
+const GLOBAL_PAUSE_SEED: &[u8] = b"global_pause";
+const RESERVATION_SEED: &[u8] = b"object_reservation";
+const CREATOR_ROYALTY_SEED: &[u8] = b"creator_royalty";
+const RENT_PAYER_LAMPORTS: u64 = 1_000_000_000;
 
 struct CreatorContext {
     payer: Pubkey,
@@ -58,6 +39,10 @@ struct CreatorContext {
 struct MintInvocationConfig {
     creators: Vec<CreatorInput>,
     extra_remaining_accounts: Vec<AccountMeta>,
+    /// Defaults to the payer.
+    recipient: Option<Pubkey>,
+    /// Funds rent from a second wallet instead of the minter.
+    separate_rent_payer: bool,
 }
 
 impl MintInvocationConfig {
@@ -65,244 +50,12 @@ impl MintInvocationConfig {
         Self {
             creators,
             extra_remaining_accounts: Vec::new(),
+            recipient: None,
+            separate_rent_payer: false,
         }
     }
 }
 
-struct PrebakedCollectionMetadata {
-    data: Vec<u8>,
-    mint: Pubkey,
-}
-
-fn append_collection_details_tlv(buffer: &mut Vec<u8>, details: &CollectionDetails) {
-    let mut value = Vec::new();
-    details.serialize(&mut value).unwrap();
-    let discriminator = ArrayDiscriminator::new_with_hash_input("collection_details");
-    buffer.extend_from_slice(discriminator.as_ref());
-    buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
-    buffer.extend_from_slice(&value);
-}
-
-fn prebaked_collection_metadata_with_truncated_options(size: u64) -> PrebakedCollectionMetadata {
-    let collection_mint = Pubkey::new_unique();
-    let metadata_state = MetadataAccount {
-        key: Key::MetadataV1,
-        update_authority: Pubkey::new_unique(),
-        mint: collection_mint,
-        name: "Collection".into(),
-        symbol: "COLL".into(),
-        uri: "https://example.com/collection.json".into(),
-        seller_fee_basis_points: 0,
-        creators: None,
-        primary_sale_happened: false,
-        is_mutable: true,
-        edition_nonce: None,
-        token_standard: None,
-        collection: None,
-        uses: None,
-        collection_details: None,
-        programmable_config: None,
-    };
-
-    let mut data = Vec::new();
-    metadata_state.serialize(&mut data).unwrap();
-
-    let programmable_marker = data.pop().expect("programmable config option");
-    assert_eq!(programmable_marker, 0);
-    let collection_marker = data.pop().expect("collection details option");
-    assert_eq!(collection_marker, 0);
-
-    append_collection_details_tlv(&mut data, &CollectionDetails::V1 { size });
-
-    PrebakedCollectionMetadata {
-        data,
-        mint: collection_mint,
-    }
-}
-
-mod metadata_mock {
-    use super::{
-        append_collection_details_tlv, AccountInfo, CollectionDetails, MetadataAccount,
-        ProgramResult, Pubkey, SetCollectionSizeArgs, SetCollectionSizeInstructionArgs, VerifyKind,
-        TOKEN_ID,
-    };
-    use anchor_lang::solana_program::{
-        account_info::next_account_info, program_error::ProgramError,
-    };
-    use borsh::{BorshDeserialize, BorshSerialize};
-    use once_cell::sync::Lazy;
-    use std::sync::Mutex;
-
-    static VERIFY_CALLS: Lazy<Mutex<Vec<VerifyKind>>> = Lazy::new(|| Mutex::new(Vec::new()));
-
-    pub fn reset() {
-        VERIFY_CALLS.lock().unwrap().clear();
-    }
-
-    pub fn verify_calls() -> Vec<VerifyKind> {
-        VERIFY_CALLS.lock().unwrap().clone()
-    }
-
-    pub fn process_instruction(
-        _program_id: &Pubkey,
-        _accounts: &[AccountInfo],
-        instruction_data: &[u8],
-    ) -> ProgramResult {
-        match instruction_data.first().copied() {
-            Some(30) => {
-                VERIFY_CALLS.lock().unwrap().push(VerifyKind::Sized);
-                Ok(())
-            }
-            Some(18) => {
-                VERIFY_CALLS.lock().unwrap().push(VerifyKind::Unsized);
-                Ok(())
-            }
-            Some(34) => process_set_collection_size(_program_id, _accounts, instruction_data),
-            _ => Ok(()),
-        }
-    }
-
-    fn process_set_collection_size(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        instruction_data: &[u8],
-    ) -> ProgramResult {
-        let mut args_data = &instruction_data[1..];
-        let args = SetCollectionSizeInstructionArgs::deserialize(&mut args_data)
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
-
-        let account_info_iter = &mut accounts.iter();
-        let metadata_info = next_account_info(account_info_iter)?;
-        let collection_authority_info = next_account_info(account_info_iter)?;
-        let collection_mint_info = next_account_info(account_info_iter)?;
-
-        if metadata_info.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-        if *collection_mint_info.owner != TOKEN_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-
-        if !collection_authority_info.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        let metadata_data = metadata_info.try_borrow_data()?;
-        let mut metadata = MetadataAccount::deserialize(&mut &metadata_data[..])
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        drop(metadata_data);
-
-        if metadata.update_authority != *collection_authority_info.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if metadata.collection_details.is_some() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut serialized = Vec::new();
-        metadata.collection_details = None;
-        metadata
-            .serialize(&mut serialized)
-            .map_err(|_| ProgramError::AccountDataTooSmall)?;
-
-        append_collection_details_tlv(
-            &mut serialized,
-            &CollectionDetails::V1 {
-                size: match args.set_collection_size_args {
-                    SetCollectionSizeArgs { size } => size,
-                },
-            },
-        );
-
-        let mut metadata_data = metadata_info.try_borrow_mut_data()?;
-        if serialized.len() > metadata_data.len() {
-            return Err(ProgramError::AccountDataTooSmall);
-        }
-        metadata_data[..serialized.len()].copy_from_slice(&serialized);
-        metadata_data[serialized.len()..].fill(0);
-
-        Ok(())
-    }
-}
-
-mod metadata_program_stub {
-    use super::*;
-    use anchor_lang::solana_program::{
-        account_info::next_account_info, program_error::ProgramError,
-    };
-    use borsh::BorshDeserialize;
-    use mpl_token_metadata::instructions::SetCollectionSizeInstructionArgs;
-    use mpl_token_metadata::types::SetCollectionSizeArgs;
-
-    pub fn process_instruction(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        instruction_data: &[u8],
-    ) -> ProgramResult {
-        if instruction_data.first().copied() != Some(34) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let mut args_data = &instruction_data[1..];
-        let args = SetCollectionSizeInstructionArgs::deserialize(&mut args_data)
-            .map_err(|_| ProgramError::InvalidInstructionData)?;
-
-        set_collection_size(program_id, accounts, args.set_collection_size_args)
-    }
-
-    fn set_collection_size(
-        program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        args: SetCollectionSizeArgs,
-    ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let metadata_info = next_account_info(account_info_iter)?;
-        let collection_authority_info = next_account_info(account_info_iter)?;
-        let collection_mint_info = next_account_info(account_info_iter)?;
-
-        if metadata_info.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-        if *collection_mint_info.owner != TOKEN_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
-
-        if !collection_authority_info.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        let metadata_data = metadata_info.try_borrow_data()?;
-        let mut metadata = MetadataAccount::deserialize(&mut &metadata_data[..])
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        drop(metadata_data);
-
-        if metadata.update_authority != *collection_authority_info.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        if metadata.collection_details.is_some() {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut serialized = Vec::new();
-        metadata.collection_details = None;
-        metadata
-            .serialize(&mut serialized)
-            .map_err(|_| ProgramError::AccountDataTooSmall)?;
-        append_collection_details_tlv(&mut serialized, &CollectionDetails::V1 { size: args.size });
-
-        let mut metadata_data = metadata_info.try_borrow_mut_data()?;
-        if serialized.len() > metadata_data.len() {
-            return Err(ProgramError::AccountDataTooSmall);
-        }
-        metadata_data[..serialized.len()].copy_from_slice(&serialized);
-        metadata_data[serialized.len()..].fill(0);
-
-        Ok(())
-    }
-}
-
 #[tokio::test(flavor = "current_thread")]
 #[serial]
 async fn mint_succeeds_for_sized_collection() {
@@ -324,7 +77,7 @@ async fn mint_succeeds_for_tlv_sized_collection() {
 #[tokio::test(flavor = "current_thread")]
 #[serial]
 async fn mint_succeeds_for_truncated_tlv_sized_collection() {
-    let metadata = prebaked_collection_metadata_with_truncated_options(1);
+    let metadata = test_utils::prebaked_collection_metadata_with_truncated_options(1);
     let verify_calls = execute_mint_with_metadata_override(metadata, |context| {
         MintInvocationConfig::new(vec![CreatorInput {
             address: context.payer,
@@ -349,7 +102,7 @@ async fn mint_succeeds_for_unsized_collection() {
 #[tokio::test(flavor = "current_thread")]
 #[serial]
 async fn mint_uses_verify_sized_after_real_set_collection_size() {
-    let metadata = create_collection_metadata_with_cpi_size(1).await;
+    let metadata = test_utils::prebaked_collection_metadata_sized_by_cpi(1).await;
     let verify_calls = execute_mint_with_metadata_override(metadata, |context| {
         MintInvocationConfig::new(vec![CreatorInput {
             address: context.payer,
@@ -421,153 +174,56 @@ where
 {
     metadata_mock::reset();
 
-    let mut program_test = ProgramTest::new(
-        "owner-governed-asset-ledger",
-        owner_governed_asset_ledger::id(),
-        processor!(process_instruction_adapter),
-    );
-    program_test.add_program(
-        "spl_token",
-        TOKEN_ID,
-        processor!(spl_token::processor::Processor::process),
-    );
-    program_test.add_program(
-        "spl_associated_token_account",
-        ASSOCIATED_TOKEN_ID,
-        processor!(spl_associated_token_account::processor::process_instruction),
-    );
-    program_test.add_program(
-        "mpl_token_metadata",
-        mpl_token_metadata::ID,
-        processor!(metadata_mock::process_instruction),
-    );
-
-    let rent = Rent::default();
+    let mut program_test = test_utils::program_test();
     let collection_authority = Keypair::new();
-    let (collection_mint, mut metadata_data) = if let Some(prebaked) = metadata_override {
-        (prebaked.mint, prebaked.data)
-    } else {
-        let collection_mint = Pubkey::new_unique();
-        let metadata_state = MetadataAccount {
-            key: Key::MetadataV1,
-            update_authority: collection_authority.pubkey(),
-            mint: collection_mint,
-            name: "Collection".into(),
-            symbol: "COLL".into(),
-            uri: "https://example.com/collection.json".into(),
-            seller_fee_basis_points: 0,
-            creators: None,
-            primary_sale_happened: false,
-            is_mutable: true,
-            edition_nonce: None,
-            token_standard: None,
-            collection: None,
-            uses: None,
-            collection_details: collection_details.clone(),
-            programmable_config: None,
+    let metadata = metadata_override.unwrap_or_else(|| {
+        let trailing_space = if tlv_collection_details.is_some() {
+            64
+        } else {
+            0
         };
-        let mut data = Vec::new();
-        metadata_state.serialize(&mut data).unwrap();
-        if tlv_collection_details.is_some() {
-            data.extend_from_slice(&vec![0u8; 64]);
-        }
-        (collection_mint, data)
-    };
-    let (collection_metadata_pda, _) = MetadataAccount::find_pda(&collection_mint);
-    let (collection_master_edition_pda, _) = MetadataMasterEdition::find_pda(&collection_mint);
-    let collection_metadata_account = Account {
-        lamports: rent.minimum_balance(metadata_data.len()),
-        data: metadata_data,
-        owner: mpl_token_metadata::ID,
-        executable: false,
-        rent_epoch: 0,
-    };
-    program_test.add_account(collection_metadata_pda, collection_metadata_account);
-
-    let collection_mint_account = Account {
-        lamports: rent.minimum_balance(spl_token::state::Mint::LEN),
-        data: vec![0; spl_token::state::Mint::LEN],
-        owner: spl_token::ID,
-        executable: false,
-        rent_epoch: 0,
-    };
-    program_test.add_account(collection_mint, collection_mint_account);
-
-    let master_edition_account = Account {
-        lamports: rent.minimum_balance(0),
-        data: Vec::new(),
-        owner: mpl_token_metadata::ID,
-        executable: false,
-        rent_epoch: 0,
-    };
-    program_test.add_account(collection_master_edition_pda, master_edition_account);
-
-    let instructions_account = Account::new(1, 0, &sysvar::instructions::ID);
-    program_test.add_account(sysvar::instructions::id(), instructions_account);
-
+        test_utils::prebaked_collection_metadata(
+            collection_authority.pubkey(),
+            collection_details.clone(),
+            trailing_space,
+        )
+    });
+    let collection = test_utils::add_collection(&mut program_test, metadata);
+    let collection_mint = collection.mint;
+    let collection_metadata_pda = collection.metadata;
+    let collection_master_edition_pda = collection.master_edition;
+    test_utils::add_instructions_sysvar(&mut program_test);
     program_test.add_account(
         collection_authority.pubkey(),
         Account::new(1_000_000_000, 0, &system_program::ID),
     );
+    let rent_payer = Keypair::new();
+    program_test.add_account(
+        rent_payer.pubkey(),
+        Account::new(RENT_PAYER_LAMPORTS, 0, &system_program::ID),
+    );
 
     let (mut banks_client, payer, _recent_blockhash) = program_test.start().await;
 
     if let Some(CollectionDetails::V1 { size }) = tlv_collection_details {
-        let instruction = SetCollectionSize {
-            collection_metadata: collection_metadata_pda,
-            collection_authority: collection_authority.pubkey(),
-            collection_mint,
-            collection_authority_record: None,
-        }
-        .instruction(SetCollectionSizeInstructionArgs {
-            set_collection_size_args: SetCollectionSizeArgs { size },
-        });
-
         let latest_blockhash = banks_client.get_latest_blockhash().await.unwrap();
-        let tx = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&payer.pubkey()),
-            &[&payer, &collection_authority],
+        test_utils::set_collection_size(
+            &mut banks_client,
+            &payer,
+            &collection_authority,
+            &collection,
+            size,
             latest_blockhash,
-        );
-        banks_client.process_transaction(tx).await.unwrap();
-
-        let metadata_account = banks_client
-            .get_account(collection_metadata_pda)
-            .await
-            .unwrap()
-            .unwrap();
+        )
+        .await
+        .unwrap();
     }
 
-    let namespace = Pubkey::new_unique();
-    let (config_pda, _) = Pubkey::find_program_address(
-        &[CONFIG_SEED, namespace.as_ref()],
-        &owner_governed_asset_ledger::id(),
-    );
-    let (auth_pda, _) = Pubkey::find_program_address(
-        &[AUTH_SEED, config_pda.as_ref()],
-        &owner_governed_asset_ledger::id(),
-    );
-
-    let initialize_accounts = owner_governed_asset_ledger::accounts::Initialize {
-        authority: payer.pubkey(),
-        payer: payer.pubkey(),
-        config: config_pda,
-        auth: auth_pda,
-        system_program: system_program::ID,
-    };
-    let initialize_ix = Instruction {
-        program_id: owner_governed_asset_ledger::id(),
-        accounts: initialize_accounts.to_account_metas(None),
-        data: owner_governed_asset_ledger::instruction::Initialize { namespace }.data(),
-    };
-    let latest_blockhash = banks_client.get_latest_blockhash().await.unwrap();
-    let mut initialize_tx = Transaction::new_with_payer(&[initialize_ix], Some(&payer.pubkey()));
-    initialize_tx.sign(&[&payer], latest_blockhash);
-    banks_client
-        .process_transaction(initialize_tx)
+    let config = test_utils::initialize_config(&mut banks_client, &payer)
         .await
         .unwrap();
+    let config_pda = config.config;
+    let auth_pda = config.auth;
 
     let object_id = 1u64;
     let (manifest_pda, _) = Pubkey::find_program_address(
@@ -580,7 +236,17 @@ where
     );
     let (metadata_pda, _) = MetadataAccount::find_pda(&object_mint_pda);
     let (master_edition_pda, _) = MetadataMasterEdition::find_pda(&object_mint_pda);
-    let recipient = payer.pubkey();
+    let invocation_config = build_creators(CreatorContext {
+        payer: payer.pubkey(),
+        collection_metadata: collection_metadata_pda,
+    });
+    let recipient = invocation_config.recipient.unwrap_or(payer.pubkey());
+    let separate_rent_payer = invocation_config.separate_rent_payer;
+    let rent_payer_key = if separate_rent_payer {
+        rent_payer.pubkey()
+    } else {
+        payer.pubkey()
+    };
     let recipient_token_account = get_associated_token_address(&recipient, &object_mint_pda);
 
     let mint_accounts = owner_governed_asset_ledger::accounts::MintObjectNft {
@@ -588,7 +254,8 @@ where
             authority: payer.pubkey(),
             config: config_pda,
             auth: auth_pda,
-            payer: payer.pubkey(),
+            minter: payer.pubkey(),
+            rent_payer: rent_payer_key,
             object_manifest: manifest_pda,
             object_mint: object_mint_pda,
             recipient_token_account,
@@ -596,18 +263,50 @@ where
             token_program: TOKEN_ID,
             associated_token_program: ASSOCIATED_TOKEN_ID,
             system_program: system_program::ID,
+            config_stats: None,
+            creator_record: None,
+            epoch_stats: None,
+            object_index_page: None,
+            minted_bitmap: None,
+            mint_price: None,
+            price_update: None,
+            treasury: None,
+            kyc_gate: None,
+            kyc_attestation: None,
+            global_pause: Pubkey::find_program_address(
+                &[GLOBAL_PAUSE_SEED],
+                &owner_governed_asset_ledger::id(),
+            )
+            .0,
+            object_reservation: Pubkey::find_program_address(
+                &[
+                    RESERVATION_SEED,
+                    config_pda.as_ref(),
+                    &object_id.to_le_bytes(),
+                ],
+                &owner_governed_asset_ledger::id(),
+            )
+            .0,
+            approved_creator: None,
         },
         metadata: owner_governed_asset_ledger::accounts::MintObjectNftMetadata {
             metadata: metadata_pda,
             master_edition: master_edition_pda,
             collection_mint,
             token_metadata_program: mpl_token_metadata::ID,
+            metadata_defaults: None,
+            creator_profile: None,
+            creator_royalty: Pubkey::find_program_address(
+                &[
+                    CREATOR_ROYALTY_SEED,
+                    config_pda.as_ref(),
+                    payer.pubkey().as_ref(),
+                ],
+                &owner_governed_asset_ledger::id(),
+            )
+            .0,
         },
     };
-    let invocation_config = build_creators(CreatorContext {
-        payer: payer.pubkey(),
-        collection_metadata: collection_metadata_pda,
-    });
     let creators = invocation_config.creators;
 
     let mut mint_ix = Instruction {
@@ -621,6 +320,10 @@ where
             metadata_symbol: "TT".into(),
             seller_fee_basis_points: 0,
             creators,
+            hash_algorithm: 0,
+            category: 0,
+            tag_hash: [0u8; 32],
+            arweave_tx_id: [0u8; 32],
         }
         .data(),
     };
@@ -636,7 +339,11 @@ where
 
     let latest_blockhash = banks_client.get_latest_blockhash().await.unwrap();
     let mut mint_tx = Transaction::new_with_payer(&[mint_ix], Some(&payer.pubkey()));
-    mint_tx.sign(&[&payer], latest_blockhash);
+    if separate_rent_payer {
+        mint_tx.sign(&[&payer, &rent_payer], latest_blockhash);
+    } else {
+        mint_tx.sign(&[&payer], latest_blockhash);
+    }
     banks_client.process_transaction(mint_tx).await?;
 
     let manifest_account = banks_client
@@ -647,92 +354,57 @@ where
     let manifest = bytemuck::from_bytes::<ObjectManifest>(manifest_slice);
     assert!(manifest.minted());
 
+    let token_account = banks_client
+        .get_account(recipient_token_account)
+        .await?
+        .expect("recipient token account");
+    let token_account = spl_token::state::Account::unpack(&token_account.data).unwrap();
+    assert_eq!(token_account.owner, recipient);
+    assert_eq!(token_account.amount, 1);
+
+    let rent_payer_balance = banks_client.get_balance(rent_payer.pubkey()).await?;
+    if separate_rent_payer {
+        assert!(rent_payer_balance < RENT_PAYER_LAMPORTS);
+    } else {
+        assert_eq!(rent_payer_balance, RENT_PAYER_LAMPORTS);
+    }
+
     Ok(metadata_mock::verify_calls())
 }
 
-async fn create_collection_metadata_with_cpi_size(size: u64) -> PrebakedCollectionMetadata {
-    let mut program_test = ProgramTest::new(
-        "mpl_token_metadata",
-        mpl_token_metadata::ID,
-        processor!(metadata_program_stub::process_instruction),
-    );
-
-    let rent = Rent::default();
-    let collection_mint = Pubkey::new_unique();
-    let collection_authority = Keypair::new();
-    let (collection_metadata_pda, _) = MetadataAccount::find_pda(&collection_mint);
-
-    let metadata_state = MetadataAccount {
-        key: Key::MetadataV1,
-        update_authority: collection_authority.pubkey(),
-        mint: collection_mint,
-        name: "Collection".into(),
-        symbol: "COLL".into(),
-        uri: "https://example.com/collection.json".into(),
-        seller_fee_basis_points: 0,
-        creators: None,
-        primary_sale_happened: false,
-        is_mutable: true,
-        edition_nonce: None,
-        token_standard: None,
-        collection: None,
-        uses: None,
-        collection_details: None,
-        programmable_config: None,
-    };
-
-    let mut metadata_data = Vec::new();
-    metadata_state.serialize(&mut metadata_data).unwrap();
-    metadata_data.extend_from_slice(&vec![0u8; 64]);
-    program_test.add_account(
-        collection_metadata_pda,
-        Account {
-            lamports: rent.minimum_balance(metadata_data.len()),
-            data: metadata_data,
-            owner: mpl_token_metadata::ID,
-            executable: false,
-            rent_epoch: 0,
-        },
-    );
-
-    program_test.add_account(collection_mint, Account::new(1, 0, &spl_token::ID));
-    program_test.add_account(
-        collection_authority.pubkey(),
-        Account::new(1_000_000_000, 0, &system_program::ID),
-    );
-
-    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-
-    let instruction = mpl_token_metadata::instructions::SetCollectionSize {
-        collection_metadata: collection_metadata_pda,
-        collection_authority: collection_authority.pubkey(),
-        collection_mint,
-        collection_authority_record: None,
-    }
-    .instruction(
-        mpl_token_metadata::instructions::SetCollectionSizeInstructionArgs {
-            set_collection_size_args: mpl_token_metadata::types::SetCollectionSizeArgs { size },
-        },
-    );
+#[tokio::test(flavor = "current_thread")]
+#[serial]
+async fn mint_succeeds_for_pda_recipient() {
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique());
+    let verify_calls = execute_mint_with_creators(None, None, |context| MintInvocationConfig {
+        recipient: Some(vault),
+        ..MintInvocationConfig::new(vec![CreatorInput {
+            address: context.payer,
+            verified: true,
+            share: 100,
+        }])
+    })
+    .await
+    .unwrap();
 
-    let tx = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&payer.pubkey()),
-        &[&payer, &collection_authority],
-        recent_blockhash,
-    );
-    banks_client.process_transaction(tx).await.unwrap();
+    assert_eq!(verify_calls, vec![VerifyKind::Unsized]);
+}
 
-    let metadata_account = banks_client
-        .get_account(collection_metadata_pda)
-        .await
-        .unwrap()
-        .unwrap();
+#[tokio::test(flavor = "current_thread")]
+#[serial]
+async fn mint_succeeds_with_separate_rent_payer() {
+    let verify_calls = execute_mint_with_creators(None, None, |context| MintInvocationConfig {
+        separate_rent_payer: true,
+        ..MintInvocationConfig::new(vec![CreatorInput {
+            address: context.payer,
+            verified: true,
+            share: 100,
+        }])
+    })
+    .await
+    .unwrap();
 
-    PrebakedCollectionMetadata {
-        data: metadata_account.data,
-        mint: collection_mint,
-    }
+    assert_eq!(verify_calls, vec![VerifyKind::Unsized]);
 }
 
 #[tokio::test(flavor = "current_thread")]
@@ -5,6 +5,7 @@ const { Keypair, PublicKey } = require('@solana/web3.js');
 const {
   deserializeManifestCreator,
   ensureManifestCreatorPresent,
+  parseArweaveTxId,
 } = require('../utils');
 
 test('deserializeManifestCreator returns the stored creator public key', () => {
@@ -59,3 +60,10 @@ test('ensureManifestCreatorPresent throws when the manifest creator is missing',
     },
   );
 });
+
+test('parseArweaveTxId decodes base64url ids and defaults to zeroes', () => {
+  const id = Buffer.alloc(32, 7);
+  assert.deepStrictEqual(parseArweaveTxId(id.toString('base64url')), id);
+  assert.deepStrictEqual(parseArweaveTxId(undefined), Buffer.alloc(32));
+  assert.throws(() => parseArweaveTxId('not-a-tx-id'), /43 character base64url/);
+});
@@ -18,6 +18,7 @@ const { hideBin } = require('yargs/helpers');
 const PROGRAM_ID = new PublicKey('GwMpopxNkDYsnucBRPf47QSEsEzA3rS1o6ioMX78hgqx');
 const INITIALIZE_DISCRIMINATOR = Buffer.from('afaf6d1f0d989bed', 'hex');
 const SYSTEM_PROGRAM_ID = SystemProgram.programId;
+const NAMESPACE_REGISTRY_PAGE_CAPACITY = 32n;
 
 function expandPath(p) {
   if (!p) {
@@ -117,6 +118,20 @@ async function main() {
     PROGRAM_ID,
   );
 
+  // The config is listed on the registry page holding entry `config_count`.
+  const [registry] = PublicKey.findProgramAddressSync(
+    [Buffer.from('namespace_registry')],
+    PROGRAM_ID,
+  );
+  const registryAccount = await connection.getAccountInfo(registry);
+  const configCount = registryAccount ? registryAccount.data.readBigUInt64LE(8) : 0n;
+  const pageBuffer = Buffer.alloc(4);
+  pageBuffer.writeUInt32LE(Number(configCount / NAMESPACE_REGISTRY_PAGE_CAPACITY));
+  const [registryPage] = PublicKey.findProgramAddressSync(
+    [Buffer.from('namespace_registry_page'), pageBuffer],
+    PROGRAM_ID,
+  );
+
   const data = Buffer.concat([INITIALIZE_DISCRIMINATOR, namespace.toBuffer()]);
 
   const keys = [
@@ -124,6 +139,8 @@ async function main() {
     { pubkey: payer, isSigner: true, isWritable: true },
     { pubkey: config, isSigner: false, isWritable: true },
     { pubkey: auth, isSigner: false, isWritable: true },
+    { pubkey: registry, isSigner: false, isWritable: true },
+    { pubkey: registryPage, isSigner: false, isWritable: true },
     { pubkey: SYSTEM_PROGRAM_ID, isSigner: false, isWritable: false },
   ];
 
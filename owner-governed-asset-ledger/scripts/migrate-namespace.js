@@ -17,6 +17,7 @@ const {
   instructionDiscriminator,
   deriveConfigPda,
   deriveAuthPda,
+  resolveNamespaceRegistry,
   logStructured,
   collectLogs,
 } = require('./utils');
@@ -69,6 +70,7 @@ async function main() {
   const [newConfigPda] = deriveConfigPda(newNamespace);
   const [oldAuthPda] = deriveAuthPda(oldConfigPda);
   const [newAuthPda] = deriveAuthPda(newConfigPda);
+  const { registry, registryPage } = await resolveNamespaceRegistry(connection);
 
   logStructured('info', 'derived-pdas', {
     oldNamespace: oldNamespace.toBase58(),
@@ -77,6 +79,8 @@ async function main() {
     newConfigPda: newConfigPda.toBase58(),
     oldAuthPda: oldAuthPda.toBase58(),
     newAuthPda: newAuthPda.toBase58(),
+    registry: registry.toBase58(),
+    registryPage: registryPage.toBase58(),
   });
 
   const data = Buffer.alloc(8 + 32);
@@ -91,6 +95,8 @@ async function main() {
       { pubkey: newConfigPda, isSigner: false, isWritable: true },
       { pubkey: oldAuthPda, isSigner: false, isWritable: false },
       { pubkey: newAuthPda, isSigner: false, isWritable: true },
+      { pubkey: registry, isSigner: false, isWritable: true },
+      { pubkey: registryPage, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     data,
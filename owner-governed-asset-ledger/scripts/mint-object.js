@@ -1,5 +1,6 @@
 #!/usr/bin/env node
 
+const crypto = require('crypto');
 const fs = require('fs');
 const path = require('path');
 const {
@@ -27,11 +28,36 @@ const {
   collectLogs,
   deserializeManifestCreator,
   ensureManifestCreatorPresent,
+  parseArweaveTxId,
 } = require('./utils');
 
 const METAPLEX_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const MEMO_PROGRAM_ID = new PublicKey('MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr');
 const MANIFEST_SEED = Buffer.from('object_manifest');
 const MINT_SEED = Buffer.from('object_mint');
+const STATS_SEED = Buffer.from('config_stats');
+const CREATOR_SEED = Buffer.from('creator_record');
+const EPOCH_STATS_SEED = Buffer.from('epoch_stats');
+const OBJECT_INDEX_SEED = Buffer.from('object_index');
+const OBJECT_INDEX_PAGE_CAPACITY = 64n;
+const MINTED_BITMAP_SEED = Buffer.from('minted_bitmap');
+const MINTED_BITMAP_BITS = 8192n;
+const MINT_PRICE_SEED = Buffer.from('mint_price');
+const MINT_PRICE_USD_CENTS_OFFSET = 40;
+const MINT_PRICE_TREASURY_OFFSET = 48;
+const KYC_GATE_SEED = Buffer.from('kyc_gate');
+const KYC_GATE_RESTRICTED_CATEGORIES_OFFSET = 104;
+const KYC_GATE_RESTRICTED_COUNT_OFFSET = 136;
+const METADATA_DEFAULTS_SEED = Buffer.from('metadata_defaults');
+const GLOBAL_PAUSE_SEED = Buffer.from('global_pause');
+const RESERVATION_SEED = Buffer.from('object_reservation');
+const CREATOR_PROFILE_SEED = Buffer.from('creator_profile');
+const APPROVED_CREATOR_SEED = Buffer.from('approved_creator');
+const CREATOR_ROYALTY_SEED = Buffer.from('creator_royalty');
+// Zero-copy config offsets, including the discriminator (see Config::*_OFFSET).
+const CONFIG_INDEXED_OBJECT_COUNT_OFFSET = 80;
+const CONFIG_OBJECT_INDEX_ENABLED_OFFSET = 126;
+const CONFIG_MINTED_BITMAP_ENABLED_OFFSET = 127;
 const MAX_CREATOR_LIMIT = 5;
 
 const MINT_OBJECT_DISCRIMINATOR = instructionDiscriminator('mint_object_nft');
@@ -117,6 +143,90 @@ function deriveObjectMintPda(manifest) {
   );
 }
 
+function deriveConfigStatsPda(config) {
+  return PublicKey.findProgramAddressSync([STATS_SEED, config.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveCreatorRecordPda(config, creator) {
+  return PublicKey.findProgramAddressSync(
+    [CREATOR_SEED, config.toBuffer(), creator.toBuffer()],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveEpochStatsPda(config, epoch) {
+  const epochBuffer = Buffer.alloc(8);
+  epochBuffer.writeBigUInt64LE(BigInt(epoch));
+  return PublicKey.findProgramAddressSync(
+    [EPOCH_STATS_SEED, config.toBuffer(), epochBuffer],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveObjectIndexPagePda(config, page) {
+  const pageBuffer = Buffer.alloc(4);
+  pageBuffer.writeUInt32LE(page);
+  return PublicKey.findProgramAddressSync(
+    [OBJECT_INDEX_SEED, config.toBuffer(), pageBuffer],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveMintedBitmapPda(config, objectId) {
+  const chunkBuffer = Buffer.alloc(8);
+  chunkBuffer.writeBigUInt64LE(BigInt(objectId) / MINTED_BITMAP_BITS);
+  return PublicKey.findProgramAddressSync(
+    [MINTED_BITMAP_SEED, config.toBuffer(), chunkBuffer],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveMintPricePda(config) {
+  return PublicKey.findProgramAddressSync([MINT_PRICE_SEED, config.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveKycGatePda(config) {
+  return PublicKey.findProgramAddressSync([KYC_GATE_SEED, config.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveGlobalPausePda() {
+  return PublicKey.findProgramAddressSync([GLOBAL_PAUSE_SEED], OGAL_PROGRAM_ID);
+}
+
+function deriveObjectReservationPda(config, objectId) {
+  const objectIdBytes = Buffer.alloc(8);
+  objectIdBytes.writeBigUInt64LE(objectId, 0);
+  return PublicKey.findProgramAddressSync(
+    [RESERVATION_SEED, config.toBuffer(), objectIdBytes],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveApprovedCreatorPda(config, creator) {
+  return PublicKey.findProgramAddressSync(
+    [APPROVED_CREATOR_SEED, config.toBuffer(), creator.toBuffer()],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveMetadataDefaultsPda(config) {
+  return PublicKey.findProgramAddressSync([METADATA_DEFAULTS_SEED, config.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveCreatorProfilePda(config, creator) {
+  return PublicKey.findProgramAddressSync(
+    [CREATOR_PROFILE_SEED, config.toBuffer(), creator.toBuffer()],
+    OGAL_PROGRAM_ID,
+  );
+}
+
+function deriveCreatorRoyaltyPda(config, creator) {
+  return PublicKey.findProgramAddressSync(
+    [CREATOR_ROYALTY_SEED, config.toBuffer(), creator.toBuffer()],
+    OGAL_PROGRAM_ID,
+  );
+}
+
 function deriveMetadataPda(mint) {
   return PublicKey.findProgramAddressSync(
     [Buffer.from('metadata'), METAPLEX_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
@@ -136,6 +246,22 @@ function deriveMasterEditionPda(mint) {
   );
 }
 
+const HASH_ALGORITHMS = {
+  sha256: 0,
+  keccak256: 1,
+  blake3: 2,
+};
+
+function parseHashAlgorithm(value) {
+  const normalized = String(value ?? 'sha256').toLowerCase();
+  if (!(normalized in HASH_ALGORITHMS)) {
+    throw new Error(
+      `Unsupported --hash-algorithm '${value}'. Expected one of: ${Object.keys(HASH_ALGORITHMS).join(', ')}.`,
+    );
+  }
+  return HASH_ALGORITHMS[normalized];
+}
+
 function encodeString(value) {
   const stringBytes = Buffer.from(value ?? '', 'utf8');
   const lenBuf = Buffer.alloc(4);
@@ -250,6 +376,24 @@ async function main() {
       demandOption: true,
       describe: '32-byte manifest hash encoded as hex',
     })
+    .option('hash-algorithm', {
+      type: 'string',
+      default: 'sha256',
+      describe: 'Algorithm used to compute --manifest-hash (sha256, keccak256, blake3)',
+    })
+    .option('category', {
+      type: 'number',
+      default: 0,
+      describe: 'Curated category id enabled on the config (0 = uncategorized)',
+    })
+    .option('tag', {
+      type: 'string',
+      describe: 'Optional free-form tag; its SHA-256 hash is stored on the manifest',
+    })
+    .option('arweave-tx-id', {
+      type: 'string',
+      describe: 'Arweave transaction id (base64url) of the permanent copy; required for Arweave URIs',
+    })
     .option('metadata-name', {
       type: 'string',
       demandOption: true,
@@ -280,6 +424,10 @@ async function main() {
       demandOption: true,
       describe: 'Path to the payer (and manifest creator) keypair JSON file',
     })
+    .option('rent-payer-keypair', {
+      type: 'string',
+      describe: 'Optional keypair that funds account rent instead of the payer',
+    })
     .option('authority', {
       type: 'string',
       describe: 'Optional override for the config authority public key',
@@ -305,6 +453,19 @@ async function main() {
       default: false,
       describe: 'Include the instructions sysvar account (required for sized collections)',
     })
+    .option('include-memo-program', {
+      type: 'boolean',
+      default: false,
+      describe: 'Include the SPL Memo program account (required when the config enables mint memos)',
+    })
+    .option('price-update', {
+      type: 'string',
+      describe: 'Pyth SOL/USD PriceUpdateV2 account (required when the config charges a USD mint price)',
+    })
+    .option('kyc-attestation', {
+      type: 'string',
+      describe: "Solana Attestation Service KYC attestation naming the recipient (required when the config's KYC gate restricts the category)",
+    })
     .option('creator', {
       type: 'string',
       array: true,
@@ -346,6 +507,15 @@ async function main() {
   const objectId = parseBigInt(argv['object-id'], 'object-id');
   const manifestUri = argv['manifest-uri'];
   const manifestHash = parseManifestHash(argv['manifest-hash']);
+  const hashAlgorithm = parseHashAlgorithm(argv['hash-algorithm']);
+  const category = Number(argv.category ?? 0);
+  if (!Number.isInteger(category) || category < 0 || category > 0xffff) {
+    throw new Error('Category must be an integer between 0 and 65535.');
+  }
+  const tagHash = argv.tag
+    ? crypto.createHash('sha256').update(argv.tag, 'utf8').digest()
+    : Buffer.alloc(32);
+  const arweaveTxId = parseArweaveTxId(argv['arweave-tx-id']);
   const metadataName = argv['metadata-name'];
   const metadataSymbol = argv['metadata-symbol'] ?? '';
   const sellerFeeBasisPoints = Number.parseInt(argv['seller-fee-bps'], 10);
@@ -385,6 +555,10 @@ async function main() {
 
   const payerKeypair = loadKeypair(argv['payer-keypair']);
   const payer = payerKeypair.publicKey;
+  const rentPayerKeypair = argv['rent-payer-keypair']
+    ? loadKeypair(argv['rent-payer-keypair'])
+    : payerKeypair;
+  const rentPayer = rentPayerKeypair.publicKey;
 
   const recipient = new PublicKey(argv.recipient);
   const collectionMint = new PublicKey(argv['collection-mint']);
@@ -411,7 +585,8 @@ async function main() {
   }
   const manifestAccountInfo = await connection.getAccountInfo(manifestPda);
   let manifestCreator;
-  if (!manifestAccountInfo || manifestAccountInfo.data.length === 0) {
+  const isNewManifest = !manifestAccountInfo || manifestAccountInfo.data.length === 0;
+  if (isNewManifest) {
     manifestCreator = payer;
     logStructured('info', 'mint_object_nft.manifest_initializing', {
       manifestPda,
@@ -463,6 +638,106 @@ async function main() {
     );
   }
 
+  // Configs tracking stats require the stats PDA, the manifest creator's
+  // record, and the current epoch's stats PDA; otherwise every slot takes the
+  // program id "none" placeholder. A transaction landing after an epoch
+  // boundary is rejected and must be rebuilt.
+  const [configStatsPda] = deriveConfigStatsPda(configPda);
+  const configStatsInfo = await connection.getAccountInfo(configStatsPda);
+  const configStatsAccount = configStatsInfo ? configStatsPda : OGAL_PROGRAM_ID;
+  const creatorRecordAccount = configStatsInfo
+    ? deriveCreatorRecordPda(configPda, manifestCreator)[0]
+    : OGAL_PROGRAM_ID;
+  const epochStatsAccount = configStatsInfo
+    ? deriveEpochStatsPda(configPda, (await connection.getEpochInfo()).epoch)[0]
+    : OGAL_PROGRAM_ID;
+
+  // New objects under an indexed config are appended to the page holding the
+  // next index entry.
+  let objectIndexPageAccount = OGAL_PROGRAM_ID;
+  const objectIndexEnabled =
+    configAccountInfo.data.length > CONFIG_OBJECT_INDEX_ENABLED_OFFSET &&
+    configAccountInfo.data[CONFIG_OBJECT_INDEX_ENABLED_OFFSET] === 1;
+  if (objectIndexEnabled && isNewManifest) {
+    const indexedCount = configAccountInfo.data.readBigUInt64LE(CONFIG_INDEXED_OBJECT_COUNT_OFFSET);
+    const page = Number(indexedCount / OBJECT_INDEX_PAGE_CAPACITY);
+    [objectIndexPageAccount] = deriveObjectIndexPagePda(configPda, page);
+  }
+
+  const mintedBitmapEnabled =
+    configAccountInfo.data.length > CONFIG_MINTED_BITMAP_ENABLED_OFFSET &&
+    configAccountInfo.data[CONFIG_MINTED_BITMAP_ENABLED_OFFSET] === 1;
+  const mintedBitmapAccount = mintedBitmapEnabled
+    ? deriveMintedBitmapPda(configPda, objectId)[0]
+    : OGAL_PROGRAM_ID;
+
+  // A USD mint price charges the treasury recorded on the price PDA, at the
+  // rate of the Pyth price update passed with --price-update.
+  let mintPriceAccount = OGAL_PROGRAM_ID;
+  let priceUpdateAccount = OGAL_PROGRAM_ID;
+  let treasuryAccount = OGAL_PROGRAM_ID;
+  const [mintPricePda] = deriveMintPricePda(configPda);
+  const mintPriceInfo = await connection.getAccountInfo(mintPricePda);
+  if (mintPriceInfo && mintPriceInfo.data.readBigUInt64LE(MINT_PRICE_USD_CENTS_OFFSET) > 0n) {
+    if (!argv['price-update']) {
+      throw new Error('The config charges a USD mint price; pass the Pyth account with --price-update.');
+    }
+    mintPriceAccount = mintPricePda;
+    priceUpdateAccount = new PublicKey(argv['price-update']);
+    treasuryAccount = new PublicKey(
+      mintPriceInfo.data.slice(MINT_PRICE_TREASURY_OFFSET, MINT_PRICE_TREASURY_OFFSET + 32),
+    );
+  }
+
+  // The KYC gate PDA is passed whenever it exists; restricted categories also
+  // take the recipient's attestation from --kyc-attestation.
+  let kycGateAccount = OGAL_PROGRAM_ID;
+  let kycAttestationAccount = OGAL_PROGRAM_ID;
+  const [kycGatePda] = deriveKycGatePda(configPda);
+  const kycGateInfo = await connection.getAccountInfo(kycGatePda);
+  if (kycGateInfo) {
+    kycGateAccount = kycGatePda;
+    const restrictedCategories = Array.from(
+      { length: kycGateInfo.data[KYC_GATE_RESTRICTED_COUNT_OFFSET] },
+      (_, i) => kycGateInfo.data.readUInt16LE(KYC_GATE_RESTRICTED_CATEGORIES_OFFSET + 2 * i),
+    );
+    if (restrictedCategories.includes(category)) {
+      if (!argv['kyc-attestation']) {
+        throw new Error('The KYC gate restricts this category; pass the attestation with --kyc-attestation.');
+      }
+      kycAttestationAccount = new PublicKey(argv['kyc-attestation']);
+    }
+  }
+
+  // The global pause PDA is always passed, even before it has been created.
+  const [globalPausePda] = deriveGlobalPausePda();
+  // So is the object's reservation PDA, which usually does not exist.
+  const [objectReservationPda] = deriveObjectReservationPda(configPda, objectId);
+  // The creator's allowlist approval is passed when it exists; new objects
+  // need it while the config's creator allowlist is on.
+  const [approvedCreatorPda] = deriveApprovedCreatorPda(configPda, manifestCreator);
+  const approvedCreatorAccount = (await connection.getAccountInfo(approvedCreatorPda))
+    ? approvedCreatorPda
+    : OGAL_PROGRAM_ID;
+
+  // Metadata defaults are optional; passing the PDA lets the program fill in
+  // an empty symbol, seller fee, or creator list.
+  const [metadataDefaultsPda] = deriveMetadataDefaultsPda(configPda);
+  const metadataDefaultsAccount = (await connection.getAccountInfo(metadataDefaultsPda))
+    ? metadataDefaultsPda
+    : OGAL_PROGRAM_ID;
+
+  // A creator profile routes the manifest creator's royalty share to its
+  // payout wallet when it exists.
+  const [creatorProfilePda] = deriveCreatorProfilePda(configPda, manifestCreator);
+  const creatorProfileAccount = (await connection.getAccountInfo(creatorProfilePda))
+    ? creatorProfilePda
+    : OGAL_PROGRAM_ID;
+
+  // The creator royalty PDA is always passed; when it exists, it sets the
+  // first mint's seller fee and creators.
+  const [creatorRoyaltyPda] = deriveCreatorRoyaltyPda(configPda, manifestCreator);
+
   const creatorUniqueSigners = new Map();
   const creatorAccounts = [];
   for (const creator of creators) {
@@ -501,6 +776,9 @@ async function main() {
   const objectIdBuffer = Buffer.alloc(8);
   objectIdBuffer.writeBigUInt64LE(objectId, 0);
 
+  const categoryBuffer = Buffer.alloc(2);
+  categoryBuffer.writeUInt16LE(category, 0);
+
   const data = Buffer.concat([
     MINT_OBJECT_DISCRIMINATOR,
     objectIdBuffer,
@@ -510,6 +788,10 @@ async function main() {
     encodeString(metadataSymbol),
     sellerFeeBuffer,
     serializedCreators,
+    Buffer.from([hashAlgorithm]),
+    categoryBuffer,
+    tagHash,
+    arweaveTxId,
   ]);
 
   const baseAccounts = [
@@ -517,6 +799,7 @@ async function main() {
     { pubkey: configPda, isSigner: false, isWritable: true },
     { pubkey: authPda, isSigner: false, isWritable: true },
     { pubkey: payer, isSigner: true, isWritable: true },
+    { pubkey: rentPayer, isSigner: true, isWritable: true },
     { pubkey: manifestPda, isSigner: false, isWritable: true },
     { pubkey: objectMint, isSigner: false, isWritable: true },
     { pubkey: recipientAta, isSigner: false, isWritable: true },
@@ -524,10 +807,50 @@ async function main() {
     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
     { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
     { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    {
+      pubkey: configStatsAccount,
+      isSigner: false,
+      isWritable: !configStatsAccount.equals(OGAL_PROGRAM_ID),
+    },
+    {
+      pubkey: creatorRecordAccount,
+      isSigner: false,
+      isWritable: !creatorRecordAccount.equals(OGAL_PROGRAM_ID),
+    },
+    {
+      pubkey: epochStatsAccount,
+      isSigner: false,
+      isWritable: !epochStatsAccount.equals(OGAL_PROGRAM_ID),
+    },
+    {
+      pubkey: objectIndexPageAccount,
+      isSigner: false,
+      isWritable: !objectIndexPageAccount.equals(OGAL_PROGRAM_ID),
+    },
+    {
+      pubkey: mintedBitmapAccount,
+      isSigner: false,
+      isWritable: !mintedBitmapAccount.equals(OGAL_PROGRAM_ID),
+    },
+    { pubkey: mintPriceAccount, isSigner: false, isWritable: false },
+    { pubkey: priceUpdateAccount, isSigner: false, isWritable: false },
+    {
+      pubkey: treasuryAccount,
+      isSigner: false,
+      isWritable: !treasuryAccount.equals(OGAL_PROGRAM_ID),
+    },
+    { pubkey: kycGateAccount, isSigner: false, isWritable: false },
+    { pubkey: kycAttestationAccount, isSigner: false, isWritable: false },
+    { pubkey: globalPausePda, isSigner: false, isWritable: false },
+    { pubkey: objectReservationPda, isSigner: false, isWritable: false },
+    { pubkey: approvedCreatorAccount, isSigner: false, isWritable: false },
     { pubkey: metadataPda, isSigner: false, isWritable: true },
     { pubkey: masterEditionPda, isSigner: false, isWritable: true },
     { pubkey: collectionMint, isSigner: false, isWritable: false },
     { pubkey: tokenMetadataProgram, isSigner: false, isWritable: false },
+    { pubkey: metadataDefaultsAccount, isSigner: false, isWritable: false },
+    { pubkey: creatorProfileAccount, isSigner: false, isWritable: false },
+    { pubkey: creatorRoyaltyPda, isSigner: false, isWritable: false },
     { pubkey: collectionMetadataPda, isSigner: false, isWritable: true },
     { pubkey: collectionMasterEditionPda, isSigner: false, isWritable: true },
     { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
@@ -541,6 +864,14 @@ async function main() {
     });
   }
 
+  if (argv['include-memo-program']) {
+    baseAccounts.push({
+      pubkey: MEMO_PROGRAM_ID,
+      isSigner: false,
+      isWritable: false,
+    });
+  }
+
   for (const [addressBase58] of creatorUniqueSigners) {
     const address = new PublicKey(addressBase58);
     creatorAccounts.push({ pubkey: address, isSigner: true, isWritable: false });
@@ -590,6 +921,9 @@ async function main() {
   tx.recentBlockhash = blockhash;
 
   const signers = [payerKeypair];
+  if (!rentPayer.equals(payer)) {
+    signers.push(rentPayerKeypair);
+  }
   for (const keypair of creatorUniqueSigners.values()) {
     signers.push(keypair);
   }
@@ -3,6 +3,7 @@
 const {
   Connection,
   PublicKey,
+  SystemProgram,
   Transaction,
   TransactionInstruction,
   SYSVAR_RENT_PUBKEY,
@@ -21,10 +22,14 @@ const {
   deriveAuthPda,
   logStructured,
   collectLogs,
+  parseArweaveTxId,
 } = require('./utils');
 
 const MANIFEST_SEED = Buffer.from('object_manifest');
 const MINT_SEED = Buffer.from('object_mint');
+const HISTORY_SEED = Buffer.from('manifest_history');
+const STATS_SEED = Buffer.from('config_stats');
+const GLOBAL_PAUSE_SEED = Buffer.from('global_pause');
 const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
 
 const UPDATE_MANIFEST_DISCRIMINATOR = instructionDiscriminator('update_object_manifest');
@@ -62,6 +67,22 @@ function parseManifestHash(input) {
   return Buffer.from(cleaned, 'hex');
 }
 
+const HASH_ALGORITHMS = {
+  sha256: 0,
+  keccak256: 1,
+  blake3: 2,
+};
+
+function parseHashAlgorithm(value) {
+  const normalized = String(value ?? 'sha256').toLowerCase();
+  if (!(normalized in HASH_ALGORITHMS)) {
+    throw new Error(
+      `Unsupported --hash-algorithm '${value}'. Expected one of: ${Object.keys(HASH_ALGORITHMS).join(', ')}.`,
+    );
+  }
+  return HASH_ALGORITHMS[normalized];
+}
+
 function encodeString(value) {
   const stringBytes = Buffer.from(value ?? '', 'utf8');
   const lenBuf = Buffer.alloc(4);
@@ -100,6 +121,18 @@ function deriveManifestPda(config, objectId) {
   );
 }
 
+function deriveManifestHistoryPda(manifest) {
+  return PublicKey.findProgramAddressSync([HISTORY_SEED, manifest.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveConfigStatsPda(config) {
+  return PublicKey.findProgramAddressSync([STATS_SEED, config.toBuffer()], OGAL_PROGRAM_ID);
+}
+
+function deriveGlobalPausePda() {
+  return PublicKey.findProgramAddressSync([GLOBAL_PAUSE_SEED], OGAL_PROGRAM_ID);
+}
+
 function deriveObjectMintPda(manifest) {
   return PublicKey.findProgramAddressSync(
     [MINT_SEED, manifest.toBuffer()],
@@ -139,11 +172,20 @@ async function main() {
       demandOption: true,
       describe: '32-byte manifest hash encoded as hex',
     })
+    .option('hash-algorithm', {
+      type: 'string',
+      default: 'sha256',
+      describe: 'Algorithm used to compute --manifest-hash (sha256, keccak256, blake3)',
+    })
     .option('metadata-uri', {
       type: 'string',
       demandOption: true,
       describe: 'New metadata URI to store on the manifest',
     })
+    .option('arweave-tx-id', {
+      type: 'string',
+      describe: 'Arweave transaction id (base64url) of the permanent copy; required for Arweave URIs',
+    })
     .option('is-active', {
       type: 'string',
       default: 'true',
@@ -180,6 +222,8 @@ async function main() {
   const manifestHash = parseManifestHash(argv['manifest-hash']);
   const metadataUri = argv['metadata-uri'];
   const isActive = parseBoolean(argv['is-active'], 'is-active');
+  const hashAlgorithm = parseHashAlgorithm(argv['hash-algorithm']);
+  const arweaveTxId = parseArweaveTxId(argv['arweave-tx-id']);
 
   if (!metadataUri || metadataUri.length === 0) {
     throw new Error('Metadata URI cannot be empty.');
@@ -226,6 +270,17 @@ async function main() {
     TOKEN_METADATA_PROGRAM_ID,
   );
 
+  // Manifests that opted into on-chain history must pass their history PDA;
+  // everyone else passes the program id as the "none" placeholder.
+  const [manifestHistoryPda] = deriveManifestHistoryPda(manifestPda);
+  const manifestHistoryInfo = await connection.getAccountInfo(manifestHistoryPda, argv.commitment);
+  const manifestHistoryAccount = manifestHistoryInfo ? manifestHistoryPda : OGAL_PROGRAM_ID;
+  const [configStatsPda] = deriveConfigStatsPda(configPda);
+  const configStatsInfo = await connection.getAccountInfo(configStatsPda, argv.commitment);
+  const configStatsAccount = configStatsInfo ? configStatsPda : OGAL_PROGRAM_ID;
+  // The global pause PDA is always passed, even before it has been created.
+  const [globalPausePda] = deriveGlobalPausePda();
+
   logStructured('info', 'update_object_manifest.derived_accounts', {
     namespace,
     objectId: objectId.toString(),
@@ -240,6 +295,9 @@ async function main() {
     owner,
     ownerTokenAccount,
     metadataPda,
+    manifestHistory: manifestHistoryAccount,
+    configStats: configStatsAccount,
+    globalPause: globalPausePda,
     tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
     rentSysvar: SYSVAR_RENT_PUBKEY,
     instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -255,12 +313,15 @@ async function main() {
   console.log('  Token Metadata Program:', TOKEN_METADATA_PROGRAM_ID.toBase58());
   console.log('  Rent Sysvar:', SYSVAR_RENT_PUBKEY.toBase58());
   console.log('  Instructions Sysvar:', SYSVAR_INSTRUCTIONS_PUBKEY.toBase58());
+  console.log('  Global Pause PDA:', globalPausePda.toBase58());
 
   const data = Buffer.concat([
     UPDATE_MANIFEST_DISCRIMINATOR,
     manifestHash,
     encodeString(metadataUri),
     Buffer.from([isActive ? 1 : 0]),
+    Buffer.from([hashAlgorithm]),
+    arweaveTxId,
   ]);
 
   const keys = [
@@ -274,6 +335,18 @@ async function main() {
     { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
     { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
     { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    {
+      pubkey: manifestHistoryAccount,
+      isSigner: false,
+      isWritable: !manifestHistoryAccount.equals(OGAL_PROGRAM_ID),
+    },
+    {
+      pubkey: configStatsAccount,
+      isSigner: false,
+      isWritable: !configStatsAccount.equals(OGAL_PROGRAM_ID),
+    },
+    { pubkey: globalPausePda, isSigner: false, isWritable: false },
   ];
 
   const instruction = new TransactionInstruction({
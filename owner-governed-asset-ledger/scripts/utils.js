@@ -20,6 +20,7 @@ const MANIFEST_URI_PADDING_LENGTH = 1;
 const MANIFEST_URI_LENGTH_FIELD_LENGTH = 2;
 
 // ObjectManifest layout defined in programs/owner_governed_asset_ledger/src/lib.rs.
+// Must equal `ObjectManifest::CREATOR_OFFSET` (248).
 const MANIFEST_CREATOR_OFFSET =
   ACCOUNT_DISCRIMINATOR_LENGTH +
   PUBKEY_LENGTH + // config
@@ -73,6 +74,27 @@ function deriveAuthPda(config) {
   return PublicKey.findProgramAddressSync([Buffer.from('auth'), config.toBuffer()], OGAL_PROGRAM_ID);
 }
 
+const NAMESPACE_REGISTRY_PAGE_CAPACITY = 32n;
+
+function deriveNamespaceRegistryPda() {
+  return PublicKey.findProgramAddressSync([Buffer.from('namespace_registry')], OGAL_PROGRAM_ID);
+}
+
+// Resolves the registry and the page the next config is listed in, from
+// the registry's `config_count` (zero before the first config).
+async function resolveNamespaceRegistry(connection) {
+  const [registry] = deriveNamespaceRegistryPda();
+  const registryAccount = await connection.getAccountInfo(registry);
+  const configCount = registryAccount ? registryAccount.data.readBigUInt64LE(8) : 0n;
+  const pageBuffer = Buffer.alloc(4);
+  pageBuffer.writeUInt32LE(Number(configCount / NAMESPACE_REGISTRY_PAGE_CAPACITY));
+  const [registryPage] = PublicKey.findProgramAddressSync(
+    [Buffer.from('namespace_registry_page'), pageBuffer],
+    OGAL_PROGRAM_ID,
+  );
+  return { registry, registryPage };
+}
+
 function logStructured(level, event, payload) {
   const entry = { level, event, ...payload };
   const serialized = JSON.stringify(entry, (_key, value) => {
@@ -143,6 +165,18 @@ function ensureManifestCreatorPresent(creators, manifestCreator, manifestPda) {
   );
 }
 
+function parseArweaveTxId(value) {
+  if (value === undefined || value === null || value === '') {
+    return Buffer.alloc(32);
+  }
+
+  const bytes = Buffer.from(String(value), 'base64url');
+  if (bytes.length !== 32 || bytes.toString('base64url') !== String(value)) {
+    throw new Error('Arweave transaction id must be a 43 character base64url string.');
+  }
+  return bytes;
+}
+
 module.exports = {
   OGAL_PROGRAM_ID,
   expandPath,
@@ -150,8 +184,11 @@ module.exports = {
   instructionDiscriminator,
   deriveConfigPda,
   deriveAuthPda,
+  deriveNamespaceRegistryPda,
+  resolveNamespaceRegistry,
   logStructured,
   collectLogs,
   deserializeManifestCreator,
   ensureManifestCreatorPresent,
+  parseArweaveTxId,
 };
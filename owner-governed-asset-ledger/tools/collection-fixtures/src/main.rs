@@ -0,0 +1,97 @@
+//! Writes collection metadata account files for `solana-test-validator
+//! --account`, one per encoding OGAL's mint has to parse.
+//!
+//! ```text
+//! cargo run -p collection-fixtures -- --update-authority <PUBKEY> [--size N] [--out-dir DIR]
+//! ```
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mpl_token_metadata::accounts::Metadata;
+use owner_governed_asset_ledger::test_utils::CollectionMetadataVariant;
+use serde_json::json;
+use solana_sdk::{pubkey::Pubkey, rent::Rent, signature::Keypair, signer::Signer};
+use std::{fs, path::PathBuf, process::ExitCode, str::FromStr};
+
+const USAGE: &str =
+    "usage: collection-fixtures --update-authority <PUBKEY> [--size N] [--out-dir DIR]";
+
+struct Args {
+    update_authority: Pubkey,
+    size: u64,
+    out_dir: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut update_authority = None;
+    let mut size = 1;
+    let mut out_dir = PathBuf::from("fixtures");
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or(format!("{flag} needs a value"));
+        match flag.as_str() {
+            "--update-authority" => {
+                let value = value()?;
+                update_authority =
+                    Some(Pubkey::from_str(&value).map_err(|_| format!("invalid pubkey {value}"))?);
+            }
+            "--size" => {
+                let value = value()?;
+                size = value.parse().map_err(|_| format!("invalid size {value}"))?;
+            }
+            "--out-dir" => out_dir = PathBuf::from(value()?),
+            _ => return Err(format!("unknown argument {flag}")),
+        }
+    }
+
+    Ok(Args {
+        update_authority: update_authority.ok_or("--update-authority is required")?,
+        size,
+        out_dir,
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = fs::create_dir_all(&args.out_dir) {
+        eprintln!("cannot create {}: {err}", args.out_dir.display());
+        return ExitCode::FAILURE;
+    }
+
+    let rent = Rent::default();
+    for variant in CollectionMetadataVariant::ALL {
+        // Each variant gets its own mint so all files can be loaded at once.
+        let mint = Keypair::new().pubkey();
+        let (metadata, _) = Metadata::find_pda(&mint);
+        let data = variant.account_data(mint, args.update_authority, args.size);
+        let account = json!({
+            "pubkey": metadata.to_string(),
+            "account": {
+                "lamports": rent.minimum_balance(data.len()),
+                "data": [STANDARD.encode(&data), "base64"],
+                "owner": mpl_token_metadata::ID.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": data.len(),
+            },
+        });
+
+        let path = args.out_dir.join(format!("{}.json", variant.name()));
+        if let Err(err) = fs::write(&path, serde_json::to_string_pretty(&account).unwrap()) {
+            eprintln!("cannot write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+        println!(
+            "{:<18} mint {mint} --account {metadata} {}",
+            variant.name(),
+            path.display()
+        );
+    }
+    ExitCode::SUCCESS
+}